@@ -0,0 +1,96 @@
+//! Builder for form (`application/x-www-form-urlencoded`) bodies
+
+use std::collections::HashMap;
+
+use pact_models::generators::Generator;
+use pact_models::matchingrules::MatchingRuleCategory;
+use pact_models::path_exp::DocPath;
+
+use crate::patterns::{Pattern, StringPattern};
+
+/// Builder for a URL encoded form body. Field values can use a `StringPattern` to match the actual
+/// values with. A field can be added more than once to provide multiple values.
+///
+/// ```
+/// use pact_consumer::prelude::*;
+/// use pact_consumer::builders::FormBuilder;
+///
+/// let body = FormBuilder::new()
+///   .field("name", "Baxter")
+///   .field("age", term!("^[0-9]+$", "3"))
+///   .field("tag", "dog")
+///   .field("tag", "puppy");
+/// ```
+#[derive(Debug, Default)]
+pub struct FormBuilder {
+  fields: Vec<(String, StringPattern)>
+}
+
+impl FormBuilder {
+  /// Create a new, empty, form body builder.
+  pub fn new() -> Self {
+    FormBuilder::default()
+  }
+
+  /// Adds a field to the form.
+  pub fn field<N: Into<String>, V: Into<StringPattern>>(mut self, name: N, value: V) -> Self {
+    self.fields.push((name.into(), value.into()));
+    self
+  }
+
+  /// Generates the example body.
+  pub fn to_example(&self) -> String {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    for (name, value) in &self.fields {
+      serializer.append_pair(name, value.to_example().as_str());
+    }
+    serializer.finish()
+  }
+
+  /// Extracts the matching rules for the fields of the form.
+  pub fn extract_matching_rules(&self, rules_out: &mut MatchingRuleCategory) {
+    for (name, value) in &self.fields {
+      value.extract_matching_rules(DocPath::root().join(name.as_str()), rules_out);
+    }
+  }
+
+  /// Extracts any generators for the fields of the form.
+  pub fn extract_generators(&self, generators_out: &mut HashMap<DocPath, Generator>) {
+    for (name, value) in &self.fields {
+      value.extract_generators(DocPath::root().join(name.as_str()), generators_out);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use pact_models::matchingrules::MatchingRule;
+  use pact_models::matchingrules_list;
+  use regex::Regex;
+
+  use crate::patterns::Term;
+
+  use super::*;
+
+  #[test]
+  fn generates_the_form_body() {
+    let builder = FormBuilder::new()
+      .field("name", "Baxter the dog")
+      .field("tag", "dog")
+      .field("tag", "a&b");
+    expect!(builder.to_example()).to(be_equal_to("name=Baxter+the+dog&tag=dog&tag=a%26b"));
+  }
+
+  #[test]
+  fn extracts_the_matching_rules_for_the_fields() {
+    let builder = FormBuilder::new()
+      .field("name", "Baxter")
+      .field("age", Term::new(Regex::new("^[0-9]+$").unwrap(), "3"));
+    let mut rules = MatchingRuleCategory::empty("body");
+    builder.extract_matching_rules(&mut rules);
+    expect!(rules).to(be_equal_to(matchingrules_list! {
+      "body"; "$.age" => [ MatchingRule::Regex("^[0-9]+$".to_string()) ]
+    }));
+  }
+}
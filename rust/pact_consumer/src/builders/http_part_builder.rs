@@ -6,6 +6,7 @@ use pact_models::generators::{Generator, GeneratorCategory, Generators};
 use pact_models::headers::parse_header;
 use pact_models::matchingrules::MatchingRules;
 use pact_models::path_exp::DocPath;
+use tracing::error;
 
 use crate::prelude::*;
 
@@ -110,6 +111,48 @@ pub trait HttpPartBuilder {
       self
     }
 
+    /// Add a generator for a value in the request or response. The path is the name of the
+    /// header or query parameter, or a JSON path expression for the body, and is ignored for the
+    /// path and status. If the body path is not a valid JSON path expression, the error is logged
+    /// and the generator is not added.
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    /// use maplit::btreemap;
+    ///
+    /// RequestBuilder::default()
+    ///     .json_body(json_pattern!({ "id": like!(1), "email": like!("mary@example.com") }))
+    ///     .generator(GeneratorCategory::BODY, "$.id", Generator::sequence(1))
+    ///     .generator(GeneratorCategory::BODY, "$.email", Generator::Fake(FakeKind::Email))
+    ///     .generator(GeneratorCategory::HEADER, "X-Order", Generator::Template(
+    ///       "ORD-{seq}-{hex}".to_string(),
+    ///       btreemap!{
+    ///         "seq".to_string() => Generator::sequence(1000),
+    ///         "hex".to_string() => Generator::RandomHexadecimal(4)
+    ///       }
+    ///     ));
+    /// ```
+    fn generator<P: AsRef<str>>(&mut self, category: GeneratorCategory, path: P, generator: Generator) -> &mut Self {
+      {
+        let generators = self.generators();
+        match category {
+          GeneratorCategory::PATH | GeneratorCategory::STATUS | GeneratorCategory::METHOD =>
+            generators.add_generator(&category, generator),
+          GeneratorCategory::HEADER | GeneratorCategory::QUERY | GeneratorCategory::METADATA => {
+            let mut sub_category_path = DocPath::root();
+            sub_category_path.push_field(path.as_ref());
+            generators.add_generator_with_subcategory(&category, sub_category_path, generator)
+          }
+          GeneratorCategory::BODY => match DocPath::new(path.as_ref()) {
+            Ok(doc_path) => generators.add_generator_with_subcategory(&category, doc_path, generator),
+            Err(err) => error!("Ignoring the body generator as '{}' is not a valid path - {}", path.as_ref(), err)
+          }
+        }
+      }
+      self
+    }
+
     /// Set the `Content-Type` header.
     fn content_type<CT>(&mut self, content_type: CT) -> &mut Self
     where
@@ -185,6 +228,44 @@ pub trait HttpPartBuilder {
             *body_ref = OptionalBody::Present(body.to_example().to_string().into(), Some("application/json".into()), None);
             body.extract_matching_rules(DocPath::root(), rules.add_category("body"));
         }
+        {
+            let mut body_generators = HashMap::new();
+            body.extract_generators(DocPath::root(), &mut body_generators);
+            let generators = self.generators();
+            for (path, generator) in body_generators {
+                generators.add_generator_with_subcategory(&GeneratorCategory::BODY, path, generator);
+            }
+        }
+        self
+    }
+
+    /// Specify an XML body (application/xml), possibly including special matching rules and
+    /// generators.
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    ///
+    /// RequestBuilder::default().xml_body(XmlElement::new("order")
+    ///     .attribute("id", term!("^[0-9]+$", "1234"))
+    ///     .each_like(XmlElement::new("item").text(term!("^[A-Z]-[0-9]+$", "A-1")), 1));
+    /// ```
+    #[cfg(feature = "xml")]
+    fn xml_body<B: Into<XmlPattern>>(&mut self, body: B) -> &mut Self {
+        let body = body.into();
+        {
+            let (body_ref, rules) = self.body_and_matching_rules_mut();
+            *body_ref = OptionalBody::Present(body.to_example_bytes().into(), Some("application/xml".into()), None);
+            body.extract_matching_rules(DocPath::root(), rules.add_category("body"));
+        }
+        {
+            let mut body_generators = HashMap::new();
+            body.extract_generators(DocPath::root(), &mut body_generators);
+            let generators = self.generators();
+            for (path, generator) in body_generators {
+                generators.add_generator_with_subcategory(&GeneratorCategory::BODY, path, generator);
+            }
+        }
         self
     }
 
@@ -238,8 +319,14 @@ mod tests {
   use regex::Regex;
   use serde_json::json;
 
+  use pact_models::expression_parser::DataType;
+  use pact_models::generators::{FakeKind, Generator, GeneratorCategory};
+  use pact_models::path_exp::DocPath;
+
   use crate::builders::{HttpPartBuilder, PactBuilder};
-  use crate::patterns::{Like, Term};
+  use crate::json_pattern;
+  use crate::patterns::{Like, Term, from_provider_state, random_int};
+  #[cfg(feature = "xml")] use crate::patterns::XmlElement;
 
   #[test_log::test]
   fn header_pattern() {
@@ -269,6 +356,64 @@ mod tests {
     assert_requests_do_not_match!(bad, pattern);
   }
 
+  #[test]
+  fn body_generators() {
+    let actual = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request
+          .json_body(json_pattern!({ "id": 1, "name": "Mary" }))
+          .generator(GeneratorCategory::BODY, "$.id", Generator::sequence(100))
+          .generator(GeneratorCategory::BODY, "$.name", Generator::Fake(FakeKind::FirstName));
+        i
+      }).build();
+
+    let interaction = actual.interactions().first().unwrap().as_v4_http().unwrap();
+    expect!(interaction.request.generators.categories.get(&GeneratorCategory::BODY).cloned()).to(be_some().value(hashmap!{
+      DocPath::new_unwrap("$.id") => Generator::sequence(100),
+      DocPath::new_unwrap("$.name") => Generator::Fake(FakeKind::FirstName)
+    }));
+  }
+
+  #[test]
+  fn json_body_with_generator_patterns() {
+    let actual = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.response.json_body(json_pattern!({
+          "count": random_int(1, 10),
+          "link": from_provider_state("/orders/${id}", "/orders/1")
+        }));
+        i
+      }).build();
+
+    let interaction = actual.interactions().first().unwrap().as_v4_http().unwrap();
+    expect!(interaction.response.generators.categories.get(&GeneratorCategory::BODY).cloned()).to(be_some().value(hashmap!{
+      DocPath::new_unwrap("$.count") => Generator::RandomInt(1, 10),
+      DocPath::new_unwrap("$.link") => Generator::ProviderStateGenerator("/orders/${id}".to_string(), Some(DataType::STRING))
+    }));
+  }
+
+  #[test]
+  #[cfg(feature = "xml")]
+  fn xml_body_pattern() {
+    let actual = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.response.xml_body(XmlElement::new("order")
+          .attribute("id", Term::new(Regex::new("^[0-9]+$").unwrap(), "1234"))
+          .each_like(XmlElement::new("item").text("A-1"), 1));
+        i
+      }).build();
+
+    let interaction = actual.interactions().first().unwrap().as_v4_http().unwrap();
+    expect!(interaction.response.body.value_as_string()).to(be_some().value(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?><order id=\"1234\"><item>A-1</item></order>".to_string()));
+    expect!(interaction.response.body.content_type().unwrap().is_xml()).to(be_true());
+    expect!(interaction.response.matching_rules.rules_for_category("body")).to(be_some().value(matchingrules_list! {
+      "body";
+      "$.order['@id']" => [ MatchingRule::Regex("^[0-9]+$".to_string()) ],
+      "$.order.item" => [ MatchingRule::MinType(1) ]
+    }));
+  }
+
   #[test]
   fn header_generator() {
     let actual = PactBuilder::new("C", "P")
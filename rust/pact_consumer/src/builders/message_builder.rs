@@ -8,7 +8,7 @@ use maplit::hashmap;
 use pact_models::content_types::ContentType;
 use pact_models::json_utils::json_to_string;
 use pact_models::matchingrules::MatchingRuleCategory;
-use pact_models::generators::Generators;
+use pact_models::generators::{GeneratorCategory, Generators};
 use pact_models::message::Message;
 use pact_models::path_exp::DocPath;
 #[cfg(feature = "plugins")] use pact_models::plugins::PluginData;
@@ -434,6 +434,10 @@ impl MessageInteractionBuilder {
           Some(mr) => mr.add_rules(rules.clone())
         }
       }
+      if let Some(body_generators) = body_generators(&body) {
+        let generators = self.message_contents.generators.get_or_insert_with(Generators::default);
+        generators.add_generators(body_generators);
+      }
     }
     self
   }
@@ -460,13 +464,33 @@ impl MessageInteractionBuilder {
   }
 }
 
+/// Extracts any generators from the JSON body pattern, returning `None` if there are none
+pub(crate) fn body_generators(body: &JsonPattern) -> Option<Generators> {
+  let mut body_generators = HashMap::new();
+  body.extract_generators(DocPath::root(), &mut body_generators);
+  if body_generators.is_empty() {
+    None
+  } else {
+    let mut generators = Generators::default();
+    for (path, generator) in body_generators {
+      generators.add_generator_with_subcategory(&GeneratorCategory::BODY, path, generator);
+    }
+    Some(generators)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use expectest::prelude::*;
   use maplit::hashmap;
   use serde_json::json;
 
+  use pact_models::generators::{Generator, GeneratorCategory};
+  use pact_models::path_exp::DocPath;
+
   use crate::builders::MessageInteractionBuilder;
+  use crate::json_pattern;
+  use crate::patterns::random_int;
 
   #[test]
   fn supports_setting_metadata_values() {
@@ -481,4 +505,14 @@ mod tests {
       "c".to_string() => json!([1, 2, 3])
     }));
   }
+
+  #[test]
+  fn json_body_with_generators() {
+    let message = MessageInteractionBuilder::new("test")
+      .json_body(json_pattern!({ "count": random_int(1, 10) }))
+      .build();
+    expect!(message.contents.generators.categories.get(&GeneratorCategory::BODY).cloned()).to(be_some().value(hashmap!{
+      DocPath::new_unwrap("$.count") => Generator::RandomInt(1, 10)
+    }));
+  }
 }
@@ -1,12 +1,16 @@
-use std::collections::VecDeque;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
 use std::env;
-use std::panic::RefUnwindSafe;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind, RefUnwindSafe};
 use std::path::PathBuf;
-use std::sync::mpsc::channel;
-use itertools::Itertools;
+use std::thread;
 
+use futures::FutureExt;
 use maplit::hashmap;
-use pact_models::generators::GeneratorTestMode;
+use pact_models::generators::{GENERATOR_SEED_KEY, GeneratorTestMode};
+use pact_models::interaction::Interaction;
 use pact_models::message::Message;
 use pact_models::pact::write_pact;
 use pact_models::prelude::{MessagePact, Pact};
@@ -14,10 +18,16 @@ use pact_models::prelude::v4::V4Pact;
 use pact_models::v4::async_message::AsynchronousMessage;
 use pact_models::v4::sync_message::SynchronousMessage;
 use pact_models::v4::V4InteractionType;
-use tokio::runtime::Handle;
+use serde_json::{json, Value};
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+use tokio::task::block_in_place;
 use tracing::{debug, error, info, warn};
 
-use pact_matching::generators::{apply_generators_to_sync_message, generate_message};
+use pact_matching::generators::{
+  apply_generators_to_async_message,
+  apply_generators_to_sync_message,
+  generate_message
+};
 
 /// Iterator over the messages build with the PactBuilder
 pub struct MessageIterator<MT> {
@@ -25,114 +35,290 @@ pub struct MessageIterator<MT> {
   message_list: VecDeque<MT>,
   // Output directory to write pact files to when done
   output_dir: Option<PathBuf>,
+  // If the Pact file should be written when the iterator is dropped
+  write_pact: bool
 }
 
-/// Construct a new iterator over the asynchronous messages in the pact
-pub fn asynchronous_messages_iter(pact: V4Pact, output_dir: &Option<PathBuf>) -> MessageIterator<AsynchronousMessage> {
-  MessageIterator {
-    pact: pact.boxed(),
-    message_list: pact.filter_interactions(V4InteractionType::Asynchronous_Messages)
-      .iter()
-      .map(|item| item.as_v4_async_message().unwrap())
-      .collect(),
-    output_dir: output_dir.clone()
+/// Failure of the message handler to accept a message
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageFailure {
+  /// Description of the message interaction
+  pub description: String,
+  /// Error returned from the handler
+  pub error: String
+}
+
+/// Error returned when the message handler did not accept all the messages in the Pact
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageVerificationError {
+  /// Failures for each message that was not accepted
+  pub failures: Vec<MessageFailure>
+}
+
+impl Display for MessageVerificationError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} message(s) were not accepted by the message handler:", self.failures.len())?;
+    for failure in &self.failures {
+      write!(f, "\n  '{}': {}", failure.description, failure.error)?;
+    }
+    Ok(())
   }
 }
 
-/// Construct a new iterator over the synchronous messages in the pact
-pub fn synchronous_messages_iter(pact: V4Pact, output_dir: &Option<PathBuf>) -> MessageIterator<SynchronousMessage> {
-  let original_messages = pact.filter_interactions(V4InteractionType::Synchronous_Messages)
-    .iter()
-    .map(|item| item.as_v4_sync_message().unwrap())
-    .collect_vec();
+impl std::error::Error for MessageVerificationError {}
+
+/// Generator context for the messages, with the seed for the random generators if one was set
+fn generator_context(generator_seed: Option<u64>) -> HashMap<&'static str, Value> {
+  generator_seed
+    .map(|seed| hashmap!{ GENERATOR_SEED_KEY => json!(seed) })
+    .unwrap_or_default()
+}
+
+/// Applies the generators to the contents of the asynchronous messages
+async fn generate_async_messages(
+  messages: Vec<AsynchronousMessage>,
+  context: HashMap<&'static str, Value>
+) -> Vec<AsynchronousMessage> {
+  let mut generated = vec![];
+  for message in messages {
+    let contents = apply_generators_to_async_message(&message, &GeneratorTestMode::Consumer, &context, &vec![], &hashmap!{}).await;
+    generated.push(AsynchronousMessage {
+      contents,
+      .. message
+    });
+  }
+  generated
+}
+
+/// Applies the generators to the requests and responses of the synchronous messages
+async fn generate_sync_messages(
+  messages: Vec<SynchronousMessage>,
+  context: HashMap<&'static str, Value>
+) -> Vec<SynchronousMessage> {
+  let mut generated = vec![];
+  for message in messages {
+    let (request, response) = apply_generators_to_sync_message(&message, &GeneratorTestMode::Consumer, &context, &vec![], &hashmap!{}).await;
+    generated.push(SynchronousMessage {
+      request,
+      response,
+      .. message
+    });
+  }
+  generated
+}
 
-  let (sx, rx) = channel();
+/// Blocks until the messages have been generated. The original messages are returned if the
+/// generation fails.
+///
+/// On a multi-threaded Tokio runtime, the messages are generated on the current runtime. A
+/// current-thread runtime can not make progress while the calling thread is blocked, so in that
+/// case (or if there is no runtime) the messages are generated on a new runtime. Async code
+/// should await the generation directly.
+fn generate_blocking<MT, F>(original_messages: Vec<MT>, generate: F) -> Vec<MT>
+  where MT: Send + 'static,
+        F: Future<Output = Vec<MT>> + Send + 'static
+{
   match Handle::try_current() {
-    Ok(handle) => {
-      let messages_to_generate = original_messages.clone();
-      handle.spawn(async move {
-        let mut messages = vec![];
-        for message in messages_to_generate {
-          let (req, res) = apply_generators_to_sync_message(&message, &GeneratorTestMode::Consumer, &hashmap! {}, &vec![], &hashmap! {}).await;
-          messages.push(SynchronousMessage {
-            request: req,
-            response: res,
-            ..      message
-          });
+    Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+      block_in_place(|| handle.block_on(generate))
+    }
+    Ok(_) => {
+      debug!("The current Tokio runtime is single threaded, will generate the messages on a new runtime");
+      match thread::spawn(move || generator_runtime().block_on(generate)).join() {
+        Ok(messages) => messages,
+        Err(_) => {
+          error!("Was not able to apply generators to the messages, as the generation panicked");
+          original_messages
         }
-        let _ = sx.send(messages);
-      })
-    },
+      }
+    }
     Err(err) => {
       warn!("Could not access the Tokio runtime, will start a new one: {}", err);
-      let messages_to_generate = original_messages.clone();
-      tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .expect("Could not start a Tokio runtime for running async tasks")
-        .spawn(async move {
-          let mut messages = vec![];
-          for message in messages_to_generate {
-            let (req, res) = apply_generators_to_sync_message(&message, &GeneratorTestMode::Consumer, &hashmap! {}, &vec![], &hashmap! {}).await;
-            messages.push(SynchronousMessage {
-              request: req,
-              response: res,
-              ..       message
-            });
-          }
-          let _ = sx.send(messages);
-        })
+      generator_runtime().block_on(generate)
     }
-  };
+  }
+}
 
-  let message_list = match rx.recv() {
-    Ok(messages) => messages,
-    Err(err) => {
-      error!("Was not able to apply generators to the messages: {}", err);
-      original_messages
-    }
-  };
+fn generator_runtime() -> Runtime {
+  tokio::runtime::Builder::new_multi_thread()
+    .enable_all()
+    .build()
+    .expect("Could not start a Tokio runtime for running async tasks")
+}
+
+fn async_messages(pact: &V4Pact) -> Vec<AsynchronousMessage> {
+  pact.filter_interactions(V4InteractionType::Asynchronous_Messages)
+    .iter()
+    .map(|item| item.as_v4_async_message().unwrap())
+    .collect()
+}
+
+fn sync_messages(pact: &V4Pact) -> Vec<SynchronousMessage> {
+  pact.filter_interactions(V4InteractionType::Synchronous_Messages)
+    .iter()
+    .map(|item| item.as_v4_sync_message().unwrap())
+    .collect()
+}
+
+/// Construct a new iterator over the asynchronous messages in the pact
+pub fn asynchronous_messages_iter(
+  pact: V4Pact,
+  output_dir: &Option<PathBuf>,
+  generator_seed: Option<u64>
+) -> MessageIterator<AsynchronousMessage> {
+  let original_messages = async_messages(&pact);
+  let generate = generate_async_messages(original_messages.clone(), generator_context(generator_seed));
+  let message_list = generate_blocking(original_messages, generate);
 
   MessageIterator {
     pact: pact.boxed(),
-    message_list: message_list.iter().cloned().collect(),
-    output_dir: output_dir.clone()
+    message_list: message_list.into(),
+    output_dir: output_dir.clone(),
+    write_pact: true
+  }
+}
+
+/// Construct a new iterator over the asynchronous messages in the pact, awaiting the generation
+/// of the messages on the current runtime
+pub async fn asynchronous_messages_iter_async(
+  pact: V4Pact,
+  output_dir: &Option<PathBuf>,
+  generator_seed: Option<u64>
+) -> MessageIterator<AsynchronousMessage> {
+  let message_list = generate_async_messages(async_messages(&pact), generator_context(generator_seed)).await;
+
+  MessageIterator {
+    pact: pact.boxed(),
+    message_list: message_list.into(),
+    output_dir: output_dir.clone(),
+    write_pact: true
+  }
+}
+
+/// Construct a new iterator over the synchronous messages in the pact
+pub fn synchronous_messages_iter(
+  pact: V4Pact,
+  output_dir: &Option<PathBuf>,
+  generator_seed: Option<u64>
+) -> MessageIterator<SynchronousMessage> {
+  let original_messages = sync_messages(&pact);
+  let generate = generate_sync_messages(original_messages.clone(), generator_context(generator_seed));
+  let message_list = generate_blocking(original_messages, generate);
+
+  MessageIterator {
+    pact: pact.boxed(),
+    message_list: message_list.into(),
+    output_dir: output_dir.clone(),
+    write_pact: true
+  }
+}
+
+/// Construct a new iterator over the synchronous messages in the pact, awaiting the generation
+/// of the messages on the current runtime
+pub async fn synchronous_messages_iter_async(
+  pact: V4Pact,
+  output_dir: &Option<PathBuf>,
+  generator_seed: Option<u64>
+) -> MessageIterator<SynchronousMessage> {
+  let message_list = generate_sync_messages(sync_messages(&pact), generator_context(generator_seed)).await;
+
+  MessageIterator {
+    pact: pact.boxed(),
+    message_list: message_list.into(),
+    output_dir: output_dir.clone(),
+    write_pact: true
   }
 }
 
 /// Construct a new iterator over the messages in the Message Pact
 // TODO: This needs a mechanism to pass in the test context and plugin data
-pub fn messages_iter(pact: MessagePact, output_dir: &Option<PathBuf>) -> MessageIterator<Message> {
+pub fn messages_iter(
+  pact: MessagePact,
+  output_dir: &Option<PathBuf>,
+  generator_seed: Option<u64>
+) -> MessageIterator<Message> {
+  let context = generator_context(generator_seed);
   let original_messages = pact.messages.clone();
-  let (sx, rx) = channel();
-  match Handle::try_current() {
-    Ok(handle) => handle.spawn(async move {
-      let mut messages = VecDeque::new();
-      for message in original_messages {
-        messages.push_back(generate_message(&message, &GeneratorTestMode::Consumer, &hashmap!{}, &vec![], &hashmap!{}).await);
-      }
-      let _ = sx.send(messages);
-    }),
-    Err(err) => {
-      warn!("Could not access the Tokio runtime, will start a new one: {}", err);
-      tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .expect("Could not start a Tokio runtime for running async tasks")
-        .spawn(async move {
-          let mut messages = VecDeque::new();
-          for message in original_messages {
-            messages.push_back(generate_message(&message, &GeneratorTestMode::Consumer, &hashmap!{}, &vec![], &hashmap!{}).await);
-          }
-          let _ = sx.send(messages);
-        })
+  let messages = original_messages.clone();
+  let generate = async move {
+    let mut generated = vec![];
+    for message in messages {
+      generated.push(generate_message(&message, &GeneratorTestMode::Consumer, &context, &vec![], &hashmap!{}).await);
     }
+    generated
   };
+  let message_list = generate_blocking(original_messages, generate);
 
   MessageIterator {
     pact: pact.boxed(),
-    message_list: rx.recv().expect("Did not receive any messages"),
-    output_dir: output_dir.clone()
+    message_list: message_list.into(),
+    output_dir: output_dir.clone(),
+    write_pact: true
+  }
+}
+
+impl <MT: Interaction> MessageIterator<MT> {
+  /// Passes each message to the handler, collecting any errors returned. Panics from the handler
+  /// are also treated as errors. The Pact file is only written if the handler accepted all the
+  /// messages.
+  pub fn verify<F, E>(mut self, mut handler: F) -> Result<(), MessageVerificationError>
+    where F: FnMut(MT) -> Result<(), E>,
+          E: Display
+  {
+    let mut failures = vec![];
+    while let Some(message) = self.message_list.pop_front() {
+      let description = message.description();
+      debug!("Verifying message '{}'", description);
+      let result = catch_unwind(AssertUnwindSafe(|| handler(message)))
+        .map_err(panic_message)
+        .and_then(|result| result.map_err(|err| err.to_string()));
+      if let Err(error) = result {
+        failures.push(MessageFailure { description, error });
+      }
+    }
+    self.verification_result(failures)
+  }
+
+  /// Passes each message to the async handler, collecting any errors returned. Panics from the
+  /// handler are also treated as errors. The Pact file is only written if the handler accepted
+  /// all the messages.
+  pub async fn verify_async<F, Fut, E>(mut self, mut handler: F) -> Result<(), MessageVerificationError>
+    where F: FnMut(MT) -> Fut,
+          Fut: Future<Output = Result<(), E>>,
+          E: Display
+  {
+    let mut failures = vec![];
+    while let Some(message) = self.message_list.pop_front() {
+      let description = message.description();
+      debug!("Verifying message '{}'", description);
+      let result = AssertUnwindSafe(handler(message)).catch_unwind().await
+        .map_err(panic_message)
+        .and_then(|result| result.map_err(|err| err.to_string()));
+      if let Err(error) = result {
+        failures.push(MessageFailure { description, error });
+      }
+    }
+    self.verification_result(failures)
+  }
+
+  fn verification_result(&mut self, failures: Vec<MessageFailure>) -> Result<(), MessageVerificationError> {
+    if failures.is_empty() {
+      Ok(())
+    } else {
+      error!("{} message(s) were not accepted by the message handler, not writing the Pact file", failures.len());
+      self.write_pact = false;
+      Err(MessageVerificationError { failures })
+    }
+  }
+}
+
+fn panic_message(err: Box<dyn Any + Send>) -> String {
+  if let Some(message) = err.downcast_ref::<&str>() {
+    format!("handler panicked: {}", message)
+  } else if let Some(message) = err.downcast_ref::<String>() {
+    format!("handler panicked: {}", message)
+  } else {
+    "handler panicked".to_string()
   }
 }
 
@@ -146,7 +332,7 @@ impl <MT> Iterator for MessageIterator<MT> {
 
 impl <MT> Drop for MessageIterator<MT> {
   fn drop(&mut self) {
-    if !::std::thread::panicking() {
+    if self.write_pact && !::std::thread::panicking() {
 
       // Write out the Pact file
       let output_dir = self.output_dir.as_ref().map(|dir| dir.to_string_lossy().to_string())
@@ -2,6 +2,7 @@
 //! theoretically be moved into `pact_matching::models` at some future date,
 //! but that's currently undergoing heavy construction.
 
+mod form_builder;
 mod interaction_builder;
 mod http_part_builder;
 mod pact_builder;
@@ -11,8 +12,10 @@ mod request_builder;
 mod response_builder;
 mod message_builder;
 mod message_iter;
+#[cfg(feature = "multipart")] mod multipart_builder;
 mod sync_message_builder;
 
+pub use self::form_builder::*;
 pub use self::interaction_builder::*;
 pub use self::http_part_builder::*;
 pub use self::pact_builder::*;
@@ -20,6 +23,8 @@ pub use self::pact_builder_async::*;
 pub use self::request_builder::*;
 pub use self::response_builder::*;
 pub use self::message_builder::*;
+pub use self::message_iter::{MessageFailure, MessageVerificationError};
+#[cfg(feature = "multipart")] pub use self::multipart_builder::*;
 pub use self::sync_message_builder::*;
 
 #[test]
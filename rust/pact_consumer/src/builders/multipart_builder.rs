@@ -0,0 +1,203 @@
+//! Builder for MIME multipart (`multipart/form-data`) bodies
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use bytes::{Bytes, BytesMut};
+use pact_models::generators::Generator;
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
+use pact_models::path_exp::DocPath;
+
+use crate::patterns::{Pattern, StringPattern};
+
+/// Regex used to match the content type header of multipart bodies, as the boundary will be
+/// different for each request
+pub(crate) const MULTIPART_CONTENT_TYPE_REGEX: &str = r"multipart/form-data;(\s*charset=[^;]*;)?\s*boundary=.*";
+
+/// A part of a multipart body
+#[derive(Debug)]
+enum MultipartPart {
+  /// Text field
+  Text {
+    name: String,
+    value: StringPattern
+  },
+  /// File upload
+  File {
+    name: String,
+    filename: String,
+    content_type: String,
+    contents: Bytes
+  }
+}
+
+/// Builder for a MIME multipart form body, made up of text and file parts. Text parts can use a
+/// `StringPattern` to match the values, and file parts are matched on their content type.
+///
+/// ```
+/// use pact_consumer::prelude::*;
+/// use pact_consumer::builders::MultipartBuilder;
+///
+/// let body = MultipartBuilder::new()
+///   .text_part("name", "Baxter")
+///   .text_part("age", term!("^[0-9]+ months?$", "1 month"))
+///   .file_part("photo", "baxter.png", "image/png", vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+/// ```
+#[derive(Debug)]
+pub struct MultipartBuilder {
+  boundary: String,
+  parts: Vec<MultipartPart>
+}
+
+impl MultipartBuilder {
+  /// Create a new multipart body builder with a random boundary.
+  pub fn new() -> Self {
+    MultipartBuilder {
+      boundary: format!("----pact-boundary-{}", uuid::Uuid::new_v4().simple()),
+      parts: vec![]
+    }
+  }
+
+  /// Use the given boundary to separate the parts of the body.
+  pub fn boundary<S: Into<String>>(mut self, boundary: S) -> Self {
+    self.boundary = boundary.into();
+    self
+  }
+
+  /// Adds a text part to the body. The value can be a `StringPattern` to match the actual value
+  /// with.
+  pub fn text_part<N: Into<String>, V: Into<StringPattern>>(mut self, name: N, value: V) -> Self {
+    self.parts.push(MultipartPart::Text { name: name.into(), value: value.into() });
+    self
+  }
+
+  /// Adds a file part to the body. The actual file will be matched on its content type, which is
+  /// detected from the contents of the file.
+  pub fn file_part<N, F, C, B>(mut self, name: N, filename: F, content_type: C, contents: B) -> Self
+    where N: Into<String>, F: Into<String>, C: Into<String>, B: Into<Bytes>
+  {
+    self.parts.push(MultipartPart::File {
+      name: name.into(),
+      filename: filename.into(),
+      content_type: content_type.into(),
+      contents: contents.into()
+    });
+    self
+  }
+
+  /// Adds a file part to the body, loading the contents from the given file. The actual file will
+  /// be matched on its content type, which is detected from the contents of the file.
+  pub fn file_part_from_path<N, P, C>(self, name: N, path: P, content_type: C) -> anyhow::Result<Self>
+    where N: Into<String>, P: AsRef<Path>, C: Into<String>
+  {
+    let path = path.as_ref();
+    let contents = fs::read(path)
+      .with_context(|| format!("Failed to load multipart file '{}'", path.display()))?;
+    let filename = path.file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    Ok(self.file_part(name, filename, content_type, contents))
+  }
+
+  /// The value for the content type header for this body.
+  pub fn content_type(&self) -> String {
+    format!("multipart/form-data; boundary={}", self.boundary)
+  }
+
+  /// Generates the example body.
+  pub fn to_example_bytes(&self) -> Bytes {
+    let mut buffer = BytesMut::new();
+    for part in &self.parts {
+      buffer.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+      match part {
+        MultipartPart::Text { name, value } => {
+          buffer.extend_from_slice(format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", name).as_bytes());
+          buffer.extend_from_slice(value.to_example_bytes().as_slice());
+        }
+        MultipartPart::File { name, filename, content_type, contents } => {
+          buffer.extend_from_slice(format!("Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+            name, filename).as_bytes());
+          buffer.extend_from_slice(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+          buffer.extend_from_slice(contents);
+        }
+      }
+      buffer.extend_from_slice(b"\r\n");
+    }
+    buffer.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+    buffer.freeze()
+  }
+
+  /// Extracts the matching rules for the parts of the body.
+  pub fn extract_matching_rules(&self, rules_out: &mut MatchingRuleCategory) {
+    for part in &self.parts {
+      match part {
+        MultipartPart::Text { name, value } => {
+          value.extract_matching_rules(DocPath::root().join(name.as_str()), rules_out);
+        }
+        MultipartPart::File { name, content_type, .. } => {
+          rules_out.add_rule(DocPath::root().join(name.as_str()),
+            MatchingRule::ContentType(content_type.clone()), RuleLogic::And);
+        }
+      }
+    }
+  }
+
+  /// Extracts any generators for the text parts of the body.
+  pub fn extract_generators(&self, generators_out: &mut HashMap<DocPath, Generator>) {
+    for part in &self.parts {
+      if let MultipartPart::Text { name, value } = part {
+        value.extract_generators(DocPath::root().join(name.as_str()), generators_out);
+      }
+    }
+  }
+}
+
+impl Default for MultipartBuilder {
+  fn default() -> Self {
+    MultipartBuilder::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use regex::Regex;
+
+  use crate::patterns::Term;
+
+  use super::*;
+
+  #[test]
+  fn generates_the_multipart_body() {
+    let builder = MultipartBuilder::new()
+      .boundary("1234")
+      .text_part("name", "Baxter")
+      .file_part("file", "008.csv", "text/csv", "1,2,3,4");
+    expect!(builder.content_type()).to(be_equal_to("multipart/form-data; boundary=1234"));
+    expect!(builder.to_example_bytes()).to(be_equal_to(Bytes::from("--1234\r\n\
+      Content-Disposition: form-data; name=\"name\"\r\n\r\nBaxter\r\n\
+      --1234\r\n\
+      Content-Disposition: form-data; name=\"file\"; filename=\"008.csv\"\r\n\
+      Content-Type: text/csv\r\n\r\n\
+      1,2,3,4\r\n\
+      --1234--\r\n")));
+  }
+
+  #[test]
+  fn extracts_the_matching_rules_for_the_parts() {
+    let builder = MultipartBuilder::new()
+      .text_part("name", "Baxter")
+      .text_part("age", Term::new(Regex::new("^[0-9]+ months?$").unwrap(), "1 month"))
+      .file_part("file", "008.csv", "text/csv", "1,2,3,4");
+    let mut rules = MatchingRuleCategory::empty("body");
+    builder.extract_matching_rules(&mut rules);
+    expect!(rules).to(be_equal_to(matchingrules_list! {
+      "body";
+      "$.age" => [ MatchingRule::Regex("^[0-9]+ months?$".to_string()) ],
+      "$.file" => [ MatchingRule::ContentType("text/csv".to_string()) ]
+    }));
+  }
+}
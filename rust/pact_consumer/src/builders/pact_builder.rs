@@ -1,4 +1,4 @@
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::panic::RefUnwindSafe;
 use std::path::PathBuf;
 use pact_mock_server::mock_server::MockServerConfig;
@@ -25,11 +25,12 @@ use crate::builders::message_iter::{
   asynchronous_messages_iter,
   messages_iter,
   MessageIterator,
+  MessageVerificationError,
   synchronous_messages_iter
 };
 #[cfg(feature = "plugins")] use crate::builders::pact_builder_async::PactBuilderAsync;
 use crate::builders::sync_message_builder::SyncMessageInteractionBuilder;
-use crate::mock_server::http_mock_server::ValidatingHttpMockServer;
+use crate::mock_server::http_mock_server::{ReportOptions, ValidatingHttpMockServer};
 #[cfg(feature = "plugins")] use crate::mock_server::plugin_mock_server::PluginMockServer;
 use crate::PACT_CONSUMER_VERSION;
 use crate::prelude::*;
@@ -58,7 +59,9 @@ use super::interaction_builder::InteractionBuilder;
 /// ```
 pub struct PactBuilder {
   pact: Box<dyn Pact + Send + Sync + RefUnwindSafe>,
-  output_dir: Option<PathBuf>
+  output_dir: Option<PathBuf>,
+  generator_seed: Option<u64>,
+  pub(crate) reports: ReportOptions
 }
 
 impl PactBuilder {
@@ -84,7 +87,7 @@ impl PactBuilder {
           pact.add_md_version("consumer", version);
         }
 
-        PactBuilder { pact: pact.boxed(), output_dir: None }
+        PactBuilder { pact: pact.boxed(), output_dir: None, generator_seed: None, reports: ReportOptions::default() }
     }
 
   /// Create a new `PactBuilder`, specifying the names of the service
@@ -110,7 +113,7 @@ impl PactBuilder {
       pact.add_md_version("consumer", version);
     }
 
-    PactBuilder { pact: pact.boxed(), output_dir: None }
+    PactBuilder { pact: pact.boxed(), output_dir: None, generator_seed: None, reports: ReportOptions::default() }
   }
 
     /// Create a new `PactBuilder` for a V4 specification Pact, specifying the names of the service
@@ -133,7 +136,7 @@ impl PactBuilder {
         pact.add_md_version("consumer", version);
       }
 
-      PactBuilder { pact: pact.boxed(), output_dir: None }
+      PactBuilder { pact: pact.boxed(), output_dir: None, generator_seed: None, reports: ReportOptions::default() }
     }
 
   ///  Sets the output directory to write any pact files to. If this is not set, will default
@@ -144,6 +147,33 @@ impl PactBuilder {
     self
   }
 
+  /// Sets the seed for the random generators that are applied to the messages returned from the
+  /// message iterators, so that the same values are generated on every run. The HTTP mock server
+  /// does not take a generator context, so it uses the `PACT_GENERATOR_SEED` environment variable.
+  pub fn with_generator_seed(&mut self, seed: u64) -> &mut Self {
+    self.generator_seed = Some(seed);
+    self
+  }
+
+  /// Writes a report of the mismatches (`<consumer>-<provider>-<test name>-mismatches.json`,
+  /// along with a rendered diff of each request) to the Pact output directory when a mock server
+  /// started from this builder fails verification. This can also be enabled by setting the
+  /// `PACT_MISMATCH_REPORT` environment variable to `true`.
+  #[cfg(feature = "reports")]
+  pub fn with_mismatch_report(&mut self, enabled: bool) -> &mut Self {
+    self.reports.mismatch_report = enabled;
+    self
+  }
+
+  /// Merges the interaction coverage of each mock server started from this builder into the
+  /// coverage report (`<consumer>-<provider>-coverage.json`) in the Pact output directory. This
+  /// can also be enabled by setting the `PACT_COVERAGE_REPORT` environment variable to `true`.
+  #[cfg(feature = "reports")]
+  pub fn with_coverage_report(&mut self, enabled: bool) -> &mut Self {
+    self.reports.coverage_report = enabled;
+    self
+  }
+
     /// Add a plugin to be used by the test. Note this will return an async version of the Pact
     /// builder and requires the plugin crate feature.
     ///
@@ -170,7 +200,7 @@ impl PactBuilder {
         Err(err) => panic!("Could not load plugin - {}", err)
       }
 
-      PactBuilderAsync::from_builder(pact, self.output_dir.clone())
+      PactBuilderAsync::from_builder(pact, self.output_dir.clone(), self.generator_seed, self.reports.clone())
     }
 
     /// Add a new HTTP `Interaction` to the `Pact`. Needs to return a clone of the builder
@@ -256,7 +286,7 @@ impl PactBuilder {
       app_name: "pact_consumer".to_string(),
       app_version: env!("CARGO_PKG_VERSION").to_string()
     });
-    asynchronous_messages_iter(self.pact.as_v4_pact().unwrap(), &self.output_dir)
+    asynchronous_messages_iter(self.pact.as_v4_pact().unwrap(), &self.output_dir, self.generator_seed)
   }
 
   /// Returns an iterator over the asynchronous messages in a V3 Message Pact
@@ -267,7 +297,7 @@ impl PactBuilder {
       app_name: "pact_consumer".to_string(),
       app_version: env!("CARGO_PKG_VERSION").to_string()
     });
-    messages_iter(self.pact.as_message_pact().unwrap(), &self.output_dir)
+    messages_iter(self.pact.as_message_pact().unwrap(), &self.output_dir, self.generator_seed)
   }
 
   /// Returns an iterator over the synchronous req/res messages in the Pact
@@ -278,7 +308,37 @@ impl PactBuilder {
       app_name: "pact_consumer".to_string(),
       app_version: env!("CARGO_PKG_VERSION").to_string()
     });
-    synchronous_messages_iter(self.pact.as_v4_pact().unwrap(), &self.output_dir)
+    synchronous_messages_iter(self.pact.as_v4_pact().unwrap(), &self.output_dir, self.generator_seed)
+  }
+
+  /// Passes each asynchronous message in the Pact (with any generators applied) to the message
+  /// handler. Any errors returned from the handler are collected, and the Pact file is only
+  /// written if the handler accepted all the messages.
+  pub fn verify_messages<F, E>(&self, handler: F) -> Result<(), MessageVerificationError>
+    where F: FnMut(AsynchronousMessage) -> Result<(), E>,
+          E: Display
+  {
+    self.messages().verify(handler)
+  }
+
+  /// Passes each message in a V3 Message Pact (with any generators applied) to the message
+  /// handler. Any errors returned from the handler are collected, and the Pact file is only
+  /// written if the handler accepted all the messages.
+  pub fn verify_v3_messages<F, E>(&self, handler: F) -> Result<(), MessageVerificationError>
+    where F: FnMut(Message) -> Result<(), E>,
+          E: Display
+  {
+    self.v3_messages().verify(handler)
+  }
+
+  /// Passes each synchronous req/res message in the Pact (with any generators applied) to the
+  /// message handler. Any errors returned from the handler are collected, and the Pact file is
+  /// only written if the handler accepted all the messages.
+  pub fn verify_synchronous_messages<F, E>(&self, handler: F) -> Result<(), MessageVerificationError>
+    where F: FnMut(SynchronousMessage) -> Result<(), E>,
+          E: Display
+  {
+    self.synchronous_messages().verify(handler)
   }
 }
 
@@ -300,13 +360,13 @@ impl StartMockServer for PactBuilder {
           }
           None => panic!("Did not find a catalogue entry for key '{}'", entry_name)
         }
-        None => ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config)
+        None => ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config, self.reports.clone())
       }
     }
 
     #[cfg(not(feature = "plugins"))]
     {
-      ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config)
+      ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config, self.reports.clone())
     }
   }
 }
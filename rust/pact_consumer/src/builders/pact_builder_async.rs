@@ -1,3 +1,4 @@
+use std::fmt::Display;
 use std::future::Future;
 use std::path::PathBuf;
 
@@ -19,9 +20,14 @@ use tracing::trace;
 use pact_matching::metrics::{MetricEvent, send_metrics};
 
 use crate::builders::message_builder::MessageInteractionBuilder;
-use crate::builders::message_iter::{asynchronous_messages_iter, MessageIterator, synchronous_messages_iter};
+use crate::builders::message_iter::{
+  asynchronous_messages_iter_async,
+  MessageIterator,
+  MessageVerificationError,
+  synchronous_messages_iter_async
+};
 use crate::builders::sync_message_builder::SyncMessageInteractionBuilder;
-use crate::mock_server::http_mock_server::ValidatingHttpMockServer;
+use crate::mock_server::http_mock_server::{ReportOptions, ValidatingHttpMockServer};
 #[cfg(feature = "plugins")] use crate::mock_server::plugin_mock_server::PluginMockServer;
 use crate::mock_server::StartMockServerAsync;
 use crate::PACT_CONSUMER_VERSION;
@@ -55,7 +61,9 @@ use super::interaction_builder::InteractionBuilder;
 #[derive(Debug)]
 pub struct PactBuilderAsync {
   pact: Box<dyn Pact + Send + Sync>,
-  output_dir: Option<PathBuf>
+  output_dir: Option<PathBuf>,
+  generator_seed: Option<u64>,
+  pub(crate) reports: ReportOptions
 }
 
 impl PactBuilderAsync {
@@ -81,7 +89,7 @@ impl PactBuilderAsync {
           pact.add_md_version("consumer", version);
         }
 
-      PactBuilderAsync { pact: pact.boxed(), output_dir: None }
+      PactBuilderAsync { pact: pact.boxed(), output_dir: None, generator_seed: None, reports: ReportOptions::default() }
     }
 
     /// Create a new `PactBuilder` for a V4 specification Pact, specifying the names of the service
@@ -104,14 +112,21 @@ impl PactBuilderAsync {
         pact.add_md_version("consumer", version);
       }
 
-      PactBuilderAsync { pact: pact.boxed(), output_dir: None }
+      PactBuilderAsync { pact: pact.boxed(), output_dir: None, generator_seed: None, reports: ReportOptions::default() }
     }
 
     #[cfg(feature = "plugins")]
-    pub(crate) fn from_builder(pact: Box<dyn Pact + Send + Sync>, output_dir: Option<PathBuf>) -> Self {
+    pub(crate) fn from_builder(
+      pact: Box<dyn Pact + Send + Sync>,
+      output_dir: Option<PathBuf>,
+      generator_seed: Option<u64>,
+      reports: ReportOptions
+    ) -> Self {
       PactBuilderAsync {
         pact,
-        output_dir
+        output_dir,
+        generator_seed,
+        reports
       }
     }
 
@@ -187,6 +202,33 @@ impl PactBuilderAsync {
     self
   }
 
+  /// Sets the seed for the random generators that are applied to the messages returned from the
+  /// message iterators, so that the same values are generated on every run. The HTTP mock server
+  /// does not take a generator context, so it uses the `PACT_GENERATOR_SEED` environment variable.
+  pub fn with_generator_seed(&mut self, seed: u64) -> &mut Self {
+    self.generator_seed = Some(seed);
+    self
+  }
+
+  /// Writes a report of the mismatches (`<consumer>-<provider>-<test name>-mismatches.json`,
+  /// along with a rendered diff of each request) to the Pact output directory when a mock server
+  /// started from this builder fails verification. This can also be enabled by setting the
+  /// `PACT_MISMATCH_REPORT` environment variable to `true`.
+  #[cfg(feature = "reports")]
+  pub fn with_mismatch_report(&mut self, enabled: bool) -> &mut Self {
+    self.reports.mismatch_report = enabled;
+    self
+  }
+
+  /// Merges the interaction coverage of each mock server started from this builder into the
+  /// coverage report (`<consumer>-<provider>-coverage.json`) in the Pact output directory. This
+  /// can also be enabled by setting the `PACT_COVERAGE_REPORT` environment variable to `true`.
+  #[cfg(feature = "reports")]
+  pub fn with_coverage_report(&mut self, enabled: bool) -> &mut Self {
+    self.reports.coverage_report = enabled;
+    self
+  }
+
   /// Add a new Asynchronous message `Interaction` to the `Pact`. Needs to return a clone of the builder
   /// that is passed in.
   pub async fn message_interaction<D, F, O>(&mut self, description: D, build_fn: F) -> &mut Self
@@ -228,26 +270,48 @@ impl PactBuilderAsync {
     self.push_interaction(&interaction.build())
   }
 
-  /// Returns an iterator over the asynchronous messages in the Pact
-  pub fn messages(&self) -> MessageIterator<AsynchronousMessage> {
-    send_metrics(MetricEvent::ConsumerTestRun {
-      interactions: self.pact.interactions().len(),
-      test_framework: "pact_consumer".to_string(),
-      app_name: "pact_consumer".to_string(),
-      app_version: env!("CARGO_PKG_VERSION").to_string()
-    });
-    asynchronous_messages_iter(self.pact.as_v4_pact().unwrap(), &self.output_dir)
+  /// Returns an iterator over the asynchronous messages in the Pact (with any generators applied)
+  pub async fn messages(&self) -> MessageIterator<AsynchronousMessage> {
+    self.send_test_run_metrics();
+    asynchronous_messages_iter_async(self.pact.as_v4_pact().unwrap(), &self.output_dir, self.generator_seed).await
   }
 
-  /// Returns an iterator over the synchronous req/res messages in the Pact
-  pub fn synchronous_messages(&self) -> MessageIterator<SynchronousMessage> {
+  /// Returns an iterator over the synchronous req/res messages in the Pact (with any generators
+  /// applied)
+  pub async fn synchronous_messages(&self) -> MessageIterator<SynchronousMessage> {
+    self.send_test_run_metrics();
+    synchronous_messages_iter_async(self.pact.as_v4_pact().unwrap(), &self.output_dir, self.generator_seed).await
+  }
+
+  fn send_test_run_metrics(&self) {
     send_metrics(MetricEvent::ConsumerTestRun {
       interactions: self.pact.interactions().len(),
       test_framework: "pact_consumer".to_string(),
       app_name: "pact_consumer".to_string(),
       app_version: env!("CARGO_PKG_VERSION").to_string()
     });
-    synchronous_messages_iter(self.pact.as_v4_pact().unwrap(), &self.output_dir)
+  }
+
+  /// Passes each asynchronous message in the Pact (with any generators applied) to the async
+  /// message handler. Any errors returned from the handler are collected, and the Pact file is
+  /// only written if the handler accepted all the messages.
+  pub async fn verify_messages<F, Fut, E>(&self, handler: F) -> Result<(), MessageVerificationError>
+    where F: FnMut(AsynchronousMessage) -> Fut,
+          Fut: Future<Output = Result<(), E>>,
+          E: Display
+  {
+    self.messages().await.verify_async(handler).await
+  }
+
+  /// Passes each synchronous req/res message in the Pact (with any generators applied) to the
+  /// async message handler. Any errors returned from the handler are collected, and the Pact file
+  /// is only written if the handler accepted all the messages.
+  pub async fn verify_synchronous_messages<F, Fut, E>(&self, handler: F) -> Result<(), MessageVerificationError>
+    where F: FnMut(SynchronousMessage) -> Fut,
+          Fut: Future<Output = Result<(), E>>,
+          E: Display
+  {
+    self.synchronous_messages().await.verify_async(handler).await
   }
 }
 
@@ -269,13 +333,13 @@ impl StartMockServer for PactBuilderAsync {
           }
           None => panic!("Did not find a catalogue entry for key '{}'", entry_name)
         }
-        None => ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config)
+        None => ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config, self.reports.clone())
       }
     }
 
     #[cfg(not(feature = "plugins"))]
     {
-      ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config)
+      ValidatingHttpMockServer::start(self.build(), self.output_dir.clone(), mock_server_config, self.reports.clone())
     }
   }
 }
@@ -299,13 +363,13 @@ impl StartMockServerAsync for PactBuilderAsync {
           }
           None => panic!("Did not find a catalogue entry for key '{}'", entry_name)
         }
-        None => ValidatingHttpMockServer::start_async(self.build(), self.output_dir.clone(), mock_server_config).await
+        None => ValidatingHttpMockServer::start_async(self.build(), self.output_dir.clone(), mock_server_config, self.reports.clone()).await
       }
     }
 
     #[cfg(not(feature = "plugins"))]
     {
-      ValidatingHttpMockServer::start_async(self.build(), self.output_dir.clone(), mock_server_config).await
+      ValidatingHttpMockServer::start_async(self.build(), self.output_dir.clone(), mock_server_config, self.reports.clone()).await
     }
   }
 }
@@ -25,6 +25,9 @@ use serde_json::json;
 use serde_json::Value;
 #[allow(unused_imports)] use tracing::debug;
 
+use crate::builders::form_builder::FormBuilder;
+#[cfg(feature = "multipart")] use crate::builders::multipart_builder::{MULTIPART_CONTENT_TYPE_REGEX, MultipartBuilder};
+#[cfg(feature = "multipart")] use crate::patterns::build_regex;
 use crate::prelude::*;
 use crate::util::GetDefaulting;
 
@@ -146,6 +149,64 @@ impl RequestBuilder {
         self
     }
 
+    /// Specify a MIME multipart form body (multipart/form-data), made up of text and file parts.
+    /// The content type header will be set to match any multipart boundary.
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::builders::{MultipartBuilder, RequestBuilder};
+    ///
+    /// RequestBuilder::default()
+    ///     .post()
+    ///     .path("/dogs")
+    ///     .multipart_body(MultipartBuilder::new()
+    ///         .text_part("name", "Baxter")
+    ///         .file_part("data", "baxter.csv", "text/csv", "1,2,3,4"));
+    /// ```
+    #[cfg(feature = "multipart")]
+    pub fn multipart_body(&mut self, body: MultipartBuilder) -> &mut Self {
+        let content_type = body.content_type();
+        self.header("Content-Type", Term::<StringPattern>::new(
+            build_regex(MULTIPART_CONTENT_TYPE_REGEX), content_type.as_str()));
+        self.request.body = OptionalBody::Present(body.to_example_bytes(),
+            ContentType::parse(content_type.as_str()).ok(), None);
+        body.extract_matching_rules(self.request.matching_rules.add_category("body"));
+
+        let mut body_generators = HashMap::new();
+        body.extract_generators(&mut body_generators);
+        for (path, generator) in body_generators {
+            self.request.generators.add_generator_with_subcategory(&GeneratorCategory::BODY, path, generator);
+        }
+        self
+    }
+
+    /// Specify a URL encoded form body (application/x-www-form-urlencoded).
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::builders::{FormBuilder, RequestBuilder};
+    ///
+    /// RequestBuilder::default()
+    ///     .post()
+    ///     .path("/dogs")
+    ///     .form_body(FormBuilder::new()
+    ///         .field("name", "Baxter")
+    ///         .field("age", term!("^[0-9]+$", "3")));
+    /// ```
+    pub fn form_body(&mut self, body: FormBuilder) -> &mut Self {
+        self.header("Content-Type", "application/x-www-form-urlencoded");
+        self.request.body = OptionalBody::Present(body.to_example().into(),
+            Some("application/x-www-form-urlencoded".into()), None);
+        body.extract_matching_rules(self.request.matching_rules.add_category("body"));
+
+        let mut body_generators = HashMap::new();
+        body.extract_generators(&mut body_generators);
+        for (path, generator) in body_generators {
+            self.request.generators.add_generator_with_subcategory(&GeneratorCategory::BODY, path, generator);
+        }
+        self
+    }
+
     /// Build the specified `Request` object.
     pub fn build(&self) -> Request {
          self.request.as_v3_request()
@@ -418,3 +479,113 @@ fn term_does_not_require_anchors() {
     assert_requests_do_not_match!(bad1, pattern);
     assert_requests_do_not_match!(bad2, pattern);
 }
+
+#[test]
+#[cfg(feature = "multipart")]
+fn multipart_body() {
+    use expectest::prelude::*;
+    use pact_models::http_parts::HttpPart;
+    use pact_models::matchingrules::MatchingRule;
+    use pact_models::matchingrules_list;
+
+    let pattern = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.post().path("/dogs").multipart_body(MultipartBuilder::new()
+                .boundary("1234")
+                .text_part("name", "Baxter")
+                .file_part("data", "baxter.csv", "text/csv", "1,2,3,4"));
+            i
+        })
+        .build();
+    let interaction = pattern.interactions().first().unwrap().as_v4_http().unwrap();
+    expect!(interaction.request.lookup_header_value("content-type")).to(
+        be_some().value("multipart/form-data; boundary=1234".to_string()));
+    expect!(interaction.request.body.value_as_string()).to(be_some().value("--1234\r\n\
+      Content-Disposition: form-data; name=\"name\"\r\n\r\nBaxter\r\n\
+      --1234\r\n\
+      Content-Disposition: form-data; name=\"data\"; filename=\"baxter.csv\"\r\n\
+      Content-Type: text/csv\r\n\r\n\
+      1,2,3,4\r\n\
+      --1234--\r\n".to_string()));
+    expect!(interaction.request.matching_rules.rules_for_category("header")).to(be_some().value(matchingrules_list! {
+        "header"; "$['Content-Type']" => [ MatchingRule::Regex(MULTIPART_CONTENT_TYPE_REGEX.to_string()) ]
+    }));
+    expect!(interaction.request.matching_rules.rules_for_category("body")).to(be_some().value(matchingrules_list! {
+        "body"; "$.data" => [ MatchingRule::ContentType("text/csv".to_string()) ]
+    }));
+}
+
+#[test]
+#[cfg(feature = "multipart")]
+fn multipart_body_with_generators() {
+    use expectest::prelude::*;
+    use pact_models::matchingrules::MatchingRuleCategory;
+    use crate::patterns::{Pattern, StringPattern};
+
+    // String pattern that generates a random integer string
+    #[derive(Debug)]
+    struct GeneratedId;
+
+    impl Pattern for GeneratedId {
+        type Matches = String;
+
+        fn to_example(&self) -> String {
+            "100".to_string()
+        }
+
+        fn to_example_bytes(&self) -> Vec<u8> {
+            self.to_example().into_bytes()
+        }
+
+        fn extract_matching_rules(&self, _path: DocPath, _rules_out: &mut MatchingRuleCategory) {}
+
+        fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+            generators_out.insert(path, Generator::RandomInt(1, 1000));
+        }
+    }
+
+    let pattern = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.post().path("/dogs").multipart_body(MultipartBuilder::new()
+                .text_part("name", "Baxter")
+                .text_part("id", StringPattern::pattern(GeneratedId))
+                .file_part("data", "baxter.csv", "text/csv", "1,2,3,4"));
+            i
+        })
+        .build();
+    let interaction = pattern.interactions().first().unwrap().as_v4_http().unwrap();
+    let mut expected = Generators::default();
+    expected.add_generator_with_subcategory(&GeneratorCategory::BODY, DocPath::new_unwrap("$.id"),
+        Generator::RandomInt(1, 1000));
+    expect!(interaction.request.generators).to(be_equal_to(expected));
+}
+
+#[test]
+fn form_body() {
+    let pattern = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.post().path("/dogs").form_body(FormBuilder::new()
+                .field("name", "Baxter")
+                .field("age", Term::new(Regex::new("^[0-9]+$").unwrap(), "3")));
+            i
+        })
+        .build();
+    let good = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.post().path("/dogs")
+                .content_type("application/x-www-form-urlencoded")
+                .body("name=Baxter&age=12");
+            i
+        })
+        .build();
+    let bad = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.post().path("/dogs")
+                .content_type("application/x-www-form-urlencoded")
+                .body("name=Baxter&age=twelve");
+            i
+        })
+        .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+}
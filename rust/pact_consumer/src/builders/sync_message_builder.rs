@@ -21,7 +21,7 @@ use pact_models::v4::interaction::InteractionMarkup;
 use pact_models::v4::message_parts::MessageContents;
 use pact_models::v4::sync_message::SynchronousMessage;
 
-use crate::builders::message_builder::{InteractionContents, PluginConfiguration};
+use crate::builders::message_builder::{body_generators, InteractionContents, PluginConfiguration};
 #[cfg(not(feature = "plugins"))] use crate::builders::message_builder::PactPluginManifest;
 use crate::prelude::{JsonPattern, Pattern};
 #[cfg(feature = "plugins")] use crate::prelude::PluginInteractionBuilder;
@@ -428,6 +428,10 @@ impl SyncMessageInteractionBuilder {
           Some(mr) => mr.add_rules(rules.clone())
         }
       }
+      if let Some(body_generators) = body_generators(&body) {
+        let generators = self.request_contents.generators.get_or_insert_with(Generators::default);
+        generators.add_generators(body_generators);
+      }
     }
     self
   }
@@ -497,6 +501,7 @@ impl SyncMessageInteractionBuilder {
         part_name: "response".to_string(),
         body: message_body.clone(),
         rules: if rules.is_not_empty() { Some(rules) } else { None },
+        generators: body_generators(&body),
         .. InteractionContents::default()
       });
     }
@@ -0,0 +1,337 @@
+//! Support for the `#[pact_consumer::test]` attribute macro. Each test gets its own Pact builder,
+//! which writes the Pact file to a temporary directory for that test. Once the test has passed,
+//! the Pact file is merged into the output directory while holding an exclusive lock, so tests
+//! running in parallel (or in separate processes) don't clobber each other's interactions.
+//!
+//! The test can either start mock servers from the builder, or use the mock server provided by
+//! the harness (`PactTest::mock_server`), which is started the first time it is used and shut
+//! down when the test finishes.
+
+use std::{env, fs};
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use fs2::FileExt;
+use pact_models::pact::{Pact, read_pact, write_pact};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::builders::{PactBuilder, PactBuilderAsync};
+use crate::mock_server::{StartMockServer, StartMockServerAsync, ValidatingMockServer};
+use crate::mock_server::coverage::{CoverageReport, write_coverage_report};
+
+/// Pact builders that can be used with a `PactTest`
+pub trait PactTestBuilder {
+  /// Creates a new V4 Pact builder for the consumer and provider, that writes the Pact file to
+  /// the test directory. Any mismatch reports are written to the report directory, as the test
+  /// directory is removed once the test is done.
+  fn new_for_test(consumer: &str, provider: &str, test_name: &str, test_dir: &Path, report_dir: &Path) -> Self;
+}
+
+impl PactTestBuilder for PactBuilder {
+  fn new_for_test(consumer: &str, provider: &str, test_name: &str, test_dir: &Path, report_dir: &Path) -> Self {
+    let mut builder = PactBuilder::new_v4(consumer, provider);
+    builder.with_output_dir(test_dir);
+    builder.reports.report_dir = Some(report_dir.to_path_buf());
+    builder.reports.test_name = Some(test_name.to_string());
+    builder
+  }
+}
+
+impl PactTestBuilder for PactBuilderAsync {
+  fn new_for_test(consumer: &str, provider: &str, test_name: &str, test_dir: &Path, report_dir: &Path) -> Self {
+    let mut builder = PactBuilderAsync::new_v4(consumer, provider);
+    builder.output_dir(test_dir);
+    builder.reports.report_dir = Some(report_dir.to_path_buf());
+    builder.reports.test_name = Some(test_name.to_string());
+    builder
+  }
+}
+
+/// Result of a test function. The Pact file is only merged into the output directory if the
+/// test was successful.
+pub trait TestOutcome {
+  /// If the test passed
+  fn is_success(&self) -> bool;
+}
+
+impl TestOutcome for () {
+  fn is_success(&self) -> bool {
+    true
+  }
+}
+
+impl <T, E> TestOutcome for Result<T, E> {
+  fn is_success(&self) -> bool {
+    self.is_ok()
+  }
+}
+
+/// State for a single consumer Pact test. This dereferences to the Pact builder, so it can be used
+/// to set up the interactions and start the mock server.
+pub struct PactTest<B: PactTestBuilder> {
+  builder: B,
+  test_name: String,
+  // Temporary directory the Pact file for this test is written to
+  test_dir: PathBuf,
+  // Directory the Pact file will be merged into
+  output_dir: PathBuf,
+  // Mock server provided by the harness, started the first time it is used
+  mock_server: Option<Box<dyn ValidatingMockServer>>
+}
+
+impl <B: PactTestBuilder> PactTest<B> {
+  /// Creates the state for the test. If the output directory is not provided, it will default to
+  /// the `PACT_OUTPUT_DIR` environment variable or `target/pacts`.
+  pub fn new(consumer: &str, provider: &str, test_name: &str, output_dir: Option<&str>) -> Self {
+    let output_dir = output_dir.map(PathBuf::from)
+      .unwrap_or_else(|| {
+        let val = env::var("PACT_OUTPUT_DIR");
+        debug!("env:PACT_OUTPUT_DIR = {:?}", val);
+        PathBuf::from(val.unwrap_or_else(|_| "target/pacts".to_owned()))
+      });
+    let test_dir = env::temp_dir()
+      .join("pact-tests")
+      .join(format!("{}-{}", test_name.replace("::", "-"), Uuid::new_v4()));
+    debug!("Pact test '{}' will write Pact files to {}", test_name, test_dir.display());
+
+    PactTest {
+      builder: B::new_for_test(consumer, provider, test_name, &test_dir, &output_dir),
+      test_name: test_name.to_string(),
+      test_dir,
+      output_dir,
+      mock_server: None
+    }
+  }
+
+  /// Name of the test
+  pub fn test_name(&self) -> &str {
+    self.test_name.as_str()
+  }
+
+  /// Shuts down the mock server provided by the harness (if it was started), and merges any Pact
+  /// files written by the test into the output directory, if the test was successful.
+  ///
+  /// Panics:
+  /// Will panic if the mock server did not receive the expected requests, or if the Pact files
+  /// can not be merged.
+  pub fn finish<R: TestOutcome>(&mut self, result: &R) {
+    // The mock server writes the Pact file when it is shut down
+    drop(self.mock_server.take());
+
+    if result.is_success() {
+      if let Err(err) = merge_pact_files(&self.test_dir, &self.output_dir) {
+        panic!("Failed to merge the Pact file for test '{}' - {}", self.test_name, err);
+      }
+    } else {
+      warn!("Test '{}' failed, not writing the Pact file", self.test_name);
+    }
+  }
+}
+
+impl PactTest<PactBuilder> {
+  /// Mock server for the interactions set up with the builder. This is started the first time
+  /// it is used, so all the interactions must be set up before then. The mock server is shut down
+  /// (and verified) when the test finishes.
+  pub fn mock_server(&mut self) -> &dyn ValidatingMockServer {
+    if self.mock_server.is_none() {
+      self.mock_server = Some(self.builder.start_mock_server(None, None));
+    }
+    self.mock_server.as_deref().unwrap()
+  }
+}
+
+impl PactTest<PactBuilderAsync> {
+  /// Mock server for the interactions set up with the builder. This is started the first time
+  /// it is used, so all the interactions must be set up before then. The mock server is shut down
+  /// (and verified) when the test finishes.
+  pub async fn mock_server(&mut self) -> &dyn ValidatingMockServer {
+    if self.mock_server.is_none() {
+      self.mock_server = Some(self.builder.start_mock_server_async(None, None).await);
+    }
+    self.mock_server.as_deref().unwrap()
+  }
+}
+
+impl <B: PactTestBuilder> Deref for PactTest<B> {
+  type Target = B;
+
+  fn deref(&self) -> &Self::Target {
+    &self.builder
+  }
+}
+
+impl <B: PactTestBuilder> DerefMut for PactTest<B> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.builder
+  }
+}
+
+impl <B: PactTestBuilder> Drop for PactTest<B> {
+  fn drop(&mut self) {
+    // Shut down the mock server before the test directory it writes to is removed
+    drop(self.mock_server.take());
+
+    if self.test_dir.exists() {
+      if let Err(err) = fs::remove_dir_all(&self.test_dir) {
+        warn!("Failed to remove the test directory {} - {}", self.test_dir.display(), err);
+      }
+    }
+  }
+}
+
+/// Merges all the Pact files in the source directory into the output directory. An exclusive
+/// lock is held on a lock file for each Pact file, so that the read-merge-write can not be
+/// interleaved with another test. Any interaction coverage reports are also merged.
+pub fn merge_pact_files(source_dir: &Path, output_dir: &Path) -> anyhow::Result<()> {
+  if !source_dir.exists() {
+    debug!("No Pact files were written to {}", source_dir.display());
+    return Ok(());
+  }
+
+  fs::create_dir_all(output_dir)?;
+  for entry in fs::read_dir(source_dir)? {
+    let path = entry?.path();
+    let file_name = path.file_name()
+      .ok_or_else(|| anyhow!("{} is not a valid Pact file name", path.display()))?
+      .to_string_lossy()
+      .to_string();
+    if let Some(name) = file_name.strip_suffix("-coverage.json") {
+      let report = CoverageReport::read(&path)?;
+      write_coverage_report(output_dir, name, &report)?;
+    } else if file_name.ends_with(".json") && !file_name.ends_with("-mismatches.json") {
+      let pact = read_pact(&path)?;
+      let target = output_dir.join(&file_name);
+
+      let lock_file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(output_dir.join(format!("{}.lock", file_name)))?;
+      lock_file.lock_exclusive()?;
+      info!("Merging Pact file {} into {}", path.display(), target.display());
+      let result = write_pact(pact.boxed(), &target, pact.specification_version(), false);
+      if let Err(err) = FileExt::unlock(&lock_file) {
+        warn!("Failed to release the lock on {} - {}", target.display(), err);
+      }
+      result?;
+    }
+  }
+  Ok(())
+}
+
+/// Runs the future for an async Pact test to completion on a new multi-threaded Tokio runtime
+pub fn block_on<F: Future>(future: F) -> F::Output {
+  tokio::runtime::Builder::new_multi_thread()
+    .enable_all()
+    .build()
+    .expect("Could not start a Tokio runtime for running the Pact test")
+    .block_on(future)
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+
+  use super::*;
+
+  #[test]
+  fn finish_merges_the_pact_files_from_each_test() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let output_path = output_dir.path().to_string_lossy().to_string();
+
+    for description in ["first interaction", "second interaction"] {
+      let mut test = PactTest::<PactBuilder>::new("harness-consumer", "harness-provider",
+        "harness::tests::merge", Some(output_path.as_str()));
+      test.message_interaction(description, |i| i);
+      drop(test.messages());
+      test.finish(&());
+    }
+
+    let pact = read_pact(&output_dir.path().join("harness-consumer-harness-provider.json")).unwrap();
+    expect!(pact.interactions().len()).to(be_equal_to(2));
+  }
+
+  #[test]
+  fn finish_does_not_write_the_pact_file_if_the_test_failed() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let output_path = output_dir.path().to_string_lossy().to_string();
+
+    let mut test = PactTest::<PactBuilder>::new("harness-consumer", "harness-provider",
+      "harness::tests::failed", Some(output_path.as_str()));
+    test.message_interaction("an interaction", |i| i);
+    drop(test.messages());
+    let test_dir = test.test_dir.clone();
+    expect!(test_dir.exists()).to(be_true());
+    test.finish(&Err::<(), _>("failed"));
+    drop(test);
+
+    expect!(output_dir.path().join("harness-consumer-harness-provider.json").exists()).to(be_false());
+    expect!(test_dir.exists()).to(be_false());
+  }
+
+  #[test]
+  fn finish_shuts_down_the_mock_server_and_merges_the_pact_file() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let output_path = output_dir.path().to_string_lossy().to_string();
+
+    let mut test = PactTest::<PactBuilder>::new("harness-consumer", "harness-http-provider",
+      "harness::tests::mock_server", Some(output_path.as_str()));
+    test.interaction("a retrieve Mallory request", "", |mut i| {
+      i.request.path("/mallory");
+      i.response.ok().body("That is some good Mallory.");
+      i
+    });
+    let url = test.mock_server().path("/mallory");
+    let response = reqwest::blocking::get(url).unwrap();
+    expect!(response.text().unwrap()).to(be_equal_to("That is some good Mallory."));
+    test.finish(&());
+
+    let pact = read_pact(&output_dir.path().join("harness-consumer-harness-http-provider.json")).unwrap();
+    expect!(pact.interactions().len()).to(be_equal_to(1));
+  }
+
+  #[test]
+  fn mismatch_reports_are_written_to_the_output_directory() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let output_path = output_dir.path().to_string_lossy().to_string();
+
+    let mut test = PactTest::<PactBuilder>::new("harness-consumer", "harness-provider",
+      "harness::tests::mismatches", Some(output_path.as_str()));
+    test.with_mismatch_report(true);
+    test.interaction("a request that is never made", "", |mut i| {
+      i.request.path("/missing");
+      i
+    });
+    let mock_server = test.start_mock_server(None, None);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(mock_server)));
+    expect!(result.is_err()).to(be_true());
+    test.finish(&Err::<(), _>("failed"));
+    drop(test);
+
+    expect!(output_dir.path().join("harness-consumer-harness-provider-harness-tests-mismatches-mismatches.json").exists())
+      .to(be_true());
+  }
+
+  #[test]
+  fn merge_pact_files_merges_the_coverage_reports() {
+    let source_dir = tempfile::tempdir().unwrap();
+    let output_dir = tempfile::tempdir().unwrap();
+    let report = CoverageReport {
+      consumer: "consumer".to_string(),
+      provider: "provider".to_string(),
+      mock_servers: 1,
+      .. CoverageReport::default()
+    };
+    write_coverage_report(source_dir.path(), "consumer-provider", &report).unwrap();
+    write_coverage_report(output_dir.path(), "consumer-provider", &report).unwrap();
+
+    merge_pact_files(source_dir.path(), output_dir.path()).unwrap();
+
+    let merged = CoverageReport::read(&output_dir.path().join("consumer-provider-coverage.json")).unwrap();
+    expect!(merged.mock_servers).to(be_equal_to(2));
+  }
+}
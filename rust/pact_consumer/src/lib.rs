@@ -148,6 +148,41 @@
 //! fixtures. This can be done using the string passed to `given` in the
 //! examples above.
 //!
+//! There are patterns for all the other matching rules as well, such as
+//! `integer!`, `decimal!`, `number!`, `boolean!`, `null_value!`, `includes!`,
+//! `semver!`, `not_empty!`, `content_type!`, `array_containing!` and (with the
+//! `datetime` feature) `datetime!`, `date!` and `time!`:
+//!
+//! ```
+//! use pact_consumer::prelude::*;
+//!
+//! json_pattern!({
+//!     "id": integer!(1234),
+//!     "price": decimal!(10.99),
+//!     "active": boolean!(true),
+//!     "version": semver!("1.2.0"),
+//!     "name": not_empty!("Eureka!"),
+//!     "deleted_at": null_value!(),
+//!     "tags": array_containing!(["greek", includes!("sci", "science")]),
+//!     "created": date!("yyyy-MM-dd", "2024-01-31"),
+//! });
+//! ```
+//!
+//! Some patterns also add a generator, which replaces the example value with a
+//! new one when the interaction is used. For example, `uuid()` and
+//! `random_int(min, max)` generate random values, while `from_provider_state`
+//! uses a value returned from the provider state callback during verification:
+//!
+//! ```
+//! use pact_consumer::prelude::*;
+//!
+//! json_pattern!({
+//!     "id": uuid(),
+//!     "count": random_int(1, 10),
+//!     "link": from_provider_state("/orders/${id}", "/orders/100"),
+//! });
+//! ```
+//!
 //! ## Testing using domain objects
 //!
 //! Normally, it's best to generate your JSON using your actual domain objects.
@@ -379,9 +414,16 @@ mod test_support;
 
 // Other child modules.
 pub mod builders;
+#[cfg(feature = "macros")]
+pub mod harness;
 pub mod mock_server;
+#[cfg(feature = "recorder")]
+pub mod recorder;
 pub mod util;
 
+#[cfg(feature = "macros")]
+pub use pact_consumer_macros::test;
+
 /// A "prelude" or a default list of import types to include. This includes
 /// the basic DSL, but it avoids including rarely-used types.
 ///
@@ -394,10 +436,22 @@ pub mod prelude {
         each_like,
         each_like_helper,
         term,
+        integer,
+        decimal,
+        number,
+        boolean,
+        null_value,
+        includes,
+        semver,
+        not_empty,
+        content_type,
+        array_containing,
+        one_of,
         json_pattern,
         json_pattern_internal
     };
     pub use crate::builders::{HttpPartBuilder, PactBuilder, PactBuilderAsync};
+    #[cfg(feature = "macros")] pub use crate::harness::PactTest;
     #[cfg(feature = "plugins")] pub use crate::builders::plugin_builder::PluginInteractionBuilder;
     pub use crate::mock_server::{StartMockServer, ValidatingMockServer};
     pub use crate::patterns::{
@@ -407,15 +461,33 @@ pub mod prelude {
         ObjectMatching,
         EachKey,
         EachValue,
+        Integer,
+        Decimal,
+        Number,
+        Boolean,
+        Null,
+        Include,
+        Semver,
+        NotEmpty,
+        ContentType,
+        ArrayContains,
+        OneOf,
+        Generated,
         JsonPattern,
         Pattern,
         StringPattern,
         each_key,
-        each_value
+        each_value,
+        uuid,
+        random_int,
+        from_provider_state
     };
-    #[cfg(feature = "datetime")] pub use crate::patterns::{DateTime};
+    #[cfg(feature = "datetime")] pub use crate::{datetime, date, time};
+    #[cfg(feature = "datetime")] pub use crate::patterns::{DateTime, Date, Time, date_generated};
+    #[cfg(feature = "xml")] pub use crate::patterns::{XmlElement, XmlPattern};
     pub use crate::util::strip_null_fields;
     pub use pact_mock_server::mock_server::MockServerConfig;
+    pub use pact_models::generators::{FakeKind, Generator, GeneratorCategory};
 }
 
 /// Consumer version
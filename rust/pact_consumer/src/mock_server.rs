@@ -8,9 +8,14 @@ use url::Url;
 use pact_mock_server::matching::MatchResult;
 use pact_mock_server::mock_server::{MockServerConfig, MockServerMetrics};
 
-use crate::mock_server::http_mock_server::ValidatingHttpMockServer;
+use crate::mock_server::http_mock_server::{ReportOptions, ValidatingHttpMockServer};
 
+#[cfg(feature = "reports")]
+pub use crate::mock_server::coverage::{CoverageReport, InteractionCoverage, MatchingRuleCoverage, UnmatchedRequest};
+
+#[cfg(feature = "reports")] pub(crate) mod coverage;
 pub(crate) mod http_mock_server;
+#[cfg(feature = "reports")] pub(crate) mod mismatch_report;
 #[cfg(feature = "plugins")] pub(crate) mod plugin_mock_server;
 
 /// A mock server that handles the requests described in a `Pact`, intended
@@ -66,7 +71,7 @@ impl StartMockServer for RequestResponsePact {
     _catalog_entry: Option<&str>,
     mock_server_config: Option<MockServerConfig>
   ) -> Box<dyn ValidatingMockServer> {
-    ValidatingHttpMockServer::start(self.boxed(), None, mock_server_config)
+    ValidatingHttpMockServer::start(self.boxed(), None, mock_server_config, ReportOptions::default())
   }
 }
 
@@ -77,6 +82,6 @@ impl StartMockServerAsync for RequestResponsePact {
     _catalog_entry: Option<&str>,
     mock_server_config: Option<MockServerConfig>
   ) -> Box<dyn ValidatingMockServer> {
-    ValidatingHttpMockServer::start_async(self.boxed(), None, mock_server_config).await
+    ValidatingHttpMockServer::start_async(self.boxed(), None, mock_server_config, ReportOptions::default()).await
   }
 }
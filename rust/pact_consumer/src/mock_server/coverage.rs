@@ -0,0 +1,505 @@
+//! Interaction coverage reports for mock servers. The report records how many times each
+//! interaction in the Pact was matched, the requests that did not match any interaction, and which
+//! of the request matching rules were exercised by the requests made in the tests.
+//!
+//! The reports from each mock server are merged into a single report file in the Pact output
+//! directory (while holding an exclusive lock on it), so the report covers all the tests in a run.
+//! A report left over from a previous run is replaced instead of merged. Interactions that were
+//! never called are candidates for removal from the contract.
+
+use std::collections::HashMap;
+use std::{env, fs};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+use lazy_static::lazy_static;
+use pact_mock_server::matching::MatchResult;
+use pact_models::json_utils::resolve_path;
+use pact_models::matchingrules::Category;
+use pact_models::pact::Pact;
+use pact_models::path_exp::DocPath;
+use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use pact_models::v4::interaction::V4Interaction;
+use pact_models::v4::synch_http::SynchronousHttp;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+lazy_static! {
+  /// Identifies the test run the coverage reports are from. This is the `PACT_COVERAGE_RUN_ID`
+  /// environment variable if it is set. Otherwise, it is the ID of the parent process (i.e. the
+  /// `cargo test` process that runs each of the test binaries) on Unix, or the ID of the test
+  /// process on other platforms.
+  static ref RUN_ID: String = env::var("PACT_COVERAGE_RUN_ID")
+    .unwrap_or_else(|_| default_run_id());
+}
+
+#[cfg(unix)]
+fn default_run_id() -> String {
+  format!("ppid-{}", std::os::unix::process::parent_id())
+}
+
+#[cfg(not(unix))]
+fn default_run_id() -> String {
+  format!("pid-{}", std::process::id())
+}
+
+/// Coverage of the interactions in a Pact by the consumer tests
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageReport {
+  /// Consumer name
+  pub consumer: String,
+  /// Provider name
+  pub provider: String,
+  /// Test run the report is from. Reports from previous runs are replaced, not merged.
+  #[serde(default)]
+  pub run_id: String,
+  /// Number of mock servers (i.e. tests) the report has been aggregated from
+  pub mock_servers: usize,
+  /// Coverage for each HTTP interaction in the Pact
+  pub interactions: Vec<InteractionCoverage>,
+  /// Requests received by the mock servers that did not match an interaction
+  pub unmatched_requests: Vec<UnmatchedRequest>,
+  /// Descriptions of the interactions that were never called
+  #[serde(default)]
+  pub never_called: Vec<String>
+}
+
+/// Coverage of a single interaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InteractionCoverage {
+  /// Unique key of the interaction
+  #[serde(default)]
+  pub key: String,
+  /// Interaction description
+  pub description: String,
+  /// Provider state names for the interaction
+  pub provider_states: Vec<String>,
+  /// Number of requests that matched the interaction
+  pub hits: usize,
+  /// Request matching rules for the interaction
+  pub matching_rules: Vec<MatchingRuleCoverage>
+}
+
+/// Number of times a request matching rule was applied to a value in an actual request
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchingRuleCoverage {
+  /// Request category (path, query, header or body) the rule applies to
+  pub category: String,
+  /// Path the rule applies to
+  pub path: String,
+  /// Names of the matching rules (i.e. `type`, `regex`)
+  pub rules: Vec<String>,
+  /// Number of requests that had a value the rule was applied to
+  pub exercised: usize
+}
+
+/// Request that did not match any interaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmatchedRequest {
+  /// Request method
+  pub method: String,
+  /// Request path
+  pub path: String,
+  /// Type of result (`request-mismatch` or `request-not-found`)
+  #[serde(rename = "type")]
+  pub result_type: String,
+  /// Number of times the request was received
+  pub count: usize
+}
+
+impl CoverageReport {
+  /// Creates the coverage report for a single mock server from the Pact and all the match results
+  pub fn from_results(pact: &dyn Pact, results: &[MatchResult]) -> CoverageReport {
+    let http_interactions = pact.interactions().iter()
+      .filter_map(|interaction| interaction.as_v4_http())
+      .collect::<Vec<_>>();
+
+    // Each matched request is attributed to a single interaction, so interactions with the same
+    // request (i.e. with different provider states) are not all counted as hit
+    let mut matched: HashMap<String, Vec<&HttpRequest>> = HashMap::new();
+    for result in results {
+      if let MatchResult::RequestMatch(expected, response, actual) = result {
+        if let Some(interaction) = matched_interaction(&http_interactions, expected, response) {
+          matched.entry(interaction.unique_key()).or_default().push(actual);
+        }
+      }
+    }
+
+    let interactions = http_interactions.iter()
+      .map(|interaction| {
+        let key = interaction.unique_key();
+        let matched = matched.get(&key).cloned().unwrap_or_default();
+        InteractionCoverage {
+          key,
+          description: interaction.description.clone(),
+          provider_states: interaction.provider_states.iter().map(|state| state.name.clone()).collect(),
+          hits: matched.len(),
+          matching_rules: rule_coverage(&interaction.request, &matched)
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let mut unmatched_requests: Vec<UnmatchedRequest> = vec![];
+    for result in results {
+      let request = match result {
+        MatchResult::RequestMismatch(_, actual, _) => Some((actual, "request-mismatch")),
+        MatchResult::RequestNotFound(actual) => Some((actual, "request-not-found")),
+        _ => None
+      };
+      if let Some((request, result_type)) = request {
+        merge_unmatched(&mut unmatched_requests, UnmatchedRequest {
+          method: request.method.to_uppercase(),
+          path: request.path.clone(),
+          result_type: result_type.to_string(),
+          count: 1
+        });
+      }
+    }
+
+    let mut report = CoverageReport {
+      consumer: pact.consumer().name,
+      provider: pact.provider().name,
+      run_id: RUN_ID.clone(),
+      mock_servers: 1,
+      interactions,
+      unmatched_requests,
+      never_called: vec![]
+    };
+    report.update_never_called();
+    report
+  }
+
+  /// Merges the other report into this one, adding together the hit and request counts
+  pub fn merge(&mut self, other: &CoverageReport) {
+    self.mock_servers += other.mock_servers;
+
+    for interaction in &other.interactions {
+      match self.interactions.iter_mut()
+        .find(|i| same_interaction(i, interaction)) {
+        Some(existing) => {
+          existing.hits += interaction.hits;
+          for rule in &interaction.matching_rules {
+            match existing.matching_rules.iter_mut()
+              .find(|r| r.category == rule.category && r.path == rule.path) {
+              Some(existing_rule) => existing_rule.exercised += rule.exercised,
+              None => existing.matching_rules.push(rule.clone())
+            }
+          }
+        }
+        None => self.interactions.push(interaction.clone())
+      }
+    }
+
+    for request in &other.unmatched_requests {
+      merge_unmatched(&mut self.unmatched_requests, request.clone());
+    }
+
+    self.update_never_called();
+  }
+
+  fn update_never_called(&mut self) {
+    self.never_called = self.interactions.iter()
+      .filter(|interaction| interaction.hits == 0)
+      .map(|interaction| interaction.description.clone())
+      .collect();
+  }
+
+  /// Loads a coverage report from a file
+  pub fn read(path: &Path) -> anyhow::Result<CoverageReport> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+  }
+}
+
+/// Finds the interaction for a matched request. If more than one interaction has the same request,
+/// the one with the same response is used, otherwise the first one.
+fn matched_interaction<'a>(
+  interactions: &'a [SynchronousHttp],
+  expected: &HttpRequest,
+  response: &HttpResponse
+) -> Option<&'a SynchronousHttp> {
+  let candidates = interactions.iter()
+    .filter(|interaction| interaction.request == *expected)
+    .collect::<Vec<_>>();
+  candidates.iter()
+    .find(|interaction| interaction.response == *response)
+    .or_else(|| candidates.first())
+    .copied()
+}
+
+/// Interactions are the same if they have the same key. Reports written before the key was
+/// recorded are compared by description and provider states.
+fn same_interaction(a: &InteractionCoverage, b: &InteractionCoverage) -> bool {
+  if !a.key.is_empty() && !b.key.is_empty() {
+    a.key == b.key
+  } else {
+    a.description == b.description && a.provider_states == b.provider_states
+  }
+}
+
+fn merge_unmatched(requests: &mut Vec<UnmatchedRequest>, request: UnmatchedRequest) {
+  match requests.iter_mut()
+    .find(|r| r.method == request.method && r.path == request.path && r.result_type == request.result_type) {
+    Some(existing) => existing.count += request.count,
+    None => requests.push(request)
+  }
+}
+
+fn rule_coverage(expected: &HttpRequest, matched: &[&HttpRequest]) -> Vec<MatchingRuleCoverage> {
+  let mut coverage = vec![];
+  for (category, rules) in &expected.matching_rules.rules {
+    for (path, rule_list) in &rules.rules {
+      coverage.push(MatchingRuleCoverage {
+        category: category.to_string(),
+        path: path.to_string(),
+        rules: rule_list.rules.iter().map(|rule| rule.name()).collect(),
+        exercised: matched.iter().filter(|actual| rule_exercised(category, path, actual)).count()
+      });
+    }
+  }
+  coverage.sort_by(|a, b| (&a.category, &a.path).cmp(&(&b.category, &b.path)));
+  coverage
+}
+
+/// If the actual request had a value that the matching rule was applied to. For JSON bodies, the
+/// rule path must resolve to a value in the body.
+fn rule_exercised(category: &Category, path: &DocPath, actual: &HttpRequest) -> bool {
+  match category {
+    Category::QUERY => match (path.first_field(), &actual.query) {
+      (Some(name), Some(query)) => query.contains_key(name),
+      _ => false
+    },
+    Category::HEADER => match (path.first_field(), &actual.headers) {
+      (Some(name), Some(headers)) => headers.keys().any(|key| key.eq_ignore_ascii_case(name)),
+      _ => false
+    },
+    Category::BODY => match actual.body.value() {
+      Some(body) => match serde_json::from_slice(&body) {
+        Ok(json) => !resolve_path(&json, path).is_empty(),
+        Err(_) => true
+      },
+      None => false
+    },
+    _ => true
+  }
+}
+
+/// Merges the coverage report into `<name>-coverage.json` in the output directory. An exclusive
+/// lock is held on a lock file while the report is merged, so that mock servers from tests
+/// running in parallel (or in separate processes) can write to the same report.
+pub(crate) fn write_coverage_report(
+  output_dir: &Path,
+  name: &str,
+  report: &CoverageReport
+) -> anyhow::Result<PathBuf> {
+  fs::create_dir_all(output_dir)?;
+  let report_path = output_dir.join(format!("{}-coverage.json", name));
+  let lock_file = OpenOptions::new()
+    .create(true)
+    .truncate(false)
+    .write(true)
+    .open(output_dir.join(format!("{}-coverage.json.lock", name)))?;
+  lock_file.lock_exclusive()?;
+
+  let result = merge_report(&report_path, report);
+  if let Err(err) = FileExt::unlock(&lock_file) {
+    warn!("Failed to release the lock on {} - {}", report_path.display(), err);
+  }
+  result.map(|_| report_path)
+}
+
+fn merge_report(report_path: &Path, report: &CoverageReport) -> anyhow::Result<()> {
+  let existing = if report_path.exists() {
+    Some(CoverageReport::read(report_path)?)
+  } else {
+    None
+  };
+  let merged = if let Some(mut existing) = existing.filter(|existing| existing.run_id == report.run_id) {
+    debug!("Merging coverage report into {}", report_path.display());
+    existing.merge(report);
+    existing
+  } else {
+    debug!("Writing coverage report to {}", report_path.display());
+    report.clone()
+  };
+  fs::write(report_path, serde_json::to_string_pretty(&merged)?)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::bodies::OptionalBody;
+  use pact_models::matchingrules;
+  use pact_models::matchingrules::MatchingRule;
+  use pact_models::prelude::v4::{SynchronousHttp, V4Pact};
+  use pact_models::prelude::{Consumer, Provider};
+  use pact_models::provider_states::ProviderState;
+  use pact_models::v4::http_parts::HttpResponse;
+  use pact_models::v4::interaction::V4Interaction;
+
+  use super::*;
+
+  fn pact() -> V4Pact {
+    let request = HttpRequest {
+      method: "POST".to_string(),
+      path: "/users".to_string(),
+      body: OptionalBody::from("{\"name\":\"Bob\",\"address\":{\"street\":\"Main\"}}"),
+      matching_rules: matchingrules! {
+        "body" => {
+          "$.name" => [ MatchingRule::Type ],
+          "$.address.postcode" => [ MatchingRule::Type ]
+        }
+      },
+      .. HttpRequest::default()
+    };
+    V4Pact {
+      consumer: Consumer { name: "consumer".to_string() },
+      provider: Provider { name: "provider".to_string() },
+      interactions: vec![
+        SynchronousHttp {
+          description: "create a user".to_string(),
+          request,
+          .. SynchronousHttp::default()
+        }.boxed_v4(),
+        SynchronousHttp {
+          description: "delete a user".to_string(),
+          request: HttpRequest { method: "DELETE".to_string(), path: "/users/1".to_string(), .. HttpRequest::default() },
+          .. SynchronousHttp::default()
+        }.boxed_v4()
+      ],
+      .. V4Pact::default()
+    }
+  }
+
+  #[test]
+  fn from_results_records_the_hits_and_exercised_matching_rules() {
+    let pact = pact();
+    let expected = pact.interactions[0].as_v4_http().unwrap().request;
+    let actual = HttpRequest {
+      body: OptionalBody::from("{\"name\":\"Alice\",\"address\":{\"street\":\"High\"}}"),
+      .. expected.clone()
+    };
+    let results = vec![
+      MatchResult::RequestMatch(expected.clone(), HttpResponse::default(), actual.clone()),
+      MatchResult::RequestMatch(expected.clone(), HttpResponse::default(), actual),
+      MatchResult::RequestNotFound(HttpRequest { path: "/other".to_string(), .. HttpRequest::default() })
+    ];
+
+    let report = CoverageReport::from_results(&pact, &results);
+
+    expect!(report.interactions[0].hits).to(be_equal_to(2));
+    expect!(report.interactions[0].matching_rules.clone()).to(be_equal_to(vec![
+      MatchingRuleCoverage {
+        category: "body".to_string(),
+        path: "$.address.postcode".to_string(),
+        rules: vec!["type".to_string()],
+        exercised: 0
+      },
+      MatchingRuleCoverage {
+        category: "body".to_string(),
+        path: "$.name".to_string(),
+        rules: vec!["type".to_string()],
+        exercised: 2
+      }
+    ]));
+    expect!(report.interactions[1].hits).to(be_equal_to(0));
+    expect!(report.never_called).to(be_equal_to(vec!["delete a user".to_string()]));
+    expect!(report.unmatched_requests).to(be_equal_to(vec![
+      UnmatchedRequest {
+        method: "GET".to_string(),
+        path: "/other".to_string(),
+        result_type: "request-not-found".to_string(),
+        count: 1
+      }
+    ]));
+  }
+
+  #[test]
+  fn write_coverage_report_merges_the_reports() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let pact = pact();
+    let delete = pact.interactions[1].as_v4_http().unwrap().request;
+    let first = CoverageReport::from_results(&pact, &[]);
+    let second = CoverageReport::from_results(&pact, &[
+      MatchResult::RequestMatch(delete.clone(), HttpResponse::default(), delete)
+    ]);
+
+    write_coverage_report(output_dir.path(), "consumer-provider", &first).unwrap();
+    let path = write_coverage_report(output_dir.path(), "consumer-provider", &second).unwrap();
+
+    let report = CoverageReport::read(&path).unwrap();
+    expect!(report.mock_servers).to(be_equal_to(2));
+    expect!(report.interactions.iter().map(|i| i.hits).collect::<Vec<_>>()).to(be_equal_to(vec![0, 1]));
+    expect!(report.never_called).to(be_equal_to(vec!["create a user".to_string()]));
+  }
+
+  #[test]
+  fn from_results_only_attributes_a_match_to_one_interaction() {
+    let request = HttpRequest { path: "/users/1".to_string(), .. HttpRequest::default() };
+    let pact = V4Pact {
+      interactions: vec![
+        SynchronousHttp {
+          description: "get an existing user".to_string(),
+          provider_states: vec![ProviderState::default("user 1 exists")],
+          request: request.clone(),
+          .. SynchronousHttp::default()
+        }.boxed_v4(),
+        SynchronousHttp {
+          description: "get a missing user".to_string(),
+          request: request.clone(),
+          response: HttpResponse { status: 404, .. HttpResponse::default() },
+          .. SynchronousHttp::default()
+        }.boxed_v4()
+      ],
+      .. V4Pact::default()
+    };
+    let results = vec![
+      MatchResult::RequestMatch(request.clone(), HttpResponse { status: 404, .. HttpResponse::default() }, request.clone())
+    ];
+
+    let report = CoverageReport::from_results(&pact, &results);
+
+    expect!(report.interactions.iter().map(|i| i.hits).collect::<Vec<_>>()).to(be_equal_to(vec![0, 1]));
+    expect!(report.never_called).to(be_equal_to(vec!["get an existing user".to_string()]));
+  }
+
+  #[test]
+  fn write_coverage_report_replaces_a_report_from_a_previous_run() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let pact = pact();
+    let previous = CoverageReport {
+      run_id: "previous-run".to_string(),
+      .. CoverageReport::from_results(&pact, &[])
+    };
+    let current = CoverageReport::from_results(&pact, &[]);
+
+    write_coverage_report(output_dir.path(), "consumer-provider", &previous).unwrap();
+    let path = write_coverage_report(output_dir.path(), "consumer-provider", &current).unwrap();
+
+    let report = CoverageReport::read(&path).unwrap();
+    expect!(report.mock_servers).to(be_equal_to(1));
+    expect!(report.run_id).to(be_equal_to(current.run_id));
+  }
+
+  #[test]
+  fn rule_exercised_checks_the_query_parameters_and_headers_were_sent() {
+    let request = HttpRequest {
+      query: Some(hashmap!{ "id".to_string() => vec![Some("1".to_string())] }),
+      headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+      .. HttpRequest::default()
+    };
+
+    expect!(rule_exercised(&Category::QUERY, &DocPath::new_unwrap("$.id"), &request)).to(be_true());
+    expect!(rule_exercised(&Category::QUERY, &DocPath::new_unwrap("$.page"), &request)).to(be_false());
+    expect!(rule_exercised(&Category::HEADER, &DocPath::new_unwrap("$['content-type']"), &request)).to(be_true());
+    expect!(rule_exercised(&Category::HEADER, &DocPath::new_unwrap("$.accept"), &request)).to(be_false());
+    expect!(rule_exercised(&Category::PATH, &DocPath::root(), &request)).to(be_true());
+  }
+}
@@ -2,6 +2,7 @@
 
 use std::{env, thread};
 use std::fmt::Write;
+#[cfg(feature = "reports")] use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -23,9 +24,40 @@ use pact_models::pact::Pact;
 #[cfg(feature = "plugins")] use pact_models::plugins::PluginData;
 use pact_models::v4::http_parts::HttpRequest;
 
+#[cfg(feature = "reports")] use crate::mock_server::coverage::{CoverageReport, write_coverage_report};
+#[cfg(feature = "reports")] use crate::mock_server::mismatch_report::write_mismatch_report;
 use crate::mock_server::ValidatingMockServer;
 use crate::util::panic_or_print_error;
 
+/// Options for the reports the mock server writes when it is dropped. The reports are only
+/// written with the `reports` feature.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(not(feature = "reports"), allow(dead_code))]
+pub(crate) struct ReportOptions {
+  /// Write a mismatch report if the mock server has mismatches
+  pub mismatch_report: bool,
+  /// Merge the interaction coverage into the coverage report in the Pact output directory
+  pub coverage_report: bool,
+  /// Directory to write the mismatch report to. Defaults to the Pact output directory.
+  pub report_dir: Option<PathBuf>,
+  /// Name of the test, which is included in the mismatch report file name. Defaults to the name
+  /// of the thread that started the mock server.
+  pub test_name: Option<String>
+}
+
+impl ReportOptions {
+  /// Sets the test name from the current thread, if it has not been set. The test harness runs
+  /// each test in a thread named after the test.
+  fn with_thread_name(mut self) -> Self {
+    if self.test_name.is_none() {
+      self.test_name = thread::current().name()
+        .filter(|name| *name != "main")
+        .map(|name| name.to_string());
+    }
+    self
+  }
+}
+
 /// A mock HTTP server that handles the requests described in a `Pact`, intended
 /// for use in tests, and validates that the requests made to that server are
 /// correct. This wraps the standard Pact HTTP mock server.
@@ -43,6 +75,8 @@ pub struct ValidatingHttpMockServer {
   output_dir: Option<PathBuf>,
   // overwrite or merge Pact files
   overwrite: bool,
+  // reports to write when the mock server is dropped
+  reports: ReportOptions,
   // Tokio Runtime used to drive the mock server
   runtime: Option<Arc<Runtime>>
 }
@@ -56,9 +90,11 @@ impl ValidatingHttpMockServer {
   pub fn start(
     pact: Box<dyn Pact + Send + Sync>,
     output_dir: Option<PathBuf>,
-    mock_server_config: Option<MockServerConfig>
+    mock_server_config: Option<MockServerConfig>,
+    reports: ReportOptions
   ) -> Box<dyn ValidatingMockServer> {
     debug!("Starting mock server from pact {:?}", pact);
+    let reports = reports.with_thread_name();
 
     // Start a tokio runtime to drive the mock server
     let runtime = Arc::new(tokio::runtime::Builder::new_multi_thread()
@@ -103,6 +139,7 @@ impl ValidatingHttpMockServer {
       mock_server,
       output_dir,
       overwrite: false,
+      reports,
       runtime: Some(runtime)
     })
   }
@@ -139,9 +176,11 @@ impl ValidatingHttpMockServer {
   pub async fn start_async(
     pact: Box<dyn Pact + Send + Sync>,
     output_dir: Option<PathBuf>,
-    mock_server_config: Option<MockServerConfig>
+    mock_server_config: Option<MockServerConfig>,
+    reports: ReportOptions
   ) -> Box<dyn ValidatingMockServer> {
     debug!("Starting mock server from pact {:?}", pact);
+    let reports = reports.with_thread_name();
 
     #[cfg(feature = "plugins")] Self::increment_plugin_access(&pact.plugin_data());
 
@@ -167,6 +206,7 @@ impl ValidatingHttpMockServer {
       mock_server,
       output_dir,
       overwrite: false,
+      reports,
       runtime: None
     })
   }
@@ -198,21 +238,20 @@ impl ValidatingHttpMockServer {
       });
     });
 
+    #[cfg(feature = "reports")]
+    if self.write_coverage_report() {
+      let report = CoverageReport::from_results(&*self.mock_server.pact, &self.mock_server.matches());
+      match write_coverage_report(Path::new(&self.pact_output_dir()), self.report_name().as_str(), &report) {
+        Ok(path) => debug!("Coverage report written to {}", path.display()),
+        Err(err) => warn!("Failed to write the coverage report - {}", err)
+      }
+    }
+
     // Look up any mismatches which occurred with the mock server.
     let mismatches = self.mock_server.mismatches();
     if mismatches.is_empty() {
       // Success! Write out the generated pact file.
-      let output_dir = self.output_dir.as_ref()
-        .map(|dir| {
-          let dir = dir.to_string_lossy().to_string();
-          if dir.is_empty() { None } else { Some(dir) }
-        })
-        .flatten()
-        .unwrap_or_else(|| {
-          let val = env::var("PACT_OUTPUT_DIR");
-          debug!("env:PACT_OUTPUT_DIR = {:?}", val);
-          val.unwrap_or_else(|_| "target/pacts".to_owned())
-        });
+      let output_dir = self.pact_output_dir();
       debug!("Pact output_dir = '{}'", output_dir);
       let overwrite = env::var("PACT_OVERWRITE")
         .map(|v| {
@@ -226,12 +265,82 @@ impl ValidatingHttpMockServer {
       Ok(())
     } else {
       // Failure. Format our errors.
-      Err(anyhow!(self.display_errors(mismatches)))
+      #[allow(unused_mut)]
+      let mut msg = self.display_errors(&mismatches);
+      #[cfg(feature = "reports")]
+      if self.write_mismatch_report() {
+        let report_dir = self.reports.report_dir.clone()
+          .unwrap_or_else(|| PathBuf::from(self.pact_output_dir()));
+        match write_mismatch_report(&report_dir, self.mismatch_report_name().as_str(),
+          &self.description, &mismatches) {
+          Ok(path) => {
+            let _ = writeln!(&mut msg, "Mismatch report written to {}", path.display());
+          }
+          Err(err) => warn!("Failed to write the mismatch report - {}", err)
+        }
+      }
+      Err(anyhow!(msg))
+    }
+  }
+
+  /// Directory to write the Pact file and any mismatch report to
+  fn pact_output_dir(&self) -> String {
+    self.output_dir.as_ref()
+      .map(|dir| {
+        let dir = dir.to_string_lossy().to_string();
+        if dir.is_empty() { None } else { Some(dir) }
+      })
+      .flatten()
+      .unwrap_or_else(|| {
+        let val = env::var("PACT_OUTPUT_DIR");
+        debug!("env:PACT_OUTPUT_DIR = {:?}", val);
+        val.unwrap_or_else(|_| "target/pacts".to_owned())
+      })
+  }
+
+  /// Base name for the report files, which is the Pact file name without the extension
+  #[cfg(feature = "reports")]
+  fn report_name(&self) -> String {
+    let name = self.mock_server.pact.default_file_name();
+    name.strip_suffix(".json").map(|name| name.to_string()).unwrap_or(name)
+  }
+
+  /// Base name for the mismatch report files, which includes the test name so that failing tests
+  /// don't overwrite each other's reports
+  #[cfg(feature = "reports")]
+  fn mismatch_report_name(&self) -> String {
+    match &self.reports.test_name {
+      Some(test_name) => format!("{}-{}", self.report_name(), test_name.replace("::", "-")),
+      None => self.report_name()
     }
   }
 
+  /// If the interaction coverage report should be written
+  #[cfg(feature = "reports")]
+  fn write_coverage_report(&self) -> bool {
+    env::var("PACT_COVERAGE_REPORT")
+      .map(|v| {
+        debug!("env:PACT_COVERAGE_REPORT = {:?}", v);
+        v == "true"
+      })
+      .ok()
+      .unwrap_or(self.reports.coverage_report)
+  }
+
+  /// If a mismatch report should be written when the mock server has mismatches
+  #[cfg(feature = "reports")]
+  fn write_mismatch_report(&self) -> bool {
+    env::var("PACT_MISMATCH_REPORT")
+      .map(|v| {
+        debug!("env:PACT_MISMATCH_REPORT = {:?}", v);
+        v == "true"
+      })
+      .ok()
+      .unwrap_or(self.reports.mismatch_report)
+  }
+
   #[cfg(feature = "colour")]
-  fn display_errors(&self, mismatches: Vec<MatchResult>) -> String {
+  fn display_errors(&self, mismatches: &[MatchResult]) -> String {
     let size = termsize::get()
       .map(|sz| if sz.cols > 2 { sz.cols - 2 } else { 0 })
       .unwrap_or(78);
@@ -270,7 +379,7 @@ impl ValidatingHttpMockServer {
   }
 
   #[cfg(not(feature = "colour"))]
-  fn display_errors(&self, mismatches: Vec<MatchResult>) -> String {
+  fn display_errors(&self, mismatches: &[MatchResult]) -> String {
     let size = termsize::get()
       .map(|sz| if sz.cols > 2 { sz.cols - 2 } else { 0 })
       .unwrap_or(78);
@@ -0,0 +1,186 @@
+//! Structured reports of the mismatches from a mock server. The report is written as a JSON
+//! document, along with a rendered diff of each request that did not match, so that the requests
+//! the consumer actually made are available (for instance, as CI build artifacts) when a test fails.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use difference::{Changeset, Difference};
+use pact_mock_server::matching::MatchResult;
+use pact_models::v4::http_parts::HttpRequest;
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+/// Builds the JSON mismatch report for the mock server mismatches
+pub(crate) fn mismatch_report(description: &str, mismatches: &[MatchResult]) -> Value {
+  let results = mismatches.iter()
+    .filter_map(|result| match result {
+      MatchResult::RequestMatch(..) => {
+        warn!("list of mismatches contains a match");
+        None
+      }
+      MatchResult::RequestMismatch(expected, actual, mismatches) => Some(json!({
+        "type": "request-mismatch",
+        "method": expected.method.to_uppercase(),
+        "path": expected.path,
+        "expected": expected.to_json(),
+        "actual": actual.to_json(),
+        "mismatches": mismatches.iter().map(|m| m.to_json()).collect::<Vec<_>>(),
+        "diff": render_diff(result)
+      })),
+      MatchResult::RequestNotFound(actual) => Some(json!({
+        "type": "request-not-found",
+        "method": actual.method.to_uppercase(),
+        "path": actual.path,
+        "actual": actual.to_json(),
+        "diff": render_diff(result)
+      })),
+      MatchResult::MissingRequest(expected) => Some(json!({
+        "type": "missing-request",
+        "method": expected.method.to_uppercase(),
+        "path": expected.path,
+        "expected": expected.to_json(),
+        "diff": render_diff(result)
+      }))
+    })
+    .collect::<Vec<_>>();
+  json!({
+    "mockServer": description,
+    "mismatches": results
+  })
+}
+
+/// Renders a diff of the expected and actual requests for the result. Lines only in the expected
+/// request are prefixed with `-`, and lines only in the actual request with `+`. Missing requests
+/// only have removed lines, and unexpected requests only have added lines.
+pub(crate) fn render_diff(result: &MatchResult) -> String {
+  let (header, expected, actual) = match result {
+    MatchResult::RequestMatch(..) => return String::default(),
+    MatchResult::RequestMismatch(expected, actual, _) =>
+      (format!("request {} did not match", short_description(expected)), request_lines(expected), request_lines(actual)),
+    MatchResult::RequestNotFound(actual) =>
+      (format!("received unexpected request {}", short_description(actual)), String::default(), request_lines(actual)),
+    MatchResult::MissingRequest(expected) =>
+      (format!("request {} expected, but never occurred", short_description(expected)), request_lines(expected), String::default())
+  };
+
+  let mut output = format!("--- expected\n+++ actual\n@@ {} @@\n", header);
+  let changeset = Changeset::new(&expected, &actual, "\n");
+  for change in changeset.diffs {
+    let (prefix, lines) = match change {
+      Difference::Same(ref lines) => (' ', lines.clone()),
+      Difference::Add(ref lines) => ('+', lines.clone()),
+      Difference::Rem(ref lines) => ('-', lines.clone())
+    };
+    for line in lines.lines() {
+      let _ = writeln!(&mut output, "{}{}", prefix, line);
+    }
+  }
+  output
+}
+
+/// Pretty-printed JSON of the request, without the matching rules and generators
+fn request_lines(request: &HttpRequest) -> String {
+  let mut json = request.to_json();
+  if let Some(map) = json.as_object_mut() {
+    map.remove("matchingRules");
+    map.remove("generators");
+  }
+  serde_json::to_string_pretty(&json).unwrap_or_default()
+}
+
+fn short_description(request: &HttpRequest) -> String {
+  format!("{} {}", request.method.to_uppercase(), request.path)
+}
+
+/// Writes the mismatch report (`<name>-mismatches.json`) and the rendered diffs
+/// (`<name>-mismatches.diff`) to the output directory. Returns the path to the JSON report.
+pub(crate) fn write_mismatch_report(
+  output_dir: &Path,
+  name: &str,
+  description: &str,
+  mismatches: &[MatchResult]
+) -> anyhow::Result<PathBuf> {
+  fs::create_dir_all(output_dir)?;
+
+  let report_path = output_dir.join(format!("{}-mismatches.json", name));
+  debug!("Writing mismatch report to {}", report_path.display());
+  let report = mismatch_report(description, mismatches);
+  fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+
+  let diff = mismatches.iter()
+    .filter(|result| !matches!(result, MatchResult::RequestMatch(..)))
+    .map(render_diff)
+    .collect::<Vec<_>>()
+    .join("\n");
+  fs::write(output_dir.join(format!("{}-mismatches.diff", name)), diff)?;
+
+  Ok(report_path)
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use pact_matching::Mismatch;
+  use pact_models::bodies::OptionalBody;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn request(method: &str, path: &str, body: &str) -> HttpRequest {
+    HttpRequest {
+      method: method.to_string(),
+      path: path.to_string(),
+      body: if body.is_empty() { OptionalBody::Missing } else { OptionalBody::from(body) },
+      .. HttpRequest::default()
+    }
+  }
+
+  #[test]
+  fn render_diff_shows_the_differences_between_the_expected_and_actual_request() {
+    let result = MatchResult::RequestMismatch(request("POST", "/users", "Alice"),
+      request("POST", "/users", "Bob"), vec![]);
+
+    let diff = render_diff(&result);
+
+    expect!(diff.starts_with("--- expected\n+++ actual\n@@ request POST /users did not match @@\n {\n")).to(be_true());
+    expect!(diff.contains("\n-    \"content\": \"Alice\"")).to(be_true());
+    expect!(diff.contains("\n+    \"content\": \"Bob\"")).to(be_true());
+    expect!(diff.contains("\n   \"method\": \"POST\",\n   \"path\": \"/users\"\n }\n")).to(be_true());
+  }
+
+  #[test]
+  fn render_diff_for_missing_and_unexpected_requests() {
+    let missing = render_diff(&MatchResult::MissingRequest(request("GET", "/users", "")));
+    let unexpected = render_diff(&MatchResult::RequestNotFound(request("DELETE", "/users", "")));
+
+    assert_eq!(missing, "--- expected\n+++ actual\n@@ request GET /users expected, but never occurred @@\n\
+      -{\n-  \"method\": \"GET\",\n-  \"path\": \"/users\"\n-}\n");
+    assert_eq!(unexpected, "--- expected\n+++ actual\n@@ received unexpected request DELETE /users @@\n\
+      +{\n+  \"method\": \"DELETE\",\n+  \"path\": \"/users\"\n+}\n");
+  }
+
+  #[test]
+  fn mismatch_report_includes_the_actual_requests() {
+    let mismatches = vec![
+      MatchResult::RequestMismatch(request("GET", "/users", ""), request("GET", "/users", ""), vec![
+        Mismatch::QueryMismatch {
+          parameter: "id".to_string(),
+          expected: "".to_string(),
+          actual: "1".to_string(),
+          mismatch: "Unexpected query parameter 'id' received".to_string()
+        }
+      ]),
+      MatchResult::RequestNotFound(request("DELETE", "/users", ""))
+    ];
+
+    let report = mismatch_report("Consumer/Provider", &mismatches);
+
+    expect!(&report["mockServer"]).to(be_equal_to(&json!("Consumer/Provider")));
+    expect!(&report["mismatches"][0]["type"]).to(be_equal_to(&json!("request-mismatch")));
+    expect!(&report["mismatches"][0]["mismatches"][0]["parameter"]).to(be_equal_to(&json!("id")));
+    expect!(&report["mismatches"][1]["type"]).to(be_equal_to(&json!("request-not-found")));
+    expect!(&report["mismatches"][1]["actual"]).to(be_equal_to(&json!({ "method": "DELETE", "path": "/users" })));
+  }
+}
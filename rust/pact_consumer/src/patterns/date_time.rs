@@ -111,3 +111,186 @@ macro_rules! datetime {
     }
   }
 }
+
+/// Match and generate strings that match a date format string.
+#[derive(Debug)]
+pub struct Date<Nested: Pattern> {
+  /// The example string we generate when asked.
+  example: String,
+  /// The format string we use to match.
+  format: String,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl <Nested: Pattern> Date<Nested> {
+  /// Construct a new `Date`, given a format string and the example string to
+  /// generate.
+  pub fn new<S: Into<String>>(format: S, example: S) -> Self {
+    Date {
+      example: example.into(),
+      format: format.into(),
+      phantom: PhantomData
+    }
+  }
+}
+
+impl <Nested> Pattern for Date<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String> {
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    From::from(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.as_bytes().to_vec()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Date(self.format.clone()), RuleLogic::And);
+  }
+}
+
+#[test]
+fn date_is_pattern() {
+  use serde_json::*;
+  use expectest::prelude::*;
+
+  let matchable = Date::<JsonPattern>::new("yyyy-MM-dd", "2001-07-04");
+  expect!(matchable.to_example()).to(be_equal_to("2001-07-04"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  let expected_rules = json!({
+    "$": {
+      "combine": "AND", "matchers": [
+        { "match": "date", "format": "yyyy-MM-dd" }
+      ]
+    }
+  });
+  expect!(rules.to_v3_json()).to(be_equal_to(expected_rules));
+}
+
+impl_from_for_pattern!(Date<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(Date<StringPattern>, StringPattern);
+
+/// A pattern which matches the date format string `$format` and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "birth_date": date!("yyyy-MM-dd", "2001-01-02")
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! date {
+  ($format:expr, $example:expr) => {
+    {
+      $crate::patterns::Date::new($crate::patterns::validate_format_string($format), $example.into())
+    }
+  }
+}
+
+/// Match and generate strings that match a time format string.
+#[derive(Debug)]
+pub struct Time<Nested: Pattern> {
+  /// The example string we generate when asked.
+  example: String,
+  /// The format string we use to match.
+  format: String,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl <Nested: Pattern> Time<Nested> {
+  /// Construct a new `Time`, given a format string and the example string to
+  /// generate.
+  pub fn new<S: Into<String>>(format: S, example: S) -> Self {
+    Time {
+      example: example.into(),
+      format: format.into(),
+      phantom: PhantomData
+    }
+  }
+}
+
+impl <Nested> Pattern for Time<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String> {
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    From::from(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.as_bytes().to_vec()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Time(self.format.clone()), RuleLogic::And);
+  }
+}
+
+#[test]
+fn time_is_pattern() {
+  use serde_json::*;
+  use expectest::prelude::*;
+
+  let matchable = Time::<JsonPattern>::new("HH:mm:ss", "12:08:56");
+  expect!(matchable.to_example()).to(be_equal_to("12:08:56"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  let expected_rules = json!({
+    "$": {
+      "combine": "AND", "matchers": [
+        { "match": "time", "format": "HH:mm:ss" }
+      ]
+    }
+  });
+  expect!(rules.to_v3_json()).to(be_equal_to(expected_rules));
+}
+
+impl_from_for_pattern!(Time<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(Time<StringPattern>, StringPattern);
+
+#[test]
+fn date_and_time_into() {
+  // Make sure we can convert `Date` and `Time` into different pattern types.
+  let _: JsonPattern = Date::new("yyyy-MM-dd", "2000-01-01").into();
+  let _: StringPattern = Date::new("yyyy-MM-dd", "2000-01-01").into();
+  let _: JsonPattern = Time::new("HH:mm", "10:00").into();
+  let _: StringPattern = Time::new("HH:mm", "10:00").into();
+}
+
+/// A pattern which matches the time format string `$format` and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "opening_time": time!("HH:mm", "09:30")
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! time {
+  ($format:expr, $example:expr) => {
+    {
+      $crate::patterns::Time::new($crate::patterns::validate_format_string($format), $example.into())
+    }
+  }
+}
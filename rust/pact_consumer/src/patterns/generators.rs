@@ -0,0 +1,257 @@
+//! Patterns which generate their values, including `uuid()`, `random_int()`, etc.
+
+use std::collections::HashMap;
+
+use pact_models::expression_parser::DataType;
+use pact_models::generators::{GenerateValue, Generator, NoopVariantMatcher, UuidFormat, VariantMatcher};
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
+use pact_models::path_exp::DocPath;
+use serde_json::Value;
+
+use super::json_pattern::JsonPattern;
+use super::Pattern;
+
+/// Match values using an optional matching rule, and replace the example value with one
+/// created by a generator when the interaction is used.
+#[derive(Debug)]
+pub struct Generated {
+  /// The example value we generate when asked.
+  example: Value,
+  /// The matching rule to apply to the actual values.
+  rule: Option<MatchingRule>,
+  /// The generator to use to create new values.
+  generator: Generator
+}
+
+impl Generated {
+  /// Construct a new `Generated` pattern, given the example value, the matching rule to apply
+  /// and the generator to use.
+  pub fn new<E: Into<Value>>(example: E, rule: Option<MatchingRule>, generator: Generator) -> Self {
+    Generated {
+      example: example.into(),
+      rule,
+      generator
+    }
+  }
+
+  /// Construct a new `Generated` pattern, using the generator to create the example value.
+  /// Panics if the generator is not able to create a value without a provider state context.
+  pub fn with_generated_example(rule: Option<MatchingRule>, generator: Generator) -> Self {
+    let matcher: Box<dyn VariantMatcher + Send + Sync> = NoopVariantMatcher.boxed();
+    let example = match generator.generate_value(&Value::Null, &HashMap::new(), &matcher) {
+      Ok(value) => value,
+      Err(err) => panic!("could not generate an example value with {:?}: {}", generator, err)
+    };
+    Generated::new(example, rule, generator)
+  }
+}
+
+impl Pattern for Generated {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.example.clone()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    match &self.example {
+      Value::String(s) => s.clone().into_bytes(),
+      value => value.to_string().into_bytes()
+    }
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    if let Some(rule) = &self.rule {
+      rules_out.add_rule(path, rule.clone(), RuleLogic::And);
+    }
+  }
+
+  fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+    generators_out.insert(path, self.generator.clone());
+  }
+}
+
+impl_from_for_pattern!(Generated, JsonPattern);
+
+#[test]
+fn generated_is_pattern() {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = Generated::new(100, Some(MatchingRule::Integer), Generator::RandomInt(1, 1000));
+  expect!(matchable.to_example()).to(be_equal_to(json!(100)));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Integer ]
+  }));
+
+  let mut generators = HashMap::new();
+  matchable.extract_generators(DocPath::root(), &mut generators);
+  expect!(generators).to(be_equal_to(hashmap!{
+    DocPath::root() => Generator::RandomInt(1, 1000)
+  }));
+}
+
+#[test]
+fn generators_are_extracted_from_nested_patterns() {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+
+  let pattern = json_pattern!({
+    "id": uuid(),
+    "items": each_like!({ "count": random_int(1, 10) }),
+    "name": like!("Mary")
+  });
+
+  let mut generators = HashMap::new();
+  pattern.extract_generators(DocPath::root(), &mut generators);
+  expect!(generators).to(be_equal_to(hashmap!{
+    DocPath::new_unwrap("$.id") => Generator::Uuid(Some(UuidFormat::LowerCaseHyphenated)),
+    DocPath::new_unwrap("$.items[*].count") => Generator::RandomInt(1, 10)
+  }));
+}
+
+/// Internal regex used to match UUIDs in their lower-case hyphenated form.
+const UUID_REGEX: &str = "^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$";
+
+/// A pattern which matches any UUID, and generates a random UUID.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::patterns::uuid;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "id": uuid()
+/// });
+/// # }
+/// ```
+pub fn uuid() -> Generated {
+  Generated::with_generated_example(Some(MatchingRule::Regex(UUID_REGEX.to_string())),
+    Generator::Uuid(Some(UuidFormat::LowerCaseHyphenated)))
+}
+
+#[test]
+fn uuid_test() {
+  use regex::Regex;
+
+  let matchable = uuid();
+  let example = matchable.to_example();
+  assert!(Regex::new(UUID_REGEX).unwrap().is_match(example.as_str().unwrap_or_default()));
+}
+
+/// A pattern which matches any integer, and generates a random integer between `min` and `max`
+/// (inclusive).
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::patterns::random_int;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "count": random_int(1, 100)
+/// });
+/// # }
+/// ```
+pub fn random_int(min: i32, max: i32) -> Generated {
+  Generated::with_generated_example(Some(MatchingRule::Integer), Generator::RandomInt(min, max))
+}
+
+#[test]
+fn random_int_test() {
+  let matchable = random_int(5, 10);
+  let example = matchable.to_example().as_i64().unwrap_or_default();
+  assert!((5..=10).contains(&example));
+}
+
+/// A pattern which matches dates with the given `format`, and generates a date using the
+/// `expression` (e.g. `"today + 1 day"`). An empty expression generates the current date.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::patterns::date_generated;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "due_date": date_generated("yyyy-MM-dd", "today + 1 week")
+/// });
+/// # }
+/// ```
+#[cfg(feature = "datetime")]
+pub fn date_generated<S: Into<String>>(format: S, expression: S) -> Generated {
+  let format = format.into();
+  let expression = expression.into();
+  Generated::with_generated_example(Some(MatchingRule::Date(format.clone())),
+    Generator::Date(Some(format), if expression.is_empty() { None } else { Some(expression) }))
+}
+
+#[cfg(feature = "datetime")]
+#[test]
+fn date_generated_test() {
+  use expectest::prelude::*;
+  use regex::Regex;
+
+  let matchable = date_generated("yyyy-MM-dd", "today + 1 day");
+  let example = matchable.to_example();
+  assert!(Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap().is_match(example.as_str().unwrap_or_default()));
+
+  let mut generators = HashMap::new();
+  matchable.extract_generators(DocPath::root(), &mut generators);
+  expect!(generators.get(&DocPath::root())).to(be_some().value(
+    &Generator::Date(Some("yyyy-MM-dd".to_string()), Some("today + 1 day".to_string()))));
+}
+
+/// A pattern which matches values of the same type as `example`, and which will have its value
+/// replaced by evaluating `expression` (e.g. `"/orders/${orderId}"`) against the values returned
+/// from the provider state callback when the provider is verified.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::patterns::from_provider_state;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "id": from_provider_state("${id}", 100),
+///   "link": from_provider_state("/orders/${id}", "/orders/100")
+/// });
+/// # }
+/// ```
+pub fn from_provider_state<E: Into<JsonPattern>, S: Into<String>>(expression: S, example: E) -> Generated {
+  let example = example.into().to_example();
+  let data_type = match &example {
+    Value::String(_) => Some(DataType::STRING),
+    Value::Number(n) if n.is_i64() || n.is_u64() => Some(DataType::INTEGER),
+    Value::Number(_) => Some(DataType::DECIMAL),
+    Value::Bool(_) => Some(DataType::BOOLEAN),
+    _ => None
+  };
+  Generated::new(example, Some(MatchingRule::Type),
+    Generator::ProviderStateGenerator(expression.into(), data_type))
+}
+
+#[test]
+fn from_provider_state_test() {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = from_provider_state("/orders/${id}", "/orders/100");
+  expect!(matchable.to_example()).to(be_equal_to(json!("/orders/100")));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Type ]
+  }));
+
+  let mut generators = HashMap::new();
+  matchable.extract_generators(DocPath::root(), &mut generators);
+  expect!(generators).to(be_equal_to(hashmap!{
+    DocPath::root() => Generator::ProviderStateGenerator("/orders/${id}".to_string(), Some(DataType::STRING))
+  }));
+}
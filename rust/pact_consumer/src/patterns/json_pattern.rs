@@ -1,5 +1,6 @@
 //! Our `JsonPattern` type and supporting code.
 
+use pact_models::generators::Generator;
 use pact_models::matchingrules::MatchingRuleCategory;
 use pact_models::path_exp::DocPath;
 use std::borrow::Cow;
@@ -100,6 +101,29 @@ impl Pattern for JsonPattern {
             }
         }
     }
+
+    fn extract_generators(&self, path: DocPath, generators_out: &mut Map<DocPath, Generator>) {
+        match *self {
+            JsonPattern::Json(_) => {}
+            JsonPattern::Array(ref arr) => {
+                for (i, val) in arr.iter().enumerate() {
+                    let mut val_path = path.clone();
+                    val_path.push_index(i);
+                    val.extract_generators(val_path, generators_out);
+                }
+            }
+            JsonPattern::Object(ref obj) => {
+                for (key, val) in obj {
+                    let mut val_path = path.clone();
+                    val_path.push_field(key);
+                    val.extract_generators(val_path, generators_out);
+                }
+            }
+            JsonPattern::Pattern(ref pattern) => {
+                pattern.extract_generators(path, generators_out);
+            }
+        }
+    }
 }
 
 #[test]
@@ -0,0 +1,908 @@
+//! Patterns for the remaining matching rules, including `Integer`, `Boolean`, `Include`,
+//! `ArrayContains`, etc.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::anyhow;
+use pact_matching::matchers::match_one_of;
+use pact_models::generators::Generator;
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
+use pact_models::path_exp::DocPath;
+use serde_json::Value;
+
+use super::json_pattern::JsonPattern;
+use super::Pattern;
+use super::string_pattern::StringPattern;
+
+/// Match and generate integer values.
+#[derive(Debug)]
+pub struct Integer {
+  example: i64
+}
+
+impl Integer {
+  /// Match all integer values, and generate `example`.
+  pub fn new(example: i64) -> Self {
+    Integer { example }
+  }
+}
+
+impl Pattern for Integer {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::from(self.example)
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Integer, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Integer, JsonPattern);
+
+#[test]
+fn integer_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = Integer::new(100);
+  expect!(matchable.to_example()).to(be_equal_to(json!(100)));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Integer ]
+  }));
+}
+
+/// A pattern which matches any integer, and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "id": integer!(100)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! integer {
+  ($example:expr) => {
+    $crate::patterns::Integer::new($example)
+  }
+}
+
+/// Match and generate decimal (floating point) values.
+#[derive(Debug)]
+pub struct Decimal {
+  example: f64
+}
+
+impl Decimal {
+  /// Match all decimal values, and generate `example`. Panics if `example` is NaN or infinite,
+  /// as these can not be represented in JSON.
+  pub fn new(example: f64) -> Self {
+    if !example.is_finite() {
+      panic!("Example value {} for a decimal matcher must be a finite number", example);
+    }
+    Decimal { example }
+  }
+}
+
+impl Pattern for Decimal {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::from(self.example)
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Decimal, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Decimal, JsonPattern);
+
+#[test]
+fn decimal_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = Decimal::new(10.5);
+  expect!(matchable.to_example()).to(be_equal_to(json!(10.5)));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Decimal ]
+  }));
+}
+
+#[test]
+#[should_panic]
+fn decimal_with_a_non_finite_example() {
+  Decimal::new(f64::NAN);
+}
+
+/// A pattern which matches any decimal number, and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "price": decimal!(10.99)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! decimal {
+  ($example:expr) => {
+    $crate::patterns::Decimal::new($example)
+  }
+}
+
+/// Match and generate numeric values (either integers or decimals).
+#[derive(Debug)]
+pub struct Number {
+  example: serde_json::Number
+}
+
+impl Number {
+  /// Match all numeric values, and generate `example`.
+  pub fn new<N: Into<serde_json::Number>>(example: N) -> Self {
+    Number { example: example.into() }
+  }
+
+  /// Match all numeric values, and generate the floating point `example`. Returns an error if
+  /// the example is NaN or infinite, as those can not be represented in JSON.
+  pub fn from_f64(example: f64) -> anyhow::Result<Self> {
+    serde_json::Number::from_f64(example)
+      .map(|example| Number { example })
+      .ok_or_else(|| anyhow!("{} is not a valid example for a number, as it can not be represented in JSON", example))
+  }
+}
+
+impl Pattern for Number {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::Number(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Number, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Number, JsonPattern);
+
+#[test]
+fn number_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = Number::new(42);
+  expect!(matchable.to_example()).to(be_equal_to(json!(42)));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Number ]
+  }));
+
+  let matchable = Number::from_f64(12.5).unwrap();
+  expect!(matchable.to_example()).to(be_equal_to(json!(12.5)));
+  expect!(Number::from_f64(f64::NAN)).to(be_err());
+  expect!(Number::from_f64(f64::INFINITY)).to(be_err());
+}
+
+/// A pattern which matches any number, and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "quantity": number!(12)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! number {
+  ($example:expr) => {
+    $crate::patterns::Number::new($example)
+  }
+}
+
+/// Match and generate boolean values.
+#[derive(Debug)]
+pub struct Boolean {
+  example: bool
+}
+
+impl Boolean {
+  /// Match all boolean values, and generate `example`.
+  pub fn new(example: bool) -> Self {
+    Boolean { example }
+  }
+}
+
+impl Pattern for Boolean {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::Bool(self.example)
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Boolean, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Boolean, JsonPattern);
+
+#[test]
+fn boolean_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = Boolean::new(true);
+  expect!(matchable.to_example()).to(be_equal_to(json!(true)));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Boolean ]
+  }));
+}
+
+/// A pattern which matches any boolean, and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "active": boolean!(true)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! boolean {
+  ($example:expr) => {
+    $crate::patterns::Boolean::new($example)
+  }
+}
+
+/// Match and generate `null` values.
+#[derive(Debug, Default)]
+pub struct Null;
+
+impl Null {
+  /// Match only `null` values.
+  pub fn new() -> Self {
+    Null
+  }
+}
+
+impl Pattern for Null {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::Null
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    vec![]
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Null, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Null, JsonPattern);
+
+#[test]
+fn null_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let matchable = Null::new();
+  expect!(matchable.to_example()).to(be_equal_to(Value::Null));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Null ]
+  }));
+}
+
+/// A pattern which only matches `null` values.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "deleted_at": null_value!()
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! null_value {
+  () => {
+    $crate::patterns::Null::new()
+  }
+}
+
+/// Match and generate strings that include a given substring.
+#[derive(Debug)]
+pub struct Include<Nested: Pattern> {
+  /// The substring that must be included.
+  value: String,
+  /// The example string we generate when asked.
+  example: String,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl<Nested: Pattern> Include<Nested> {
+  /// Construct a new `Include`, given the substring to match and the example string to
+  /// generate. If the example does not contain the substring, the substring is used as the example.
+  pub fn new<S: Into<String>>(value: S, example: S) -> Self {
+    let value = value.into();
+    let example = example.into();
+    let example = if example.contains(value.as_str()) { example } else { value.clone() };
+    Include {
+      value,
+      example,
+      phantom: PhantomData
+    }
+  }
+}
+
+impl<Nested> Pattern for Include<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String>,
+{
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    From::from(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.clone().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Include(self.value.clone()), RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Include<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(Include<StringPattern>, StringPattern);
+
+#[test]
+fn include_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = Include::<JsonPattern>::new("world", "hello world");
+  expect!(matchable.to_example()).to(be_equal_to(json!("hello world")));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Include("world".to_string()) ]
+  }));
+
+  let matchable = Include::<JsonPattern>::new("world", "hello there");
+  expect!(matchable.to_example()).to(be_equal_to(json!("world")));
+}
+
+#[test]
+fn include_into() {
+  // Make sure we can convert `Include` into different pattern types.
+  let _: JsonPattern = Include::new("world", "hello world").into();
+  let _: StringPattern = Include::new("world", "hello world").into();
+}
+
+/// A pattern which matches any string that includes `$value`, and which generates `$example`.
+/// If no example is given, `$value` is used as the example.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "greeting": includes!("world", "hello world"),
+///   "farewell": includes!("bye")
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! includes {
+  ($value:expr) => {
+    {
+      let value: String = $value.into();
+      $crate::patterns::Include::new(value.clone(), value)
+    }
+  };
+  ($value:expr, $example:expr) => {
+    $crate::patterns::Include::new($value, $example)
+  }
+}
+
+/// Match and generate strings that are valid semantic version numbers.
+#[derive(Debug)]
+pub struct Semver<Nested: Pattern> {
+  /// The example version we generate when asked.
+  example: String,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl<Nested: Pattern> Semver<Nested> {
+  /// Construct a new `Semver`, given the example version to generate.
+  pub fn new<S: Into<String>>(example: S) -> Self {
+    Semver {
+      example: example.into(),
+      phantom: PhantomData
+    }
+  }
+}
+
+impl<Nested> Pattern for Semver<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String>,
+{
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    From::from(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.clone().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Semver, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Semver<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(Semver<StringPattern>, StringPattern);
+
+#[test]
+fn semver_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = Semver::<JsonPattern>::new("1.2.3");
+  expect!(matchable.to_example()).to(be_equal_to(json!("1.2.3")));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Semver ]
+  }));
+}
+
+#[test]
+fn semver_into() {
+  // Make sure we can convert `Semver` into different pattern types.
+  let _: JsonPattern = Semver::new("1.2.3").into();
+  let _: StringPattern = Semver::new("1.2.3").into();
+}
+
+/// A pattern which matches any semantic version, and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "version": semver!("1.0.0")
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! semver {
+  ($example:expr) => {
+    $crate::patterns::Semver::new($example)
+  }
+}
+
+/// Match values which are not empty (i.e. not an empty string, array or object, and not `null`).
+#[derive(Debug)]
+pub struct NotEmpty<Nested: Pattern> {
+  example: Nested
+}
+
+impl<Nested: Pattern> NotEmpty<Nested> {
+  /// Match all non-empty values with the same type as `example`.
+  pub fn new<E: Into<Nested>>(example: E) -> Self {
+    NotEmpty { example: example.into() }
+  }
+}
+
+impl<Nested: Pattern> Pattern for NotEmpty<Nested> {
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    self.example.to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_example_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path.clone(), MatchingRule::NotEmpty, RuleLogic::And);
+    self.example.extract_matching_rules(path, rules_out);
+  }
+
+  fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+    self.example.extract_generators(path, generators_out);
+  }
+}
+
+impl_from_for_pattern!(NotEmpty<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(NotEmpty<StringPattern>, StringPattern);
+
+#[test]
+fn not_empty_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = NotEmpty::<JsonPattern>::new(json_pattern!(["a", "b"]));
+  expect!(matchable.to_example()).to(be_equal_to(json!(["a", "b"])));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::NotEmpty ]
+  }));
+}
+
+#[test]
+fn not_empty_into() {
+  // Make sure we can convert `NotEmpty` into different pattern types.
+  let _: JsonPattern = NotEmpty::new(json_pattern!("hello")).into();
+  let _: StringPattern = NotEmpty::new("hello".to_owned()).into();
+}
+
+/// A pattern which matches any non-empty value of the same type as the example. This is intended
+/// for use inside `json_pattern!`, and it interprets its arguments as a `json_pattern!`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "name": not_empty!("J. Smith"),
+///   "roles": not_empty!(["admin"])
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! not_empty {
+  ($($json_pattern:tt)+) => {
+    $crate::patterns::NotEmpty::new(json_pattern!($($json_pattern)+))
+  }
+}
+
+/// Match and generate values whose content matches a content type (e.g. `image/png`). The content
+/// type of the actual value is detected from its contents.
+#[derive(Debug)]
+pub struct ContentType<Nested: Pattern> {
+  /// The content type to match.
+  content_type: String,
+  /// The example contents we generate when asked.
+  example: String,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl<Nested: Pattern> ContentType<Nested> {
+  /// Construct a new `ContentType`, given the content type to match and the example contents to
+  /// generate.
+  pub fn new<S: Into<String>>(content_type: S, example: S) -> Self {
+    ContentType {
+      content_type: content_type.into(),
+      example: example.into(),
+      phantom: PhantomData
+    }
+  }
+}
+
+impl<Nested> Pattern for ContentType<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String>,
+{
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    From::from(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.clone().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::ContentType(self.content_type.clone()), RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(ContentType<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(ContentType<StringPattern>, StringPattern);
+
+#[test]
+fn content_type_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let matchable = ContentType::<StringPattern>::new("application/xml", "<?xml version=\"1.0\"?><a/>");
+  expect!(matchable.to_example()).to(be_equal_to("<?xml version=\"1.0\"?><a/>"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::ContentType("application/xml".to_string()) ]
+  }));
+}
+
+/// A pattern which matches values with the content type `$content_type`, and which generates
+/// `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "document": content_type!("application/xml", "<?xml version=\"1.0\"?><doc/>")
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! content_type {
+  ($content_type:expr, $example:expr) => {
+    $crate::patterns::ContentType::new($content_type, $example)
+  }
+}
+
+/// Match arrays which contain at least one item matching each of the given variants. The
+/// order of the items in the array does not matter.
+#[derive(Debug)]
+pub struct ArrayContains {
+  variants: Vec<JsonPattern>
+}
+
+impl ArrayContains {
+  /// Match arrays containing an item matching each of `variants`.
+  pub fn new(variants: Vec<JsonPattern>) -> Self {
+    ArrayContains { variants }
+  }
+}
+
+impl Pattern for ArrayContains {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::Array(self.variants.iter().map(|variant| variant.to_example()).collect())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    let variants = self.variants.iter()
+      .enumerate()
+      .map(|(index, variant)| {
+        let mut category = MatchingRuleCategory::empty("body");
+        variant.extract_matching_rules(DocPath::root(), &mut category);
+        let mut generators = HashMap::new();
+        variant.extract_generators(DocPath::root(), &mut generators);
+        (index, category, generators)
+      })
+      .collect();
+    rules_out.add_rule(path, MatchingRule::ArrayContains(variants), RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(ArrayContains, JsonPattern);
+
+#[test]
+fn array_contains_is_pattern() {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = ArrayContains::new(vec![
+    json_pattern!({ "id": integer!(1), "type": "admin" }),
+    json_pattern!(like!("user"))
+  ]);
+  expect!(matchable.to_example()).to(be_equal_to(json!([{ "id": 1, "type": "admin" }, "user"])));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::ArrayContains(vec![
+        (0, matchingrules_list! { "body"; "$.id" => [ MatchingRule::Integer ] }, hashmap!{}),
+        (1, matchingrules_list! { "body"; "$" => [ MatchingRule::Type ] }, hashmap!{})
+      ])
+    ]
+  }));
+}
+
+/// A pattern which matches arrays that contain at least one item matching each of the given
+/// patterns, in any order. Each variant is interpreted as a `json_pattern!`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "roles": array_containing!([
+///     { "name": "admin", "id": integer!(1) },
+///     like!("user")
+///   ])
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! array_containing {
+  ([ $($variants:tt)+ ]) => {
+    $crate::patterns::ArrayContains::new($crate::json_pattern_internal!(@array [] $($variants)+))
+  }
+}
+
+/// Match values which are equal to one of a fixed list of values, such as the allowed values of
+/// an enumeration.
+#[derive(Debug)]
+pub struct OneOf {
+  values: Vec<Value>,
+  example: Value
+}
+
+impl OneOf {
+  /// Match any of `values`, and generate `example`. Panics if `example` is not one of the
+  /// allowed values (numbers are compared by value, as the matcher does).
+  pub fn new<E: Into<Value>>(values: Vec<Value>, example: E) -> Self {
+    let example = example.into();
+    if match_one_of(&example, &values).is_err() {
+      panic!("Example value {} is not one of the allowed values {:?}", example, values);
+    }
+    OneOf { values, example }
+  }
+
+  /// Match any of `values`, and generate the first value. Panics if `values` is empty.
+  pub fn from_values(values: Vec<Value>) -> Self {
+    let example = values.first().cloned().expect("OneOf requires at least one value");
+    OneOf { values, example }
+  }
+}
+
+impl Pattern for OneOf {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.example.clone()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    match &self.example {
+      Value::String(s) => s.clone().into_bytes(),
+      value => value.to_string().into_bytes()
+    }
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::OneOf(self.values.clone()), RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(OneOf, JsonPattern);
+
+#[test]
+fn one_of_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = OneOf::new(vec![json!("ACTIVE"), json!("SUSPENDED"), json!("CLOSED")], "SUSPENDED");
+  expect!(matchable.to_example()).to(be_equal_to(json!("SUSPENDED")));
+  expect!(matchable.to_example_bytes()).to(be_equal_to(b"SUSPENDED".to_vec()));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::OneOf(vec![json!("ACTIVE"), json!("SUSPENDED"), json!("CLOSED")]) ]
+  }));
+
+  let matchable = OneOf::new(vec![json!(1.0), json!(2.0)], 1);
+  expect!(matchable.to_example()).to(be_equal_to(json!(1)));
+}
+
+#[test]
+#[should_panic]
+fn one_of_with_an_invalid_example() {
+  use serde_json::json;
+
+  OneOf::new(vec![json!(1), json!(2)], 3);
+}
+
+#[test]
+fn one_of_macro_defaults_the_example_to_the_first_value() {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  let matchable = one_of!([1, 2, 3]);
+  expect!(matchable.to_example()).to(be_equal_to(json!(1)));
+}
+
+/// A pattern which matches any of the given values, and which generates `$example`. If the
+/// example is omitted, the first value is used.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "status": one_of!(["ACTIVE", "SUSPENDED", "CLOSED"], "ACTIVE"),
+///   "priority": one_of!([1, 2, 3])
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! one_of {
+  ([ $($value:expr),+ $(,)? ], $example:expr) => {
+    $crate::patterns::OneOf::new(vec![ $(::std::convert::Into::into($value)),+ ], $example)
+  };
+  ([ $($value:expr),+ $(,)? ]) => {
+    $crate::patterns::OneOf::from_values(vec![ $(::std::convert::Into::into($value)),+ ])
+  }
+}
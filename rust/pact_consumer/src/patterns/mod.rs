@@ -1,20 +1,28 @@
 //! JSON "patterns", which can be used to either generate JSON documents or
 //! match them.
 
+use pact_models::generators::Generator;
 use pact_models::matchingrules::MatchingRuleCategory;
 use pact_models::path_exp::DocPath;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 #[macro_use] mod json_macros;
 mod json_pattern;
 #[macro_use] mod special_rules;
+#[macro_use] mod matchers;
+mod generators;
 mod string_pattern;
 #[cfg(feature = "datetime")] #[macro_use] mod date_time;
+#[cfg(feature = "xml")] mod xml_pattern;
 
 pub use self::json_pattern::*;
 pub use self::special_rules::*;
+pub use self::matchers::*;
+pub use self::generators::*;
 pub use self::string_pattern::*;
 #[cfg(feature = "datetime")] pub use self::date_time::*;
+#[cfg(feature = "xml")] pub use self::xml_pattern::*;
 
 /// Abstract interface to types which can:
 ///
@@ -55,4 +63,9 @@ pub trait Pattern: Debug {
     /// [ruby]:
     /// https://github.com/pact-foundation/pact-support/blob/master/lib/pact/matching_rules/extract.rb
     fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory);
+
+    /// Extract any generators from this `Matchable`, and insert them into
+    /// `generators_out`, using `path` as the base path. Most patterns do not
+    /// have any generators, so the default implementation does nothing.
+    fn extract_generators(&self, _path: DocPath, _generators_out: &mut HashMap<DocPath, Generator>) {}
 }
@@ -1,9 +1,11 @@
 //! Special matching rules, including `Like`, `Term`, etc.
 
+use std::collections::HashMap;
 use std::iter::repeat;
 use std::marker::PhantomData;
 use itertools::{Either, Itertools};
 
+use pact_models::generators::Generator;
 use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
 use pact_models::matchingrules::expressions::{MatchingRuleDefinition, ValueType};
 use pact_models::path_exp::DocPath;
@@ -52,6 +54,10 @@ impl<Nested: Pattern> Pattern for Like<Nested> {
         rules_out.add_rule(path.clone(), MatchingRule::Type, RuleLogic::And);
         self.example.extract_matching_rules(path, rules_out);
     }
+
+    fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+        self.example.extract_generators(path, generators_out);
+    }
 }
 
 impl_from_for_pattern!(Like<JsonPattern>, JsonPattern);
@@ -164,6 +170,12 @@ impl Pattern for EachLike {
             rules_out,
         );
     }
+
+    fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+        let mut example_path = path.clone();
+        example_path.push_star_index();
+        self.example_element.extract_generators(example_path, generators_out);
+    }
 }
 
 #[test]
@@ -459,6 +471,19 @@ impl Pattern for ObjectMatching {
       }
     }
   }
+
+  fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+    let child_path = path.join("*");
+    let mut child_generators = HashMap::new();
+    self.example.extract_generators(DocPath::root(), &mut child_generators);
+    for (path, generator) in child_generators {
+      let mut generator_path = child_path.clone();
+      for segment in path.tokens().iter().dropping(2) {
+        generator_path.push(segment.clone());
+      }
+      generators_out.insert(generator_path, generator);
+    }
+  }
 }
 
 impl_from_for_pattern!(ObjectMatching, JsonPattern);
@@ -640,6 +665,10 @@ impl Pattern for EachKey {
   fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
     rules_out.add_rule(path, self.into(), RuleLogic::And);
   }
+
+  fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+    self.pattern.extract_generators(path, generators_out);
+  }
 }
 
 impl Into<MatchingRule> for EachKey {
@@ -750,6 +779,10 @@ impl Pattern for EachValue
   fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
     rules_out.add_rule(path, self.into(), RuleLogic::And);
   }
+
+  fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+    self.rule.extract_generators(path, generators_out);
+  }
 }
 
 impl Into<MatchingRule> for EachValue {
@@ -795,6 +828,23 @@ fn each_value_is_pattern() {
   }));
 }
 
+#[test]
+fn each_value_extracts_the_generators_of_the_pattern() {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use crate::patterns::Generated;
+
+  let matchable = EachValue::new(
+    Generated::new(100, Some(MatchingRule::Integer), Generator::RandomInt(1, 1000))
+  );
+
+  let mut generators = HashMap::new();
+  matchable.extract_generators(DocPath::new_unwrap("$.values"), &mut generators);
+  expect!(generators).to(be_equal_to(hashmap!{
+    DocPath::new_unwrap("$.values") => Generator::RandomInt(1, 1000)
+  }));
+}
+
 /// A pattern which applies another pattern to each value of an object, and which generates an
 /// example value.
 ///
@@ -1,7 +1,9 @@
 //! Support for patterns which match only strings, not JSON.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 
+use pact_models::generators::Generator;
 use pact_models::matchingrules::MatchingRuleCategory;
 use pact_models::path_exp::DocPath;
 
@@ -48,6 +50,15 @@ impl Pattern for StringPattern {
             }
         }
     }
+
+    fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+        match *self {
+            StringPattern::String(_) => {},
+            StringPattern::Pattern(ref p) => {
+                p.extract_generators(path, generators_out);
+            }
+        }
+    }
 }
 
 #[test]
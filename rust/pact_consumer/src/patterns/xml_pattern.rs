@@ -0,0 +1,297 @@
+//! Support for building XML bodies, along with the matching rules and generators for them.
+
+use std::collections::HashMap;
+
+use pact_models::generators::Generator;
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
+use pact_models::path_exp::DocPath;
+
+use super::Pattern;
+use super::string_pattern::StringPattern;
+
+/// A node that is a child of an XML element.
+#[derive(Debug)]
+enum XmlNode {
+  /// A child element.
+  Element(XmlElement),
+  /// A child element which will be matched against all children with the same name, with a
+  /// minimum number of elements.
+  EachLike(XmlElement, usize),
+  /// A text node.
+  Text(StringPattern)
+}
+
+/// Builder for an XML element, with attributes, namespaces and child nodes. Attribute values and
+/// text can be any `StringPattern`, so can include matching rules.
+///
+/// ```
+/// use pact_consumer::prelude::*;
+///
+/// let body = XmlElement::new("ns:order")
+///   .namespace("ns", "urn:orders")
+///   .attribute("id", term!("^[0-9]+$", "1234"))
+///   .child(XmlElement::new("ns:customer").text("Mary"))
+///   .each_like(XmlElement::new("ns:item").attribute("sku", "A-1"), 1);
+/// ```
+#[derive(Debug)]
+pub struct XmlElement {
+  /// Qualified name of the element (i.e. `prefix:name`).
+  name: String,
+  /// Namespaces declared on this element, as prefix and URI pairs. The default namespace has an
+  /// empty prefix.
+  namespaces: Vec<(String, String)>,
+  /// Attributes of the element.
+  attributes: Vec<(String, StringPattern)>,
+  /// Child nodes of the element.
+  children: Vec<XmlNode>
+}
+
+impl XmlElement {
+  /// Create a new element with the given name. The name can include a namespace prefix (e.g.
+  /// `ns:item`), which must be declared on this element or one of its parents.
+  pub fn new<S: Into<String>>(name: S) -> Self {
+    XmlElement {
+      name: name.into(),
+      namespaces: vec![],
+      attributes: vec![],
+      children: vec![]
+    }
+  }
+
+  /// Declares a namespace on this element. An empty prefix declares the default namespace.
+  pub fn namespace<P: Into<String>, U: Into<String>>(mut self, prefix: P, uri: U) -> Self {
+    self.namespaces.push((prefix.into(), uri.into()));
+    self
+  }
+
+  /// Adds an attribute to this element.
+  pub fn attribute<N: Into<String>, V: Into<StringPattern>>(mut self, name: N, value: V) -> Self {
+    self.attributes.push((name.into(), value.into()));
+    self
+  }
+
+  /// Adds a child element.
+  pub fn child(mut self, element: XmlElement) -> Self {
+    self.children.push(XmlNode::Element(element));
+    self
+  }
+
+  /// Adds a child element which will match any number (at least `min`) of child elements with
+  /// the same name, each of which must match the example element. The example will be repeated
+  /// `min` times in the generated body (at least once).
+  pub fn each_like(mut self, element: XmlElement, min: usize) -> Self {
+    self.children.push(XmlNode::EachLike(element, min));
+    self
+  }
+
+  /// Adds a text node.
+  pub fn text<V: Into<StringPattern>>(mut self, text: V) -> Self {
+    self.children.push(XmlNode::Text(text.into()));
+    self
+  }
+
+  fn write_xml(&self, buffer: &mut String) {
+    buffer.push('<');
+    buffer.push_str(&self.name);
+    for (prefix, uri) in &self.namespaces {
+      if prefix.is_empty() {
+        buffer.push_str(" xmlns=\"");
+      } else {
+        buffer.push_str(" xmlns:");
+        buffer.push_str(prefix);
+        buffer.push_str("=\"");
+      }
+      buffer.push_str(&escape_xml(uri));
+      buffer.push('"');
+    }
+    for (name, value) in &self.attributes {
+      buffer.push(' ');
+      buffer.push_str(name);
+      buffer.push_str("=\"");
+      buffer.push_str(&escape_xml(&value.to_example()));
+      buffer.push('"');
+    }
+
+    if self.children.is_empty() {
+      buffer.push_str("/>");
+    } else {
+      buffer.push('>');
+      for child in &self.children {
+        match child {
+          XmlNode::Element(element) => element.write_xml(buffer),
+          XmlNode::EachLike(element, min) => for _ in 0..(*min).max(1) {
+            element.write_xml(buffer);
+          },
+          XmlNode::Text(text) => buffer.push_str(&escape_xml(&text.to_example()))
+        }
+      }
+      buffer.push_str("</");
+      buffer.push_str(&self.name);
+      buffer.push('>');
+    }
+  }
+
+  /// Returns the namespaces in scope for this element, given the namespaces of the parent.
+  fn scope(&self, parent_scope: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut scope = parent_scope.clone();
+    for (prefix, uri) in &self.namespaces {
+      scope.insert(prefix.clone(), uri.clone());
+    }
+    scope
+  }
+
+  fn visit<F>(&self, path: DocPath, parent_scope: &HashMap<String, String>, callback: &mut F)
+    where F: FnMut(&DocPath, Option<MatchingRule>, Option<&StringPattern>)
+  {
+    let scope = self.scope(parent_scope);
+    let path = path.join(qualified_name(&self.name, &scope, true));
+
+    for (name, value) in &self.attributes {
+      let attribute_path = path.join(format!("@{}", qualified_name(name, &scope, false)));
+      callback(&attribute_path, None, Some(value));
+    }
+
+    for child in &self.children {
+      match child {
+        XmlNode::Element(element) => element.visit(path.clone(), &scope, callback),
+        XmlNode::EachLike(element, min) => {
+          let element_scope = element.scope(&scope);
+          let element_path = path.join(qualified_name(&element.name, &element_scope, true));
+          callback(&element_path, Some(MatchingRule::MinType(*min)), None);
+          element.visit(path.clone(), &scope, callback);
+        }
+        XmlNode::Text(text) => callback(&path.join("#text"), None, Some(text))
+      }
+    }
+  }
+}
+
+/// Returns the name of the element or attribute as used in matching rule paths, where any
+/// namespace prefix is replaced with the namespace URI.
+fn qualified_name(name: &str, scope: &HashMap<String, String>, use_default: bool) -> String {
+  let (prefix, local_name) = match name.split_once(':') {
+    Some((prefix, local_name)) => (prefix, local_name),
+    None => ("", name)
+  };
+  if prefix.is_empty() && !use_default {
+    return local_name.to_string();
+  }
+  match scope.get(prefix) {
+    Some(uri) if !uri.is_empty() => format!("{}:{}", uri, local_name),
+    _ => name.to_string()
+  }
+}
+
+fn escape_xml(value: &str) -> String {
+  value.replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+/// A pattern for an XML document. This generates the XML body, and the matching rules and
+/// generators for it.
+#[derive(Debug)]
+pub struct XmlPattern {
+  root: XmlElement
+}
+
+impl XmlPattern {
+  /// Create an XML pattern with the given root element.
+  pub fn new(root: XmlElement) -> Self {
+    XmlPattern { root }
+  }
+}
+
+impl From<XmlElement> for XmlPattern {
+  fn from(root: XmlElement) -> Self {
+    XmlPattern::new(root)
+  }
+}
+
+impl Pattern for XmlPattern {
+  type Matches = String;
+
+  fn to_example(&self) -> Self::Matches {
+    let mut buffer = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    self.root.write_xml(&mut buffer);
+    buffer
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    self.root.visit(path, &HashMap::new(), &mut |path, rule, value| {
+      if let Some(rule) = rule {
+        rules_out.add_rule(path.clone(), rule, RuleLogic::And);
+      }
+      if let Some(value) = value {
+        value.extract_matching_rules(path.clone(), rules_out);
+      }
+    });
+  }
+
+  fn extract_generators(&self, path: DocPath, generators_out: &mut HashMap<DocPath, Generator>) {
+    self.root.visit(path, &HashMap::new(), &mut |path, _, value| {
+      if let Some(value) = value {
+        value.extract_generators(path.clone(), generators_out);
+      }
+    });
+  }
+}
+
+#[test]
+fn xml_pattern_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use regex::Regex;
+
+  use super::special_rules::Term;
+
+  let pattern = XmlPattern::new(XmlElement::new("order")
+    .attribute("id", Term::new(Regex::new("^[0-9]+$").unwrap(), "1234"))
+    .child(XmlElement::new("customer").text(Term::new(Regex::new("^[A-Za-z ]+$").unwrap(), "Mary & Co")))
+    .each_like(XmlElement::new("item").attribute("sku", "A-1"), 2));
+
+  expect!(pattern.to_example()).to(be_equal_to(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?><order id=\"1234\"><customer>Mary &amp; Co</customer>\
+    <item sku=\"A-1\"/><item sku=\"A-1\"/></order>"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  pattern.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body";
+    "$.order['@id']" => [ MatchingRule::Regex("^[0-9]+$".to_string()) ],
+    "$.order.customer['#text']" => [ MatchingRule::Regex("^[A-Za-z ]+$".to_string()) ],
+    "$.order.item" => [ MatchingRule::MinType(2) ]
+  }));
+}
+
+#[test]
+fn xml_pattern_with_namespaces() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use regex::Regex;
+
+  use super::special_rules::Term;
+
+  let pattern = XmlPattern::new(XmlElement::new("ns:order")
+    .namespace("ns", "urn:orders")
+    .attribute("ns:id", Term::new(Regex::new("^[0-9]+$").unwrap(), "1234"))
+    .child(XmlElement::new("ns:total").text(Term::new(Regex::new("^[0-9.]+$").unwrap(), "10.00"))));
+
+  expect!(pattern.to_example()).to(be_equal_to(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?><ns:order xmlns:ns=\"urn:orders\" ns:id=\"1234\">\
+    <ns:total>10.00</ns:total></ns:order>"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  pattern.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body";
+    "$['urn:orders:order']['@urn:orders:id']" => [ MatchingRule::Regex("^[0-9]+$".to_string()) ],
+    "$['urn:orders:order']['urn:orders:total']['#text']" => [ MatchingRule::Regex("^[0-9.]+$".to_string()) ]
+  }));
+}
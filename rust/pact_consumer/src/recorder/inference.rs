@@ -0,0 +1,172 @@
+//! Infers matching rules and generators from the JSON bodies of recorded interactions.
+//!
+//! The recorded values are only examples, so every leaf value is replaced with a type matcher.
+//! Values that look like identifiers, UUIDs, dates or timestamps get a more specific matcher, and
+//! when they are in a response, a generator so the provider is not required to return the exact
+//! values that were recorded.
+
+use lazy_static::lazy_static;
+use pact_models::generators::{Generator, GeneratorCategory, Generators};
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
+use pact_models::path_exp::DocPath;
+use regex::Regex;
+use serde_json::Value;
+
+/// Regex used to match UUID values
+pub const UUID_REGEX: &str = "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$";
+/// Format used for matching and generating ISO dates
+pub const DATE_FORMAT: &str = "yyyy-MM-dd";
+
+lazy_static! {
+  static ref UUID: Regex = Regex::new(UUID_REGEX).unwrap();
+  static ref DATE: Regex = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+  static ref TIMESTAMP: Regex = Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.(\d+))?(Z|[+-]\d{2}:\d{2})$").unwrap();
+}
+
+/// Walks the JSON document, adding matching rules to the body category. If generators are provided
+/// (i.e. for a response body), generators will also be added for any IDs, UUIDs, dates or
+/// timestamps found.
+pub fn infer_rules(
+  json: &Value,
+  rules: &mut MatchingRuleCategory,
+  mut generators: Option<&mut Generators>
+) {
+  infer_rules_for_path(json, &DocPath::root(), None, rules, &mut generators);
+}
+
+fn infer_rules_for_path(
+  json: &Value,
+  path: &DocPath,
+  key: Option<&str>,
+  rules: &mut MatchingRuleCategory,
+  generators: &mut Option<&mut Generators>
+) {
+  match json {
+    Value::Object(map) => for (field, value) in map {
+      let mut field_path = path.clone();
+      field_path.push_field(field.as_str());
+      infer_rules_for_path(value, &field_path, Some(field.as_str()), rules, generators);
+    },
+    Value::Array(items) => {
+      rules.add_rule(path.clone(), MatchingRule::MinType(1), RuleLogic::And);
+      if let Some(first) = items.first() {
+        let mut item_path = path.clone();
+        item_path.push_star_index();
+        infer_rules_for_path(first, &item_path, key, rules, generators);
+      }
+    }
+    Value::String(s) => {
+      let (rule, generator) = infer_string_rule(s);
+      rules.add_rule(path.clone(), rule, RuleLogic::And);
+      add_generator(generators, path, generator);
+    }
+    Value::Number(n) if n.is_i64() && key.map(is_id_key).unwrap_or(false) => {
+      rules.add_rule(path.clone(), MatchingRule::Integer, RuleLogic::And);
+      add_generator(generators, path, Some(Generator::RandomInt(1, i32::MAX)));
+    }
+    Value::Null => {}
+    _ => rules.add_rule(path.clone(), MatchingRule::Type, RuleLogic::And)
+  }
+}
+
+fn infer_string_rule(value: &str) -> (MatchingRule, Option<Generator>) {
+  if UUID.is_match(value) {
+    (MatchingRule::Regex(UUID_REGEX.to_string()), Some(Generator::Uuid(None)))
+  } else if let Some(captures) = TIMESTAMP.captures(value) {
+    let fraction = captures.get(2)
+      .map(|digits| format!(".{}", "S".repeat(digits.as_str().len())))
+      .unwrap_or_default();
+    let format = format!("yyyy-MM-dd'T'HH:mm:ss{}XXX", fraction);
+    (MatchingRule::Timestamp(format.clone()), Some(Generator::DateTime(Some(format), None)))
+  } else if DATE.is_match(value) {
+    (MatchingRule::Date(DATE_FORMAT.to_string()), Some(Generator::Date(Some(DATE_FORMAT.to_string()), None)))
+  } else {
+    (MatchingRule::Type, None)
+  }
+}
+
+fn add_generator(generators: &mut Option<&mut Generators>, path: &DocPath, generator: Option<Generator>) {
+  if let (Some(generators), Some(generator)) = (generators, generator) {
+    generators.add_generator_with_subcategory(&GeneratorCategory::BODY, path.clone(), generator);
+  }
+}
+
+/// If the JSON key looks like it is for an ID value (`id`, `user_id`, `userId`)
+fn is_id_key(key: &str) -> bool {
+  key.eq_ignore_ascii_case("id") || key.ends_with("_id") || key.ends_with("Id")
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use super::*;
+
+  fn rule_for(rules: &MatchingRuleCategory, path: &'static str) -> Vec<MatchingRule> {
+    rules.rules.get(&DocPath::new_unwrap(path))
+      .map(|list| list.rules.clone())
+      .unwrap_or_default()
+  }
+
+  #[test]
+  fn infer_rules_adds_type_matchers_for_leaf_values() {
+    let json = json!({
+      "name": "Bob",
+      "age": 42,
+      "active": true,
+      "nickname": null,
+      "tags": ["a", "b"]
+    });
+    let mut rules = MatchingRuleCategory::empty("body");
+
+    infer_rules(&json, &mut rules, None);
+
+    expect!(rule_for(&rules, "$.name")).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(rule_for(&rules, "$.age")).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(rule_for(&rules, "$.active")).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(rule_for(&rules, "$.nickname")).to(be_equal_to(vec![]));
+    expect!(rule_for(&rules, "$.tags")).to(be_equal_to(vec![MatchingRule::MinType(1)]));
+    expect!(rule_for(&rules, "$.tags[*]")).to(be_equal_to(vec![MatchingRule::Type]));
+  }
+
+  #[test]
+  fn infer_rules_adds_generators_for_ids_and_timestamps() {
+    let json = json!({
+      "id": 100,
+      "accountId": 200,
+      "count": 3,
+      "ref": "5a4a3f2e-0b53-4a04-a9c3-8c1f2a3b4c5d",
+      "created": "2024-01-02T03:04:05.123Z",
+      "updated": "2024-01-02T03:04:05+10:00",
+      "dob": "2000-02-29",
+      "items": [{ "item_id": 1 }]
+    });
+    let mut rules = MatchingRuleCategory::empty("body");
+    let mut generators = Generators::default();
+
+    infer_rules(&json, &mut rules, Some(&mut generators));
+
+    expect!(rule_for(&rules, "$.id")).to(be_equal_to(vec![MatchingRule::Integer]));
+    expect!(rule_for(&rules, "$.accountId")).to(be_equal_to(vec![MatchingRule::Integer]));
+    expect!(rule_for(&rules, "$.count")).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(rule_for(&rules, "$.ref")).to(be_equal_to(vec![MatchingRule::Regex(UUID_REGEX.to_string())]));
+    expect!(rule_for(&rules, "$.created")).to(be_equal_to(vec![
+      MatchingRule::Timestamp("yyyy-MM-dd'T'HH:mm:ss.SSSXXX".to_string())
+    ]));
+    expect!(rule_for(&rules, "$.updated")).to(be_equal_to(vec![
+      MatchingRule::Timestamp("yyyy-MM-dd'T'HH:mm:ssXXX".to_string())
+    ]));
+    expect!(rule_for(&rules, "$.dob")).to(be_equal_to(vec![MatchingRule::Date("yyyy-MM-dd".to_string())]));
+    expect!(rule_for(&rules, "$.items[*].item_id")).to(be_equal_to(vec![MatchingRule::Integer]));
+
+    let body = generators.categories.get(&GeneratorCategory::BODY).cloned().unwrap_or_default();
+    expect!(body.get(&DocPath::new_unwrap("$.id"))).to(be_some().value(&Generator::RandomInt(1, i32::MAX)));
+    expect!(body.get(&DocPath::new_unwrap("$.ref"))).to(be_some().value(&Generator::Uuid(None)));
+    expect!(body.get(&DocPath::new_unwrap("$.created"))).to(be_some().value(
+      &Generator::DateTime(Some("yyyy-MM-dd'T'HH:mm:ss.SSSXXX".to_string()), None)));
+    expect!(body.get(&DocPath::new_unwrap("$.dob"))).to(be_some().value(
+      &Generator::Date(Some("yyyy-MM-dd".to_string()), None)));
+    expect!(body.get(&DocPath::new_unwrap("$.count"))).to(be_none());
+  }
+}
@@ -0,0 +1,499 @@
+//! Recording proxy that can be used to generate a Pact from the traffic between an existing
+//! consumer and a real provider. This is intended to help bootstrap the Pact tests for consumers
+//! that don't have any.
+//!
+//! The proxy forwards every request to the provider, and records the request and the response
+//! that was returned as a V4 HTTP interaction. Matching rules are inferred for JSON bodies, so
+//! the generated Pact file is a starting point that should be curated into a real contract (
+//! the recorded values and headers will be more specific than the consumer requires).
+//!
+//! ```no_run
+//! use pact_consumer::recorder::RecordingProxy;
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let proxy = RecordingProxy::start("http://localhost:8080", "Legacy Consumer", "Provider").await?;
+//!
+//! // Point the consumer at proxy.url() and exercise it
+//!
+//! proxy.write_pact("target/pacts")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::{Request, Response, StatusCode};
+use hyper::body::Incoming;
+use hyper::header::{HeaderMap, HeaderName};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use pact_models::bodies::OptionalBody;
+use pact_models::content_types::ContentType;
+use pact_models::pact::{Pact, write_pact};
+use pact_models::PactSpecification;
+use pact_models::prelude::{Consumer, Provider};
+use pact_models::query_strings::parse_query_string;
+use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use pact_models::v4::interaction::V4Interaction;
+use pact_models::v4::pact::V4Pact;
+use pact_models::v4::synch_http::SynchronousHttp;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+use crate::PACT_CONSUMER_VERSION;
+
+pub mod inference;
+
+/// Headers that are not recorded or forwarded, as they are either specific to the connection or
+/// are not something the consumer controls. The accept encoding is not forwarded so that the
+/// provider does not return a compressed body, which could not be recorded as is.
+const IGNORED_HEADERS: [&str; 12] = [
+  "accept-encoding",
+  "connection",
+  "content-length",
+  "date",
+  "host",
+  "keep-alive",
+  "proxy-connection",
+  "te",
+  "trailer",
+  "transfer-encoding",
+  "upgrade",
+  "user-agent"
+];
+
+/// Headers that carry credentials. These are not written to the Pact file unless enabled with
+/// `RecordingProxy::record_credentials`, as the Pact file is normally published.
+const CREDENTIAL_HEADERS: [&str; 7] = [
+  "authorization",
+  "cookie",
+  "proxy-authorization",
+  "set-cookie",
+  "x-api-key",
+  "x-auth-token",
+  "x-csrf-token"
+];
+
+/// Request/response pair captured by the proxy
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedExchange {
+  /// Request that was received from the consumer
+  pub request: HttpRequest,
+  /// Response that was returned from the provider
+  pub response: HttpResponse
+}
+
+struct ProxyState {
+  provider_url: Url,
+  client: reqwest::Client,
+  exchanges: Mutex<Vec<RecordedExchange>>
+}
+
+/// Proxy that forwards requests to a real provider, and records the requests and responses so
+/// they can be written out as a Pact file. The proxy will be shut down when it is dropped.
+pub struct RecordingProxy {
+  consumer: String,
+  provider: String,
+  address: SocketAddr,
+  state: Arc<ProxyState>,
+  infer_matching_rules: bool,
+  record_credentials: bool,
+  shutdown: Option<oneshot::Sender<()>>
+}
+
+impl RecordingProxy {
+  /// Starts a recording proxy for the provider running at the given URL. The proxy will be bound
+  /// to a random port on the loopback interface, use `url()` to get the URL to point the consumer
+  /// at.
+  pub async fn start<C, P>(provider_url: &str, consumer: C, provider: P) -> anyhow::Result<RecordingProxy>
+    where C: Into<String>, P: Into<String>
+  {
+    let provider_url = Url::parse(provider_url)
+      .map_err(|err| anyhow!("'{}' is not a valid provider URL - {}", provider_url, err))?;
+    let client = reqwest::Client::builder()
+      .redirect(reqwest::redirect::Policy::none())
+      .build()?;
+    let state = Arc::new(ProxyState {
+      provider_url,
+      client,
+      exchanges: Mutex::new(vec![])
+    });
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
+    let server_state = state.clone();
+    tokio::spawn(async move {
+      loop {
+        tokio::select! {
+          connection = listener.accept() => match connection {
+            Ok((stream, remote)) => {
+              debug!("Recording proxy received connection from {}", remote);
+              let state = server_state.clone();
+              tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                  let state = state.clone();
+                  async move { Ok::<_, Infallible>(handle_request(request, &state).await) }
+                });
+                if let Err(err) = http1::Builder::new()
+                  .serve_connection(TokioIo::new(stream), service)
+                  .await {
+                  debug!("Recording proxy connection failed: {}", err);
+                }
+              });
+            }
+            Err(err) => warn!("Recording proxy failed to accept connection: {}", err)
+          },
+          _ = &mut shutdown_rx => {
+            debug!("Shutting down recording proxy on {}", address);
+            break;
+          }
+        }
+      }
+    });
+
+    info!("Recording proxy started on {}, forwarding to {}", address, state.provider_url);
+    Ok(RecordingProxy {
+      consumer: consumer.into(),
+      provider: provider.into(),
+      address,
+      state,
+      infer_matching_rules: true,
+      record_credentials: false,
+      shutdown: Some(shutdown_tx)
+    })
+  }
+
+  /// URL of the proxy. The consumer must be configured to use this URL instead of the provider
+  /// URL.
+  pub fn url(&self) -> Url {
+    // unwrap is safe, as this is always a valid URL
+    Url::parse(&format!("http://{}", self.address)).unwrap()
+  }
+
+  /// Port the proxy is listening on
+  pub fn port(&self) -> u16 {
+    self.address.port()
+  }
+
+  /// Enables or disables inferring matching rules and generators for JSON bodies. This is enabled
+  /// by default.
+  pub fn infer_matching_rules(&mut self, enabled: bool) -> &mut Self {
+    self.infer_matching_rules = enabled;
+    self
+  }
+
+  /// Enables or disables writing the headers that carry credentials (like `Authorization` and
+  /// `Cookie`) to the Pact. This is disabled by default, so these headers are dropped from the
+  /// recorded interactions.
+  pub fn record_credentials(&mut self, enabled: bool) -> &mut Self {
+    self.record_credentials = enabled;
+    self
+  }
+
+  /// Returns all the request/response pairs that have been recorded so far
+  pub fn exchanges(&self) -> Vec<RecordedExchange> {
+    self.state.exchanges.lock().unwrap().clone()
+  }
+
+  /// Builds a V4 Pact from the request/response pairs that have been recorded so far
+  pub fn pact(&self) -> V4Pact {
+    let mut descriptions: HashMap<String, usize> = HashMap::new();
+    let interactions = self.exchanges().iter()
+      .map(|exchange| {
+        let base = format!("a request to {} {}", exchange.request.method, exchange.request.path);
+        let count = descriptions.entry(base.clone()).or_insert(0);
+        *count += 1;
+        let description = if *count > 1 { format!("{} ({})", base, count) } else { base };
+        let interaction = build_interaction(description, exchange, self.infer_matching_rules,
+          self.record_credentials);
+        Box::new(interaction) as Box<dyn V4Interaction + Send + Sync + std::panic::RefUnwindSafe>
+      })
+      .collect();
+
+    let mut pact = V4Pact {
+      consumer: Consumer { name: self.consumer.clone() },
+      provider: Provider { name: self.provider.clone() },
+      interactions,
+      .. V4Pact::default()
+    };
+    if let Some(version) = PACT_CONSUMER_VERSION {
+      pact.add_md_version("consumer", version);
+    }
+    pact
+  }
+
+  /// Writes the recorded interactions as a Pact file to the given directory. Any existing Pact
+  /// file for the consumer and provider will be overwritten.
+  pub fn write_pact<D: AsRef<Path>>(&self, dir: D) -> anyhow::Result<()> {
+    let pact = self.pact();
+    let path = dir.as_ref().join(pact.default_file_name());
+    info!("Writing {} recorded interaction(s) to {}", pact.interactions.len(), path.display());
+    write_pact(pact.boxed(), &path, PactSpecification::V4, true)
+  }
+}
+
+impl Drop for RecordingProxy {
+  fn drop(&mut self) {
+    if let Some(shutdown) = self.shutdown.take() {
+      let _ = shutdown.send(());
+    }
+  }
+}
+
+async fn handle_request(request: Request<Incoming>, state: &ProxyState) -> Response<Full<Bytes>> {
+  let (parts, body) = request.into_parts();
+  let body = match body.collect().await {
+    Ok(body) => body.to_bytes(),
+    Err(err) => {
+      error!("Failed to read the request body: {}", err);
+      return error_response(StatusCode::BAD_REQUEST, format!("Failed to read the request body: {}", err));
+    }
+  };
+
+  let mut url = state.provider_url.clone();
+  url.set_path(&join_paths(state.provider_url.path(), parts.uri.path()));
+  url.set_query(parts.uri.query());
+  debug!("Forwarding {} {} to {}", parts.method, parts.uri, url);
+
+  let mut provider_request = state.client.request(parts.method.clone(), url.clone());
+  for (name, value) in parts.headers.iter().filter(|(name, _)| !is_ignored_header(name)) {
+    provider_request = provider_request.header(name, value);
+  }
+  if !body.is_empty() {
+    provider_request = provider_request.body(body.clone());
+  }
+
+  let provider_response = match provider_request.send().await {
+    Ok(response) => response,
+    Err(err) => {
+      error!("Failed to forward the request to {}: {}", url, err);
+      return error_response(StatusCode::BAD_GATEWAY, format!("Failed to forward the request to the provider: {}", err));
+    }
+  };
+  let status = provider_response.status();
+  let headers = provider_response.headers().clone();
+  let response_body = match provider_response.bytes().await {
+    Ok(body) => body,
+    Err(err) => {
+      error!("Failed to read the response body from {}: {}", url, err);
+      return error_response(StatusCode::BAD_GATEWAY, format!("Failed to read the response body from the provider: {}", err));
+    }
+  };
+
+  let request = HttpRequest {
+    method: parts.method.to_string(),
+    path: parts.uri.path().to_string(),
+    query: parts.uri.query().and_then(parse_query_string),
+    headers: recorded_headers(&parts.headers),
+    body: recorded_body(&parts.headers, &body),
+    .. HttpRequest::default()
+  };
+  let response = HttpResponse {
+    status: status.as_u16(),
+    headers: recorded_headers(&headers),
+    body: recorded_body(&headers, &response_body),
+    .. HttpResponse::default()
+  };
+  state.exchanges.lock().unwrap().push(RecordedExchange { request, response });
+
+  let mut builder = Response::builder().status(status);
+  for (name, value) in headers.iter().filter(|(name, _)| !is_ignored_header(name)) {
+    builder = builder.header(name, value);
+  }
+  builder.body(Full::new(response_body))
+    .unwrap_or_else(|err| error_response(StatusCode::BAD_GATEWAY, err.to_string()))
+}
+
+fn error_response(status: StatusCode, message: String) -> Response<Full<Bytes>> {
+  let mut response = Response::new(Full::new(Bytes::from(message)));
+  *response.status_mut() = status;
+  response
+}
+
+fn join_paths(base: &str, path: &str) -> String {
+  let base = base.trim_end_matches('/');
+  if path.is_empty() || path == "/" {
+    if base.is_empty() { "/".to_string() } else { base.to_string() }
+  } else {
+    format!("{}{}", base, path)
+  }
+}
+
+fn is_ignored_header(name: &HeaderName) -> bool {
+  IGNORED_HEADERS.contains(&name.as_str())
+}
+
+fn recorded_headers(headers: &HeaderMap) -> Option<HashMap<String, Vec<String>>> {
+  let mut recorded: HashMap<String, Vec<String>> = HashMap::new();
+  for (name, value) in headers.iter().filter(|(name, _)| !is_ignored_header(name)) {
+    recorded.entry(name.to_string())
+      .or_default()
+      .push(String::from_utf8_lossy(value.as_bytes()).to_string());
+  }
+  if recorded.is_empty() { None } else { Some(recorded) }
+}
+
+fn recorded_body(headers: &HeaderMap, body: &Bytes) -> OptionalBody {
+  if body.is_empty() {
+    OptionalBody::Missing
+  } else {
+    let content_type = headers.get(hyper::header::CONTENT_TYPE)
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| ContentType::parse(value).ok());
+    OptionalBody::Present(body.clone(), content_type, None)
+  }
+}
+
+fn without_credentials(headers: &Option<HashMap<String, Vec<String>>>) -> Option<HashMap<String, Vec<String>>> {
+  headers.as_ref()
+    .map(|headers| headers.iter()
+      .filter(|(name, _)| !CREDENTIAL_HEADERS.contains(&name.to_lowercase().as_str()))
+      .map(|(name, values)| (name.clone(), values.clone()))
+      .collect::<HashMap<_, _>>())
+    .filter(|headers| !headers.is_empty())
+}
+
+fn build_interaction(
+  description: String,
+  exchange: &RecordedExchange,
+  infer_matching_rules: bool,
+  record_credentials: bool
+) -> SynchronousHttp {
+  let mut request = exchange.request.clone();
+  let mut response = exchange.response.clone();
+
+  if !record_credentials {
+    request.headers = without_credentials(&request.headers);
+    response.headers = without_credentials(&response.headers);
+  }
+
+  if infer_matching_rules {
+    if let Some(json) = json_body(&request.body) {
+      inference::infer_rules(&json, request.matching_rules.add_category("body"), None);
+    }
+    if let Some(json) = json_body(&response.body) {
+      inference::infer_rules(&json, response.matching_rules.add_category("body"),
+        Some(&mut response.generators));
+    }
+  }
+
+  SynchronousHttp {
+    description,
+    request,
+    response,
+    .. SynchronousHttp::default()
+  }.with_key()
+}
+
+fn json_body(body: &OptionalBody) -> Option<serde_json::Value> {
+  match body {
+    OptionalBody::Present(bytes, Some(content_type), _) if content_type.is_json() =>
+      serde_json::from_slice(bytes).ok(),
+    _ => None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::matchingrules::MatchingRule;
+  use pact_models::path_exp::DocPath;
+
+  use super::*;
+
+  #[test]
+  fn join_paths_test() {
+    expect!(join_paths("/", "/users")).to(be_equal_to("/users"));
+    expect!(join_paths("", "/users")).to(be_equal_to("/users"));
+    expect!(join_paths("/api/", "/users")).to(be_equal_to("/api/users"));
+    expect!(join_paths("/api", "/")).to(be_equal_to("/api"));
+    expect!(join_paths("/", "/")).to(be_equal_to("/"));
+  }
+
+  #[test]
+  fn build_interaction_infers_matching_rules_for_json_bodies() {
+    let exchange = RecordedExchange {
+      request: HttpRequest {
+        method: "POST".to_string(),
+        path: "/users".to_string(),
+        headers: Some(hashmap!{ "content-type".to_string() => vec!["application/json".to_string()] }),
+        body: OptionalBody::Present(Bytes::from("{\"name\":\"Bob\"}"), ContentType::parse("application/json").ok(), None),
+        .. HttpRequest::default()
+      },
+      response: HttpResponse {
+        status: 201,
+        body: OptionalBody::Present(Bytes::from("{\"id\":10,\"name\":\"Bob\"}"), ContentType::parse("application/json").ok(), None),
+        .. HttpResponse::default()
+      }
+    };
+
+    let interaction = build_interaction("a request to POST /users".to_string(), &exchange, true, false);
+    expect!(interaction.key.is_some()).to(be_true());
+    let request_rules = interaction.request.matching_rules.rules_for_category("body").unwrap();
+    expect!(request_rules.rules.get(&DocPath::new_unwrap("$.name")).map(|r| r.rules.clone()))
+      .to(be_some().value(vec![MatchingRule::Type]));
+    expect!(interaction.request.generators.is_empty()).to(be_true());
+    let response_rules = interaction.response.matching_rules.rules_for_category("body").unwrap();
+    expect!(response_rules.rules.get(&DocPath::new_unwrap("$.id")).map(|r| r.rules.clone()))
+      .to(be_some().value(vec![MatchingRule::Integer]));
+    expect!(interaction.response.generators.is_empty()).to(be_false());
+
+    let interaction = build_interaction("a request to POST /users".to_string(), &exchange, false, false);
+    expect!(interaction.request.matching_rules.is_empty()).to(be_true());
+    expect!(interaction.response.generators.is_empty()).to(be_true());
+  }
+
+  #[test]
+  fn build_interaction_drops_credential_headers_unless_enabled() {
+    let exchange = RecordedExchange {
+      request: HttpRequest {
+        path: "/users".to_string(),
+        headers: Some(hashmap!{
+          "Authorization".to_string() => vec!["Bearer 1234".to_string()],
+          "cookie".to_string() => vec!["session=abc".to_string()],
+          "accept".to_string() => vec!["application/json".to_string()]
+        }),
+        .. HttpRequest::default()
+      },
+      response: HttpResponse {
+        headers: Some(hashmap!{ "set-cookie".to_string() => vec!["session=def".to_string()] }),
+        .. HttpResponse::default()
+      }
+    };
+
+    let interaction = build_interaction("a request to GET /users".to_string(), &exchange, true, false);
+    expect!(interaction.request.headers).to(be_some().value(hashmap!{
+      "accept".to_string() => vec!["application/json".to_string()]
+    }));
+    expect!(interaction.response.headers).to(be_none());
+
+    let interaction = build_interaction("a request to GET /users".to_string(), &exchange, true, true);
+    expect!(interaction.request.headers).to(be_equal_to(exchange.request.headers.clone()));
+    expect!(interaction.response.headers).to(be_equal_to(exchange.response.headers.clone()));
+  }
+
+  #[test]
+  fn accept_encoding_is_not_forwarded_or_recorded() {
+    let mut headers = HeaderMap::new();
+    headers.insert(hyper::header::ACCEPT_ENCODING, "gzip".parse().unwrap());
+    headers.insert(hyper::header::ACCEPT, "application/json".parse().unwrap());
+
+    expect!(is_ignored_header(&hyper::header::ACCEPT_ENCODING)).to(be_true());
+    expect!(recorded_headers(&headers)).to(be_some().value(hashmap!{
+      "accept".to_string() => vec!["application/json".to_string()]
+    }));
+  }
+}
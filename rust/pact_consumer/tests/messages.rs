@@ -5,7 +5,10 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use pact_consumer::{json_pattern, json_pattern_internal, like};
+use pact_consumer::patterns::Generated;
 use pact_consumer::prelude::*;
+use pact_models::generators::Generator;
+use pact_models::matchingrules::MatchingRule;
 
 // Example message handler
 struct MessageHandler {
@@ -120,3 +123,148 @@ fn test_req_res_message_client() {
     expect!(processed.state).to(be_equal_to("VA"));
   }
 }
+
+// This is a test for the message verification helper. Each message is passed to our message
+// handler, and the Pact file is only written if all the messages were accepted.
+#[test_log::test]
+fn test_verify_messages() {
+  let output_dir = tempfile::tempdir().unwrap();
+  let mut pact_builder = PactBuilder::new_v4("verify-consumer", "message-provider");
+  pact_builder
+    .with_output_dir(output_dir.path())
+    .message_interaction("hello message", |mut i| {
+      i.test_name("test_verify_messages");
+      i.json_body(json_pattern!({
+        "name": like!("mai"),
+        "street": like!("5th"),
+        "state": like!("VA"),
+      }));
+      i
+    });
+
+  let handler = MessageHandler::new();
+  let result = pact_builder.verify_messages(|message| {
+    handler.process(message.contents.contents.value().unwrap()).map(|_| ())
+  });
+
+  expect!(result).to(be_ok());
+  expect!(output_dir.path().join("verify-consumer-message-provider.json").exists()).to(be_true());
+}
+
+#[test_log::test]
+fn test_verify_messages_with_failures() {
+  let output_dir = tempfile::tempdir().unwrap();
+  let mut pact_builder = PactBuilder::new_v4("verify-consumer", "message-provider");
+  pact_builder
+    .with_output_dir(output_dir.path())
+    .message_interaction("hello message", |mut i| {
+      i.json_body(json_pattern!({ "name": like!("mai"), "state": like!("VA") }));
+      i
+    })
+    .message_interaction("bad message", |mut i| {
+      i.json_body(json_pattern!({ "name": like!("mai"), "state": like!("!!") }));
+      i
+    });
+
+  let handler = MessageHandler::new();
+  let result = pact_builder.verify_messages(|message| {
+    handler.process(message.contents.contents.value().unwrap()).map(|_| ())
+  });
+
+  let error = result.unwrap_err();
+  expect!(error.failures.len()).to(be_equal_to(1));
+  expect!(error.failures[0].description.as_str()).to(be_equal_to("bad message"));
+  expect!(error.failures[0].error.as_str()).to(be_equal_to("Failed to parse message: state is not valid"));
+  expect!(output_dir.path().join("verify-consumer-message-provider.json").exists()).to(be_false());
+}
+
+#[test_log::test(tokio::test)]
+async fn test_verify_messages_async() {
+  let output_dir = tempfile::tempdir().unwrap();
+  let mut pact_builder = PactBuilderAsync::new_v4("verify-consumer-async", "message-provider");
+  pact_builder
+    .output_dir(output_dir.path())
+    .message_interaction("hello message", |mut i| async move {
+      i.json_body(json_pattern!({ "name": like!("mai"), "state": like!("VA") }));
+      i
+    })
+    .await;
+
+  let result = pact_builder.verify_messages(|message| async move {
+    let handler = MessageHandler::new();
+    handler.process(message.contents.contents.value().unwrap()).map(|_| ())
+  }).await;
+
+  expect!(result).to(be_ok());
+  expect!(output_dir.path().join("verify-consumer-async-message-provider.json").exists()).to(be_true());
+}
+
+#[test_log::test]
+fn test_verify_messages_applies_generators() {
+  let output_dir = tempfile::tempdir().unwrap();
+  let mut pact_builder = PactBuilder::new_v4("generators-consumer", "message-provider");
+  pact_builder
+    .with_output_dir(output_dir.path())
+    .message_interaction("counter message", |mut i| {
+      i.json_body(json_pattern!({
+        "count": Generated::new(0, Some(MatchingRule::Integer), Generator::RandomInt(100, 200))
+      }));
+      i
+    });
+
+  let result = pact_builder.verify_messages(|message| {
+    let json: serde_json::Value = serde_json::from_slice(&message.contents.contents.value().unwrap()).unwrap_or_default();
+    match json["count"].as_i64() {
+      Some(count) if (100..=200).contains(&count) => Ok(()),
+      _ => Err(anyhow!("count was not generated: {}", json))
+    }
+  });
+
+  expect!(result).to(be_ok());
+}
+
+#[test_log::test(tokio::test)]
+async fn test_verify_messages_async_applies_generators() {
+  let output_dir = tempfile::tempdir().unwrap();
+  let mut pact_builder = PactBuilderAsync::new_v4("generators-consumer-async", "message-provider");
+  pact_builder
+    .output_dir(output_dir.path())
+    .message_interaction("counter message", |mut i| async move {
+      i.json_body(json_pattern!({
+        "count": Generated::new(0, Some(MatchingRule::Integer), Generator::RandomInt(100, 200))
+      }));
+      i
+    })
+    .await;
+
+  let result = pact_builder.verify_messages(|message| async move {
+    let json: serde_json::Value = serde_json::from_slice(&message.contents.contents.value().unwrap()).unwrap_or_default();
+    match json["count"].as_i64() {
+      Some(count) if (100..=200).contains(&count) => Ok(()),
+      _ => Err(anyhow!("count was not generated: {}", json))
+    }
+  }).await;
+
+  expect!(result).to(be_ok());
+}
+
+// The blocking builder must not deadlock when used from a single threaded Tokio runtime
+#[test_log::test(tokio::test)]
+async fn test_messages_applies_generators_on_a_current_thread_runtime() {
+  let output_dir = tempfile::tempdir().unwrap();
+  let mut pact_builder = PactBuilder::new_v4("generators-consumer-current-thread", "message-provider");
+  pact_builder
+    .with_output_dir(output_dir.path())
+    .message_interaction("counter message", |mut i| {
+      i.json_body(json_pattern!({
+        "count": Generated::new(0, Some(MatchingRule::Integer), Generator::RandomInt(100, 200))
+      }));
+      i
+    });
+
+  for message in pact_builder.messages() {
+    let json: serde_json::Value = serde_json::from_slice(&message.contents.contents.value().unwrap()).unwrap_or_default();
+    expect!(json["count"].as_i64()).to(be_some());
+    expect!((100..=200).contains(&json["count"].as_i64().unwrap())).to(be_true());
+  }
+}
@@ -551,3 +551,81 @@ async fn test_protobuf_plugin_contents_merge_with_existing_interaction() {
     interaction_2_response_contents
   );
 }
+
+#[cfg(feature = "recorder")]
+#[test_log::test(tokio::test)]
+async fn recording_proxy_records_the_requests_to_the_provider() {
+  use pact_consumer::recorder::RecordingProxy;
+  use pact_models::interaction::Interaction;
+  use pact_models::matchingrules::MatchingRule;
+  use pact_models::path_exp::DocPath;
+
+  let provider = PactBuilderAsync::new_v4("Recorder Consumer", "Recorder Provider")
+    .interaction("a request for a user", "", |mut i| async move {
+      i.request.path("/users/100");
+      i.response
+        .ok()
+        .content_type("application/json")
+        .body("{\"id\":100,\"name\":\"Bob\",\"created\":\"2024-01-02T03:04:05Z\"}");
+      i.clone()
+    })
+    .await
+    .start_mock_server_async(None, None)
+    .await;
+
+  let proxy = RecordingProxy::start(provider.url().as_str(), "Legacy Consumer", "Recorder Provider")
+    .await
+    .unwrap();
+  let response = reqwest::get(proxy.url().join("/users/100").unwrap()).await.unwrap();
+  expect!(response.status()).to(be_equal_to(StatusCode::OK));
+  let body: serde_json::Value = response.json().await.unwrap();
+  expect!(body["name"].clone()).to(be_equal_to(json!("Bob")));
+
+  let pact = proxy.pact();
+  expect!(pact.consumer.name.as_str()).to(be_equal_to("Legacy Consumer"));
+  expect!(pact.interactions.len()).to(be_equal_to(1));
+  let interaction = pact.interactions[0].as_v4_http().unwrap();
+  expect!(interaction.description.as_str()).to(be_equal_to("a request to GET /users/100"));
+  expect!(interaction.request.path.as_str()).to(be_equal_to("/users/100"));
+  expect!(interaction.response.status).to(be_equal_to(200));
+  let rules = interaction.response.matching_rules.rules_for_category("body").unwrap();
+  expect!(rules.rules.get(&DocPath::new_unwrap("$.id")).map(|r| r.rules.clone()))
+    .to(be_some().value(vec![MatchingRule::Integer]));
+  expect!(rules.rules.get(&DocPath::new_unwrap("$.created")).map(|r| r.rules.clone()))
+    .to(be_some().value(vec![MatchingRule::Timestamp("yyyy-MM-dd'T'HH:mm:ssXXX".to_string())]));
+}
+
+#[cfg(feature = "macros")]
+#[pact_consumer::test(consumer = "Macro Consumer", provider = "Alice Service", output_dir = "target/pact-macro-tests")]
+fn pact_test_macro(pact: &mut PactTest<PactBuilder>) {
+  pact.interaction("a retrieve Mallory request", "", |mut i| {
+    i.request.path("/mallory");
+    i.response
+      .ok()
+      .content_type("text/plain")
+      .body("That is some good Mallory.");
+    i
+  });
+
+  let alice_service = pact.start_mock_server(None, None);
+  let response = reqwest::blocking::get(alice_service.path("/mallory")).expect("could not fetch URL");
+  assert_eq!(response.text().unwrap(), "That is some good Mallory.");
+}
+
+#[cfg(feature = "macros")]
+#[pact_consumer::test(consumer = "Macro Consumer", provider = "Alice Service Async", output_dir = "target/pact-macro-tests")]
+async fn pact_test_macro_async(pact: &mut PactTest<PactBuilderAsync>) -> anyhow::Result<()> {
+  pact.interaction("a retrieve Mallory request", "", |mut i| async move {
+    i.request.path("/mallory");
+    i.response
+      .ok()
+      .content_type("text/plain")
+      .body("That is some good Mallory.");
+    i
+  }).await;
+
+  let url = pact.mock_server().await.path("/mallory");
+  let body = reqwest::get(url).await?.text().await?;
+  assert_eq!(body, "That is some good Mallory.");
+  Ok(())
+}
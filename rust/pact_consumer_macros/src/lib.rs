@@ -0,0 +1,233 @@
+//! Procedural macros for the `pact_consumer` crate. These should not be used directly, but via
+//! the re-exports in `pact_consumer` (i.e. `#[pact_consumer::test(...)]`).
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Attribute, ItemFn, LitStr, ReturnType};
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parser;
+
+/// Marks a function as a consumer Pact test. The function can take a single `&mut PactTest<B>`
+/// argument, where `B` is either `PactBuilder` or `PactBuilderAsync`. The `PactTest` dereferences
+/// to the builder, so it can be used to set up the interactions and start the mock server.
+///
+/// Each test writes its Pact file to its own temporary directory, and the Pact file is merged into
+/// the output directory once the test has passed. The `PactTest` also provides a mock server for
+/// the interactions (`pact.mock_server()`), which is started the first time it is used and shut
+/// down when the test function returns.
+///
+/// The function must not have another test attribute (i.e. `#[test]` or `#[tokio::test]`), as
+/// this macro adds one. Async functions are run on a new multi-threaded Tokio runtime.
+///
+/// Attributes:
+/// * `consumer` - Name of the consumer (required)
+/// * `provider` - Name of the provider (required)
+/// * `output_dir` - Directory to write the Pact file to. Defaults to the `PACT_OUTPUT_DIR`
+///   environment variable or `target/pacts`.
+///
+/// ```ignore
+/// #[pact_consumer::test(consumer = "Consumer", provider = "Alice Service")]
+/// fn alice_service_test(pact: &mut PactTest<PactBuilder>) {
+///   pact.interaction("a retrieve Mallory request", "", |mut i| {
+///     i.request.path("/mallory");
+///     i.response.ok().body("That is some good Mallory.");
+///     i
+///   });
+///   let url = pact.mock_server().path("/mallory");
+///   // Make the requests to the mock server here
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+  expand_test(attr.into(), item.into())
+    .unwrap_or_else(|err| err.to_compile_error())
+    .into()
+}
+
+/// Attributes for the test macro
+#[derive(Default)]
+struct TestArgs {
+  consumer: Option<LitStr>,
+  provider: Option<LitStr>,
+  output_dir: Option<LitStr>
+}
+
+impl TestArgs {
+  fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+    if meta.path.is_ident("consumer") {
+      self.consumer = Some(meta.value()?.parse()?);
+      Ok(())
+    } else if meta.path.is_ident("provider") {
+      self.provider = Some(meta.value()?.parse()?);
+      Ok(())
+    } else if meta.path.is_ident("output_dir") {
+      self.output_dir = Some(meta.value()?.parse()?);
+      Ok(())
+    } else {
+      Err(meta.error("unsupported attribute, expected one of consumer, provider or output_dir"))
+    }
+  }
+}
+
+fn expand_test(attr: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+  let mut args = TestArgs::default();
+  syn::meta::parser(|meta| args.parse(meta)).parse2(attr)?;
+  let consumer = args.consumer
+    .ok_or_else(|| syn::Error::new(Span::call_site(), "the consumer name is required (consumer = \"...\")"))?;
+  let provider = args.provider
+    .ok_or_else(|| syn::Error::new(Span::call_site(), "the provider name is required (provider = \"...\")"))?;
+  let output_dir = match args.output_dir {
+    Some(dir) => quote! { ::std::option::Option::Some(#dir) },
+    None => quote! { ::std::option::Option::None }
+  };
+
+  let input: ItemFn = syn::parse2(item)?;
+  if input.sig.inputs.len() > 1 {
+    return Err(syn::Error::new_spanned(&input.sig.inputs,
+      "a Pact test function can only take a single `&mut PactTest<_>` argument"));
+  }
+  if !input.sig.generics.params.is_empty() {
+    return Err(syn::Error::new_spanned(&input.sig.generics, "a Pact test function can not be generic"));
+  }
+  // The test attribute is added by this macro (and async tests are run on their own runtime), so
+  // any other test attribute (i.e. `#[test]` or `#[tokio::test]`) would create a second test
+  if let Some(attr) = input.attrs.iter().find(|attr| is_test_attribute(attr)) {
+    return Err(syn::Error::new_spanned(attr,
+      "a Pact test function must not have a test attribute, as one is added by the #[pact_consumer::test] macro"));
+  }
+
+  let name = &input.sig.ident;
+  let attrs = &input.attrs;
+  let vis = &input.vis;
+  let output = &input.sig.output;
+  let body_name = format_ident!("__pact_test_{}", name);
+  let mut body_fn = input.clone();
+  body_fn.attrs.clear();
+  body_fn.vis = syn::Visibility::Inherited;
+  body_fn.sig.ident = body_name.clone();
+
+  // With no argument, the builder type can't be inferred from the test function
+  let (let_harness, call) = if input.sig.inputs.is_empty() {
+    (quote! { let mut pact_test = ::pact_consumer::harness::PactTest::<::pact_consumer::builders::PactBuilder> },
+      quote! { #body_name() })
+  } else {
+    (quote! { let mut pact_test = ::pact_consumer::harness::PactTest }, quote! { #body_name(&mut pact_test) })
+  };
+  let result_type = match output {
+    ReturnType::Default => quote! { () },
+    ReturnType::Type(_, ty) => quote! { #ty }
+  };
+  let test_name = quote! { ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#name)) };
+
+  let test_fn = if input.sig.asyncness.is_some() {
+    quote! {
+      #(#attrs)*
+      #[test]
+      #vis fn #name() #output {
+        #body_fn
+
+        ::pact_consumer::harness::block_on(async {
+          #let_harness::new(#consumer, #provider, #test_name, #output_dir);
+          let result: #result_type = #call.await;
+          pact_test.finish(&result);
+          result
+        })
+      }
+    }
+  } else {
+    quote! {
+      #(#attrs)*
+      #[test]
+      #vis fn #name() #output {
+        #body_fn
+
+        #let_harness::new(#consumer, #provider, #test_name, #output_dir);
+        let result: #result_type = #call;
+        pact_test.finish(&result);
+        result
+      }
+    }
+  };
+  Ok(test_fn)
+}
+
+/// If the attribute is a test attribute, like `#[test]`, `#[tokio::test]` or `#[test_log::test]`
+fn is_test_attribute(attr: &Attribute) -> bool {
+  attr.path().segments.last()
+    .map(|segment| segment.ident == "test")
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use quote::quote;
+
+  use super::expand_test;
+
+  #[test]
+  fn expand_test_generates_a_test_function() {
+    let result = expand_test(
+      quote! { consumer = "Consumer", provider = "Provider" },
+      quote! {
+        #[ignore]
+        fn my_test(pact: &mut PactTest<PactBuilder>) {
+          pact.interaction("test", "", |i| i);
+        }
+      }
+    ).unwrap().to_string();
+
+    expect!(result.contains("# [ignore] # [test] fn my_test ()")).to(be_true());
+    expect!(result.contains("fn __pact_test_my_test (pact : & mut PactTest < PactBuilder >)")).to(be_true());
+    expect!(result.contains("PactTest :: new (\"Consumer\" , \"Provider\"")).to(be_true());
+    expect!(result.contains("block_on")).to(be_false());
+  }
+
+  #[test]
+  fn expand_test_with_an_async_function() {
+    let result = expand_test(
+      quote! { consumer = "Consumer", provider = "Provider", output_dir = "target/my-pacts" },
+      quote! {
+        async fn my_test(pact: &mut PactTest<PactBuilderAsync>) -> anyhow::Result<()> {
+          Ok(())
+        }
+      }
+    ).unwrap().to_string();
+
+    expect!(result.contains("fn my_test () -> anyhow :: Result < () >")).to(be_true());
+    expect!(result.contains("block_on (async")).to(be_true());
+    expect!(result.contains("Some (\"target/my-pacts\")")).to(be_true());
+  }
+
+  #[test]
+  fn expand_test_requires_the_consumer_and_provider() {
+    let result = expand_test(quote! { consumer = "Consumer" }, quote! { fn my_test() {} });
+    expect!(result.unwrap_err().to_string())
+      .to(be_equal_to("the provider name is required (provider = \"...\")"));
+
+    let result = expand_test(quote! { consumer = "Consumer", provider = "Provider", other = "value" },
+      quote! { fn my_test() {} });
+    expect!(result.unwrap_err().to_string())
+      .to(be_equal_to("unsupported attribute, expected one of consumer, provider or output_dir"));
+  }
+
+  #[test]
+  fn expand_test_rejects_other_test_attributes() {
+    let result = expand_test(quote! { consumer = "Consumer", provider = "Provider" },
+      quote! { #[test] fn my_test() {} });
+    expect!(result.is_err()).to(be_true());
+
+    let result = expand_test(quote! { consumer = "Consumer", provider = "Provider" },
+      quote! { #[tokio::test] async fn my_test() {} });
+    expect!(result.unwrap_err().to_string()).to(be_equal_to(
+      "a Pact test function must not have a test attribute, as one is added by the #[pact_consumer::test] macro"));
+  }
+
+  #[test]
+  fn expand_test_only_allows_a_single_argument() {
+    let result = expand_test(quote! { consumer = "Consumer", provider = "Provider" },
+      quote! { fn my_test(a: u32, b: u32) {} });
+    expect!(result.is_err()).to(be_true());
+  }
+}
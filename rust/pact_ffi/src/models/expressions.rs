@@ -14,6 +14,7 @@ use pact_models::matchingrules::expressions::{
 };
 use pact_models::matchingrules::MatchingRule;
 use pact_models::time_utils::validate_datetime;
+use serde_json::Value;
 use tracing::{debug, error, trace};
 
 use crate::{as_mut, as_ref, ffi_fn, safe_str};
@@ -227,6 +228,14 @@ ffi_fn! {
 /// | Semver | 21 |
 /// | EachKey | 22 |
 /// | EachValue | 23 |
+/// | NumberRange | 24 |
+/// | StringLength | 25 |
+/// | OneOf | 26 |
+/// | JsonSchema | 27 |
+/// | UniqueItems | 28 |
+/// | Sorted | 29 |
+/// | Format | 30 |
+/// | Expression | 31 |
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MatchingRuleResult {
   /// The matching rule from the expression.
@@ -292,7 +301,24 @@ impl MatchingRuleIterator {
               MatchingRule::NotEmpty => None,
               MatchingRule::Semver => None,
               MatchingRule::EachKey(_) => None,
-              MatchingRule::EachValue(_) => None
+              MatchingRule::EachValue(_) => None,
+              MatchingRule::NumberRange(min, max, inclusive) => {
+                let s = format!("{}:{}:{}", min, max, inclusive);
+                Some(CString::new(s).unwrap())
+              },
+              MatchingRule::StringLength(min, max) => {
+                let s = format!("{}:{}", min, max);
+                Some(CString::new(s).unwrap())
+              },
+              MatchingRule::OneOf(values) => Some(CString::new(Value::Array(values.clone()).to_string()).unwrap()),
+              MatchingRule::JsonSchema(schema) => Some(CString::new(schema.to_string()).unwrap()),
+              MatchingRule::UniqueItems(key) => key.as_ref().map(|key| CString::new(key.to_string()).unwrap()),
+              MatchingRule::Sorted(field, order) => {
+                let s = format!("{}:{}", field, order);
+                Some(CString::new(s).unwrap())
+              },
+              MatchingRule::Format(format) => Some(CString::new(format.to_string()).unwrap()),
+              MatchingRule::Expression(expression) => Some(CString::new(expression.as_str()).unwrap())
             };
             let rule_value = val.as_ref().map(|v| v.as_ptr()).unwrap_or_else(|| null());
             let rule_result = MatchingRuleResult::MatchingRule(rule_id(rule), rule_value, rule.clone());
@@ -346,7 +372,15 @@ fn rule_id(rule: &MatchingRule) -> u16 {
     MatchingRule::NotEmpty => 20,
     MatchingRule::Semver => 21,
     MatchingRule::EachKey(_) => 22,
-    MatchingRule::EachValue(_) => 23
+    MatchingRule::EachValue(_) => 23,
+    MatchingRule::NumberRange(_, _, _) => 24,
+    MatchingRule::StringLength(_, _) => 25,
+    MatchingRule::OneOf(_) => 26,
+    MatchingRule::JsonSchema(_) => 27,
+    MatchingRule::UniqueItems(_) => 28,
+    MatchingRule::Sorted(_, _) => 29,
+    MatchingRule::Format(_) => 30,
+    MatchingRule::Expression(_) => 31
   }
 }
 
@@ -425,6 +459,14 @@ ffi_fn! {
     /// | Semver | 21 |
     /// | EachKey | 22 |
     /// | EachValue | 23 |
+    /// | NumberRange | 24 |
+    /// | StringLength | 25 |
+    /// | OneOf | 26 |
+    /// | JsonSchema | 27 |
+    /// | UniqueItems | 28 |
+    /// | Sorted | 29 |
+    /// | Format | 30 |
+    /// | Expression | 31 |
     ///
     /// # Safety
     ///
@@ -471,6 +513,14 @@ ffi_fn! {
     /// | Semver | 21 | NULL |
     /// | EachKey | 22 | NULL |
     /// | EachValue | 23 | NULL |
+    /// | NumberRange | 24 | "min:max:inclusive" (inclusive is true or false) |
+    /// | StringLength | 25 | "min:max" |
+    /// | OneOf | 26 | JSON array of the values (as a JSON string) |
+    /// | JsonSchema | 27 | JSON schema (as a JSON string) |
+    /// | UniqueItems | 28 | Key path, or NULL if the items themselves must be unique |
+    /// | Sorted | 29 | "field:order" |
+    /// | Format | 30 | Name of the format (email, uri, ipv4, ipv6, uuid, ulid or hostname) |
+    /// | Expression | 31 | Expression to evaluate against the document |
     ///
     /// Will return a NULL pointer if the matching rule was a reference or does not have an
     /// associated value.
@@ -763,6 +813,46 @@ mod tests {
     expect!(definition.result.as_ref().right()).to(be_some());
   }
 
+  #[test_log::test]
+  fn matching_rule_value_for_a_number_range_includes_the_inclusive_flag() {
+    let value = CString::new("range(-10.5, 0.25, false)").unwrap();
+    let result = pactffi_parse_matcher_definition(value.as_ptr());
+    expect!(result.is_null()).to(be_false());
+
+    let iter = pactffi_matcher_definition_iter(result);
+    expect!(iter.is_null()).to(be_false());
+    let rule = pactffi_matching_rule_iter_next(iter);
+    expect!(rule.is_null()).to(be_false());
+    let rule_type = pactffi_matching_rule_id(rule);
+    expect!(rule_type).to(be_equal_to(24));
+    let rule_value = pactffi_matching_rule_value(rule);
+    let string =  unsafe { CStr::from_ptr(rule_value) };
+    expect!(string.to_string_lossy()).to(be_equal_to("-10.5:0.25:false"));
+    pactffi_matching_rule_iter_delete(iter);
+
+    let _definition = unsafe { Box::from_raw(result as *mut MatchingRuleDefinitionResult) };
+  }
+
+  #[test_log::test]
+  fn matching_rule_value_for_one_of_is_a_json_array() {
+    let value = CString::new("matching(oneOf, ['a,b', 1], 'a,b')").unwrap();
+    let result = pactffi_parse_matcher_definition(value.as_ptr());
+    expect!(result.is_null()).to(be_false());
+
+    let iter = pactffi_matcher_definition_iter(result);
+    expect!(iter.is_null()).to(be_false());
+    let rule = pactffi_matching_rule_iter_next(iter);
+    expect!(rule.is_null()).to(be_false());
+    let rule_type = pactffi_matching_rule_id(rule);
+    expect!(rule_type).to(be_equal_to(26));
+    let rule_value = pactffi_matching_rule_value(rule);
+    let string =  unsafe { CStr::from_ptr(rule_value) };
+    expect!(string.to_string_lossy()).to(be_equal_to("[\"a,b\",1]"));
+    pactffi_matching_rule_iter_delete(iter);
+
+    let _definition = unsafe { Box::from_raw(result as *mut MatchingRuleDefinitionResult) };
+  }
+
   #[test_log::test]
   fn pactffi_validate_datetime_test() {
     let value = CString::new("").unwrap();
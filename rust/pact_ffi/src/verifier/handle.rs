@@ -7,7 +7,18 @@ use pact_mock_server::LOG_ID;
 use serde_json::Value;
 use tracing::{debug, error};
 use pact_models::prelude::HttpAuth;
-use pact_verifier::{ConsumerVersionSelector, FilterInfo, NullRequestFilterExecutor, PactSource, ProviderInfo, ProviderTransport, PublishOptions, VerificationOptions, verify_provider_async};
+use pact_verifier::{
+  ConsumerVersionSelector,
+  FilterInfo,
+  NullRequestFilterExecutor,
+  PactSource,
+  ProviderInfo,
+  ProviderReadinessCheck,
+  ProviderTransport,
+  PublishOptions,
+  VerificationOptions,
+  verify_provider_async
+};
 use pact_verifier::callback_executors::HttpRequestProviderStateExecutor;
 use pact_verifier::metrics::VerificationMetrics;
 use pact_verifier::verification_result::VerificationExecutionResult;
@@ -136,6 +147,20 @@ impl VerifierHandle {
     }
   }
 
+  /// Update the filter to only verify the interactions with the given keys or IDs. This will
+  /// replace any description or provider state filter. If both are empty, the filter is removed.
+  pub fn update_interaction_filter(
+    &mut self,
+    keys: Vec<String>,
+    ids: Vec<String>
+  ) {
+    self.filter = if keys.is_empty() && ids.is_empty() {
+      FilterInfo::None
+    } else {
+      FilterInfo::Interactions { keys, ids, descriptions: vec![] }
+    }
+  }
+
   /// Add a file source to be verified
   pub fn add_file_source(&mut self, file: &str) {
     self.sources.push(PactSource::File(file.to_string()));
@@ -242,6 +267,32 @@ impl VerifierHandle {
     self.verification_options.no_pacts_is_error = is_error;
   }
 
+  /// Sets the number of times a request to the provider will be retried if it fails with a
+  /// connection error or timeout, and the delay (in ms) between each retry
+  pub fn set_provider_request_retries(&mut self, retries: u8, retry_delay: u64) {
+    self.verification_options.request_retries = retries;
+    self.verification_options.request_retry_delay = retry_delay;
+  }
+
+  /// Sets the readiness check URL to poll before verifying any interactions. The timeout and
+  /// interval are in ms. An empty URL will remove the readiness check.
+  pub fn set_readiness_check(&mut self, url: &str, timeout: u64, interval: u64) {
+    self.verification_options.readiness_check = if url.is_empty() {
+      None
+    } else {
+      Some(ProviderReadinessCheck {
+        url: url.to_string(),
+        timeout,
+        interval
+      })
+    };
+  }
+
+  /// Sets the seed to use for the random generators
+  pub fn set_generator_seed(&mut self, seed: u64) {
+    self.verification_options.generator_seed = Some(seed);
+  }
+
   /// Update the details used when publishing results
   /// 
   /// # Args
@@ -353,7 +404,7 @@ mod tests {
   use pact_models::v4::interaction::V4Interaction;
   use pact_models::v4::pact::V4Pact;
   use pact_models::v4::synch_http::SynchronousHttp;
-  use pact_verifier::PactSource;
+  use pact_verifier::{FilterInfo, PactSource};
 
   use crate::verifier::handle::VerifierHandle;
 
@@ -379,4 +430,16 @@ mod tests {
     let message = error.as_object().unwrap()["message"].as_str().unwrap();
     expect!(message).to(be_equal_to("error sending request for url (https://localhost:1234/)"));
   }
+
+  #[test]
+  fn update_interaction_filter_replaces_the_filter() {
+    let mut handle = VerifierHandle::new_for_application("test", "0.0.0");
+    handle.update_filter_info("bob".to_string(), String::new(), false);
+    handle.update_interaction_filter(vec!["1234abcd".to_string()], vec![]);
+    expect!(matches!(&handle.filter, FilterInfo::Interactions { keys, ids, .. }
+      if keys == &vec!["1234abcd".to_string()] && ids.is_empty())).to(be_true());
+
+    handle.update_interaction_filter(vec![], vec![]);
+    expect!(matches!(handle.filter, FilterInfo::None)).to(be_true());
+  }
 }
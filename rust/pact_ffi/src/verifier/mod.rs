@@ -218,6 +218,35 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Set the filter for the Pact verifier to only verify the interactions with the given keys or
+    /// IDs. Keys are only available for V4 interactions, and IDs are only set for interactions
+    /// fetched from a Pact Broker. An interaction is verified if it matches any of the keys or IDs.
+    ///
+    /// This replaces any filter set with `pactffi_verifier_set_filter_info`. Passing no keys and
+    /// no IDs will remove the filter.
+    ///
+    /// # Safety
+    ///
+    /// All string fields must contain valid UTF-8. Invalid UTF-8
+    /// will be replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    fn pactffi_verifier_set_interaction_filters(
+      handle: *mut handle::VerifierHandle,
+      keys: *const *const c_char,
+      keys_len: c_ushort,
+      ids: *const *const c_char,
+      ids_len: c_ushort
+    ) {
+      let handle = as_mut!(handle);
+
+      let keys = get_vector(keys, keys_len);
+      let ids = get_vector(ids, ids_len);
+
+      handle.update_interaction_filter(keys, ids);
+    }
+}
+
 ffi_fn! {
     /// Set the provider state URL for the Pact verifier.
     ///
@@ -277,6 +306,82 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Sets the number of times a request to the provider will be retried if it fails with a
+    /// connection error or a timeout (i.e. while the provider is starting up). `retry_delay` is
+    /// the time in milliseconds to wait before each retry. By default, requests are not retried.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe as long as the handle pointer points to a valid handle.
+    ///
+    fn pactffi_verifier_set_provider_retries(
+      handle: *mut handle::VerifierHandle,
+      retries: c_uchar,
+      retry_delay: c_ulong
+    ) -> c_int {
+      let handle = as_mut!(handle);
+
+      handle.set_provider_request_retries(retries, retry_delay as u64);
+
+      EXIT_SUCCESS
+    } {
+      EXIT_FAILURE
+    }
+}
+
+ffi_fn! {
+    /// Sets a readiness check URL that will be polled until it returns a successful (2xx) response
+    /// before any interactions are verified. `timeout` is the maximum time in milliseconds to wait
+    /// for the provider to be ready, and `interval` is the time in milliseconds between each attempt.
+    /// If the provider is not ready before the timeout, the verification will fail.
+    ///
+    /// Passing a NULL or empty URL will remove the readiness check.
+    ///
+    /// # Safety
+    ///
+    /// All string fields must contain valid UTF-8. Invalid UTF-8
+    /// will be replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    fn pactffi_verifier_set_readiness_check(
+      handle: *mut handle::VerifierHandle,
+      url: *const c_char,
+      timeout: c_ulong,
+      interval: c_ulong
+    ) -> c_int {
+      let handle = as_mut!(handle);
+      let url = if_null(url, "");
+
+      handle.set_readiness_check(url.as_str(), timeout as u64, interval as u64);
+
+      EXIT_SUCCESS
+    } {
+      EXIT_FAILURE
+    }
+}
+
+ffi_fn! {
+    /// Sets the seed to use for the random generators, so that the same requests are generated
+    /// for the provider on every run.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe as long as the handle pointer points to a valid handle.
+    ///
+    fn pactffi_verifier_set_generator_seed(
+      handle: *mut handle::VerifierHandle,
+      seed: u64
+    ) -> c_int {
+      let handle = as_mut!(handle);
+
+      handle.set_generator_seed(seed);
+
+      EXIT_SUCCESS
+    } {
+      EXIT_FAILURE
+    }
+}
+
 ffi_fn! {
     /// Enables or disables coloured output using ANSI escape codes in the verifier output. By default,
     /// coloured output is enabled.
@@ -633,6 +738,92 @@ ffi_fn! {
     } { -2 }
 }
 
+ffi_fn! {
+    /// Adds a URL as a source to verify, using the authentication given in JSON form. The Pact file
+    /// will be fetched from the URL.
+    ///
+    /// The authentication JSON must be an object with a `type` attribute, one of:
+    /// * `basic` - with `username` and `password` attributes
+    /// * `bearer` - with a `token` attribute
+    /// * `oauth2` - OAuth2 client credentials grant, with `tokenUrl`, `clientId`, `clientSecret`
+    ///   and optional `scope` attributes
+    /// * `header` - a custom header, with `name` and `value` attributes (i.e. `X-API-Key`)
+    ///
+    /// This function will return zero unless the authentication JSON is not valid, in which case,
+    /// it will return -1.
+    ///
+    /// # Safety
+    ///
+    /// All string fields must contain valid UTF-8. Invalid UTF-8
+    /// will be replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    fn pactffi_verifier_url_source_with_auth(
+      handle: *mut handle::VerifierHandle,
+      url: *const c_char,
+      auth: *const c_char
+    ) -> c_int {
+      let handle = as_mut!(handle);
+      let url = safe_str!(url);
+
+      match auth_from_json(auth) {
+        Ok(auth) => {
+          handle.add_url_source(url, &auth);
+          0
+        }
+        Err(err) => {
+          error!("{}", err);
+          -1
+        }
+      }
+    } { -2 }
+}
+
+ffi_fn! {
+    /// Adds a Pact broker as a source to verify, using the authentication given in JSON form.
+    /// This will fetch all the pact files from the broker that match the provider name.
+    ///
+    /// See `pactffi_verifier_url_source_with_auth` for the format of the authentication JSON.
+    ///
+    /// This function will return zero unless the authentication JSON is not valid, in which case,
+    /// it will return -1.
+    ///
+    /// # Safety
+    ///
+    /// All string fields must contain valid UTF-8. Invalid UTF-8
+    /// will be replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    fn pactffi_verifier_broker_source_with_auth(
+      handle: *mut handle::VerifierHandle,
+      url: *const c_char,
+      auth: *const c_char
+    ) -> c_int {
+      let handle = as_mut!(handle);
+      let url = safe_str!(url);
+
+      match auth_from_json(auth) {
+        Ok(auth) => {
+          handle.add_pact_broker_source(url, false, None, vec![], None, vec![], &auth);
+          0
+        }
+        Err(err) => {
+          error!("{}", err);
+          -1
+        }
+      }
+    } { -2 }
+}
+
+fn auth_from_json(auth: *const c_char) -> anyhow::Result<HttpAuth> {
+  let auth = if_null(auth, "");
+  if auth.is_empty() {
+    Ok(HttpAuth::None)
+  } else {
+    let json = serde_json::from_str(auth.as_str())
+      .map_err(|err| anyhow::anyhow!("Failed to parse the authentication as JSON: {}", err))?;
+    HttpAuth::from_json(&json)
+  }
+}
+
 ffi_fn! {
     /// Runs the verification.
     ///
@@ -17,6 +17,7 @@ use pact_models::xml_utils::{group_children, text_nodes};
 
 use crate::engine::{build_matching_rule_node, ExecutionPlanNode, NodeValue, PlanMatchingContext};
 use crate::engine::xml::name;
+use crate::json_schema::json_schema_rule;
 
 /// Trait for implementations of builders for different types of bodies
 pub trait PlanBodyBuilder: Debug {
@@ -94,6 +95,21 @@ impl JsonPlanBuilder {
     root_node: &mut ExecutionPlanNode
   ) {
     trace!(%json, %path, ">>> process_body_node");
+    if context.matcher_is_defined(path) {
+      let rules = context.select_best_matcher(path);
+      if let Some(schema) = json_schema_rule(&rules) {
+        root_node.add(ExecutionPlanNode::annotation(format!("{} {}",
+          path.last_field().unwrap_or_default(),
+          MatchingRule::JsonSchema(schema.clone()).generate_description(false))));
+        root_node.add(
+          ExecutionPlanNode::action("json:match:schema")
+            .add(ExecutionPlanNode::value_node(NodeValue::NAMESPACED("json".to_string(), schema.to_string())))
+            .add(ExecutionPlanNode::resolve_current_value(path))
+        );
+        return;
+      }
+    }
+
     match &json {
       Value::Array(items) => {
         if context.matcher_is_defined(path) {
@@ -277,6 +293,22 @@ impl PlanBodyBuilder for JsonPlanBuilder {
     let path = DocPath::root();
     let mut root_node = ExecutionPlanNode::container(&path);
     Self::process_body_node(context, &expected_json, &path, &mut root_node);
+
+    // Expressions are evaluated against the whole document, so are added to the root node
+    let expression_rules = context.matching_rules.rules.iter()
+      .sorted_by_key(|(path, _)| path.to_string())
+      .flat_map(|(path, rules)| rules.rules.iter().map(move |rule| (path, rule)));
+    for (rule_path, rule) in expression_rules {
+      if let MatchingRule::Expression(expression) = rule {
+        root_node.add(ExecutionPlanNode::annotation(format!("{} {}", rule_path, rule.generate_description(false))));
+        root_node.add(
+          ExecutionPlanNode::action("json:match:expression")
+            .add(ExecutionPlanNode::value_node(expression.clone()))
+            .add(ExecutionPlanNode::value_node(rule_path.to_string()))
+            .add(ExecutionPlanNode::resolve_current_value(&path))
+        );
+      }
+    }
     body_node.add(root_node);
 
     Ok(body_node)
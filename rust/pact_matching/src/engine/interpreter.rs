@@ -18,7 +18,8 @@ use crate::engine::context::PlanMatchingContext;
 use crate::engine::value_resolvers::ValueResolver;
 use crate::engine::xml::XmlValue;
 use crate::headers::{parse_charset_parameters, strip_whitespace};
-use crate::json::type_of;
+use crate::json::{compare_expression, type_of};
+use crate::json_schema::validate_json_schema;
 use crate::matchers::Matches;
 
 /// Main interpreter for the matching plan AST
@@ -279,6 +280,8 @@ impl ExecutionPlanInterpreter {
         "xml:attributes" => self.execute_xml_attributes(action, value_resolver, node, &action_path),
         "json:expect:empty" => self.execute_json_expect_empty(action, value_resolver, node, &action_path),
         "json:match:length" => self.execute_json_match_length(action, value_resolver, node, &action_path),
+        "json:match:schema" => self.execute_json_match_schema(action, value_resolver, node, &action_path),
+        "json:match:expression" => self.execute_json_match_expression(action, value_resolver, node, &action_path),
         "json:expect:entries" => self.execute_json_expect_entries(action, value_resolver, node, &action_path),
         "check:exists" => self.execute_check_exists(action, value_resolver, node, &action_path),
         "expect:entries" => self.execute_check_entries(action, value_resolver, node, &action_path),
@@ -582,6 +585,159 @@ impl ExecutionPlanInterpreter {
     }
   }
 
+  fn execute_json_match_schema(
+    &mut self,
+    action: &str,
+    value_resolver: &dyn ValueResolver,
+    node: &ExecutionPlanNode,
+    action_path: &Vec<String>
+  ) -> ExecutionPlanNode {
+    match self.validate_two_args(node, action, value_resolver, &action_path) {
+      Ok((first_node, second_node)) => {
+        let schema = match first_node.value().unwrap_or_default().as_value() {
+          Some(NodeValue::JSON(schema)) => schema,
+          value => {
+            return ExecutionPlanNode {
+              node_type: node.node_type.clone(),
+              result: Some(NodeResult::ERROR(format!("Was expecting a JSON schema, but got {:?}", value))),
+              children: vec![first_node, second_node]
+            }
+          }
+        };
+        let json_value = match second_node.value().unwrap_or_default() {
+          NodeResult::VALUE(NodeValue::JSON(json)) => json,
+          NodeResult::ERROR(err) => {
+            return ExecutionPlanNode {
+              node_type: node.node_type.clone(),
+              result: Some(NodeResult::ERROR(err)),
+              children: vec![first_node, second_node]
+            }
+          }
+          value => {
+            return ExecutionPlanNode {
+              node_type: node.node_type.clone(),
+              result: Some(NodeResult::ERROR(format!("Was expecting a JSON value, but got {}", value))),
+              children: vec![first_node, second_node]
+            }
+          }
+        };
+
+        // Violations are reported against the path of the value that violated the schema, so
+        // each path gets a container node with the errors
+        let path = match &second_node.node_type {
+          PlanNodeType::RESOLVE_CURRENT(path) => path.clone(),
+          _ => DocPath::root()
+        };
+        let violations = validate_json_schema(&schema, &json_value, &path);
+        let mut violation_nodes: Vec<ExecutionPlanNode> = vec![];
+        for violation in &violations {
+          let label = violation.path.to_string();
+          let error_node = ExecutionPlanNode::action("error")
+            .clone_with_result(NodeResult::ERROR(violation.message.clone()));
+          let existing = violation_nodes.iter_mut()
+            .find(|node| matches!(&node.node_type, PlanNodeType::CONTAINER(l) if *l == label));
+          match existing {
+            Some(container) => container.children.push(error_node),
+            None => violation_nodes.push(ExecutionPlanNode {
+              node_type: PlanNodeType::CONTAINER(label),
+              result: Some(NodeResult::VALUE(NodeValue::BOOL(false))),
+              children: vec![error_node]
+            })
+          }
+        }
+        ExecutionPlanNode {
+          node_type: node.node_type.clone(),
+          result: Some(NodeResult::VALUE(NodeValue::BOOL(violations.is_empty()))),
+          children: [first_node, second_node].into_iter().chain(violation_nodes).collect()
+        }
+      }
+      Err(err) => {
+        ExecutionPlanNode {
+          node_type: node.node_type.clone(),
+          result: Some(NodeResult::ERROR(err.to_string())),
+          children: node.children.clone()
+        }
+      }
+    }
+  }
+
+  fn execute_json_match_expression(
+    &mut self,
+    action: &str,
+    value_resolver: &dyn ValueResolver,
+    node: &ExecutionPlanNode,
+    action_path: &Vec<String>
+  ) -> ExecutionPlanNode {
+    match self.validate_three_args(node, action, value_resolver, &action_path) {
+      Ok((first_node, second_node, third_node)) => {
+        let result1 = first_node.value().unwrap_or_default();
+        let expression = match result1.as_string() {
+          Some(expression) => expression,
+          None => {
+            return ExecutionPlanNode {
+              node_type: node.node_type.clone(),
+              result: Some(NodeResult::ERROR(format!("'{}' is not a valid expression", result1))),
+              children: vec![first_node, second_node, third_node]
+            }
+          }
+        };
+        let result2 = second_node.value().unwrap_or_default();
+        let path = match result2.as_string().map(DocPath::new) {
+          Some(Ok(path)) => path,
+          _ => {
+            return ExecutionPlanNode {
+              node_type: node.node_type.clone(),
+              result: Some(NodeResult::ERROR(format!("'{}' is not a valid path expression", result2))),
+              children: vec![first_node, second_node, third_node]
+            }
+          }
+        };
+        let json_value = match third_node.value().unwrap_or_default() {
+          NodeResult::VALUE(NodeValue::JSON(json)) => json,
+          NodeResult::ERROR(err) => {
+            return ExecutionPlanNode {
+              node_type: node.node_type.clone(),
+              result: Some(NodeResult::ERROR(err)),
+              children: vec![first_node, second_node, third_node]
+            }
+          }
+          value => {
+            return ExecutionPlanNode {
+              node_type: node.node_type.clone(),
+              result: Some(NodeResult::ERROR(format!("Was expecting a JSON value, but got {}", value))),
+              children: vec![first_node, second_node, third_node]
+            }
+          }
+        };
+
+        // Mismatches are reported against the path of each value that did not match the expression
+        let mismatches = compare_expression(&path, expression.as_str(), &json_value);
+        let mismatch_nodes = mismatches.iter()
+          .map(|mismatch| ExecutionPlanNode {
+            node_type: PlanNodeType::CONTAINER(mismatch.path.clone()),
+            result: Some(NodeResult::VALUE(NodeValue::BOOL(false))),
+            children: vec![
+              ExecutionPlanNode::action("error")
+                .clone_with_result(NodeResult::ERROR(mismatch.description.clone()))
+            ]
+          })
+          .collect_vec();
+        ExecutionPlanNode {
+          node_type: node.node_type.clone(),
+          result: Some(NodeResult::VALUE(NodeValue::BOOL(mismatches.is_empty()))),
+          children: [first_node, second_node, third_node].into_iter().chain(mismatch_nodes).collect()
+        }
+      }
+      Err(err) => {
+        ExecutionPlanNode {
+          node_type: node.node_type.clone(),
+          result: Some(NodeResult::ERROR(err.to_string())),
+          children: node.children.clone()
+        }
+      }
+    }
+  }
+
   fn execute_json_parse(
     &mut self,
     action: &str,
@@ -30,6 +30,7 @@ use crate::engine::value_resolvers::HttpRequestValueResolver;
 use crate::engine::xml::XmlValue;
 use crate::headers::{parse_charset_parameters, strip_whitespace};
 use crate::matchers::Matches;
+use crate::matchingrules::list_item_mismatches;
 
 mod bodies;
 mod value_resolvers;
@@ -381,6 +382,10 @@ impl From<&Element> for NodeValue {
 
 impl Matches<NodeValue> for NodeValue {
   fn matches_with(&self, actual: NodeValue, matcher: &MatchingRule, cascaded: bool) -> anyhow::Result<()> {
+    if let MatchingRule::UniqueItems(_) | MatchingRule::Sorted(_, _) = matcher {
+      return match_list_items(&actual, matcher);
+    }
+
     match self {
       NodeValue::NULL => Value::Null.matches_with(actual.as_json().unwrap_or_default(), matcher, cascaded),
       NodeValue::STRING(s) => if let Some(actual_str) = actual.as_string() {
@@ -424,6 +429,21 @@ impl Matches<NodeValue> for NodeValue {
   }
 }
 
+/// Applies the UniqueItems and Sorted matching rules to the items of the actual list. Any other
+/// value is treated as a list with a single item, which is always unique and sorted.
+fn match_list_items(actual: &NodeValue, matcher: &MatchingRule) -> anyhow::Result<()> {
+  let mismatches = match actual {
+    NodeValue::JSON(Value::Array(items)) => list_item_mismatches(matcher, items.as_slice()),
+    NodeValue::SLIST(items) => list_item_mismatches(matcher, items.as_slice()),
+    _ => vec![]
+  };
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(anyhow!(mismatches.join(", ")))
+  }
+}
+
 impl Display for NodeValue {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     write!(f, "{}", self.str_form())
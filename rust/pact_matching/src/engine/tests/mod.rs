@@ -7,6 +7,8 @@ use serde_json::json;
 use pact_models::bodies::OptionalBody;
 use pact_models::content_types::TEXT;
 use pact_models::matchingrules;
+use pact_models::matchingrules::SortOrder;
+use pact_models::path_exp::DocPath;
 use pact_models::v4::http_parts::HttpRequest;
 use pact_models::v4::interaction::V4Interaction;
 use pact_models::v4::synch_http::SynchronousHttp;
@@ -18,6 +20,7 @@ use crate::engine::{
   PlanMatchingContext
 };
 use crate::{BodyMatchResult, MatchingRule, RequestMatchResult};
+use crate::matchers::Matches;
 use crate::Mismatch::{ MethodMismatch, BodyMismatch };
 
 mod walk_tree_tests;
@@ -59,6 +62,22 @@ fn node_result_and(#[case] a: NodeResult, #[case] b: Option<NodeResult>, #[case]
   expect!(a.and(&b)).to(be_equal_to(result));
 }
 
+#[test]
+fn node_value_matches_with_unique_items_and_sorted_rules() {
+  let expected = NodeValue::JSON(json!([1]));
+  expect!(expected.matches_with(NodeValue::JSON(json!([1, 2, 3])), &MatchingRule::UniqueItems(None), false)).to(be_ok());
+  expect!(expected.matches_with(NodeValue::JSON(json!([1, 2, 1])), &MatchingRule::UniqueItems(None), false)).to(be_err());
+  expect!(expected.matches_with(NodeValue::JSON(json!([1, 2, 3])),
+    &MatchingRule::Sorted(DocPath::root(), SortOrder::Ascending), false)).to(be_ok());
+  expect!(expected.matches_with(NodeValue::JSON(json!([1, 3, 2])),
+    &MatchingRule::Sorted(DocPath::root(), SortOrder::Ascending), false)).to(be_err());
+
+  let expected = NodeValue::SLIST(vec!["a".to_string()]);
+  expect!(expected.matches_with(NodeValue::SLIST(vec!["a".to_string(), "a".to_string()]),
+    &MatchingRule::UniqueItems(None), false)).to(be_err());
+  expect!(expected.matches_with(NodeValue::STRING("a".to_string()), &MatchingRule::UniqueItems(None), false)).to(be_ok());
+}
+
 #[test_log::test]
 fn simple_match_request_test() -> anyhow::Result<()> {
   let request = HttpRequest {
@@ -571,3 +590,197 @@ r#"(
 
   Ok(())
 }
+
+#[test_log::test]
+fn match_path_with_string_length_matching_rule() -> anyhow::Result<()> {
+  let matching_rules = matchingrules! {
+    "path" => { "" => [ MatchingRule::StringLength(5, 10) ] }
+  };
+  let expected_request = HttpRequest {
+    method: "get".to_string(),
+    path: "/test".to_string(),
+    matching_rules: matching_rules.clone(),
+    .. Default::default()
+  };
+  let expected_interaction = SynchronousHttp {
+    request: expected_request.clone(),
+    .. SynchronousHttp::default()
+  };
+  let mut context = PlanMatchingContext {
+    interaction: expected_interaction.boxed_v4(),
+    .. PlanMatchingContext::default()
+  };
+  let plan = build_request_plan(&expected_request, &context)?;
+
+  let request = HttpRequest {
+    method: "get".to_string(),
+    path: "/test1234".to_string(),
+    .. Default::default()
+  };
+  let executed_plan = execute_request_plan(&plan, &request, &mut context)?;
+  let mismatches: RequestMatchResult = executed_plan.into();
+  expect!(mismatches.path).to(be_none());
+
+  let request = HttpRequest {
+    method: "get".to_string(),
+    path: "/test12345678".to_string(),
+    .. Default::default()
+  };
+  let executed_plan = execute_request_plan(&plan, &request, &mut context)?;
+  let mismatches: RequestMatchResult = executed_plan.into();
+  expect!(mismatches.path).to(be_some());
+
+  Ok(())
+}
+
+#[test_log::test]
+fn match_body_with_json_schema_matching_rule() -> anyhow::Result<()> {
+  let matching_rules = matchingrules! {
+    "body" => {
+      "$.user" => [ MatchingRule::JsonSchema(json!({
+        "type": "object",
+        "required": ["id", "name"],
+        "properties": {
+          "id": { "type": "integer" },
+          "name": { "type": "string" }
+        }
+      })) ]
+    }
+  };
+  let expected_request = HttpRequest {
+    method: "POST".to_string(),
+    path: "/test".to_string(),
+    body: OptionalBody::from(&json!({ "user": { "id": 1, "name": "Fred" } })),
+    matching_rules: matching_rules.clone(),
+    .. Default::default()
+  };
+  let expected_interaction = SynchronousHttp {
+    request: expected_request.clone(),
+    .. SynchronousHttp::default()
+  };
+  let mut context = PlanMatchingContext {
+    interaction: expected_interaction.boxed_v4(),
+    .. PlanMatchingContext::default()
+  };
+  let plan = build_request_plan(&expected_request, &context)?;
+
+  let request = HttpRequest {
+    method: "POST".to_string(),
+    path: "/test".to_string(),
+    body: OptionalBody::from(&json!({ "user": { "id": 2, "name": "Mary", "age": 20 } })),
+    .. Default::default()
+  };
+  let executed_plan = execute_request_plan(&plan, &request, &mut context)?;
+  let mismatches: RequestMatchResult = executed_plan.into();
+  expect!(mismatches.body).to(be_equal_to(BodyMatchResult::Ok));
+
+  let request = HttpRequest {
+    method: "POST".to_string(),
+    path: "/test".to_string(),
+    body: OptionalBody::from(&json!({ "user": { "id": "2" } })),
+    .. Default::default()
+  };
+  let executed_plan = execute_request_plan(&plan, &request, &mut context)?;
+  let mismatches: RequestMatchResult = executed_plan.into();
+  assert_eq!(BodyMatchResult::BodyMismatches(hashmap!{
+    "$.user".to_string() => vec![
+      BodyMismatch {
+        path: "$.user".to_string(),
+        expected: None,
+        actual: None,
+        mismatch: "Expected the required property 'name' but was missing".to_string()
+      }
+    ],
+    "$.user.id".to_string() => vec![
+      BodyMismatch {
+        path: "$.user.id".to_string(),
+        expected: None,
+        actual: None,
+        mismatch: "Expected '2' (String) to be of type integer".to_string()
+      }
+    ]
+  }), mismatches.body);
+
+  Ok(())
+}
+
+#[test_log::test]
+fn match_body_with_expression_matching_rules() -> anyhow::Result<()> {
+  let matching_rules = matchingrules! {
+    "body" => {
+      "$.password" => [ MatchingRule::Type ],
+      "$.confirmPassword" => [ MatchingRule::Expression("$.password".to_string()) ],
+      "$.total" => [ MatchingRule::Expression("sum($.items[*].price)".to_string()) ]
+    }
+  };
+  let expected_request = HttpRequest {
+    method: "POST".to_string(),
+    path: "/test".to_string(),
+    body: OptionalBody::from(&json!({
+      "password": "secret",
+      "confirmPassword": "secret",
+      "items": [ { "price": 10 }, { "price": 25 } ],
+      "total": 35
+    })),
+    matching_rules: matching_rules.clone(),
+    .. Default::default()
+  };
+  let expected_interaction = SynchronousHttp {
+    request: expected_request.clone(),
+    .. SynchronousHttp::default()
+  };
+  let mut context = PlanMatchingContext {
+    interaction: expected_interaction.boxed_v4(),
+    .. PlanMatchingContext::default()
+  };
+  let plan = build_request_plan(&expected_request, &context)?;
+
+  let request = HttpRequest {
+    method: "POST".to_string(),
+    path: "/test".to_string(),
+    body: OptionalBody::from(&json!({
+      "password": "p1",
+      "confirmPassword": "p1",
+      "items": [ { "price": 10 }, { "price": 25 } ],
+      "total": 35
+    })),
+    .. Default::default()
+  };
+  let executed_plan = execute_request_plan(&plan, &request, &mut context)?;
+  let mismatches: RequestMatchResult = executed_plan.into();
+  expect!(mismatches.body).to(be_equal_to(BodyMatchResult::Ok));
+
+  let request = HttpRequest {
+    method: "POST".to_string(),
+    path: "/test".to_string(),
+    body: OptionalBody::from(&json!({
+      "password": "p1",
+      "confirmPassword": "p2",
+      "items": [ { "price": 10 }, { "price": 25 } ],
+      "total": 30
+    })),
+    .. Default::default()
+  };
+  let executed_plan = execute_request_plan(&plan, &request, &mut context)?;
+  let mismatches: RequestMatchResult = executed_plan.into();
+  assert_eq!(BodyMatchResult::BodyMismatches(hashmap!{
+    "$.confirmPassword".to_string() => vec![
+      BodyMismatch {
+        path: "$.confirmPassword".to_string(),
+        expected: None,
+        actual: None,
+        mismatch: "Expected 'p2' to equal the value of the expression '$.password', which resolved to 'p1'".to_string()
+      }
+    ],
+    "$.total".to_string() => vec![
+      BodyMismatch {
+        path: "$.total".to_string(),
+        expected: None,
+        actual: None,
+        mismatch: "Expected 30 to equal the value of the expression 'sum($.items[*].price)', which resolved to 35".to_string()
+      }
+    ]
+  }), mismatches.body);
+
+  Ok(())
+}
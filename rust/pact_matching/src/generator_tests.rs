@@ -129,6 +129,23 @@ async fn applies_body_generator_to_the_copy_of_the_request() {
   expect!(&body["b"]).to(be_equal_to(&json!("B")));
 }
 
+#[tokio::test]
+async fn generates_the_same_request_when_a_seed_is_provided() {
+  let request = HttpRequest { body: OptionalBody::Present("{\"a\": 100, \"b\": \"B\"}".into(), None, None),
+    generators: generators! {
+      "BODY" => {
+        "$.a" => Generator::RandomInt(1, 1000000),
+        "$.b" => Generator::Uuid(None)
+      }
+    }, .. HttpRequest::default()
+  };
+  let context = hashmap!{ "generatorSeed" => json!(100) };
+  let generated_request = generate_request(&request, &GeneratorTestMode::Provider, &context).await;
+  let generated_request2 = generate_request(&request, &GeneratorTestMode::Provider, &context).await;
+  expect!(generated_request.body.display_string()).to_not(be_equal_to(request.body.display_string()));
+  expect!(generated_request2.body.display_string()).to(be_equal_to(generated_request.body.display_string()));
+}
+
 #[tokio::test]
 async fn applies_body_generator_to_the_copy_of_the_response() {
   let response = HttpResponse { body: OptionalBody::Present("{\"a\": 100, \"b\": \"B\"}".into(), None, None),
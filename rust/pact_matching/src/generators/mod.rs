@@ -7,6 +7,7 @@ use pact_models::bodies::OptionalBody;
 use pact_models::content_types::ContentType;
 use pact_models::generators::{
   apply_generators,
+  context_for_path,
   GenerateValue,
   Generator,
   GeneratorCategory,
@@ -132,6 +133,8 @@ pub async fn apply_generators_to_sync_message(
   if !generators.is_empty() {
     debug!("Applying request metadata generators...");
     apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::METADATA, key.to_string().as_str());
+      let context = context.as_ref();
       if let Some(k) = key.first_field() {
         let value = request.metadata.get(k).cloned().unwrap_or_default();
         if let Ok(v) = generator.generate_value(&value, context, &vm_boxed) {
@@ -157,6 +160,8 @@ pub async fn apply_generators_to_sync_message(
     if !generators.is_empty() {
       debug!("Applying response metadata generators...");
       apply_generators(mode, &generators, &mut |key, generator| {
+        let context = context_for_path(context, &GeneratorCategory::METADATA, key.to_string().as_str());
+        let context = context.as_ref();
         if let Some(k) = key.first_field() {
           let value = response.metadata.get(k).cloned().unwrap_or_default();
           if let Ok(v) = generator.generate_value(&value, context, &vm_boxed) {
@@ -196,6 +201,8 @@ pub async fn apply_generators_to_async_message(
   if !generators.is_empty() {
     debug!("Applying metadata generators...");
     apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::METADATA, key.to_string().as_str());
+      let context = context.as_ref();
       if let Some(k) = key.first_field() {
         let value = message.contents.metadata.get(k).cloned().unwrap_or_default();
         if let Ok(v) = generator.generate_value(&value, context, &vm_boxed) {
@@ -233,6 +240,8 @@ pub async fn generate_message(
   if !generators.is_empty() {
     debug!("Applying metadata generators...");
     apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::METADATA, key.to_string().as_str());
+      let context = context.as_ref();
       if let Some(header) = key.first_field() {
         if message.metadata.contains_key(header) {
           if let Ok(v) = generator.generate_value(&message.metadata.get(header).unwrap().clone(), context, &DefaultVariantMatcher.boxed()) {
@@ -12,7 +12,7 @@ use tracing::{instrument, debug};
 
 use crate::{matchers, MatchingContext, Mismatch, CommonMismatch};
 use crate::matchers::Matches;
-use crate::matchingrules::compare_lists_with_matchingrules;
+use crate::matchingrules::compare_resolvable_lists_with_matchingrules;
 
 pub(crate) fn strip_whitespace<'a, T: FromIterator<&'a str>>(val: &'a str, split_by: &'a str) -> T {
   val.split(split_by).map(|v| v.trim()).filter(|v| !v.is_empty()).collect()
@@ -159,7 +159,7 @@ fn match_header_maps(
           let path = DocPath::root().join(key.to_lowercase());
           if context.matcher_is_defined(&path) {
             debug!("match_header_maps: Matcher is defined for path {}", path);
-            let values_result = compare_lists_with_matchingrules(&path, &context.select_best_matcher(&path), value, &actual_values, context.clone_with(context.matchers()).as_ref(), &mut |_, expected, actual, context| {
+            let values_result = compare_resolvable_lists_with_matchingrules(&path, &context.select_best_matcher(&path), value, &actual_values, context.clone_with(context.matchers()).as_ref(), &mut |_, expected, actual, context| {
               match_header_value(key, 0, expected, actual, context, false)
             });
             mismatches.extend(values_result.err().unwrap_or_default());
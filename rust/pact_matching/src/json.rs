@@ -5,22 +5,25 @@ use std::str::FromStr;
 use ansi_term::Colour::*;
 use anyhow::anyhow;
 use difference::*;
+use itertools::Itertools;
 use lazy_static::lazy_static;
 use onig::Regex;
 use semver::Version;
 use serde_json::{json, Value};
 
+use pact_models::expression_parser::evaluate_document_expression;
 use pact_models::http_parts::HttpPart;
-use pact_models::json_utils::json_to_string;
-use pact_models::matchingrules::MatchingRule;
+use pact_models::json_utils::{json_to_string, resolve_path};
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory};
 use pact_models::path_exp::DocPath;
 #[cfg(feature = "datetime")] use pact_models::time_utils::validate_datetime;
 use tracing::debug;
 
 use crate::{DiffConfig, MatchingContext, Mismatch, CommonMismatch, merge_result};
 use crate::binary_utils::{convert_data, match_content_type};
+use crate::json_schema::{json_schema_rule, validate_json_schema};
 use crate::matchers::*;
-use crate::matchingrules::{compare_lists_with_matchingrules, compare_maps_with_matchingrule};
+use crate::matchingrules::{compare_maps_with_matchingrule, compare_resolvable_lists_with_matchingrules, match_sorted_items, match_unique_items};
 
 lazy_static! {
   static ref DEC_REGEX: Regex = Regex::new(r"\d+\.\d+").unwrap();
@@ -41,7 +44,7 @@ pub(crate) fn type_of(json: &Value) -> String {
   }.to_string()
 }
 
-fn value_of(json: &Value) -> String {
+pub(crate) fn value_of(json: &Value) -> String {
   match json {
     Value::Null => "null".to_string(),
     Value::String(s) => format!("'{}'", s),
@@ -276,6 +279,53 @@ impl Matches<&Value> for Value {
         }
         _ => Err(anyhow!("Expected something that matches a semantic version, but got '{}'", actual))
       }
+      MatchingRule::NumberRange(min, max, inclusive) => match actual.as_f64() {
+        Some(number) => match_number_range(number, *min, *max, *inclusive),
+        None => Err(anyhow!("Expected {} ({}) to be a number", value_of(actual), type_of(actual)))
+      }
+      MatchingRule::StringLength(min, max) => match actual {
+        Value::String(s) => match_string_length(s, *min, *max),
+        _ => Err(anyhow!("Expected {} ({}) to be a string", value_of(actual), type_of(actual)))
+      }
+      MatchingRule::OneOf(values) => match_one_of(actual, values),
+      MatchingRule::JsonSchema(schema) => {
+        let violations = validate_json_schema(schema, actual, &DocPath::root());
+        if violations.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected {} to be valid against the JSON schema: {}", value_of(actual),
+            violations.iter().map(|v| format!("{} - {}", v.path, v.message)).join(", ")))
+        }
+      }
+      MatchingRule::Format(format) => match actual {
+        Value::String(s) => match_format(format, s),
+        _ => Err(anyhow!("Expected {} ({}) to be {}", value_of(actual), type_of(actual), format.description()))
+      }
+      MatchingRule::UniqueItems(_) | MatchingRule::Sorted(_, _) if cascaded => Ok(()),
+      MatchingRule::UniqueItems(key) => match actual {
+        Value::Array(items) => {
+          let mismatches = match_unique_items(items, key.as_ref());
+          if mismatches.is_empty() {
+            Ok(())
+          } else {
+            Err(anyhow!(mismatches.join(", ")))
+          }
+        }
+        _ => Err(anyhow!("Expected {} ({}) to be a list", value_of(actual), type_of(actual)))
+      }
+      MatchingRule::Sorted(field, order) => match actual {
+        Value::Array(items) => {
+          let mismatches = match_sorted_items(items, field, *order);
+          if mismatches.is_empty() {
+            Ok(())
+          } else {
+            Err(anyhow!(mismatches.join(", ")))
+          }
+        }
+        _ => Err(anyhow!("Expected {} ({}) to be a list", value_of(actual), type_of(actual)))
+      }
+      // Expressions are evaluated against the whole document (see compare_expression_rules)
+      MatchingRule::Expression(_) => Ok(()),
       _ => Ok(())
     };
     debug!("JSON -> JSON: Comparing '{}' ({}) to '{}' ({}) using {:?} -> {:?}", self,
@@ -380,8 +430,32 @@ pub fn compare_json(
   expected: &Value,
   actual: &Value,
   context: &(dyn MatchingContext + Send + Sync)
+) -> Result<(), Vec<CommonMismatch>> {
+  let result = compare_json_value(path, expected, actual, context);
+  if path.is_root() {
+    let mismatches = compare_expression_rules(actual, context.matchers());
+    if mismatches.is_empty() {
+      result
+    } else {
+      merge_result(result, Err(mismatches))
+    }
+  } else {
+    result
+  }
+}
+
+fn compare_json_value(
+  path: &DocPath,
+  expected: &Value,
+  actual: &Value,
+  context: &(dyn MatchingContext + Send + Sync)
 ) -> Result<(), Vec<CommonMismatch>> {
   debug!("compare: Comparing path {}", path);
+  if context.matcher_is_defined(path) {
+    if let Some(schema) = json_schema_rule(&context.select_best_matcher(path)) {
+      return compare_json_schema(path, schema, actual);
+    }
+  }
   match (expected, actual) {
     (&Value::Object(ref emap), &Value::Object(ref amap)) => compare_maps(path, emap, amap, context),
     (&Value::Object(_), _) => {
@@ -407,6 +481,100 @@ pub fn compare_json(
   }
 }
 
+fn compare_json_schema(
+  path: &DocPath,
+  schema: &Value,
+  actual: &Value
+) -> Result<(), Vec<CommonMismatch>> {
+  debug!("compare_json_schema: Validating path {} against JSON schema", path);
+  let violations = validate_json_schema(schema, actual, path);
+  if violations.is_empty() {
+    Ok(())
+  } else {
+    Err(violations.iter().map(|violation| CommonMismatch {
+      path: violation.path.to_string(),
+      expected: json_to_string(schema),
+      actual: json_to_string(&violation.actual),
+      description: violation.message.clone()
+    }).collect())
+  }
+}
+
+/// Evaluates any Expression matching rules against the actual document, and checks that the values
+/// at the rule paths are equal to the result of the expression.
+pub(crate) fn compare_expression_rules(actual: &Value, rules: &MatchingRuleCategory) -> Vec<CommonMismatch> {
+  rules.rules.iter()
+    .sorted_by_key(|(path, _)| path.to_string())
+    .flat_map(|(path, rule_list)| {
+      rule_list.rules.iter()
+        .filter_map(|rule| match rule {
+          MatchingRule::Expression(expression) => Some(expression),
+          _ => None
+        })
+        .flat_map(|expression| compare_expression(path, expression, actual))
+        .collect_vec()
+    })
+    .collect()
+}
+
+pub(crate) fn compare_expression(path: &DocPath, expression: &str, actual: &Value) -> Vec<CommonMismatch> {
+  debug!("compare_expression: Evaluating expression '{}' for path {}", expression, path);
+  let pointers = if path.is_root() {
+    vec![ String::default() ]
+  } else {
+    resolve_path(actual, path)
+  };
+  match evaluate_document_expression(expression, actual) {
+    Ok(resolved) => pointers.iter()
+      .filter_map(|pointer| actual.pointer(pointer).map(|value| (pointer, value)))
+      .filter(|(_, value)| !expression_values_equal(value, &resolved))
+      .map(|(pointer, value)| CommonMismatch {
+        path: pointer_to_path(actual, pointer).to_string(),
+        expected: json_to_string(&resolved),
+        actual: json_to_string(value),
+        description: format!("Expected {} to equal the value of the expression '{}', which resolved to {}",
+          value_of(value), expression, value_of(&resolved))
+      })
+      .collect(),
+    Err(err) => vec![ CommonMismatch {
+      path: path.to_string(),
+      expected: expression.to_string(),
+      actual: String::default(),
+      description: format!("Failed to evaluate the expression '{}' - {}", expression, err)
+    } ]
+  }
+}
+
+fn expression_values_equal(value: &Value, resolved: &Value) -> bool {
+  match (value.as_f64(), resolved.as_f64()) {
+    (Some(a), Some(b)) => (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0),
+    _ => value == resolved
+  }
+}
+
+/// Converts a JSON pointer into a path expression, using the document to determine which parts are
+/// array indices
+fn pointer_to_path(document: &Value, pointer: &str) -> DocPath {
+  let mut path = DocPath::root();
+  let mut cursor = Some(document);
+  for part in pointer.split('/').skip(1) {
+    match (cursor, part.parse::<usize>()) {
+      (Some(Value::Array(items)), Ok(index)) => {
+        path.push_index(index);
+        cursor = items.get(index);
+      }
+      (Some(value), _) => {
+        path.push_field(part);
+        cursor = value.get(part);
+      }
+      (None, _) => {
+        path.push_field(part);
+      }
+    }
+  }
+  path
+}
+
 fn compare_maps(
   path: &DocPath,
   expected: &serde_json::Map<String, Value>,
@@ -461,7 +629,7 @@ fn compare_lists(
   let spath = path.to_string();
   if context.matcher_is_defined(path) {
     debug!("compare_lists: matcher defined for path '{}'", path);
-    compare_lists_with_matchingrules(path, &context.select_best_matcher(path), expected, actual, context, &mut |p, expected, actual, context| {
+    compare_resolvable_lists_with_matchingrules(path, &context.select_best_matcher(path), expected, actual, context, &mut |p, expected, actual, context| {
         compare_json(p, expected, actual, context)
     })
   } else if expected.is_empty() && !actual.is_empty() {
@@ -1333,7 +1501,7 @@ mod tests2 {
   use serde_json::{json, Value};
 
   use pact_models::matchingrules_list;
-  use pact_models::matchingrules::MatchingRule;
+  use pact_models::matchingrules::{MatchingRule, SortOrder};
   use crate::{CoreMatchingContext, DiffConfig};
 
   use super::*;
@@ -1411,4 +1579,104 @@ mod tests2 {
       expect!(result).to(be_err());
     }
   }
+
+  #[test]
+  fn compare_json_with_json_schema_matcher() {
+    let matchingrules = matchingrules_list! {
+      "body"; "$.user" => [
+        MatchingRule::JsonSchema(json!({
+          "type": "object",
+          "required": ["id", "name"],
+          "properties": {
+            "id": { "type": "integer" },
+            "name": { "type": "string" }
+          }
+        }))
+      ]
+    };
+    let context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys, &matchingrules, &hashmap!{});
+    let expected = json!({ "user": { "id": 1, "name": "Fred" } });
+
+    let result = compare_json(&DocPath::root(), &expected,
+      &json!({ "user": { "id": 2, "name": "Mary", "age": 20 } }), &context);
+    expect!(result).to(be_ok());
+
+    let result = compare_json(&DocPath::root(), &expected, &json!({ "user": { "id": "2" } }), &context);
+    let mismatches = result.unwrap_err().iter()
+      .map(|m| m.to_body_mismatch().description())
+      .collect::<Vec<_>>();
+    expect!(mismatches).to(be_equal_to(vec![
+      "$.user -> Expected the required property 'name' but was missing".to_string(),
+      "$.user.id -> Expected '2' (String) to be of type integer".to_string()
+    ]));
+  }
+
+  #[test]
+  fn compare_json_with_unique_items_and_sorted_matchers() {
+    let matchingrules = matchingrules_list! {
+      "body";
+      "$.users" => [
+        MatchingRule::MinType(1),
+        MatchingRule::UniqueItems(Some(DocPath::new_unwrap("$.id")))
+      ],
+      "$.names" => [
+        MatchingRule::MinType(1),
+        MatchingRule::Sorted(DocPath::root(), SortOrder::Ascending)
+      ]
+    };
+    let context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys, &matchingrules, &hashmap!{});
+    let expected = json!({ "users": [ { "id": 1 } ], "names": [ "a" ] });
+
+    let result = compare_json(&DocPath::root(), &expected,
+      &json!({ "users": [ { "id": 1 }, { "id": 2 } ], "names": [ "a", "b", "b" ] }), &context);
+    expect!(result).to(be_ok());
+
+    let result = compare_json(&DocPath::root(), &expected,
+      &json!({ "users": [ { "id": 1 }, { "id": 2 }, { "id": 1 } ], "names": [ "b", "a" ] }), &context);
+    let mismatches = result.unwrap_err().iter()
+      .map(|m| m.to_body_mismatch().description())
+      .sorted()
+      .collect::<Vec<_>>();
+    expect!(mismatches).to(be_equal_to(vec![
+      "$.names -> Expected the list items to be sorted by '$' in ascending order, but the item at index 1 (\"a\") is out of order with the item at index 0 (\"b\")".to_string(),
+      "$.users -> Expected the list items to have unique values for '$.id', but the item at index 2 has the same value (1) as the item at index 0".to_string()
+    ]));
+  }
+
+  #[test]
+  fn compare_json_with_expression_matchers() {
+    let matchingrules = matchingrules_list! {
+      "body";
+      "$.password" => [ MatchingRule::Type ],
+      "$.confirmPassword" => [ MatchingRule::Expression("$.password".to_string()) ],
+      "$.items" => [ MatchingRule::MinType(1) ],
+      "$.total" => [ MatchingRule::Expression("sum($.items[*].price)".to_string()) ]
+    };
+    let context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys, &matchingrules, &hashmap!{});
+    let expected = json!({ "password": "secret", "confirmPassword": "secret", "items": [ { "price": 10 } ], "total": 10 });
+
+    let result = compare_json(&DocPath::root(), &expected,
+      &json!({ "password": "p1", "confirmPassword": "p1", "items": [ { "price": 10 }, { "price": 25.5 } ], "total": 35.5 }), &context);
+    expect!(result).to(be_ok());
+
+    let result = compare_json(&DocPath::root(), &expected,
+      &json!({ "password": "p1", "confirmPassword": "p2", "items": [ { "price": 10 }, { "price": 25 } ], "total": 30 }), &context);
+    let mismatches = result.unwrap_err().iter()
+      .map(|m| m.to_body_mismatch().description())
+      .collect::<Vec<_>>();
+    expect!(mismatches).to(be_equal_to(vec![
+      "$.confirmPassword -> Expected 'p2' to equal the value of the expression '$.password', which resolved to 'p1'".to_string(),
+      "$.total -> Expected 30 to equal the value of the expression 'sum($.items[*].price)', which resolved to 35".to_string()
+    ]));
+
+    let result = compare_json(&DocPath::root(), &expected,
+      &json!({ "confirmPassword": "p2", "items": [ { "price": 10 } ], "total": 10 }), &context);
+    let mismatches = result.unwrap_err().iter()
+      .map(|m| m.to_body_mismatch().description())
+      .filter(|m| m.contains("expression"))
+      .collect::<Vec<_>>();
+    expect!(mismatches).to(be_equal_to(vec![
+      "$.confirmPassword -> Failed to evaluate the expression '$.password' - No value for '$.password' found".to_string()
+    ]));
+  }
 }
@@ -0,0 +1,524 @@
+//! Validation of JSON values against a JSON Schema, used by the `JsonSchema` matching rule.
+//!
+//! This supports a subset of the draft 2020-12 specification:
+//!
+//! | Applies to | Keywords |
+//! |------------|----------|
+//! | Any value | `type`, `enum`, `const`, `allOf`, `anyOf`, `oneOf`, `not`, `$ref` (local references only) |
+//! | Numbers | `minimum`, `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `multipleOf` |
+//! | Strings | `minLength`, `maxLength`, `pattern` |
+//! | Arrays | `prefixItems`, `items`, `contains`, `minItems`, `maxItems`, `uniqueItems` |
+//! | Objects | `properties`, `patternProperties`, `additionalProperties`, `required`, `minProperties`, `maxProperties` |
+//!
+//! Any other keywords (like `format`) are ignored.
+
+use std::cell::RefCell;
+
+use itertools::Itertools;
+use onig::Regex;
+use serde_json::{Map, Value};
+use tracing::{trace, warn};
+
+use pact_models::matchingrules::{MatchingRule, RuleList};
+use pact_models::path_exp::DocPath;
+
+use crate::json::{type_of, value_of};
+
+/// A single violation of a JSON schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+  /// Path to the value that violated the schema
+  pub path: DocPath,
+  /// The value that violated the schema
+  pub actual: Value,
+  /// Description of the violation
+  pub message: String
+}
+
+impl SchemaViolation {
+  fn new<S: Into<String>>(path: &DocPath, actual: &Value, message: S) -> Self {
+    SchemaViolation {
+      path: path.clone(),
+      actual: actual.clone(),
+      message: message.into()
+    }
+  }
+}
+
+/// Root schema that references are resolved against, along with the references currently being
+/// followed for each path, so that circular references can be detected
+struct SchemaRoot<'a> {
+  schema: &'a Value,
+  references: RefCell<Vec<(String, String)>>
+}
+
+/// Validates the actual value against the JSON schema, returning all the violations found. The
+/// path is used as the base for the paths of the violations.
+pub fn validate_json_schema(schema: &Value, actual: &Value, path: &DocPath) -> Vec<SchemaViolation> {
+  trace!(%path, "validate_json_schema");
+  let root = SchemaRoot { schema, references: RefCell::new(vec![]) };
+  let mut violations = vec![];
+  validate(&root, schema, actual, path, &mut violations);
+  violations
+}
+
+/// Returns the schema of a JsonSchema matching rule if one was defined directly against the path
+/// for the rule list (i.e. it has not cascaded from a parent path). The schema applies to the whole
+/// sub-tree at the path, so the children do not need to be compared separately.
+pub(crate) fn json_schema_rule(rules: &RuleList) -> Option<&Value> {
+  if rules.cascaded {
+    None
+  } else {
+    rules.rules.iter().find_map(|rule| match rule {
+      MatchingRule::JsonSchema(schema) => Some(schema),
+      _ => None
+    })
+  }
+}
+
+fn validate(
+  root: &SchemaRoot,
+  schema: &Value,
+  actual: &Value,
+  path: &DocPath,
+  violations: &mut Vec<SchemaViolation>
+) {
+  match schema {
+    Value::Bool(true) => {}
+    Value::Bool(false) => violations.push(SchemaViolation::new(path, actual,
+      format!("Expected no value but got {} ({})", value_of(actual), type_of(actual)))),
+    Value::Object(schema) => validate_object_schema(root, schema, actual, path, violations),
+    _ => warn!("Ignoring invalid JSON schema '{}' at path {}", schema, path)
+  }
+}
+
+fn is_valid(root: &SchemaRoot, schema: &Value, actual: &Value, path: &DocPath) -> bool {
+  let mut violations = vec![];
+  validate(root, schema, actual, path, &mut violations);
+  violations.is_empty()
+}
+
+fn validate_object_schema(
+  root: &SchemaRoot,
+  schema: &Map<String, Value>,
+  actual: &Value,
+  path: &DocPath,
+  violations: &mut Vec<SchemaViolation>
+) {
+  if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+    // A reference that is already being followed for the same value will never terminate, as
+    // following it again does not move on to a child value
+    let key = (path.to_string(), reference.to_string());
+    if root.references.borrow().contains(&key) {
+      violations.push(SchemaViolation::new(path, actual,
+        format!("Schema reference '{}' is circular", reference)));
+    } else {
+      match resolve_reference(root.schema, reference) {
+        Some(referenced) => {
+          root.references.borrow_mut().push(key);
+          validate(root, referenced, actual, path, violations);
+          root.references.borrow_mut().pop();
+        }
+        None => violations.push(SchemaViolation::new(path, actual,
+          format!("Could not resolve schema reference '{}'", reference)))
+      }
+    }
+  }
+
+  if let Some(types) = schema.get("type") {
+    let types = match types {
+      Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect_vec(),
+      Value::String(t) => vec![t.as_str()],
+      _ => vec![]
+    };
+    if !types.is_empty() && !types.iter().any(|t| is_of_type(t, actual)) {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} ({}) to be of type {}",
+        value_of(actual), type_of(actual), types.join(" or "))));
+      // The remaining keywords will only generate noise if the type is wrong
+      return;
+    }
+  }
+
+  if let Some(Value::Array(values)) = schema.get("enum") {
+    if !values.iter().any(|value| json_equals(value, actual)) {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be one of {}",
+        value_of(actual), values.iter().join(", "))));
+    }
+  }
+
+  if let Some(value) = schema.get("const") {
+    if !json_equals(value, actual) {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be equal to {}",
+        value_of(actual), value)));
+    }
+  }
+
+  match actual {
+    Value::Number(_) => validate_number(schema, actual, path, violations),
+    Value::String(s) => validate_string(schema, actual, s, path, violations),
+    Value::Array(items) => validate_array(root, schema, actual, items, path, violations),
+    Value::Object(map) => validate_map(root, schema, actual, map, path, violations),
+    _ => {}
+  }
+
+  if let Some(Value::Array(schemas)) = schema.get("allOf") {
+    for schema in schemas {
+      validate(root, schema, actual, path, violations);
+    }
+  }
+
+  if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+    if !schemas.iter().any(|schema| is_valid(root, schema, actual, path)) {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be valid against at least one of the {} 'anyOf' schemas",
+        value_of(actual), schemas.len())));
+    }
+  }
+
+  if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+    let count = schemas.iter().filter(|schema| is_valid(root, schema, actual, path)).count();
+    if count != 1 {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be valid against exactly one of the {} 'oneOf' schemas, but it was valid against {}",
+        value_of(actual), schemas.len(), count)));
+    }
+  }
+
+  if let Some(not_schema) = schema.get("not") {
+    if is_valid(root, not_schema, actual, path) {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to not be valid against the 'not' schema",
+        value_of(actual))));
+    }
+  }
+}
+
+fn validate_number(
+  schema: &Map<String, Value>,
+  actual: &Value,
+  path: &DocPath,
+  violations: &mut Vec<SchemaViolation>
+) {
+  let number = actual.as_f64().unwrap_or_default();
+
+  if let Some(minimum) = schema.get("minimum").and_then(|v| v.as_f64()) {
+    if number < minimum {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be greater than or equal to {}", actual, minimum)));
+    }
+  }
+  if let Some(maximum) = schema.get("maximum").and_then(|v| v.as_f64()) {
+    if number > maximum {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be less than or equal to {}", actual, maximum)));
+    }
+  }
+  if let Some(minimum) = schema.get("exclusiveMinimum").and_then(|v| v.as_f64()) {
+    if number <= minimum {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be greater than {}", actual, minimum)));
+    }
+  }
+  if let Some(maximum) = schema.get("exclusiveMaximum").and_then(|v| v.as_f64()) {
+    if number >= maximum {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be less than {}", actual, maximum)));
+    }
+  }
+  if let Some(multiple) = schema.get("multipleOf").and_then(|v| v.as_f64()) {
+    if multiple > 0.0 && (number / multiple).fract() != 0.0 {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected {} to be a multiple of {}", actual, multiple)));
+    }
+  }
+}
+
+fn validate_string(
+  schema: &Map<String, Value>,
+  actual: &Value,
+  string: &str,
+  path: &DocPath,
+  violations: &mut Vec<SchemaViolation>
+) {
+  let length = string.chars().count() as u64;
+
+  if let Some(min) = schema.get("minLength").and_then(|v| v.as_u64()) {
+    if length < min {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected '{}' to have a length of at least {} characters", string, min)));
+    }
+  }
+  if let Some(max) = schema.get("maxLength").and_then(|v| v.as_u64()) {
+    if length > max {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected '{}' to have a length of at most {} characters", string, max)));
+    }
+  }
+  if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
+    match Regex::new(pattern) {
+      Ok(regex) => if regex.find(string).is_none() {
+        violations.push(SchemaViolation::new(path, actual, format!("Expected '{}' to match '{}'", string, pattern)));
+      }
+      Err(err) => violations.push(SchemaViolation::new(path, actual, format!("'{}' is not a valid regular expression - {}", pattern, err)))
+    }
+  }
+}
+
+fn validate_array(
+  root: &SchemaRoot,
+  schema: &Map<String, Value>,
+  actual: &Value,
+  items: &[Value],
+  path: &DocPath,
+  violations: &mut Vec<SchemaViolation>
+) {
+  let prefix_items = match schema.get("prefixItems") {
+    Some(Value::Array(prefix_items)) => prefix_items.as_slice(),
+    _ => &[]
+  };
+  for (index, item) in items.iter().enumerate() {
+    let item_path = path.join_index(index);
+    if let Some(item_schema) = prefix_items.get(index) {
+      validate(root, item_schema, item, &item_path, violations);
+    } else if let Some(item_schema) = schema.get("items") {
+      validate(root, item_schema, item, &item_path, violations);
+    }
+  }
+
+  if let Some(min) = schema.get("minItems").and_then(|v| v.as_u64()) {
+    if (items.len() as u64) < min {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected an array with at least {} items but received {} items", min, items.len())));
+    }
+  }
+  if let Some(max) = schema.get("maxItems").and_then(|v| v.as_u64()) {
+    if (items.len() as u64) > max {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected an array with at most {} items but received {} items", max, items.len())));
+    }
+  }
+  if schema.get("uniqueItems").and_then(|v| v.as_bool()).unwrap_or(false) {
+    for (index, item) in items.iter().enumerate() {
+      if items[..index].iter().any(|other| json_equals(other, item)) {
+        violations.push(SchemaViolation::new(&path.join_index(index), item, format!("Expected array items to be unique, but {} is a duplicate", item)));
+      }
+    }
+  }
+  if let Some(contains) = schema.get("contains") {
+    if !items.iter().enumerate().any(|(index, item)| is_valid(root, contains, item, &path.join_index(index))) {
+      violations.push(SchemaViolation::new(path, actual, "Expected the array to contain at least one item matching the 'contains' schema"));
+    }
+  }
+}
+
+fn validate_map(
+  root: &SchemaRoot,
+  schema: &Map<String, Value>,
+  actual: &Value,
+  map: &Map<String, Value>,
+  path: &DocPath,
+  violations: &mut Vec<SchemaViolation>
+) {
+  if let Some(Value::Array(required)) = schema.get("required") {
+    for key in required.iter().filter_map(|key| key.as_str()) {
+      if !map.contains_key(key) {
+        violations.push(SchemaViolation::new(path, actual, format!("Expected the required property '{}' but was missing", key)));
+      }
+    }
+  }
+
+  let properties = schema.get("properties").and_then(|v| v.as_object());
+  let pattern_properties = schema.get("patternProperties")
+    .and_then(|v| v.as_object())
+    .map(|patterns| patterns.iter()
+      .filter_map(|(pattern, schema)| match Regex::new(pattern) {
+        Ok(regex) => Some((regex, schema)),
+        Err(err) => {
+          warn!("Ignoring invalid 'patternProperties' regular expression '{}' - {}", pattern, err);
+          None
+        }
+      })
+      .collect_vec())
+    .unwrap_or_default();
+  let additional_properties = schema.get("additionalProperties");
+
+  for (key, value) in map {
+    let value_path = path.join_field(key);
+    let mut matched = false;
+    if let Some(property_schema) = properties.and_then(|properties| properties.get(key)) {
+      matched = true;
+      validate(root, property_schema, value, &value_path, violations);
+    }
+    for (regex, property_schema) in &pattern_properties {
+      if regex.find(key).is_some() {
+        matched = true;
+        validate(root, property_schema, value, &value_path, violations);
+      }
+    }
+    if !matched {
+      match additional_properties {
+        Some(Value::Bool(false)) => violations.push(SchemaViolation::new(path, actual,
+          format!("Did not expect the property '{}'", key))),
+        Some(property_schema) => validate(root, property_schema, value, &value_path, violations),
+        None => {}
+      }
+    }
+  }
+
+  if let Some(min) = schema.get("minProperties").and_then(|v| v.as_u64()) {
+    if (map.len() as u64) < min {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected an object with at least {} properties but received {} properties", min, map.len())));
+    }
+  }
+  if let Some(max) = schema.get("maxProperties").and_then(|v| v.as_u64()) {
+    if (map.len() as u64) > max {
+      violations.push(SchemaViolation::new(path, actual, format!("Expected an object with at most {} properties but received {} properties", max, map.len())));
+    }
+  }
+}
+
+fn is_of_type(schema_type: &str, actual: &Value) -> bool {
+  match schema_type {
+    "null" => actual.is_null(),
+    "boolean" => actual.is_boolean(),
+    "object" => actual.is_object(),
+    "array" => actual.is_array(),
+    "string" => actual.is_string(),
+    "number" => actual.is_number(),
+    "integer" => actual.is_i64() || actual.is_u64() || actual.as_f64().map(|n| n.fract() == 0.0).unwrap_or(false),
+    _ => false
+  }
+}
+
+/// JSON Schema equality, where numbers are equal if they have the same value (i.e. 1 == 1.0)
+fn json_equals(expected: &Value, actual: &Value) -> bool {
+  match (expected, actual) {
+    (Value::Number(e), Value::Number(a)) => e.as_f64() == a.as_f64(),
+    (Value::Array(e), Value::Array(a)) => e.len() == a.len() &&
+      e.iter().zip(a.iter()).all(|(e, a)| json_equals(e, a)),
+    (Value::Object(e), Value::Object(a)) => e.len() == a.len() &&
+      e.iter().all(|(k, v)| a.get(k).map(|a| json_equals(v, a)).unwrap_or(false)),
+    _ => expected == actual
+  }
+}
+
+/// Resolves a local reference (`#` or a JSON pointer like `#/$defs/address`) against the root schema
+fn resolve_reference<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+  if reference == "#" {
+    Some(root)
+  } else {
+    reference.strip_prefix('#').and_then(|pointer| root.pointer(pointer))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use super::*;
+
+  fn violations(schema: Value, actual: Value) -> Vec<(String, String)> {
+    validate_json_schema(&schema, &actual, &DocPath::root())
+      .iter()
+      .map(|v| (v.path.to_string(), v.message.clone()))
+      .collect()
+  }
+
+  #[test]
+  fn validates_types() {
+    expect!(violations(json!({ "type": "string" }), json!("a"))).to(be_empty());
+    expect!(violations(json!({ "type": ["string", "null"] }), json!(null))).to(be_empty());
+    expect!(violations(json!({ "type": "integer" }), json!(1.0))).to(be_empty());
+    expect!(violations(json!({ "type": "integer" }), json!(1.5))).to(be_equal_to(vec![
+      ("$".to_string(), "Expected 1.5 (Decimal) to be of type integer".to_string())
+    ]));
+    expect!(violations(json!(true), json!(1))).to(be_empty());
+    expect!(violations(json!(false), json!(1))).to(be_equal_to(vec![
+      ("$".to_string(), "Expected no value but got 1 (Integer)".to_string())
+    ]));
+  }
+
+  #[test]
+  fn validates_numbers_and_strings() {
+    let schema = json!({ "minimum": 1, "exclusiveMaximum": 10, "multipleOf": 2 });
+    expect!(violations(schema.clone(), json!(4))).to(be_empty());
+    expect!(violations(schema.clone(), json!(10))).to(be_equal_to(vec![
+      ("$".to_string(), "Expected 10 to be less than 10".to_string())
+    ]));
+    expect!(violations(schema, json!(3)).len()).to(be_equal_to(1));
+
+    let schema = json!({ "minLength": 2, "maxLength": 4, "pattern": "^[a-z]+$" });
+    expect!(violations(schema.clone(), json!("abc"))).to(be_empty());
+    expect!(violations(schema.clone(), json!("abcde"))).to(be_equal_to(vec![
+      ("$".to_string(), "Expected 'abcde' to have a length of at most 4 characters".to_string())
+    ]));
+    expect!(violations(schema, json!("A1")).len()).to(be_equal_to(1));
+  }
+
+  #[test]
+  fn reports_violations_at_the_violating_path() {
+    let schema = json!({
+      "type": "object",
+      "required": ["id", "name"],
+      "additionalProperties": false,
+      "properties": {
+        "id": { "type": "integer" },
+        "name": { "type": "string" },
+        "tags": {
+          "type": "array",
+          "uniqueItems": true,
+          "items": { "$ref": "#/$defs/tag" }
+        }
+      },
+      "$defs": {
+        "tag": { "type": "string", "enum": ["a", "b"] }
+      }
+    });
+    expect!(violations(schema.clone(), json!({ "id": 1, "name": "test", "tags": ["a", "b"] }))).to(be_empty());
+    expect!(violations(schema, json!({ "id": "1", "tags": ["a", "c", "a"], "other": true }))).to(be_equal_to(vec![
+      ("$".to_string(), "Expected the required property 'name' but was missing".to_string()),
+      ("$.id".to_string(), "Expected '1' (String) to be of type integer".to_string()),
+      ("$".to_string(), "Did not expect the property 'other'".to_string()),
+      ("$.tags[1]".to_string(), "Expected 'c' to be one of \"a\", \"b\"".to_string()),
+      ("$.tags[2]".to_string(), "Expected array items to be unique, but \"a\" is a duplicate".to_string())
+    ]));
+  }
+
+  #[test]
+  fn validates_combinators() {
+    let schema = json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] });
+    expect!(violations(schema.clone(), json!(1))).to(be_empty());
+    expect!(violations(schema, json!(true)).len()).to(be_equal_to(1));
+
+    let schema = json!({ "oneOf": [{ "type": "number" }, { "type": "integer" }] });
+    expect!(violations(schema.clone(), json!(1.5))).to(be_empty());
+    expect!(violations(schema, json!(1)).len()).to(be_equal_to(1));
+
+    let schema = json!({ "allOf": [{ "minimum": 1 }, { "maximum": 5 }], "not": { "const": 3 } });
+    expect!(violations(schema.clone(), json!(2))).to(be_empty());
+    expect!(violations(schema.clone(), json!(3)).len()).to(be_equal_to(1));
+    expect!(violations(schema, json!(6)).len()).to(be_equal_to(1));
+  }
+
+  #[test]
+  fn circular_references_are_reported_as_violations() {
+    expect!(violations(json!({ "$ref": "#" }), json!(1))).to(be_equal_to(vec![
+      ("$".to_string(), "Schema reference '#' is circular".to_string())
+    ]));
+
+    let schema = json!({
+      "$ref": "#/$defs/a",
+      "$defs": {
+        "a": { "$ref": "#/$defs/b" },
+        "b": { "$ref": "#/$defs/a" }
+      }
+    });
+    expect!(violations(schema, json!(1))).to(be_equal_to(vec![
+      ("$".to_string(), "Schema reference '#/$defs/a' is circular".to_string())
+    ]));
+  }
+
+  #[test]
+  fn recursive_references_to_child_values_are_followed() {
+    let schema = json!({
+      "type": "object",
+      "properties": {
+        "name": { "type": "string" },
+        "children": { "type": "array", "items": { "$ref": "#" } }
+      }
+    });
+    expect!(violations(schema.clone(), json!({ "name": "a", "children": [{ "name": "b", "children": [] }] }))).to(be_empty());
+    expect!(violations(schema, json!({ "name": "a", "children": [{ "name": 1 }] }))).to(be_equal_to(vec![
+      ("$.children[0].name".to_string(), "Expected 1 (Integer) to be of type string".to_string())
+    ]));
+  }
+}
@@ -349,6 +349,14 @@
 //! | Semver | V4 | `{ "match": "semver" }` | Value must be valid based on the semver specification |
 //! | EachKey | V4 | `{ "match": "eachKey", "rules": [{"match": "regex", "regex": "\\$(\\.\\w+)+"}], "value": "$.test.one" }` | Allows defining matching rules to apply to the keys in a map |
 //! | EachValue | V4 | `{ "match": "eachValue", "rules": [{"match": "regex", "regex": "\\$(\\.\\w+)+"}], "value": "$.test.one" }` | Allows defining matching rules to apply to the values in a collection. For maps, delgates to the Values matcher. |
+//! | NumberRange | V4 | `{ "match": "numberRange", "min": 1, "max": 10, "inclusive": true }` | Value must be a number between the minimum and maximum values. `inclusive` defaults to true. |
+//! | StringLength | V4 | `{ "match": "stringLength", "min": 1, "max": 20 }` | Value must be a string with a length (in characters) between the minimum and maximum values (inclusive). |
+//! | OneOf | V4 | `{ "match": "oneOf", "values": ["ACTIVE", "CLOSED"] }` | Value must be equal to one of the listed values. Numbers are compared by value. |
+//! | JsonSchema | V4 | `{ "match": "jsonSchema", "schema": { "type": "object" } }` | Value (and all its children) must be valid against the JSON schema. Supports a subset of draft 2020-12, see the `json_schema` module. Each violation is reported as a mismatch against the violating path. |
+//! | UniqueItems | V4 | `{ "match": "uniqueItems", "key": "$.id" }` | The items in a list must be unique. If a key path is given, the values at that path in each item must be unique. |
+//! | Sorted | V4 | `{ "match": "sorted", "field": "$.name", "order": "asc" }` | The items in a list must be sorted by the value at the field path (`$` for the items themselves) in either `asc` or `desc` order. |
+//! | Format | V4 | `{ "match": "format", "format": "email" }` | String value must be of a well-known format: `email`, `uri`, `ipv4`, `ipv6`, `uuid`, `ulid` or `hostname`. The values are checked with parsers for the relevant specifications. |
+//! | Expression | V4 | `{ "match": "expression", "expression": "sum($.items[*].price)" }` | Value must equal the result of the expression evaluated against the actual body. The expression can be a path (`$.password`), an aggregate function (`sum`, `count`, `min` or `max`) applied to a path, or a string with `${...}` expressions. |
 
 #![warn(missing_docs)]
 
@@ -374,7 +382,14 @@ use serde_json::{json, Value};
 
 use pact_models::bodies::OptionalBody;
 use pact_models::content_types::ContentType;
-use pact_models::generators::{apply_generators, GenerateValue, GeneratorCategory, GeneratorTestMode, VariantMatcher};
+use pact_models::generators::{
+  apply_generators,
+  context_for_path,
+  GenerateValue,
+  GeneratorCategory,
+  GeneratorTestMode,
+  VariantMatcher
+};
 use pact_models::http_parts::HttpPart;
 use pact_models::interaction::Interaction;
 use pact_models::json_utils::json_to_string;
@@ -416,6 +431,7 @@ pub const PACT_RUST_VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSI
 
 pub mod matchers;
 pub mod json;
+pub mod json_schema;
 pub mod matchingrules;
 pub mod metrics;
 pub mod generators;
@@ -2275,7 +2291,9 @@ pub async fn match_sync_message_response<'a>(
   mismatches
 }
 
-/// Generates the request by applying any defined generators
+/// Generates the request by applying any defined generators. A seed for the random generators
+/// can be provided with the `generatorSeed` value in the context (or the `PACT_GENERATOR_SEED`
+/// environment variable), in which case the same request will be generated on every run.
 // TODO: Need to pass in any plugin data
 #[instrument(level = "trace")]
 pub async fn generate_request(request: &HttpRequest, mode: &GeneratorTestMode, context: &HashMap<&str, Value>) -> HttpRequest {
@@ -2285,7 +2303,9 @@ pub async fn generate_request(request: &HttpRequest, mode: &GeneratorTestMode, c
   let generators = request.build_generators(&GeneratorCategory::PATH);
   if !generators.is_empty() {
     debug!("Applying path generator...");
-    apply_generators(mode, &generators, &mut |_, generator| {
+    apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::PATH, key.to_string().as_str());
+      let context = context.as_ref();
       if let Ok(v) = generator.generate_value(&request.path, context, &DefaultVariantMatcher.boxed()) {
         request.path = v;
       }
@@ -2296,6 +2316,8 @@ pub async fn generate_request(request: &HttpRequest, mode: &GeneratorTestMode, c
   if !generators.is_empty() {
     debug!("Applying header generators...");
     apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::HEADER, key.to_string().as_str());
+      let context = context.as_ref();
       if let Some(header) = key.first_field() {
         if let Some(ref mut headers) = request.headers {
           if headers.contains_key(header) {
@@ -2322,6 +2344,8 @@ pub async fn generate_request(request: &HttpRequest, mode: &GeneratorTestMode, c
   if !generators.is_empty() {
     debug!("Applying query generators...");
     apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::QUERY, key.to_string().as_str());
+      let context = context.as_ref();
       if let Some(param) = key.first_field() {
         if let Some(ref mut parameters) = request.query {
           if let Some(parameter) = parameters.get_mut(param) {
@@ -2358,7 +2382,9 @@ pub async fn generate_request(request: &HttpRequest, mode: &GeneratorTestMode, c
   request
 }
 
-/// Generates the response by applying any defined generators
+/// Generates the response by applying any defined generators. A seed for the random generators
+/// can be provided with the `generatorSeed` value in the context (or the `PACT_GENERATOR_SEED`
+/// environment variable), in which case the same response will be generated on every run.
 // TODO: Need to pass in any plugin data
 pub async fn generate_response(response: &HttpResponse, mode: &GeneratorTestMode, context: &HashMap<&str, Value>) -> HttpResponse {
   trace!(?response, ?mode, ?context, "generate_response");
@@ -2366,7 +2392,9 @@ pub async fn generate_response(response: &HttpResponse, mode: &GeneratorTestMode
   let generators = response.build_generators(&GeneratorCategory::STATUS);
   if !generators.is_empty() {
     debug!("Applying status generator...");
-    apply_generators(mode, &generators, &mut |_, generator| {
+    apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::STATUS, key.to_string().as_str());
+      let context = context.as_ref();
       if let Ok(v) = generator.generate_value(&response.status, context, &DefaultVariantMatcher.boxed()) {
         debug!("Generated value for status: {}", v);
         response.status = v;
@@ -2377,6 +2405,8 @@ pub async fn generate_response(response: &HttpResponse, mode: &GeneratorTestMode
   if !generators.is_empty() {
     debug!("Applying header generators...");
     apply_generators(mode, &generators, &mut |key, generator| {
+      let context = context_for_path(context, &GeneratorCategory::HEADER, key.to_string().as_str());
+      let context = context.as_ref();
       if let Some(header) = key.first_field() {
         if let Some(ref mut headers) = response.headers {
           if headers.contains_key(header) {
@@ -1,5 +1,6 @@
 //! `matchingrules` module includes all the classes to deal with V3 format matchers
 
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::{Debug, Display};
 use std::str::from_utf8;
@@ -8,7 +9,7 @@ use anyhow::anyhow;
 use itertools::Itertools;
 use maplit::hashmap;
 use onig::Regex;
-use pact_models::matchingrules::{Category, MatchingRule, MatchingRuleCategory, RuleList, RuleLogic};
+use pact_models::matchingrules::{Category, MatchingRule, MatchingRuleCategory, RuleList, RuleLogic, SortOrder};
 use pact_models::path_exp::DocPath;
 use serde_json::{self, json, Value};
 use tracing::{debug, trace};
@@ -85,6 +86,12 @@ impl <T: Debug + Display + PartialEq + Clone> Matches<&[T]> for &[T] {
       MatchingRule::ContentType(_) => Ok(()),
       MatchingRule::Boolean => Ok(()),
       MatchingRule::Semver => Ok(()),
+      MatchingRule::NumberRange(_, _, _) | MatchingRule::StringLength(_, _) => Ok(()),
+      MatchingRule::OneOf(_) => Ok(()),
+      MatchingRule::JsonSchema(_) => Ok(()),
+      MatchingRule::UniqueItems(_) | MatchingRule::Sorted(_, _) => Ok(()),
+      MatchingRule::Format(_) => Ok(()),
+      MatchingRule::Expression(_) => Ok(()),
       _ => Err(anyhow!("Unable to match {} using {:?}", self.for_mismatch(), matcher))
     };
     debug!("Comparing '{:?}' to '{:?}' using {:?} -> {:?}", self, actual, matcher, result);
@@ -224,6 +231,10 @@ impl <T: Debug + Display + Clone + PartialEq> Matches<&BTreeMap<String, T>> for
       MatchingRule::EachKey(_) => Ok(()),
       MatchingRule::EachValue(_) => Ok(()),
       MatchingRule::Values => Ok(()),
+      MatchingRule::JsonSchema(_) => Ok(()),
+      MatchingRule::UniqueItems(_) | MatchingRule::Sorted(_, _) => Ok(()),
+      MatchingRule::Format(_) => Ok(()),
+      MatchingRule::Expression(_) => Ok(()),
       _ => Err(anyhow!("Unable to match {} using {:?}", self.for_mismatch(), matcher))
     };
     debug!("Comparing '{:?}' to '{:?}' using {:?} -> {:?}", self, actual, matcher, result);
@@ -231,6 +242,41 @@ impl <T: Debug + Display + Clone + PartialEq> Matches<&BTreeMap<String, T>> for
   }
 }
 
+/// Trait to resolve the value within a list item that the UniqueItems and Sorted matching rules
+/// are applied to
+pub trait ResolveItemValue {
+  /// Return the value at the given path within the item, or None if there is no value at the path
+  fn resolve_item_value(&self, path: &DocPath) -> Option<Value>;
+}
+
+impl ResolveItemValue for Value {
+  fn resolve_item_value(&self, path: &DocPath) -> Option<Value> {
+    path.as_json_pointer().ok()
+      .and_then(|pointer| self.pointer(pointer.as_str()))
+      .cloned()
+  }
+}
+
+impl ResolveItemValue for String {
+  fn resolve_item_value(&self, path: &DocPath) -> Option<Value> {
+    if path.is_root() {
+      Some(Value::String(self.clone()))
+    } else {
+      None
+    }
+  }
+}
+
+impl ResolveItemValue for &str {
+  fn resolve_item_value(&self, path: &DocPath) -> Option<Value> {
+    if path.is_root() {
+      Some(Value::String(self.to_string()))
+    } else {
+      None
+    }
+  }
+}
+
 /// Trait to convert a expected or actual complex object into a string that can be used for a mismatch
 pub trait DisplayForMismatch {
   /// Return a string representation that can be used in a mismatch to display to the user
@@ -352,8 +398,10 @@ pub fn compare_maps_with_matchingrule<T: Display + Debug + Clone + PartialEq>(
   result
 }
 
-/// Compare the expected and actual lists using the matching rule's logic
-#[tracing::instrument(ret, skip_all, fields(path, rule, cascaded), level = "trace")]
+/// Compare the expected and actual lists using the matching rule's logic. The UniqueItems and
+/// Sorted matching rules need to resolve values within the list items, so they can not be applied
+/// by this function and will result in a mismatch (use `compare_resolvable_lists_with_matchingrule`
+/// for those).
 pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone + Sized>(
   rule: &MatchingRule,
   path: &DocPath,
@@ -362,6 +410,49 @@ pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone +
   context: &(dyn MatchingContext + Send + Sync),
   cascaded: bool,
   callback: &mut dyn FnMut(&DocPath, &T, &T, &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<CommonMismatch>>
+) -> Result<(), Vec<CommonMismatch>> {
+  compare_lists_with_item_rules(rule, path, expected, actual, context, cascaded, callback, &unsupported_item_rules)
+}
+
+/// Compare the expected and actual lists using the matching rule's logic, resolving the values
+/// within the list items for the UniqueItems and Sorted matching rules
+pub fn compare_resolvable_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone + Sized + ResolveItemValue>(
+  rule: &MatchingRule,
+  path: &DocPath,
+  expected: &[T],
+  actual: &[T],
+  context: &(dyn MatchingContext + Send + Sync),
+  cascaded: bool,
+  callback: &mut dyn FnMut(&DocPath, &T, &T, &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<CommonMismatch>>
+) -> Result<(), Vec<CommonMismatch>> {
+  compare_lists_with_item_rules(rule, path, expected, actual, context, cascaded, callback, &list_item_mismatches)
+}
+
+/// Applies the UniqueItems and Sorted matching rules to the list items
+pub(crate) fn list_item_mismatches<T: ResolveItemValue>(rule: &MatchingRule, items: &[T]) -> Vec<String> {
+  match rule {
+    MatchingRule::UniqueItems(key) => match_unique_items(items, key.as_ref()),
+    MatchingRule::Sorted(field, order) => match_sorted_items(items, field, *order),
+    _ => vec![]
+  }
+}
+
+/// Used when the values within the list items can not be resolved, so the UniqueItems and Sorted
+/// matching rules can not be applied
+fn unsupported_item_rules<T>(rule: &MatchingRule, _items: &[T]) -> Vec<String> {
+  vec![format!("The {} matching rule is not supported here, as the values within the list items can not be resolved", rule.name())]
+}
+
+#[tracing::instrument(ret, skip_all, fields(path, rule, cascaded), level = "trace")]
+fn compare_lists_with_item_rules<T: Display + Debug + PartialEq + Clone + Sized>(
+  rule: &MatchingRule,
+  path: &DocPath,
+  expected: &[T],
+  actual: &[T],
+  context: &(dyn MatchingContext + Send + Sync),
+  cascaded: bool,
+  callback: &mut dyn FnMut(&DocPath, &T, &T, &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<CommonMismatch>>,
+  item_mismatches: &dyn Fn(&MatchingRule, &[T]) -> Vec<String>
 ) -> Result<(), Vec<CommonMismatch>> {
   let mut result = vec![];
 
@@ -438,6 +529,20 @@ pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone +
         let context = context.clone_with(&rules);
         result.extend(match_list_contents(path, expected, actual, context.as_ref(), callback));
       }
+      MatchingRule::UniqueItems(_) | MatchingRule::Sorted(_, _) => {
+        if !cascaded {
+          debug!("Matching {} with {}", path, rule.name());
+          for description in item_mismatches(rule, actual) {
+            result.push(CommonMismatch {
+              path: path.to_string(),
+              expected: expected.for_mismatch(),
+              actual: actual.for_mismatch(),
+              description
+            });
+          }
+        }
+        result.extend(match_list_contents(path, expected, actual, context, callback));
+      }
       _ => {
         if let Err(mismatch) = expected.matches_with(actual, rule, cascaded) {
           result.push(CommonMismatch {
@@ -460,7 +565,9 @@ pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone +
   }
 }
 
-/// Compare the expected and actual lists using matching rules
+/// Compare the expected and actual lists using matching rules. The UniqueItems and Sorted matching
+/// rules can not be applied and will result in a mismatch (use
+/// `compare_resolvable_lists_with_matchingrules` for those).
 pub fn compare_lists_with_matchingrules<T>(
   path: &DocPath,
   matching_rules: &RuleList,
@@ -468,6 +575,33 @@ pub fn compare_lists_with_matchingrules<T>(
   actual: &[T],
   context: &(dyn MatchingContext + Send + Sync),
   callback: &mut dyn FnMut(&DocPath, &T, &T, &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<CommonMismatch>>
+) -> Result<(), Vec<CommonMismatch>>
+  where T: Display + Debug + PartialEq + Clone + Sized {
+  compare_lists_with_all_item_rules(path, matching_rules, expected, actual, context, callback, &unsupported_item_rules)
+}
+
+/// Compare the expected and actual lists using matching rules, resolving the values within the
+/// list items for the UniqueItems and Sorted matching rules
+pub fn compare_resolvable_lists_with_matchingrules<T>(
+  path: &DocPath,
+  matching_rules: &RuleList,
+  expected: &[T],
+  actual: &[T],
+  context: &(dyn MatchingContext + Send + Sync),
+  callback: &mut dyn FnMut(&DocPath, &T, &T, &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<CommonMismatch>>
+) -> Result<(), Vec<CommonMismatch>>
+  where T: Display + Debug + PartialEq + Clone + Sized + ResolveItemValue {
+  compare_lists_with_all_item_rules(path, matching_rules, expected, actual, context, callback, &list_item_mismatches)
+}
+
+fn compare_lists_with_all_item_rules<T>(
+  path: &DocPath,
+  matching_rules: &RuleList,
+  expected: &[T],
+  actual: &[T],
+  context: &(dyn MatchingContext + Send + Sync),
+  callback: &mut dyn FnMut(&DocPath, &T, &T, &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<CommonMismatch>>,
+  item_mismatches: &dyn Fn(&MatchingRule, &[T]) -> Vec<String>
 ) -> Result<(), Vec<CommonMismatch>>
   where T: Display + Debug + PartialEq + Clone + Sized {
   trace!("compare_lists_with_matchingrules: {} -> {}", std::any::type_name::<T>(), std::any::type_name::<T>());
@@ -481,7 +615,7 @@ pub fn compare_lists_with_matchingrules<T>(
     })
   } else {
     let results = matching_rules.rules.iter().map(|rule| {
-      compare_lists_with_matchingrule(&rule, path, expected, actual, context, matching_rules.cascaded, callback)
+      compare_lists_with_item_rules(&rule, path, expected, actual, context, matching_rules.cascaded, callback, item_mismatches)
     }).collect::<Vec<Result<(), Vec<CommonMismatch>>>>();
     match matching_rules.rule_logic {
       RuleLogic::And => for result in results {
@@ -546,6 +680,78 @@ fn match_list_contents<T: Display + Debug + PartialEq + Clone + Sized>(
   result
 }
 
+/// Checks that the items in the list are unique (or the values at the key path in each item if
+/// one is given). Returns a description for each duplicate item.
+pub(crate) fn match_unique_items<T: ResolveItemValue>(items: &[T], key: Option<&DocPath>) -> Vec<String> {
+  let root = DocPath::root();
+  let key_path = key.unwrap_or(&root);
+  let mut seen: Vec<(usize, Value)> = vec![];
+  let mut result = vec![];
+
+  for (index, item) in items.iter().enumerate() {
+    match item.resolve_item_value(key_path) {
+      Some(value) => if let Some((first, _)) = seen.iter().find(|(_, v)| *v == value) {
+        result.push(match key {
+          Some(key) => format!("Expected the list items to have unique values for '{}', but the item at index {} has the same value ({}) as the item at index {}",
+            key, index, value, first),
+          None => format!("Expected the list items to be unique, but the item at index {} ({}) is a duplicate of the item at index {}",
+            index, value, first)
+        });
+      } else {
+        seen.push((index, value));
+      }
+      None => result.push(format!("Expected the item at index {} to have a value for '{}', but it was missing",
+        index, key_path))
+    }
+  }
+
+  result
+}
+
+/// Checks that the items in the list are sorted by the value at the field path in the given
+/// order. Returns a description for each item that is out of order.
+pub(crate) fn match_sorted_items<T: ResolveItemValue>(items: &[T], field: &DocPath, order: SortOrder) -> Vec<String> {
+  let mut previous: Option<(usize, Value)> = None;
+  let mut result = vec![];
+
+  for (index, item) in items.iter().enumerate() {
+    match item.resolve_item_value(field) {
+      Some(value) => {
+        if let Some((previous_index, previous_value)) = &previous {
+          match compare_sort_values(previous_value, &value) {
+            Some(ordering) => {
+              let out_of_order = match order {
+                SortOrder::Ascending => ordering == Ordering::Greater,
+                SortOrder::Descending => ordering == Ordering::Less
+              };
+              if out_of_order {
+                result.push(format!("Expected the list items to be sorted by '{}' in {} order, but the item at index {} ({}) is out of order with the item at index {} ({})",
+                  field, order.description(), index, value, previous_index, previous_value));
+              }
+            }
+            None => result.push(format!("Expected the list items to be sorted by '{}', but the value of the item at index {} ({}) can not be compared with the item at index {} ({})",
+              field, index, value, previous_index, previous_value))
+          }
+        }
+        previous = Some((index, value));
+      }
+      None => result.push(format!("Expected the item at index {} to have a value for '{}', but it was missing",
+        index, field))
+    }
+  }
+
+  result
+}
+
+fn compare_sort_values(a: &Value, b: &Value) -> Option<Ordering> {
+  match (a, b) {
+    (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+    (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+    (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+    _ => None
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use std::collections::{BTreeSet, HashMap, HashSet};
@@ -553,16 +759,23 @@ mod tests {
 
   use expectest::prelude::*;
   use maplit::{btreemap, hashmap};
-  use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleList};
+  use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleList, SortOrder};
   use pact_models::matchingrules::expressions::{MatchingRuleDefinition, ValueType};
   use pact_models::path_exp::DocPath;
   use pact_models::prelude::RuleLogic;
+  use serde_json::json;
   #[cfg(feature = "plugins")] use pact_plugin_driver::plugin_models::PluginInteractionConfig;
   #[cfg(not(feature = "plugins"))] use crate::PluginInteractionConfig;
 
   use crate::{CoreMatchingContext, DiffConfig, MatchingContext, CommonMismatch};
   use crate::matchers::match_strings;
-  use crate::matchingrules::{compare_lists_with_matchingrule, compare_maps_with_matchingrule};
+  use crate::matchingrules::{
+    compare_lists_with_matchingrule,
+    compare_maps_with_matchingrule,
+    compare_resolvable_lists_with_matchingrule,
+    match_sorted_items,
+    match_unique_items
+  };
 
   #[derive(Debug)]
   struct MockContext {
@@ -943,4 +1156,90 @@ mod tests {
       expected, &["*", "x"], &context, false, &mut callback);
     expect!(result).to(be_err());
   }
+
+  #[test]
+  fn compare_resolvable_lists_with_matchingrule_with_unique_items_matcher() {
+    let expected = vec![ "one".to_string() ];
+    let actual = vec![ "one".to_string(), "two".to_string(), "one".to_string() ];
+
+    let context = MockContext {
+      calls: RwLock::new(vec![]),
+      matchers: MatchingRuleCategory::default()
+    };
+    let mut callback = |_: &DocPath, _: &String, _: &String, _: &(dyn MatchingContext + Send + Sync)| Ok(());
+
+    let result = compare_resolvable_lists_with_matchingrule(&MatchingRule::UniqueItems(None),
+      &DocPath::new_unwrap("$.values"), &expected, &actual, &context, false, &mut callback);
+    expect!(result.unwrap_err().iter().map(|m| m.description.clone()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "Expected the list items to be unique, but the item at index 2 (\"one\") is a duplicate of the item at index 0".to_string()
+    ]));
+
+    let result = compare_lists_with_matchingrule(&MatchingRule::UniqueItems(None),
+      &DocPath::new_unwrap("$.values"), &expected, &actual, &context, false, &mut callback);
+    expect!(result.unwrap_err().iter().map(|m| m.description.clone()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "The unique-items matching rule is not supported here, as the values within the list items can not be resolved".to_string()
+    ]));
+
+    let actual = vec![ "one".to_string(), "two".to_string() ];
+    let result = compare_resolvable_lists_with_matchingrule(&MatchingRule::UniqueItems(None),
+      &DocPath::new_unwrap("$.values"), &expected, &actual, &context, false, &mut callback);
+    expect!(result).to(be_ok());
+  }
+
+  #[test]
+  fn compare_resolvable_lists_with_matchingrule_with_sorted_matcher() {
+    let expected = vec![ "a".to_string() ];
+    let actual = vec![ "a".to_string(), "c".to_string(), "b".to_string() ];
+
+    let context = MockContext {
+      calls: RwLock::new(vec![]),
+      matchers: MatchingRuleCategory::default()
+    };
+    let mut callback = |_: &DocPath, _: &String, _: &String, _: &(dyn MatchingContext + Send + Sync)| Ok(());
+
+    let result = compare_resolvable_lists_with_matchingrule(&MatchingRule::Sorted(DocPath::root(), SortOrder::Ascending),
+      &DocPath::root(), &expected, &actual, &context, false, &mut callback);
+    expect!(result.unwrap_err().iter().map(|m| m.description.clone()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "Expected the list items to be sorted by '$' in ascending order, but the item at index 2 (\"b\") is out of order with the item at index 1 (\"c\")".to_string()
+    ]));
+
+    let result = compare_resolvable_lists_with_matchingrule(&MatchingRule::Sorted(DocPath::root(), SortOrder::Ascending),
+      &DocPath::root(), &expected, &actual, &context, true, &mut callback);
+    expect!(result).to(be_ok());
+  }
+
+  #[test]
+  fn match_unique_items_with_a_key() {
+    let items = vec![
+      json!({ "id": 1, "name": "a" }),
+      json!({ "id": 2, "name": "b" }),
+      json!({ "id": 1, "name": "c" }),
+      json!({ "name": "d" })
+    ];
+    expect!(match_unique_items(&items, Some(&DocPath::new_unwrap("$.id")))).to(be_equal_to(vec![
+      "Expected the list items to have unique values for '$.id', but the item at index 2 has the same value (1) as the item at index 0".to_string(),
+      "Expected the item at index 3 to have a value for '$.id', but it was missing".to_string()
+    ]));
+    expect!(match_unique_items(&items, Some(&DocPath::new_unwrap("$.name")))).to(be_equal_to(Vec::<String>::new()));
+    expect!(match_unique_items(&items, None)).to(be_equal_to(Vec::<String>::new()));
+  }
+
+  #[test]
+  fn match_sorted_items_by_field() {
+    let items = vec![
+      json!({ "age": 40 }),
+      json!({ "age": 30.5 }),
+      json!({ "age": 30.5 }),
+      json!({ "age": 35 })
+    ];
+    expect!(match_sorted_items(&items, &DocPath::new_unwrap("$.age"), SortOrder::Descending)).to(be_equal_to(vec![
+      "Expected the list items to be sorted by '$.age' in descending order, but the item at index 3 (35) is out of order with the item at index 2 (30.5)".to_string()
+    ]));
+    expect!(match_sorted_items(&items[..3], &DocPath::new_unwrap("$.age"), SortOrder::Descending)).to(be_equal_to(Vec::<String>::new()));
+
+    let mixed = vec![ json!(1), json!("2") ];
+    expect!(match_sorted_items(&mixed, &DocPath::root(), SortOrder::Ascending)).to(be_equal_to(vec![
+      "Expected the list items to be sorted by '$', but the value of the item at index 1 (\"2\") can not be compared with the item at index 0 (1)".to_string()
+    ]));
+  }
 }
@@ -9,7 +9,7 @@ use pact_models::path_exp::DocPath;
 use tracing::debug;
 
 use crate::{matchers, Matches, MatchingContext, merge_result, Mismatch, CommonMismatch};
-use crate::matchingrules::compare_lists_with_matchingrules;
+use crate::matchingrules::compare_resolvable_lists_with_matchingrules;
 
 /// Match the query parameters as Maps
 pub(crate) fn match_query_maps(
@@ -59,7 +59,7 @@ fn match_query_values(
   let path = DocPath::root().join(key);
   if context.matcher_is_defined(&path) {
     debug!("match_query_values: Matcher defined for query parameter '{}", key);
-    compare_lists_with_matchingrules(&path, &context.select_best_matcher(&path), expected, actual, context.clone_with(context.matchers()).as_ref(), &mut |p, expected, actual, context| {
+    compare_resolvable_lists_with_matchingrules(&path, &context.select_best_matcher(&path), expected, actual, context.clone_with(context.matchers()).as_ref(), &mut |p, expected, actual, context| {
       compare_query_parameter_value(p, expected, actual, 0, context)
     })
   } else {
@@ -0,0 +1,524 @@
+//! Functions for comparing two Pacts and reporting the differences between them.
+//!
+//! Interactions are paired using their type, description and provider states (the same values
+//! used when merging Pacts), and then the JSON form of the paired interactions is compared to
+//! find any structural differences.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::panic::RefUnwindSafe;
+
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+use crate::pact::Pact;
+use crate::path_exp::{DocPath, PathToken};
+use crate::v4::interaction::V4Interaction;
+
+/// Interaction attributes that are not compared, as they are derived from the other values
+const IGNORED_INTERACTION_ATTRIBUTES: [&str; 2] = ["key", "_id"];
+
+/// Part of the Pact that a difference was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DiffCategory {
+  /// Pact level attributes (consumer, provider and specification version)
+  Pact,
+  /// Interaction level attributes (i.e. provider states, comments)
+  Interaction,
+  /// HTTP request or synchronous message request
+  Request,
+  /// HTTP response or synchronous message response
+  Response,
+  /// Asynchronous message contents and metadata
+  Message,
+  /// Matching rules
+  MatchingRules,
+  /// Generators
+  Generators
+}
+
+impl Display for DiffCategory {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      DiffCategory::Pact => write!(f, "pact"),
+      DiffCategory::Interaction => write!(f, "interaction"),
+      DiffCategory::Request => write!(f, "request"),
+      DiffCategory::Response => write!(f, "response"),
+      DiffCategory::Message => write!(f, "message"),
+      DiffCategory::MatchingRules => write!(f, "matching rules"),
+      DiffCategory::Generators => write!(f, "generators")
+    }
+  }
+}
+
+/// Type of difference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DifferenceType {
+  /// Value only exists in the new Pact
+  Added,
+  /// Value only exists in the old Pact
+  Removed,
+  /// Value is different between the Pacts
+  Changed
+}
+
+impl Display for DifferenceType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      DifferenceType::Added => write!(f, "added"),
+      DifferenceType::Removed => write!(f, "removed"),
+      DifferenceType::Changed => write!(f, "changed")
+    }
+  }
+}
+
+/// Structural difference between two values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+  /// Part of the Pact the difference is in
+  pub category: DiffCategory,
+  /// Path to the value in the JSON form of the Pact or interaction
+  pub path: DocPath,
+  /// Type of difference
+  pub difference_type: DifferenceType,
+  /// Value from the old Pact
+  pub old_value: Option<Value>,
+  /// Value from the new Pact
+  pub new_value: Option<Value>
+}
+
+impl Difference {
+  /// Converts this difference to a JSON struct
+  pub fn to_json(&self) -> Value {
+    let mut json = json!({
+      "category": self.category.to_string(),
+      "path": self.path.to_string(),
+      "type": self.difference_type.to_string()
+    });
+    if let Some(old_value) = &self.old_value {
+      json["oldValue"] = old_value.clone();
+    }
+    if let Some(new_value) = &self.new_value {
+      json["newValue"] = new_value.clone();
+    }
+    json
+  }
+}
+
+impl Display for Difference {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self.difference_type {
+      DifferenceType::Added => write!(f, "{}: {} was added with {}", self.category, self.path,
+        self.new_value.clone().unwrap_or_default()),
+      DifferenceType::Removed => write!(f, "{}: {} was removed (was {})", self.category, self.path,
+        self.old_value.clone().unwrap_or_default()),
+      DifferenceType::Changed => write!(f, "{}: {} changed from {} to {}", self.category, self.path,
+        self.old_value.clone().unwrap_or_default(), self.new_value.clone().unwrap_or_default())
+    }
+  }
+}
+
+/// Values used to identify an interaction in a Pact
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InteractionSummary {
+  /// Type of interaction
+  pub interaction_type: String,
+  /// Interaction description
+  pub description: String,
+  /// Names of the provider states for the interaction
+  pub provider_states: Vec<String>
+}
+
+impl InteractionSummary {
+  fn from_interaction(interaction: &(dyn V4Interaction + Send + Sync + RefUnwindSafe)) -> Self {
+    InteractionSummary {
+      interaction_type: interaction.v4_type().to_string(),
+      description: interaction.description(),
+      provider_states: interaction.provider_states().iter().map(|ps| ps.name.clone()).collect()
+    }
+  }
+
+  /// Converts this summary to a JSON struct
+  pub fn to_json(&self) -> Value {
+    json!({
+      "type": self.interaction_type,
+      "description": self.description,
+      "providerStates": self.provider_states
+    })
+  }
+}
+
+impl Display for InteractionSummary {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} '{}'", self.interaction_type, self.description)?;
+    if !self.provider_states.is_empty() {
+      write!(f, " (given {})", self.provider_states.iter().join(", "))?;
+    }
+    Ok(())
+  }
+}
+
+/// Differences found for an interaction that is in both Pacts
+#[derive(Debug, Clone, PartialEq)]
+pub struct InteractionDiff {
+  /// Interaction that has changed
+  pub interaction: InteractionSummary,
+  /// Differences between the old and new versions of the interaction
+  pub differences: Vec<Difference>
+}
+
+impl InteractionDiff {
+  /// Converts this interaction diff to a JSON struct
+  pub fn to_json(&self) -> Value {
+    json!({
+      "interaction": self.interaction.to_json(),
+      "differences": self.differences.iter().map(|d| d.to_json()).collect_vec()
+    })
+  }
+}
+
+/// Result of comparing two Pacts
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PactDiff {
+  /// Differences in the Pact level attributes
+  pub pact_differences: Vec<Difference>,
+  /// Interactions that only exist in the new Pact
+  pub added: Vec<InteractionSummary>,
+  /// Interactions that only exist in the old Pact
+  pub removed: Vec<InteractionSummary>,
+  /// Interactions that exist in both Pacts, but are different
+  pub changed: Vec<InteractionDiff>
+}
+
+impl PactDiff {
+  /// If no differences were found
+  pub fn is_empty(&self) -> bool {
+    self.pact_differences.is_empty() && self.added.is_empty() && self.removed.is_empty()
+      && self.changed.is_empty()
+  }
+
+  /// Converts this diff to a JSON struct
+  pub fn to_json(&self) -> Value {
+    json!({
+      "pactDifferences": self.pact_differences.iter().map(|d| d.to_json()).collect_vec(),
+      "added": self.added.iter().map(|i| i.to_json()).collect_vec(),
+      "removed": self.removed.iter().map(|i| i.to_json()).collect_vec(),
+      "changed": self.changed.iter().map(|i| i.to_json()).collect_vec()
+    })
+  }
+}
+
+impl Display for PactDiff {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    if self.is_empty() {
+      return writeln!(f, "No differences found");
+    }
+
+    if !self.pact_differences.is_empty() {
+      writeln!(f, "Pact differences:")?;
+      for difference in &self.pact_differences {
+        writeln!(f, "  {}", difference)?;
+      }
+    }
+    if !self.added.is_empty() {
+      writeln!(f, "Added interactions:")?;
+      for interaction in &self.added {
+        writeln!(f, "  + {}", interaction)?;
+      }
+    }
+    if !self.removed.is_empty() {
+      writeln!(f, "Removed interactions:")?;
+      for interaction in &self.removed {
+        writeln!(f, "  - {}", interaction)?;
+      }
+    }
+    if !self.changed.is_empty() {
+      writeln!(f, "Changed interactions:")?;
+      for interaction in &self.changed {
+        writeln!(f, "  ~ {}", interaction.interaction)?;
+        for difference in &interaction.differences {
+          writeln!(f, "      {}", difference)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Compares the two Pacts, returning the interactions that have been added, removed or changed
+/// in the new Pact. Both Pacts are converted to the V4 format before being compared.
+pub fn diff_pacts(
+  old: &(dyn Pact + Send + Sync + RefUnwindSafe),
+  new: &(dyn Pact + Send + Sync + RefUnwindSafe)
+) -> anyhow::Result<PactDiff> {
+  let old = old.as_v4_pact()?;
+  let new = new.as_v4_pact()?;
+  let mut result = PactDiff::default();
+
+  let old_pact_json = json!({
+    "consumer": old.consumer.name,
+    "provider": old.provider.name,
+    "pactSpecification": old.specification_version().to_string()
+  });
+  let new_pact_json = json!({
+    "consumer": new.consumer.name,
+    "provider": new.provider.name,
+    "pactSpecification": new.specification_version().to_string()
+  });
+  diff_json(&old_pact_json, &new_pact_json, &DocPath::root(), &mut |path, difference_type, old_value, new_value| {
+    result.pact_differences.push(Difference {
+      category: DiffCategory::Pact,
+      path,
+      difference_type,
+      old_value,
+      new_value
+    });
+  });
+
+  let old_interactions = group_interactions(&old.interactions);
+  let mut new_interactions = group_interactions(&new.interactions);
+  let summaries = old_interactions.keys().chain(new_interactions.keys()).cloned().collect::<BTreeSet<_>>();
+  for summary in summaries {
+    let old_values = old_interactions.get(&summary).cloned().unwrap_or_default();
+    let new_values = new_interactions.remove(&summary).unwrap_or_default();
+    for pair in old_values.iter().zip_longest(new_values.iter()) {
+      match pair.left_and_right() {
+        (Some(old), Some(new)) => {
+          let differences = diff_interactions(old, new);
+          if !differences.is_empty() {
+            result.changed.push(InteractionDiff {
+              interaction: summary.clone(),
+              differences
+            });
+          }
+        }
+        (Some(_), None) => result.removed.push(summary.clone()),
+        (None, Some(_)) => result.added.push(summary.clone()),
+        (None, None) => {}
+      }
+    }
+  }
+
+  Ok(result)
+}
+
+fn group_interactions(
+  interactions: &[Box<dyn V4Interaction + Send + Sync + RefUnwindSafe>]
+) -> BTreeMap<InteractionSummary, Vec<Value>> {
+  let mut grouped: BTreeMap<InteractionSummary, Vec<Value>> = BTreeMap::new();
+  for interaction in interactions {
+    let mut json = interaction.to_json();
+    if let Value::Object(attributes) = &mut json {
+      for attribute in IGNORED_INTERACTION_ATTRIBUTES {
+        attributes.remove(attribute);
+      }
+    }
+    grouped.entry(InteractionSummary::from_interaction(interaction.as_ref()))
+      .or_default()
+      .push(json);
+  }
+  grouped
+}
+
+/// Compares the JSON form of two interactions, returning the structural differences
+pub fn diff_interactions(old: &Value, new: &Value) -> Vec<Difference> {
+  let mut differences = vec![];
+  diff_json(old, new, &DocPath::root(), &mut |path, difference_type, old_value, new_value| {
+    differences.push(Difference {
+      category: category_for_path(&path),
+      path,
+      difference_type,
+      old_value,
+      new_value
+    });
+  });
+  differences
+}
+
+fn category_for_path(path: &DocPath) -> DiffCategory {
+  let fields = path.tokens().iter()
+    .filter_map(|token| match token {
+      PathToken::Field(name) => Some(name.as_str()),
+      _ => None
+    })
+    .collect_vec();
+  if fields.contains(&"matchingRules") {
+    DiffCategory::MatchingRules
+  } else if fields.contains(&"generators") {
+    DiffCategory::Generators
+  } else {
+    match fields.first() {
+      Some(&"request") => DiffCategory::Request,
+      Some(&"response") => DiffCategory::Response,
+      Some(&"contents") | Some(&"metadata") => DiffCategory::Message,
+      _ => DiffCategory::Interaction
+    }
+  }
+}
+
+fn diff_json<F>(old: &Value, new: &Value, path: &DocPath, callback: &mut F)
+  where F: FnMut(DocPath, DifferenceType, Option<Value>, Option<Value>) {
+  match (old, new) {
+    (Value::Object(old_map), Value::Object(new_map)) => {
+      let keys = old_map.keys().chain(new_map.keys()).collect::<BTreeSet<_>>();
+      for key in keys {
+        let key_path = path.join_field(key);
+        match (old_map.get(key), new_map.get(key)) {
+          (Some(old_value), Some(new_value)) => diff_json(old_value, new_value, &key_path, callback),
+          (Some(old_value), None) => callback(key_path, DifferenceType::Removed, Some(old_value.clone()), None),
+          (None, Some(new_value)) => callback(key_path, DifferenceType::Added, None, Some(new_value.clone())),
+          (None, None) => {}
+        }
+      }
+    }
+    (Value::Array(old_list), Value::Array(new_list)) => {
+      for (index, pair) in old_list.iter().zip_longest(new_list.iter()).enumerate() {
+        let index_path = path.join_index(index);
+        match pair.left_and_right() {
+          (Some(old_value), Some(new_value)) => diff_json(old_value, new_value, &index_path, callback),
+          (Some(old_value), None) => callback(index_path, DifferenceType::Removed, Some(old_value.clone()), None),
+          (None, Some(new_value)) => callback(index_path, DifferenceType::Added, None, Some(new_value.clone())),
+          (None, None) => {}
+        }
+      }
+    }
+    _ => if old != new {
+      callback(path.clone(), DifferenceType::Changed, Some(old.clone()), Some(new.clone()))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use crate::bodies::OptionalBody;
+  use crate::generators::Generator;
+  use crate::generators;
+  use crate::matchingrules;
+  use crate::matchingrules::MatchingRule;
+  use crate::provider_states::ProviderState;
+  use crate::{Consumer, Provider};
+  use crate::v4::http_parts::{HttpRequest, HttpResponse};
+  use crate::v4::pact::V4Pact;
+  use crate::v4::synch_http::SynchronousHttp;
+
+  use super::*;
+
+  fn interaction(description: &str, path: &str, status: u16) -> SynchronousHttp {
+    SynchronousHttp {
+      description: description.to_string(),
+      provider_states: vec![ProviderState::default("a user exists")],
+      request: HttpRequest {
+        path: path.to_string(),
+        .. HttpRequest::default()
+      },
+      response: HttpResponse {
+        status,
+        body: OptionalBody::from(&json!({ "id": 100, "name": "Mary" })),
+        .. HttpResponse::default()
+      },
+      .. SynchronousHttp::default()
+    }
+  }
+
+  fn pact(interactions: Vec<SynchronousHttp>) -> V4Pact {
+    V4Pact {
+      consumer: Consumer { name: "consumer".to_string() },
+      provider: Provider { name: "provider".to_string() },
+      interactions: interactions.iter().map(|i| i.boxed_v4()).collect(),
+      .. V4Pact::default()
+    }
+  }
+
+  #[test]
+  fn diff_of_the_same_pact_is_empty() {
+    let pact = pact(vec![interaction("a", "/a", 200), interaction("b", "/b", 200)]);
+    let diff = diff_pacts(&pact, &pact).unwrap();
+    expect!(diff.is_empty()).to(be_true());
+    expect!(diff.to_string()).to(be_equal_to("No differences found\n"));
+  }
+
+  #[test]
+  fn returns_added_and_removed_interactions() {
+    let old = pact(vec![interaction("a", "/a", 200), interaction("b", "/b", 200)]);
+    let new = pact(vec![interaction("a", "/a", 200), interaction("c", "/c", 200)]);
+    let diff = diff_pacts(&old, &new).unwrap();
+    expect!(diff.added.iter().map(|i| i.description.as_str()).collect_vec()).to(be_equal_to(vec!["c"]));
+    expect!(diff.removed.iter().map(|i| i.description.as_str()).collect_vec()).to(be_equal_to(vec!["b"]));
+    expect!(diff.changed.is_empty()).to(be_true());
+  }
+
+  #[test]
+  fn returns_the_differences_for_changed_interactions() {
+    let old = pact(vec![interaction("a", "/a", 200)]);
+    let mut changed = interaction("a", "/a/1", 201);
+    changed.response.matching_rules = matchingrules! {
+      "body" => { "$.id" => [ MatchingRule::Integer ] }
+    };
+    changed.response.generators = generators! {
+      "BODY" => { "$.id" => Generator::RandomInt(1, 10) }
+    };
+    changed.response.body = OptionalBody::from(&json!({ "id": 100, "name": "Mary", "age": 21 }));
+    let new = pact(vec![changed]);
+
+    let diff = diff_pacts(&old, &new).unwrap();
+    expect!(diff.added.is_empty()).to(be_true());
+    expect!(diff.removed.is_empty()).to(be_true());
+    expect!(diff.changed.len()).to(be_equal_to(1));
+    let differences = diff.changed[0].differences.iter()
+      .map(|d| (d.category, d.path.to_string(), d.difference_type))
+      .collect_vec();
+    assert_eq!(vec![
+      (DiffCategory::Request, "$.request.path".to_string(), DifferenceType::Changed),
+      (DiffCategory::Response, "$.response.body.content.age".to_string(), DifferenceType::Added),
+      (DiffCategory::Generators, "$.response.generators".to_string(), DifferenceType::Added),
+      (DiffCategory::MatchingRules, "$.response.matchingRules".to_string(), DifferenceType::Added),
+      (DiffCategory::Response, "$.response.status".to_string(), DifferenceType::Changed)
+    ], differences);
+  }
+
+  #[test]
+  fn returns_pact_level_differences() {
+    let old = pact(vec![]);
+    let mut new = pact(vec![]);
+    new.provider.name = "other-provider".to_string();
+    let diff = diff_pacts(&old, &new).unwrap();
+    expect!(diff.pact_differences).to(be_equal_to(vec![
+      Difference {
+        category: DiffCategory::Pact,
+        path: DocPath::new_unwrap("$.provider"),
+        difference_type: DifferenceType::Changed,
+        old_value: Some(json!("provider")),
+        new_value: Some(json!("other-provider"))
+      }
+    ]));
+  }
+
+  #[test]
+  fn diff_to_json() {
+    let old = pact(vec![interaction("a", "/a", 200)]);
+    let new = pact(vec![interaction("a", "/a", 204), interaction("b", "/b", 200)]);
+    let diff = diff_pacts(&old, &new).unwrap();
+    assert_eq!(json!({
+      "pactDifferences": [],
+      "added": [
+        { "type": "Synchronous/HTTP", "description": "b", "providerStates": ["a user exists"] }
+      ],
+      "removed": [],
+      "changed": [
+        {
+          "interaction": { "type": "Synchronous/HTTP", "description": "a", "providerStates": ["a user exists"] },
+          "differences": [
+            { "category": "response", "path": "$.response.status", "type": "changed", "oldValue": 200, "newValue": 204 }
+          ]
+        }
+      ]
+    }), diff.to_json());
+    expect!(diff.to_string()).to(be_equal_to(
+      "Added interactions:\n  + Synchronous/HTTP 'b' (given a user exists)\nChanged interactions:\n  ~ Synchronous/HTTP 'a' (given a user exists)\n      response: $.response.status changed from 200 to 204\n"
+    ));
+  }
+}
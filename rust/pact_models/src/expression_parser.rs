@@ -10,7 +10,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tracing::error;
 
-use crate::json_utils::json_to_string;
+use crate::json_utils::{json_to_string, resolve_path};
+use crate::path_exp::{DocPath, PathToken};
 
 /// Data type to cast to for provider state context values
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
@@ -406,6 +407,100 @@ fn replace_expressions(value: &str, value_resolver: &dyn ValueResolver<Value>) -
   }
 }
 
+/// Value resolver that looks values up from a JSON document. Names are either path expressions
+/// (i.e. `$.items[*].price`), or one of the aggregate functions `sum`, `count`, `min` or `max`
+/// applied to a path expression (i.e. `sum($.items[*].price)`). Path expressions that contain
+/// wildcards resolve to an array of all the matching values.
+#[derive(PartialEq, Debug, Clone)]
+pub struct JsonDocumentResolver<'a> {
+  /// Document to resolve values from
+  pub document: &'a Value
+}
+
+impl <'a> JsonDocumentResolver<'a> {
+  /// Create a resolver for the given JSON document
+  pub fn new(document: &'a Value) -> Self {
+    JsonDocumentResolver { document }
+  }
+
+  fn resolve_path(&self, path: &str) -> Option<Value> {
+    let path = DocPath::new(path).ok()?;
+    if path.is_root() {
+      Some(self.document.clone())
+    } else {
+      let values = resolve_path(self.document, &path).iter()
+        .filter_map(|pointer| self.document.pointer(pointer.as_str()).cloned())
+        .collect_vec();
+      if path.tokens().iter().any(|token| *token == PathToken::Star || *token == PathToken::StarIndex) {
+        Some(Value::Array(values))
+      } else {
+        values.first().cloned()
+      }
+    }
+  }
+
+  fn apply_function(&self, function: &str, path: &str) -> Option<Value> {
+    let values = match self.resolve_path(path)? {
+      Value::Array(values) => values,
+      value => vec![value]
+    };
+    if function == "count" {
+      return Some(json!(values.len()));
+    }
+
+    let numbers = values.iter()
+      .map(|value| match value {
+        Value::Number(_) => f64::try_from(DataValue { wrapped: value.clone(), data_type: DataType::DECIMAL }).ok(),
+        _ => None
+      })
+      .collect::<Option<Vec<f64>>>()?;
+    let integers = values.iter().map(|value| value.as_i64()).collect::<Option<Vec<i64>>>();
+    match function {
+      "sum" => match integers {
+        Some(integers) => Some(json!(integers.iter().sum::<i64>())),
+        None => Some(json!(numbers.iter().sum::<f64>()))
+      },
+      "min" => match integers {
+        Some(integers) => integers.iter().min().map(|n| json!(n)),
+        None => numbers.iter().cloned().reduce(f64::min).map(|n| json!(n))
+      },
+      "max" => match integers {
+        Some(integers) => integers.iter().max().map(|n| json!(n)),
+        None => numbers.iter().cloned().reduce(f64::max).map(|n| json!(n))
+      },
+      _ => None
+    }
+  }
+}
+
+impl ValueResolver<Value> for JsonDocumentResolver<'_> {
+  fn resolve_value(&self, name: &str) -> Option<Value> {
+    let name = name.trim();
+    if let Some(start) = name.find('(') {
+      if name.ends_with(')') {
+        self.apply_function(name[0..start].trim(), name[(start + 1)..(name.len() - 1)].trim())
+      } else {
+        None
+      }
+    } else {
+      self.resolve_path(name)
+    }
+  }
+}
+
+/// Evaluates the expression against the JSON document, returning the resolved value. The expression
+/// can either be a single value (i.e. `$.password` or `sum($.items[*].price)`), or a string containing
+/// `${...}` expressions.
+pub fn evaluate_document_expression(expression: &str, document: &Value) -> anyhow::Result<Value> {
+  let resolver = JsonDocumentResolver::new(document);
+  if contains_expressions(expression) {
+    parse_expression(expression, &resolver)
+  } else {
+    resolver.resolve_value(expression)
+      .ok_or_else(|| anyhow!("No value for '{}' found", expression.trim()))
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use expectest::prelude::*;
@@ -559,4 +654,51 @@ mod tests {
     expect!(DataValue { wrapped: json!("true"), data_type: DataType::BOOLEAN }.as_json()).to(be_ok().value(json!(true)));
     expect!(DataValue { wrapped: json!(true), data_type: DataType::BOOLEAN }.as_json()).to(be_ok().value(json!(true)));
   }
+
+  #[test]
+  fn json_document_resolver_test() {
+    let document = json!({
+      "password": "secret",
+      "confirmPassword": "secret",
+      "total": 35,
+      "items": [
+        { "id": 1, "price": 10 },
+        { "id": 2, "price": 25 }
+      ],
+      "discounts": [ 1.5, 2.25 ]
+    });
+    let resolver = JsonDocumentResolver::new(&document);
+
+    expect!(resolver.resolve_value("$.password")).to(be_some().value(json!("secret")));
+    expect!(resolver.resolve_value("$.items[1].price")).to(be_some().value(json!(25)));
+    expect!(resolver.resolve_value("$.items[*].price")).to(be_some().value(json!([10, 25])));
+    expect!(resolver.resolve_value("$")).to(be_some().value(document.clone()));
+    expect!(resolver.resolve_value("$.missing")).to(be_none());
+    expect!(resolver.resolve_value("sum($.items[*].price)")).to(be_some().value(json!(35)));
+    expect!(resolver.resolve_value("sum($.discounts)")).to(be_some().value(json!(3.75)));
+    expect!(resolver.resolve_value(" count( $.items ) ")).to(be_some().value(json!(2)));
+    expect!(resolver.resolve_value("min($.items[*].price)")).to(be_some().value(json!(10)));
+    expect!(resolver.resolve_value("max($.discounts)")).to(be_some().value(json!(2.25)));
+    expect!(resolver.resolve_value("sum($.password)")).to(be_none());
+    expect!(resolver.resolve_value("average($.items[*].price)")).to(be_none());
+  }
+
+  #[test]
+  fn evaluate_document_expression_test() {
+    let document = json!({
+      "firstName": "Mary",
+      "lastName": "Smith",
+      "items": [
+        { "price": 10 },
+        { "price": 25 }
+      ]
+    });
+
+    expect!(evaluate_document_expression("sum($.items[*].price)", &document)).to(be_ok().value(json!(35)));
+    expect!(evaluate_document_expression("${sum($.items[*].price)}", &document)).to(be_ok().value(json!(35)));
+    expect!(evaluate_document_expression("$.firstName", &document)).to(be_ok().value(json!("Mary")));
+    expect!(evaluate_document_expression("${$.firstName} ${$.lastName}", &document)).to(be_ok().value(json!("Mary Smith")));
+    expect!(evaluate_document_expression("$.middleName", &document)).to(be_err());
+    expect!(evaluate_document_expression("${$.middleName}", &document)).to(be_err());
+  }
 }
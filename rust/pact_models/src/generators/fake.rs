@@ -0,0 +1,214 @@
+//! Support for generating fake (but realistic looking) data. The data is not localised, and is
+//! selected from a small built-in set of values.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const FIRST_NAMES: &[&str] = &[
+  "James", "Mary", "John", "Patricia", "Robert", "Jennifer", "Michael", "Linda", "William",
+  "Elizabeth", "David", "Barbara", "Richard", "Susan", "Joseph", "Jessica", "Thomas", "Sarah",
+  "Charles", "Karen", "Daniel", "Nancy", "Matthew", "Lisa", "Anthony", "Betty", "Mark", "Sandra",
+  "Steven", "Ashley", "Paul", "Emily", "Andrew", "Michelle", "Joshua", "Amanda", "Kevin", "Melissa"
+];
+
+const LAST_NAMES: &[&str] = &[
+  "Smith", "Johnson", "Williams", "Brown", "Jones", "Garcia", "Miller", "Davis", "Rodriguez",
+  "Martinez", "Hernandez", "Lopez", "Wilson", "Anderson", "Thomas", "Taylor", "Moore", "Jackson",
+  "Martin", "Lee", "Thompson", "White", "Harris", "Clark", "Lewis", "Robinson", "Walker", "Young",
+  "Allen", "King", "Wright", "Scott", "Green", "Baker", "Adams", "Nelson", "Hill", "Campbell"
+];
+
+const STREET_NAMES: &[&str] = &[
+  "Main", "High", "Park", "Oak", "Pine", "Maple", "Cedar", "Elm", "Church", "Station", "Mill",
+  "Victoria", "Lake", "Hill", "Washington", "King", "Queen", "River", "Bridge", "Market"
+];
+
+const STREET_SUFFIXES: &[&str] = &[
+  "Street", "Road", "Avenue", "Lane", "Drive", "Way", "Place", "Court", "Terrace", "Boulevard"
+];
+
+const CITIES: &[&str] = &[
+  "Springfield", "Riverside", "Fairview", "Franklin", "Greenville", "Bristol", "Clinton",
+  "Georgetown", "Salem", "Madison", "Arlington", "Ashland", "Burlington", "Manchester", "Oxford",
+  "Milton", "Newport", "Dover", "Hudson", "Kingston"
+];
+
+const COUNTRIES: &[&str] = &[
+  "Australia", "Brazil", "Canada", "Denmark", "Egypt", "France", "Germany", "India", "Ireland",
+  "Italy", "Japan", "Kenya", "Mexico", "Netherlands", "New Zealand", "Norway", "Portugal",
+  "South Africa", "Spain", "Sweden", "United Kingdom", "United States"
+];
+
+const COMPANY_SUFFIXES: &[&str] = &[
+  "Inc", "LLC", "Ltd", "Group", "and Sons", "Partners", "Holdings", "Industries"
+];
+
+const EMAIL_DOMAINS: &[&str] = &[
+  "example.com", "example.org", "example.net"
+];
+
+const WORDS: &[&str] = &[
+  "alpha", "bravo", "cloud", "delta", "echo", "forest", "garden", "harbor", "island", "jungle",
+  "kettle", "lemon", "meadow", "nectar", "ocean", "pepper", "quartz", "river", "summit", "timber",
+  "umbrella", "valley", "willow", "yellow", "zephyr"
+];
+
+/// Kind of fake data to generate
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FakeKind {
+  /// First (given) name (e.g. Mary)
+  FirstName,
+  /// Last (family) name (e.g. Smith)
+  LastName,
+  /// First and last name (e.g. Mary Smith)
+  FullName,
+  /// Email address (e.g. mary.smith@example.com)
+  Email,
+  /// Username (e.g. mary.smith42)
+  Username,
+  /// Phone number (e.g. 555-123-4567)
+  PhoneNumber,
+  /// Street address (e.g. 123 Main Street)
+  StreetAddress,
+  /// Name of a city (e.g. Springfield)
+  City,
+  /// Numeric postal code (e.g. 12345)
+  PostCode,
+  /// Name of a country (e.g. Canada)
+  Country,
+  /// Company name (e.g. Smith and Sons)
+  CompanyName,
+  /// Single word (e.g. river)
+  Word,
+  /// Sentence of between 4 and 10 words (e.g. Ocean river summit alpha.)
+  Sentence
+}
+
+impl FakeKind {
+  /// Generates a fake value using the provided random number generator
+  pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+    match self {
+      FakeKind::FirstName => choose(FIRST_NAMES, rng).to_string(),
+      FakeKind::LastName => choose(LAST_NAMES, rng).to_string(),
+      FakeKind::FullName => format!("{} {}", choose(FIRST_NAMES, rng), choose(LAST_NAMES, rng)),
+      FakeKind::Email => format!("{}.{}@{}", choose(FIRST_NAMES, rng).to_lowercase(),
+        choose(LAST_NAMES, rng).to_lowercase(), choose(EMAIL_DOMAINS, rng)),
+      FakeKind::Username => format!("{}.{}{}", choose(FIRST_NAMES, rng).to_lowercase(),
+        choose(LAST_NAMES, rng).to_lowercase(), rng.gen_range(1..100)),
+      FakeKind::PhoneNumber => format!("555-{:03}-{:04}", rng.gen_range(100..1000), rng.gen_range(0..10000)),
+      FakeKind::StreetAddress => format!("{} {} {}", rng.gen_range(1..1000), choose(STREET_NAMES, rng),
+        choose(STREET_SUFFIXES, rng)),
+      FakeKind::City => choose(CITIES, rng).to_string(),
+      FakeKind::PostCode => format!("{:05}", rng.gen_range(10000..100000)),
+      FakeKind::Country => choose(COUNTRIES, rng).to_string(),
+      FakeKind::CompanyName => format!("{} {}", choose(LAST_NAMES, rng), choose(COMPANY_SUFFIXES, rng)),
+      FakeKind::Word => choose(WORDS, rng).to_string(),
+      FakeKind::Sentence => {
+        let count = rng.gen_range(4..=10);
+        let words = (0..count).map(|_| choose(WORDS, rng)).collect::<Vec<_>>().join(" ");
+        let mut chars = words.chars();
+        match chars.next() {
+          Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+          None => String::default()
+        }
+      }
+    }
+  }
+}
+
+fn choose<'a, R: Rng + ?Sized>(values: &[&'a str], rng: &mut R) -> &'a str {
+  values.choose(rng).copied().unwrap_or_default()
+}
+
+impl Display for FakeKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      FakeKind::FirstName => write!(f, "firstName"),
+      FakeKind::LastName => write!(f, "lastName"),
+      FakeKind::FullName => write!(f, "fullName"),
+      FakeKind::Email => write!(f, "email"),
+      FakeKind::Username => write!(f, "username"),
+      FakeKind::PhoneNumber => write!(f, "phoneNumber"),
+      FakeKind::StreetAddress => write!(f, "streetAddress"),
+      FakeKind::City => write!(f, "city"),
+      FakeKind::PostCode => write!(f, "postCode"),
+      FakeKind::Country => write!(f, "country"),
+      FakeKind::CompanyName => write!(f, "companyName"),
+      FakeKind::Word => write!(f, "word"),
+      FakeKind::Sentence => write!(f, "sentence")
+    }
+  }
+}
+
+impl FromStr for FakeKind {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "firstName" => Ok(FakeKind::FirstName),
+      "lastName" => Ok(FakeKind::LastName),
+      "fullName" => Ok(FakeKind::FullName),
+      "email" => Ok(FakeKind::Email),
+      "username" => Ok(FakeKind::Username),
+      "phoneNumber" => Ok(FakeKind::PhoneNumber),
+      "streetAddress" => Ok(FakeKind::StreetAddress),
+      "city" => Ok(FakeKind::City),
+      "postCode" => Ok(FakeKind::PostCode),
+      "country" => Ok(FakeKind::Country),
+      "companyName" => Ok(FakeKind::CompanyName),
+      "word" => Ok(FakeKind::Word),
+      "sentence" => Ok(FakeKind::Sentence),
+      _ => Err(anyhow!("'{}' is not a valid kind of fake data", s))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use hamcrest2::*;
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(FakeKind::FirstName, r"^[A-Z][a-z]+$")]
+  #[case(FakeKind::LastName, r"^[A-Z][a-z]+$")]
+  #[case(FakeKind::FullName, r"^[A-Z][a-z]+ [A-Z][a-z]+$")]
+  #[case(FakeKind::Email, r"^[a-z]+\.[a-z]+@example\.(com|org|net)$")]
+  #[case(FakeKind::Username, r"^[a-z]+\.[a-z]+\d{1,2}$")]
+  #[case(FakeKind::PhoneNumber, r"^555-\d{3}-\d{4}$")]
+  #[case(FakeKind::StreetAddress, r"^\d{1,3} [A-Za-z]+ [A-Za-z]+$")]
+  #[case(FakeKind::City, r"^[A-Z][a-z]+$")]
+  #[case(FakeKind::PostCode, r"^\d{5}$")]
+  #[case(FakeKind::Country, r"^[A-Z][A-Za-z ]+$")]
+  #[case(FakeKind::CompanyName, r"^[A-Z][a-z]+ [A-Za-z ]+$")]
+  #[case(FakeKind::Word, r"^[a-z]+$")]
+  #[case(FakeKind::Sentence, r"^[A-Z][a-z]*( [a-z]+){3,9}\.$")]
+  fn generate_test(#[case] kind: FakeKind, #[case] regex: &str) {
+    let mut rng = StdRng::seed_from_u64(100);
+    for _ in 0..20 {
+      assert_that!(kind.generate(&mut rng), matches_regex(regex));
+    }
+  }
+
+  #[rstest]
+  #[case(FakeKind::FirstName)]
+  #[case(FakeKind::Email)]
+  #[case(FakeKind::PostCode)]
+  #[case(FakeKind::Sentence)]
+  fn kind_round_trips_as_a_string(#[case] kind: FakeKind) {
+    expect!(FakeKind::from_str(kind.to_string().as_str()).unwrap()).to(be_equal_to(kind));
+  }
+
+  #[test]
+  fn from_str_with_invalid_kind() {
+    expect!(FakeKind::from_str("shoeSize")).to(be_err());
+  }
+}
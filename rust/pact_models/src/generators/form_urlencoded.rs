@@ -5,7 +5,15 @@ use serde_json::Value;
 use tracing::debug;
 use anyhow::{anyhow, Result};
 
-use crate::generators::{ContentTypeHandler, Generator, GeneratorTestMode, VariantMatcher, GenerateValue};
+use crate::generators::{
+  context_for_path,
+  ContentTypeHandler,
+  GenerateValue,
+  Generator,
+  GeneratorCategory,
+  GeneratorTestMode,
+  VariantMatcher
+};
 use crate::path_exp::DocPath;
 use crate::bodies::OptionalBody;
 
@@ -49,7 +57,9 @@ impl ContentTypeHandler<String> for FormUrlEncodedHandler {
     for (param_key, param_value) in self.params.iter_mut() {
       let index = map.entry(param_key.clone()).or_insert(0);
       if key.eq(&DocPath::root().join(param_key.clone())) || key.eq(&DocPath::root().join(param_key.clone()).join_index(*index)) {
-        return match generator.generate_value(&param_value, context, matcher) {
+        let path = format!("{}[{}]", param_key, index);
+        let context = context_for_path(context, &GeneratorCategory::BODY, path.as_str());
+        return match generator.generate_value(&param_value, &context, matcher) {
           Ok(new_value) => *param_value = new_value,
           Err(_) => ()
         }
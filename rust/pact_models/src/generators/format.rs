@@ -0,0 +1,77 @@
+//! Support for generating example values for well-known string formats (see the Format matching rule)
+
+use rand::prelude::*;
+use uuid::Builder;
+
+use crate::matchingrules::FormatType;
+
+const CROCKFORD_BASE32: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+const USER_NAMES: &[&str] = &[
+  "alice", "bob", "carol", "dave", "erin", "frank", "grace", "heidi", "ivan", "judy"
+];
+
+const HOST_LABELS: &[&str] = &[
+  "api", "www", "app", "auth", "mail", "static", "cdn", "service"
+];
+
+const EXAMPLE_DOMAINS: &[&str] = &[
+  "example.com", "example.org", "example.net"
+];
+
+/// Generates a random example value that is valid for the given format
+pub fn generate_format_value<R: Rng + ?Sized>(format: &FormatType, rng: &mut R) -> String {
+  match format {
+    FormatType::Email => format!("{}{}@{}", choose(USER_NAMES, rng), rng.gen_range(1..1000),
+      choose(EXAMPLE_DOMAINS, rng)),
+    FormatType::Uri => format!("https://{}/{}/{}", choose(EXAMPLE_DOMAINS, rng), choose(HOST_LABELS, rng),
+      rng.gen_range(1..100000)),
+    FormatType::Ipv4 => format!("{}.{}.{}.{}", rng.gen_range(1..=223), rng.gen::<u8>(), rng.gen::<u8>(),
+      rng.gen_range(1..=254)),
+    FormatType::Ipv6 => {
+      let mut segments = vec!["2001".to_string(), "db8".to_string()];
+      segments.extend((0..6).map(|_| format!("{:x}", rng.gen::<u16>())));
+      segments.join(":")
+    }
+    FormatType::Uuid => Builder::from_random_bytes(rng.gen()).into_uuid().to_string(),
+    FormatType::Ulid => {
+      // 128 bits encoded as 26 base32 characters, so the first character is always 0-7
+      let value: u128 = rng.gen();
+      (0..26).rev()
+        .map(|i| CROCKFORD_BASE32[((value >> (i * 5)) & 0x1F) as usize] as char)
+        .collect()
+    }
+    FormatType::Hostname => format!("{}{}.{}", choose(HOST_LABELS, rng), rng.gen_range(1..100),
+      choose(EXAMPLE_DOMAINS, rng))
+  }
+}
+
+fn choose<'a, R: Rng + ?Sized>(values: &[&'a str], rng: &mut R) -> &'a str {
+  values.choose(rng).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(FormatType::Email)]
+  #[case(FormatType::Uri)]
+  #[case(FormatType::Ipv4)]
+  #[case(FormatType::Ipv6)]
+  #[case(FormatType::Uuid)]
+  #[case(FormatType::Ulid)]
+  #[case(FormatType::Hostname)]
+  fn generate_format_value_test(#[case] format: FormatType) {
+    let mut rng = StdRng::seed_from_u64(100);
+    for _ in 0..20 {
+      let value = generate_format_value(&format, &mut rng);
+      expect!(format.validate(value.as_str())).to(be_ok());
+    }
+  }
+}
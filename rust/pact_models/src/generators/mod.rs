@@ -1,11 +1,14 @@
 //! `generators` module includes all the classes to deal with V3/V4 spec generators
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
 
 use anyhow::anyhow;
 #[cfg(feature = "datetime")] use chrono::{DateTime, Local};
@@ -25,7 +28,7 @@ use crate::bodies::OptionalBody;
 use crate::expression_parser::{contains_expressions, DataType, DataValue, MapValueResolver, parse_expression};
 #[cfg(feature = "datetime")] use crate::generators::datetime_expressions::{execute_date_expression, execute_datetime_expression, execute_time_expression};
 use crate::json_utils::{get_field_as_string, json_to_string, JsonToNum, resolve_path};
-use crate::matchingrules::{Category, MatchingRuleCategory};
+use crate::matchingrules::{Category, FormatType, MatchingRuleCategory};
 use crate::PactSpecification;
 use crate::path_exp::DocPath;
 #[cfg(feature = "datetime")] use crate::time_utils::{parse_pattern, to_chrono_pattern};
@@ -34,6 +37,11 @@ use crate::path_exp::DocPath;
 #[cfg(feature = "datetime")] mod date_expression_parser;
 #[cfg(feature = "datetime")] mod time_expression_parser;
 #[cfg(feature = "form_urlencoded")] pub mod form_urlencoded;
+pub mod fake;
+pub mod format;
+pub mod template;
+
+pub use fake::FakeKind;
 
 /// Trait to represent matching logic to find a matching variant for the Array Contains generator
 pub trait VariantMatcher: Debug {
@@ -115,6 +123,34 @@ impl FromStr for UuidFormat {
   }
 }
 
+/// Counter for the values generated by a sequence generator. Clones of a generator share the same
+/// counter, so the values continue to increase for every request or response generated.
+#[derive(Clone, Default)]
+pub struct SequenceCounter(Arc<AtomicI64>);
+
+impl SequenceCounter {
+  /// Returns the next value in the sequence
+  pub fn next_value(&self, start: i64) -> i64 {
+    start.saturating_add(self.0.fetch_add(1, Ordering::SeqCst))
+  }
+}
+
+impl Debug for SequenceCounter {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "SequenceCounter")
+  }
+}
+
+/// Counters are equal if they have the same current value. Note that the equality of sequence
+/// generators ignores the counter, as the current value is not part of the generator definition.
+impl PartialEq for SequenceCounter {
+  fn eq(&self, other: &Self) -> bool {
+    self.0.load(Ordering::SeqCst) == other.0.load(Ordering::SeqCst)
+  }
+}
+
+impl Eq for SequenceCounter {}
+
 /// Trait to represent a generator
 #[derive(Debug, Clone, Eq)]
 pub enum Generator {
@@ -143,7 +179,16 @@ pub enum Generator {
   /// Generates a URL with the mock server as the base URL
   MockServerURL(String, String),
   /// List of variants which can have embedded generators
-  ArrayContains(Vec<(usize, MatchingRuleCategory, HashMap<DocPath, Generator>)>)
+  ArrayContains(Vec<(usize, MatchingRuleCategory, HashMap<DocPath, Generator>)>),
+  /// Generates a sequence of increasing integers, starting from the given value
+  Sequence(i64, SequenceCounter),
+  /// Generates fake (but realistic looking) data of the given kind
+  Fake(FakeKind),
+  /// Generates a string from a template, where any `{name}` placeholders are replaced with values
+  /// from the named generators and `${expression}` placeholders with values from the provider state
+  Template(String, BTreeMap<String, Generator>),
+  /// Generates an example value of the given well-known format (email, URI, IP address, etc.)
+  Format(FormatType)
 }
 
 impl Generator {
@@ -193,10 +238,25 @@ impl Generator {
         }
       }
       Generator::MockServerURL(example, regex) => Some(json!({ "type": "MockServerURL", "example": example, "regex": regex })),
+      Generator::Sequence(start, _) => Some(json!({ "type": "Sequence", "start": start })),
+      Generator::Fake(kind) => Some(json!({ "type": "Fake", "kind": kind.to_string() })),
+      Generator::Format(format) => Some(json!({ "type": "Format", "format": format.to_string() })),
+      Generator::Template(template, generators) => Some(json!({
+        "type": "Template",
+        "template": template,
+        "generators": generators.iter()
+          .filter_map(|(name, generator)| generator.to_json().map(|json| (name.clone(), json)))
+          .collect::<serde_json::Map<_, _>>()
+      })),
       _ => None
     }
   }
 
+  /// Creates a sequence generator starting from the given value
+  pub fn sequence(start: i64) -> Generator {
+    Generator::Sequence(start, SequenceCounter::default())
+  }
+
   /// Converts a JSON map into a `Generator` struct, returning `None` if it can not be converted.
   pub fn from_map(gen_type: &str, map: &serde_json::Map<String, Value>) -> Option<Generator> {
     match gen_type {
@@ -223,6 +283,25 @@ impl Generator {
           .map(|dt| DataType::from(dt.clone())))),
       "MockServerURL" => Some(Generator::MockServerURL(get_field_as_string("example", map).unwrap_or_default(),
                                                        get_field_as_string("regex", map).unwrap_or_default())),
+      "Sequence" => Some(Generator::sequence(map.get("start").and_then(|start| start.as_i64()).unwrap_or(1))),
+      "Fake" => map.get("kind").and_then(|kind| FakeKind::from_str(json_to_string(kind).as_str()).ok())
+        .map(Generator::Fake),
+      "Format" => map.get("format").and_then(|format| FormatType::from_str(json_to_string(format).as_str()).ok())
+        .map(Generator::Format),
+      "Template" => map.get("template").map(|template| {
+        let generators = match map.get("generators") {
+          Some(Value::Object(generators)) => generators.iter()
+            .filter_map(|(name, generator)| match generator {
+              Value::Object(attributes) => attributes.get("type")
+                .and_then(|gen_type| Generator::from_map(json_to_string(gen_type).as_str(), attributes))
+                .map(|generator| (name.clone(), generator)),
+              _ => None
+            })
+            .collect(),
+          _ => BTreeMap::default()
+        };
+        Generator::Template(json_to_string(template), generators)
+      }),
       _ => {
         warn!("'{}' is not a valid generator type", gen_type);
         None
@@ -235,6 +314,8 @@ impl Generator {
     match self {
       Generator::ProviderStateGenerator(_, _) => mode == &GeneratorTestMode::Provider,
       Generator::MockServerURL(_, _) => mode == &GeneratorTestMode::Consumer,
+      Generator::Template(template, generators) => (!contains_expressions(template) || mode == &GeneratorTestMode::Provider)
+        && generators.values().all(|generator| generator.corresponds_to_mode(mode)),
       _ => true
     }
   }
@@ -255,6 +336,10 @@ impl Generator {
       Generator::ProviderStateGenerator(_, _) => "ProviderState",
       Generator::MockServerURL(_, _) => "MockServerURL",
       Generator::ArrayContains(_) => "ArrayContains",
+      Generator::Sequence(_, _) => "Sequence",
+      Generator::Fake(_) => "Fake",
+      Generator::Template(_, _) => "Template",
+      Generator::Format(_) => "Format"
     }.to_string()
   }
 
@@ -311,6 +396,15 @@ impl Generator {
             (key.to_string(), g.to_json().unwrap())
           }).collect())])
         }).collect()
+      },
+      Generator::Sequence(start, _) => hashmap!{ "start" => json!(start) },
+      Generator::Fake(kind) => hashmap!{ "kind" => Value::String(kind.to_string()) },
+      Generator::Format(format) => hashmap!{ "format" => Value::String(format.to_string()) },
+      Generator::Template(template, generators) => hashmap!{
+        "template" => Value::String(template.clone()),
+        "generators" => Value::Object(generators.iter()
+          .filter_map(|(name, generator)| generator.to_json().map(|json| (name.clone(), json)))
+          .collect())
       }
     }
   }
@@ -368,6 +462,16 @@ impl Hash for Generator {
         }
       }
       Generator::Uuid(format) => format.hash(state),
+      Generator::Sequence(start, _) => start.hash(state),
+      Generator::Fake(kind) => kind.hash(state),
+      Generator::Format(format) => format.hash(state),
+      Generator::Template(template, generators) => {
+        template.hash(state);
+        for (name, generator) in generators {
+          name.hash(state);
+          generator.hash(state);
+        }
+      }
       _ => ()
     }
   }
@@ -388,6 +492,11 @@ impl PartialEq for Generator {
       (Generator::MockServerURL(ex1, re1), Generator::MockServerURL(ex2, re2)) => ex1 == ex2 && re1 == re2,
       (Generator::ArrayContains(variants1), Generator::ArrayContains(variants2)) => variants1 == variants2,
       (Generator::Uuid(format), Generator::Uuid(format2)) => format == format2,
+      (Generator::Sequence(start1, _), Generator::Sequence(start2, _)) => start1 == start2,
+      (Generator::Fake(kind1), Generator::Fake(kind2)) => kind1 == kind2,
+      (Generator::Format(format1), Generator::Format(format2)) => format1 == format2,
+      (Generator::Template(template1, generators1), Generator::Template(template2, generators2)) =>
+        template1 == template2 && generators1 == generators2,
       _ => mem::discriminant(self) == mem::discriminant(other)
     }
   }
@@ -782,22 +891,106 @@ pub fn generate_value_from_context(expression: &str, context: &HashMap<&str, Val
   data_type.clone().unwrap_or(DataType::RAW).wrap(result)
 }
 
+/// Key in the generator context that can be used to provide a seed for the random generators
+pub const GENERATOR_SEED_KEY: &str = "generatorSeed";
+/// Environment variable that can be used to provide a seed for the random generators if one is
+/// not provided in the generator context
+pub const GENERATOR_SEED_ENV_VAR: &str = "PACT_GENERATOR_SEED";
+
+/// Key in the generator context with the location (category and path) of the value being
+/// generated. This is mixed into the seed, so values at different locations are not the same.
+pub const GENERATOR_PATH_KEY: &str = "generatorPath";
+
+/// Returns the seed to use for the random generators. This is taken from the `generatorSeed` value
+/// in the generator context, falling back to the `PACT_GENERATOR_SEED` environment variable.
+pub fn generator_seed(context: &HashMap<&str, Value>) -> Option<u64> {
+  context.get(GENERATOR_SEED_KEY)
+    .and_then(|seed| match seed {
+      Value::Number(n) => n.as_u64(),
+      Value::String(s) => s.trim().parse().ok(),
+      _ => None
+    })
+    .or_else(|| std::env::var(GENERATOR_SEED_ENV_VAR).ok()
+      .and_then(|seed| seed.trim().parse().ok()))
+}
+
+/// Returns a copy of the generator context with the location of the value being generated added,
+/// if a seed has been configured. Otherwise, the context is returned as is.
+pub fn context_for_path<'a, 'b>(
+  context: &'b HashMap<&'a str, Value>,
+  category: &GeneratorCategory,
+  path: &str
+) -> Cow<'b, HashMap<&'a str, Value>> {
+  if generator_seed(context).is_some() {
+    let mut context = context.clone();
+    let category: &str = category.clone().into();
+    context.insert(GENERATOR_PATH_KEY, Value::String(format!("{}:{}", category, path)));
+    Cow::Owned(context)
+  } else {
+    Cow::Borrowed(context)
+  }
+}
+
+/// Returns the random number generator to use for generating a value to replace the given one.
+/// If a seed has been configured (see `generator_seed`), the generator is seeded from the seed
+/// combined with the generator, the location of the value (see `context_for_path`) and the value
+/// being replaced, so that the same value is generated on every run. Otherwise, the thread random
+/// number generator is used.
+pub fn generator_rng<V: Display + ?Sized>(
+  generator: &Generator,
+  value: &V,
+  context: &HashMap<&str, Value>
+) -> Box<dyn RngCore> {
+  match generator_seed(context) {
+    Some(seed) => {
+      // FNV-1a, so the derived seed does not depend on the Rust version
+      let path = context.get(GENERATOR_PATH_KEY).map(json_to_string).unwrap_or_default();
+      let key = format!("{:?}:{}:{}", generator, path, value);
+      let derived_seed = key.bytes().fold(seed ^ 0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+      });
+      trace!(seed, derived_seed, "Using seeded random number generator for {:?}", generator);
+      Box::new(StdRng::seed_from_u64(derived_seed))
+    }
+    None => Box::new(thread_rng())
+  }
+}
+
+fn generate_uuid<R: Rng + ?Sized>(rng: &mut R) -> Uuid {
+  uuid::Builder::from_random_bytes(rng.r#gen()).into_uuid()
+}
+
+fn generate_uuid_string<R: Rng + ?Sized>(format: &Option<UuidFormat>, rng: &mut R) -> String {
+  let uuid = generate_uuid(rng);
+  match format.unwrap_or_default() {
+    UuidFormat::Simple => uuid.as_simple().to_string(),
+    UuidFormat::LowerCaseHyphenated => uuid.as_hyphenated().to_string(),
+    UuidFormat::UpperCaseHyphenated => uuid.as_hyphenated().to_string().to_uppercase(),
+    UuidFormat::Urn => uuid.as_urn().to_string()
+  }
+}
+
 const DIGIT_CHARSET: &str = "0123456789";
 pub fn generate_decimal(digits: usize) -> String {
-  let mut rnd = rand::thread_rng();
+  generate_decimal_with_rng(digits, &mut rand::thread_rng())
+}
+
+/// Generates a random decimal value with the given number of digits using the provided random
+/// number generator
+pub fn generate_decimal_with_rng<R: Rng + ?Sized>(digits: usize, rnd: &mut R) -> String {
   let chars: Vec<char> = DIGIT_CHARSET.chars().collect();
   match digits {
     0 => "".to_string(),
-    1 => chars.choose(&mut rnd).unwrap().to_string(),
-    2 => format!("{}.{}", chars.choose(&mut rnd).unwrap(), chars.choose(&mut rnd).unwrap()),
+    1 => chars.choose(rnd).unwrap().to_string(),
+    2 => format!("{}.{}", chars.choose(rnd).unwrap(), chars.choose(rnd).unwrap()),
     _ => {
       let mut sample = String::new();
       for _ in 0..(digits + 1) {
-        sample.push(*chars.choose(&mut rnd).unwrap());
+        sample.push(*chars.choose(rnd).unwrap());
       }
       if sample.starts_with("00") {
         let chars = DIGIT_CHARSET[1..].chars();
-        sample.insert(0, chars.choose(&mut rnd).unwrap());
+        sample.insert(0, chars.choose(rnd).unwrap());
       }
       let pos = rnd.gen_range(1..digits - 1);
       let selected_digits = if pos != 1 && sample.starts_with('0') {
@@ -815,8 +1008,13 @@ pub fn generate_decimal(digits: usize) -> String {
 
 const HEX_CHARSET: &str = "0123456789ABCDEF";
 pub fn generate_hexadecimal(digits: usize) -> String {
-  let mut rnd = rand::thread_rng();
-  HEX_CHARSET.chars().choose_multiple(&mut rnd, digits).iter().join("")
+  generate_hexadecimal_with_rng(digits, &mut rand::thread_rng())
+}
+
+/// Generates a random hexadecimal value with the given number of digits using the provided random
+/// number generator
+pub fn generate_hexadecimal_with_rng<R: Rng + ?Sized>(digits: usize, rnd: &mut R) -> String {
+  HEX_CHARSET.chars().choose_multiple(rnd, digits).iter().join("")
 }
 
 impl GenerateValue<u16> for Generator {
@@ -827,7 +1025,7 @@ impl GenerateValue<u16> for Generator {
     _matcher: &Box<dyn VariantMatcher + Send + Sync>
   ) -> anyhow::Result<u16> {
     match self {
-      &Generator::RandomInt(min, max) => Ok(rand::thread_rng().gen_range(min as u16..(max as u16).saturating_add(1))),
+      &Generator::RandomInt(min, max) => Ok(generator_rng(self, value, context).gen_range(min as u16..(max as u16).saturating_add(1))),
       &Generator::ProviderStateGenerator(ref exp, ref dt) => {
         // Provider state values may come under a "providerState" key
         let provider_state_config = if let Some(Object(psc)) = context.get("providerState") {
@@ -849,7 +1047,13 @@ impl GenerateValue<u16> for Generator {
 }
 
 pub fn generate_ascii_string(size: usize) -> String {
-  rand::thread_rng().sample_iter(&Alphanumeric).map(char::from).take(size).collect()
+  generate_ascii_string_with_rng(size, &mut rand::thread_rng())
+}
+
+/// Generates a random alphanumeric string of the given size using the provided random number
+/// generator
+pub fn generate_ascii_string_with_rng<R: Rng + ?Sized>(size: usize, rnd: &mut R) -> String {
+  rnd.sample_iter(&Alphanumeric).map(char::from).take(size).collect()
 }
 
 fn strip_anchors(regex: &str) -> &str {
@@ -876,22 +1080,17 @@ fn replace_with_regex(example: &String, url: String, re: Regex) -> String {
 impl GenerateValue<String> for Generator {
   fn generate_value(
     &self,
-    _: &String,
+    value: &String,
     context: &HashMap<&str, Value>,
-    _matcher: &Box<dyn VariantMatcher + Send + Sync>
+    matcher: &Box<dyn VariantMatcher + Send + Sync>
   ) -> anyhow::Result<String> {
-    let mut rnd = rand::thread_rng();
+    let mut rnd = generator_rng(self, value, context);
     let result = match self {
       Generator::RandomInt(min, max) => Ok(format!("{}", rnd.gen_range(*min..max.saturating_add(1)))),
-      Generator::Uuid(format) => match format.unwrap_or_default() {
-        UuidFormat::Simple => Ok(Uuid::new_v4().as_simple().to_string()),
-        UuidFormat::LowerCaseHyphenated => Ok(Uuid::new_v4().as_hyphenated().to_string()),
-        UuidFormat::UpperCaseHyphenated => Ok(Uuid::new_v4().as_hyphenated().to_string().to_uppercase()),
-        UuidFormat::Urn => Ok(Uuid::new_v4().as_urn().to_string())
-      },
-      Generator::RandomDecimal(digits) => Ok(generate_decimal(*digits as usize)),
-      Generator::RandomHexadecimal(digits) => Ok(generate_hexadecimal(*digits as usize)),
-      Generator::RandomString(size) => Ok(generate_ascii_string(*size as usize)),
+      Generator::Uuid(format) => Ok(generate_uuid_string(format, &mut rnd)),
+      Generator::RandomDecimal(digits) => Ok(generate_decimal_with_rng(*digits as usize, &mut rnd)),
+      Generator::RandomHexadecimal(digits) => Ok(generate_hexadecimal_with_rng(*digits as usize, &mut rnd)),
+      Generator::RandomString(size) => Ok(generate_ascii_string_with_rng(*size as usize, &mut rnd)),
       Generator::Regex(regex) => {
         let mut parser = regex_syntax::ParserBuilder::new().unicode(false).build();
         match parser.parse(strip_anchors(regex)) {
@@ -1015,7 +1214,11 @@ impl GenerateValue<String> for Generator {
       } else {
         Err(anyhow!("MockServerURL: can not generate a value as there is no mock server details in the test context"))
       },
-      Generator::ArrayContains(_) => Err(anyhow!("can only use ArrayContains with lists"))
+      Generator::ArrayContains(_) => Err(anyhow!("can only use ArrayContains with lists")),
+      Generator::Sequence(start, counter) => Ok(counter.next_value(*start).to_string()),
+      Generator::Fake(kind) => Ok(kind.generate(&mut rnd)),
+      Generator::Format(format) => Ok(format::generate_format_value(format, &mut rnd)),
+      Generator::Template(template, generators) => template::generate_from_template(template, generators, context, matcher)
     };
     debug!("Generator = {:?}, Generated value = {:?}", self, result);
     result
@@ -1041,9 +1244,10 @@ impl GenerateValue<Value> for Generator {
     matcher: &Box<dyn VariantMatcher + Send + Sync>
   ) -> anyhow::Result<Value> {
     debug!(context = ?context, "Generating value from {:?}", self);
+    let mut rnd = generator_rng(self, value, context);
     let result = match self {
       Generator::RandomInt(min, max) => {
-        let rand_int = rand::thread_rng().gen_range(*min..max.saturating_add(1));
+        let rand_int = rnd.gen_range(*min..max.saturating_add(1));
         match value {
           Value::String(_) => Ok(json!(format!("{}", rand_int))),
           Value::Number(_) => Ok(json!(rand_int)),
@@ -1051,30 +1255,25 @@ impl GenerateValue<Value> for Generator {
         }
       },
       Generator::Uuid(format) => match value {
-        Value::String(_) => match format.unwrap_or_default() {
-          UuidFormat::Simple => Ok(json!(Uuid::new_v4().as_simple().to_string())),
-          UuidFormat::LowerCaseHyphenated => Ok(json!(Uuid::new_v4().as_hyphenated().to_string())),
-          UuidFormat::UpperCaseHyphenated => Ok(json!(Uuid::new_v4().as_hyphenated().to_string().to_uppercase())),
-          UuidFormat::Urn => Ok(json!(Uuid::new_v4().as_urn().to_string()))
-        },
-        _ => Ok(json!(Uuid::new_v4().as_hyphenated().to_string()))
+        Value::String(_) => Ok(json!(generate_uuid_string(format, &mut rnd))),
+        _ => Ok(json!(generate_uuid(&mut rnd).as_hyphenated().to_string()))
       },
       Generator::RandomDecimal(digits) => match value {
-        Value::String(_) => Ok(json!(generate_decimal(*digits as usize))),
-        Value::Number(_) => match generate_decimal(*digits as usize).parse::<f64>() {
+        Value::String(_) => Ok(json!(generate_decimal_with_rng(*digits as usize, &mut rnd))),
+        Value::Number(_) => match generate_decimal_with_rng(*digits as usize, &mut rnd).parse::<f64>() {
           Ok(val) => Ok(json!(val)),
           Err(err) => Err(anyhow!("Could not generate a random decimal from {} - {}", value, err))
         },
-        _ => Ok(json!(generate_decimal(*digits as usize)))
+        _ => Ok(json!(generate_decimal_with_rng(*digits as usize, &mut rnd)))
       },
-      Generator::RandomHexadecimal(digits) => Ok(json!(generate_hexadecimal(*digits as usize))),
-      Generator::RandomString(size) => Ok(json!(generate_ascii_string(*size as usize))),
+      Generator::RandomHexadecimal(digits) => Ok(json!(generate_hexadecimal_with_rng(*digits as usize, &mut rnd))),
+      Generator::RandomString(size) => Ok(json!(generate_ascii_string_with_rng(*size as usize, &mut rnd))),
       Generator::Regex(regex) => {
         let mut parser = regex_syntax::ParserBuilder::new().unicode(false).build();
         match parser.parse(strip_anchors(regex)) {
           Ok(hir) => {
             match rand_regex::Regex::with_hir(hir, 20) {
-              Ok(r) => Ok(json!(thread_rng().sample::<String, _>(r))),
+              Ok(r) => Ok(json!(rnd.sample::<String, _>(r))),
               Err(err) => {
                 warn!("Failed to generate a value from regular expression - {}", err);
                 Err(anyhow!("Failed to generate a value from regular expression - {}", err))
@@ -1162,7 +1361,7 @@ impl GenerateValue<Value> for Generator {
           Err(anyhow!("DateTime generators require the 'datetime' feature to be enabled"))
         }
       },
-      Generator::RandomBoolean => Ok(json!(rand::thread_rng().r#gen::<bool>())),
+      Generator::RandomBoolean => Ok(json!(rnd.r#gen::<bool>())),
       Generator::ProviderStateGenerator(exp, dt) => {
         // Provider state values may come under a "providerState" key
         let provider_state_config = if let Some(Object(psc)) = context.get("providerState") {
@@ -1215,7 +1414,15 @@ impl GenerateValue<Value> for Generator {
           Ok(Value::Array(result))
         }
         _ => Err(anyhow!("can only use ArrayContains with lists"))
-      }
+      },
+      Generator::Sequence(start, counter) => match value {
+        Value::String(_) => Ok(json!(counter.next_value(*start).to_string())),
+        _ => Ok(json!(counter.next_value(*start)))
+      },
+      Generator::Fake(kind) => Ok(json!(kind.generate(&mut rnd))),
+      Generator::Format(format) => Ok(json!(format::generate_format_value(format, &mut rnd))),
+      Generator::Template(template, generators) => template::generate_from_template(template, generators, context, matcher)
+        .map(Value::String)
     };
     debug!("Generated value = {:?}", result);
     result
@@ -1255,8 +1462,9 @@ impl ContentTypeHandler<Value> for JsonHandler {
     let expanded_paths = resolve_path(&self.value, key);
     if !expanded_paths.is_empty() {
       for pointer_str in expanded_paths {
+        let context = context_for_path(context, &GeneratorCategory::BODY, pointer_str.as_str());
         match self.value.pointer_mut(&pointer_str) {
-          Some(json_value) => match generator.generate_value(&json_value.clone(), context, matcher) {
+          Some(json_value) => match generator.generate_value(&json_value.clone(), &context, matcher) {
             Ok(new_value) => *json_value = new_value,
             Err(_) => ()
           },
@@ -1264,7 +1472,8 @@ impl ContentTypeHandler<Value> for JsonHandler {
         }
       }
     } else if key.len() == 1 {
-      match generator.generate_value(&self.value.clone(), context, matcher) {
+      let context = context_for_path(context, &GeneratorCategory::BODY, "");
+      match generator.generate_value(&self.value.clone(), &context, matcher) {
         Ok(new_value) => self.value = new_value,
         Err(_) => ()
       }
@@ -1282,6 +1491,7 @@ mod tests {
   use expectest::prelude::*;
   use hamcrest2::*;
   use hashers::fx_hash::FxHasher;
+  use maplit::btreemap;
   use pretty_assertions::assert_eq;
   use test_log::test;
 
@@ -1696,6 +1906,88 @@ mod tests {
     expect!(Generator::from_map("RandomString", &json!({ "size": 5 }).as_object().unwrap())).to(be_some().value(Generator::RandomString(5)));
   }
 
+  #[test]
+  fn sequence_generator_from_json_test() {
+    expect!(Generator::from_map("Sequence", &serde_json::Map::new())).to(be_some().value(Generator::sequence(1)));
+    expect!(Generator::from_map("Sequence", &json!({ "start": 100 }).as_object().unwrap())).to(be_some().value(Generator::sequence(100)));
+  }
+
+  #[test]
+  fn fake_generator_from_json_test() {
+    expect!(Generator::from_map("Fake", &serde_json::Map::new())).to(be_none());
+    expect!(Generator::from_map("Fake", &json!({ "kind": "shoeSize" }).as_object().unwrap())).to(be_none());
+    expect!(Generator::from_map("Fake", &json!({ "kind": "email" }).as_object().unwrap())).to(be_some().value(Generator::Fake(FakeKind::Email)));
+  }
+
+  #[test]
+  fn format_generator_from_json_test() {
+    expect!(Generator::from_map("Format", &serde_json::Map::new())).to(be_none());
+    expect!(Generator::from_map("Format", &json!({ "format": "phone" }).as_object().unwrap())).to(be_none());
+    expect!(Generator::from_map("Format", &json!({ "format": "ulid" }).as_object().unwrap())).to(be_some().value(Generator::Format(FormatType::Ulid)));
+  }
+
+  #[test]
+  fn template_generator_from_json_test() {
+    expect!(Generator::from_map("Template", &serde_json::Map::new())).to(be_none());
+    expect!(Generator::from_map("Template", &json!({
+      "template": "ORD-{seq}-{hex}",
+      "generators": {
+        "seq": { "type": "Sequence", "start": 10 },
+        "hex": { "type": "RandomHexadecimal", "digits": 4 }
+      }
+    }).as_object().unwrap())).to(be_some().value(Generator::Template("ORD-{seq}-{hex}".to_string(), btreemap!{
+      "seq".to_string() => Generator::sequence(10),
+      "hex".to_string() => Generator::RandomHexadecimal(4)
+    })));
+  }
+
+  #[test]
+  fn new_generators_round_trip_test() {
+    let generators = vec![
+      Generator::sequence(5),
+      Generator::Fake(FakeKind::StreetAddress),
+      Generator::Format(FormatType::Hostname),
+      Generator::Template("/orders/${orderId}/{seq}".to_string(), btreemap!{ "seq".to_string() => Generator::sequence(1) })
+    ];
+    for generator in generators {
+      let json = generator.to_json().unwrap();
+      let gen_type = json.get("type").unwrap().as_str().unwrap();
+      expect!(Generator::from_map(gen_type, json.as_object().unwrap())).to(be_some().value(generator));
+    }
+  }
+
+  #[test]
+  fn sequence_generator_test() {
+    let generator = Generator::sequence(100);
+    let copy = generator.clone();
+    expect!(generator.generate_value(&json!(1), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap()).to(be_equal_to(json!(100)));
+    expect!(copy.generate_value(&json!(1), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap()).to(be_equal_to(json!(101)));
+    expect!(generator.generate_value(&"1".to_string(), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap()).to(be_equal_to("102".to_string()));
+    expect!(Generator::sequence(100).generate_value(&json!("1"), &hashmap!{}, &NoopVariantMatcher.boxed()).unwrap()).to(be_equal_to(json!("100")));
+  }
+
+  #[test]
+  fn sequence_counters_are_compared_by_current_value() {
+    let counter = SequenceCounter::default();
+    expect!(counter.clone()).to(be_equal_to(SequenceCounter::default()));
+    counter.next_value(1);
+    expect!(counter.clone()).to_not(be_equal_to(SequenceCounter::default()));
+
+    let generator = Generator::Sequence(1, counter);
+    expect!(generator).to(be_equal_to(Generator::sequence(1)));
+  }
+
+  #[test]
+  fn template_generator_mode_test() {
+    let template = Generator::Template("{seq}".to_string(), btreemap!{ "seq".to_string() => Generator::sequence(1) });
+    expect!(template.corresponds_to_mode(&GeneratorTestMode::Consumer)).to(be_true());
+    expect!(template.corresponds_to_mode(&GeneratorTestMode::Provider)).to(be_true());
+
+    let template = Generator::Template("/orders/${orderId}".to_string(), btreemap!{});
+    expect!(template.corresponds_to_mode(&GeneratorTestMode::Consumer)).to(be_false());
+    expect!(template.corresponds_to_mode(&GeneratorTestMode::Provider)).to(be_true());
+  }
+
   #[test]
   fn regex_generator_from_json_test() {
     expect!(Generator::from_map("Regex", &serde_json::Map::new())).to(be_none());
@@ -2049,6 +2341,60 @@ mod tests {
     assert_that!(generated.unwrap(), matches_regex(r"^urn:uuid:[a-fA-F0-9]{8}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{12}$"));
   }
 
+  #[test]
+  fn seeded_generators_generate_different_values_for_different_paths() {
+    let context = hashmap!{ GENERATOR_SEED_KEY => json!(1234) };
+    let generator = Generator::RandomInt(0, 1000000);
+    let id = generator.generate_value(&json!(1),
+      &context_for_path(&context, &GeneratorCategory::BODY, "/id"), &NoopVariantMatcher.boxed()).unwrap();
+    let id2 = generator.generate_value(&json!(1),
+      &context_for_path(&context, &GeneratorCategory::BODY, "/id"), &NoopVariantMatcher.boxed()).unwrap();
+    let other_id = generator.generate_value(&json!(1),
+      &context_for_path(&context, &GeneratorCategory::BODY, "/otherId"), &NoopVariantMatcher.boxed()).unwrap();
+    let header = generator.generate_value(&json!(1),
+      &context_for_path(&context, &GeneratorCategory::HEADER, "/id"), &NoopVariantMatcher.boxed()).unwrap();
+    expect!(&id2).to(be_equal_to(&id));
+    expect!(&other_id).to_not(be_equal_to(&id));
+    expect!(&header).to_not(be_equal_to(&id));
+
+    expect!(context_for_path(&hashmap!{}, &GeneratorCategory::BODY, "/id").get(GENERATOR_PATH_KEY)).to(be_none());
+  }
+
+  #[test]
+  fn generator_seed_test() {
+    expect!(generator_seed(&hashmap!{ GENERATOR_SEED_KEY => json!(1234) })).to(be_some().value(1234));
+    expect!(generator_seed(&hashmap!{ GENERATOR_SEED_KEY => json!("1234") })).to(be_some().value(1234));
+    expect!(generator_seed(&hashmap!{ GENERATOR_SEED_KEY => json!(-1) })).to(be_none());
+  }
+
+  #[test]
+  fn seeded_generators_test() {
+    let context = hashmap!{ GENERATOR_SEED_KEY => json!(1234) };
+    let other_context = hashmap!{ GENERATOR_SEED_KEY => json!(4321) };
+    let generators = vec![
+      Generator::RandomInt(0, 1000000),
+      Generator::RandomDecimal(10),
+      Generator::RandomHexadecimal(10),
+      Generator::RandomString(20),
+      Generator::Regex(r"\d{4}\w{10}".into()),
+      Generator::Uuid(None)
+    ];
+    for generator in generators {
+      let generated = generator.generate_value(&"".to_string(), &context, &NoopVariantMatcher.boxed()).unwrap();
+      let generated2 = generator.generate_value(&"".to_string(), &context, &NoopVariantMatcher.boxed()).unwrap();
+      let generated3 = generator.generate_value(&"".to_string(), &other_context, &NoopVariantMatcher.boxed()).unwrap();
+      expect!(&generated2).to(be_equal_to(&generated));
+      expect!(&generated3).to_not(be_equal_to(&generated));
+
+      let generated = generator.generate_value(&json!("a"), &context, &NoopVariantMatcher.boxed()).unwrap();
+      let generated2 = generator.generate_value(&json!("a"), &context, &NoopVariantMatcher.boxed()).unwrap();
+      expect!(generated2).to(be_equal_to(generated));
+    }
+
+    let generated = Generator::Uuid(None).generate_value(&"".to_string(), &context, &NoopVariantMatcher.boxed());
+    assert_that!(generated.unwrap(), matches_regex(r"^[a-f0-9]{8}-[a-f0-9]{4}-4[a-f0-9]{3}-[89ab][a-f0-9]{3}-[a-f0-9]{12}$"));
+  }
+
   #[test]
   fn random_decimal_generator_test() {
     for _ in 1..10 {
@@ -2368,13 +2714,16 @@ mod tests {
 
 #[cfg(test)]
 mod tests2 {
+  use std::collections::BTreeMap;
+
   use expectest::prelude::*;
   use maplit::hashmap;
   use rstest::rstest;
   use serde_json::{json, Value};
 
   use crate::expression_parser::DataType;
-  use crate::generators::{generate_value_from_context, Generator};
+  use crate::generators::{FakeKind, generate_value_from_context, Generator};
+  use crate::matchingrules::FormatType;
 
   #[rstest]
   //     expression, value,          data_type,               expected
@@ -2420,6 +2769,10 @@ mod tests2 {
   #[case(Generator::ProviderStateGenerator("".to_string(), None), "ProviderState")]
   #[case(Generator::MockServerURL("".to_string(), "".to_string()), "MockServerURL")]
   #[case(Generator::ArrayContains(vec![]), "ArrayContains")]
+  #[case(Generator::sequence(1), "Sequence")]
+  #[case(Generator::Fake(FakeKind::Email), "Fake")]
+  #[case(Generator::Template("{a}".to_string(), BTreeMap::default()), "Template")]
+  #[case(Generator::Format(FormatType::Email), "Format")]
   fn generator_name_test(#[case] generator: Generator, #[case] name: &str) {
     expect!(generator.name()).to(be_equal_to(name));
   }
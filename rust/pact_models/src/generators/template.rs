@@ -0,0 +1,122 @@
+//! Support for the template generator, which builds a string from a template containing
+//! placeholders for other generators (`{name}`) and provider state expressions (`${expression}`).
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::anyhow;
+use serde_json::Value;
+use serde_json::Value::Object;
+
+use crate::expression_parser::DataType;
+use crate::generators::{generate_value_from_context, GenerateValue, Generator, VariantMatcher};
+
+/// Generates a string from the template. Any `{name}` placeholders are replaced with a value from
+/// the generator with that name, and any `${expression}` placeholders are replaced with the value
+/// of the expression evaluated against the provider state context. Literal braces can be included
+/// by doubling them (`{{` and `}}`).
+pub fn generate_from_template(
+  template: &str,
+  generators: &BTreeMap<String, Generator>,
+  context: &HashMap<&str, Value>,
+  matcher: &Box<dyn VariantMatcher + Send + Sync>
+) -> anyhow::Result<String> {
+  let mut result = String::with_capacity(template.len());
+  let mut chars = template.chars().peekable();
+  while let Some(ch) = chars.next() {
+    match ch {
+      '$' if chars.peek() == Some(&'{') => {
+        chars.next();
+        let expression = read_placeholder(&mut chars, template)?;
+        let value = generate_value_from_context(format!("${{{}}}", expression).as_str(),
+          &provider_state_context(context), &Some(DataType::STRING))?;
+        result.push_str(value.to_string().as_str());
+      }
+      '{' if chars.peek() == Some(&'{') => {
+        chars.next();
+        result.push('{');
+      }
+      '}' if chars.peek() == Some(&'}') => {
+        chars.next();
+        result.push('}');
+      }
+      '{' => {
+        let name = read_placeholder(&mut chars, template)?;
+        let generator = generators.get(name.trim())
+          .ok_or_else(|| anyhow!("Template '{}' has no generator for the placeholder '{}'", template, name))?;
+        result.push_str(generator.generate_value(&String::default(), context, matcher)?.as_str());
+      }
+      _ => result.push(ch)
+    }
+  }
+  Ok(result)
+}
+
+fn read_placeholder<I: Iterator<Item=char>>(chars: &mut I, template: &str) -> anyhow::Result<String> {
+  let mut placeholder = String::new();
+  for ch in chars.by_ref() {
+    if ch == '}' {
+      return Ok(placeholder);
+    }
+    placeholder.push(ch);
+  }
+  Err(anyhow!("Template '{}' has an unterminated placeholder '{}'", template, placeholder))
+}
+
+fn provider_state_context<'a>(context: &HashMap<&'a str, Value>) -> HashMap<&'a str, Value> {
+  // Provider state values may come under a "providerState" key
+  if let Some(Object(psc)) = context.get("providerState") {
+    psc
+      .iter()
+      .map(|(k, v)| (k.as_str(), v.clone()))
+      .collect()
+  } else {
+    context.clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use hamcrest2::*;
+  use maplit::{btreemap, hashmap};
+  use serde_json::json;
+
+  use crate::generators::{NoopVariantMatcher, VariantMatcher};
+
+  use super::*;
+
+  #[test]
+  fn generates_a_string_from_the_template() {
+    let generators = btreemap!{
+      "seq".to_string() => Generator::sequence(100),
+      "hex".to_string() => Generator::RandomHexadecimal(4)
+    };
+    let result = generate_from_template("ORD-{seq}-{hex}", &generators, &hashmap!{},
+      &NoopVariantMatcher.boxed()).unwrap();
+    assert_that!(result, matches_regex(r"^ORD-100-[0-9A-F]{4}$"));
+    let result = generate_from_template("ORD-{seq}-{hex}", &generators, &hashmap!{},
+      &NoopVariantMatcher.boxed()).unwrap();
+    assert_that!(result, matches_regex(r"^ORD-101-[0-9A-F]{4}$"));
+  }
+
+  #[test]
+  fn replaces_provider_state_expressions() {
+    let context = hashmap!{ "providerState" => json!({ "customerId": 1234 }) };
+    expect!(generate_from_template("/customers/${customerId}/orders", &btreemap!{}, &context,
+      &NoopVariantMatcher.boxed())).to(be_ok().value("/customers/1234/orders".to_string()));
+  }
+
+  #[test]
+  fn supports_escaped_braces() {
+    expect!(generate_from_template("{{not a placeholder}}", &btreemap!{}, &hashmap!{},
+      &NoopVariantMatcher.boxed())).to(be_ok().value("{not a placeholder}".to_string()));
+  }
+
+  #[test]
+  fn returns_an_error_for_unknown_or_invalid_placeholders() {
+    expect!(generate_from_template("ORD-{seq}", &btreemap!{}, &hashmap!{},
+      &NoopVariantMatcher.boxed())).to(be_err());
+    expect!(generate_from_template("ORD-{seq", &btreemap!{ "seq".to_string() => Generator::sequence(1) },
+      &hashmap!{}, &NoopVariantMatcher.boxed())).to(be_err());
+  }
+}
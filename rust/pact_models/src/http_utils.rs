@@ -1,11 +1,17 @@
 //! Module for fetching documents via HTTP
 
 use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use reqwest::blocking::Client;
 use reqwest::Error;
 use serde_json::Value;
+use tracing::{debug, trace};
+
+/// Number of seconds before the expiry time of an access token that it will be refreshed
+const TOKEN_EXPIRY_LEEWAY_SECONDS: u64 = 30;
 
 /// Type of authentication to use
 #[derive(Debug, Clone)]
@@ -14,6 +20,11 @@ pub enum HttpAuth {
   User(String, Option<String>),
   /// Bearer token
   Token(String),
+  /// OAuth2 client credentials grant. The access token will be fetched from the token endpoint
+  /// and sent as a bearer token
+  OAuth2(OAuth2ClientCredentials),
+  /// Custom header (header name and value), i.e. `X-API-Key`
+  Header(String, String),
   /// No authentication
   None
 }
@@ -26,49 +37,255 @@ impl HttpAuth {
       _ => false
     }
   }
+
+  /// Parses the authentication from a JSON form. The JSON must be an object with a `type`
+  /// attribute, one of `basic` (with `username` and `password`), `bearer` (with `token`),
+  /// `oauth2` (with `tokenUrl`, `clientId`, `clientSecret` and optional `scope`) or `header`
+  /// (with `name` and `value`).
+  pub fn from_json(json: &Value) -> anyhow::Result<HttpAuth> {
+    let attribute = |name: &str| json.get(name).and_then(|v| v.as_str()).map(|v| v.to_string());
+    let required = |name: &str| attribute(name)
+      .ok_or_else(|| anyhow!("Authentication JSON is missing the '{}' attribute", name));
+    match json.get("type").and_then(|v| v.as_str()) {
+      Some("basic") => Ok(HttpAuth::User(required("username")?, attribute("password"))),
+      Some("bearer") => Ok(HttpAuth::Token(required("token")?)),
+      Some("oauth2") => Ok(HttpAuth::OAuth2(OAuth2ClientCredentials::new(
+        required("tokenUrl")?.as_str(),
+        required("clientId")?.as_str(),
+        required("clientSecret")?.as_str(),
+        attribute("scope")
+      ))),
+      Some("header") => Ok(HttpAuth::Header(required("name")?, required("value")?)),
+      Some("none") => Ok(HttpAuth::None),
+      Some(t) => Err(anyhow!("'{}' is not a supported authentication type", t)),
+      None => Err(anyhow!("Authentication JSON must be an object with a 'type' attribute"))
+    }
+  }
+
+  /// Adds the authentication to the blocking request. For OAuth2, this may result in a request
+  /// to the token endpoint.
+  pub fn apply_to_request(
+    &self,
+    request: reqwest::blocking::RequestBuilder
+  ) -> anyhow::Result<reqwest::blocking::RequestBuilder> {
+    match self {
+      HttpAuth::User(username, password) => Ok(request.basic_auth(username.clone(), password.clone())),
+      HttpAuth::Token(token) => Ok(request.bearer_auth(token.clone())),
+      HttpAuth::OAuth2(credentials) => Ok(request.bearer_auth(credentials.access_token()?)),
+      HttpAuth::Header(name, value) => Ok(request.header(name.as_str(), value.as_str())),
+      HttpAuth::None => Ok(request)
+    }
+  }
+
+  /// Adds the authentication to the async request. For OAuth2, this may result in a request
+  /// to the token endpoint.
+  pub async fn apply_to_async_request(
+    &self,
+    request: reqwest::RequestBuilder
+  ) -> anyhow::Result<reqwest::RequestBuilder> {
+    match self {
+      HttpAuth::User(username, password) => Ok(request.basic_auth(username.clone(), password.clone())),
+      HttpAuth::Token(token) => Ok(request.bearer_auth(token.clone())),
+      HttpAuth::OAuth2(credentials) => Ok(request.bearer_auth(credentials.access_token_async().await?)),
+      HttpAuth::Header(name, value) => Ok(request.header(name.as_str(), value.as_str())),
+      HttpAuth::None => Ok(request)
+    }
+  }
+
+  /// If the authentication has a cached access token that can be refreshed, discards it so that
+  /// the next request will fetch a new one. Returns true if a token was discarded.
+  pub fn invalidate_token(&self) -> bool {
+    match self {
+      HttpAuth::OAuth2(credentials) => credentials.invalidate(),
+      _ => false
+    }
+  }
+}
+
+/// Access token fetched from an OAuth2 token endpoint
+#[derive(Debug, Clone)]
+struct AccessToken {
+  token: String,
+  expires_at: Option<Instant>
+}
+
+impl AccessToken {
+  fn is_valid(&self) -> bool {
+    match self.expires_at {
+      Some(expires_at) => Instant::now() + Duration::from_secs(TOKEN_EXPIRY_LEEWAY_SECONDS) < expires_at,
+      None => true
+    }
+  }
+}
+
+/// Credentials for the OAuth2 client credentials grant (RFC 6749 section 4.4). The access token
+/// is cached and shared between clones, and will be refreshed from the token endpoint once
+/// it expires.
+#[derive(Debug, Clone)]
+pub struct OAuth2ClientCredentials {
+  /// URL of the token endpoint
+  pub token_url: String,
+  /// Client ID
+  pub client_id: String,
+  /// Client secret
+  pub client_secret: String,
+  /// Optional scope to request
+  pub scope: Option<String>,
+  token: Arc<Mutex<Option<AccessToken>>>
+}
+
+impl OAuth2ClientCredentials {
+  /// Create new client credentials for the token endpoint
+  pub fn new(token_url: &str, client_id: &str, client_secret: &str, scope: Option<String>) -> Self {
+    OAuth2ClientCredentials {
+      token_url: token_url.to_string(),
+      client_id: client_id.to_string(),
+      client_secret: client_secret.to_string(),
+      scope,
+      token: Arc::new(Mutex::new(None))
+    }
+  }
+
+  /// Returns the access token, fetching a new one from the token endpoint if there is no
+  /// current token or it has expired
+  pub fn access_token(&self) -> anyhow::Result<String> {
+    if let Some(token) = self.cached_token() {
+      return Ok(token);
+    }
+
+    debug!(token_url = %self.token_url, "Fetching OAuth2 access token");
+    let response = Client::new()
+      .post(self.token_url.as_str())
+      .basic_auth(self.client_id.as_str(), Some(self.client_secret.as_str()))
+      .header("accept", "application/json")
+      .form(&self.token_request_form())
+      .send()
+      .map_err(|err| anyhow!("Failed to fetch OAuth2 access token from '{}' - {}", self.token_url, err))?;
+    if response.status().is_success() {
+      let json: Value = response.json()
+        .map_err(|err| anyhow!("Failed to parse OAuth2 token response - {}", err))?;
+      self.update_token(&json)
+    } else {
+      Err(anyhow!("Failed to fetch OAuth2 access token from '{}' - request failed with status {}",
+        self.token_url, response.status()))
+    }
+  }
+
+  /// Returns the access token, fetching a new one from the token endpoint if there is no
+  /// current token or it has expired
+  pub async fn access_token_async(&self) -> anyhow::Result<String> {
+    if let Some(token) = self.cached_token() {
+      return Ok(token);
+    }
+
+    debug!(token_url = %self.token_url, "Fetching OAuth2 access token");
+    let response = reqwest::Client::new()
+      .post(self.token_url.as_str())
+      .basic_auth(self.client_id.as_str(), Some(self.client_secret.as_str()))
+      .header("accept", "application/json")
+      .form(&self.token_request_form())
+      .send()
+      .await
+      .map_err(|err| anyhow!("Failed to fetch OAuth2 access token from '{}' - {}", self.token_url, err))?;
+    if response.status().is_success() {
+      let json: Value = response.json().await
+        .map_err(|err| anyhow!("Failed to parse OAuth2 token response - {}", err))?;
+      self.update_token(&json)
+    } else {
+      Err(anyhow!("Failed to fetch OAuth2 access token from '{}' - request failed with status {}",
+        self.token_url, response.status()))
+    }
+  }
+
+  /// Discards any cached access token. Returns true if there was one.
+  pub fn invalidate(&self) -> bool {
+    let mut token = self.token.lock().unwrap();
+    token.take().is_some()
+  }
+
+  fn cached_token(&self) -> Option<String> {
+    let token = self.token.lock().unwrap();
+    token.as_ref()
+      .filter(|token| token.is_valid())
+      .map(|token| token.token.clone())
+  }
+
+  fn token_request_form(&self) -> Vec<(&'static str, String)> {
+    let mut form = vec![("grant_type", "client_credentials".to_string())];
+    if let Some(scope) = &self.scope {
+      form.push(("scope", scope.clone()));
+    }
+    form
+  }
+
+  fn update_token(&self, json: &Value) -> anyhow::Result<String> {
+    let token = json.get("access_token")
+      .and_then(|v| v.as_str())
+      .ok_or_else(|| anyhow!("OAuth2 token response did not contain an access_token"))?;
+    let expires_at = json.get("expires_in")
+      .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+      .map(|secs| Instant::now() + Duration::from_secs(secs));
+    trace!(?expires_at, "Received OAuth2 access token");
+
+    let mut cached = self.token.lock().unwrap();
+    *cached = Some(AccessToken { token: token.to_string(), expires_at });
+    Ok(token.to_string())
+  }
 }
 
 /// Fetches the JSON from a URL
 pub fn fetch_json_from_url(url: &String, auth: &Option<HttpAuth>) -> anyhow::Result<(String, Value)> {
   let client = Client::new();
-  let request = match auth {
-    &Some(ref auth) => {
-      match auth {
-        &HttpAuth::User(ref username, ref password) => client.get(url).basic_auth(username.clone(), password.clone()),
-        &HttpAuth::Token(ref token) => client.get(url).bearer_auth(token.clone()),
-        _ => client.get(url)
-      }
-    },
-    &None => client.get(url)
+  let res = match send_request(&client, url, auth)? {
+    res if res.status().as_u16() == 401 && auth.as_ref().map(|auth| auth.invalidate_token()).unwrap_or(false) => {
+      debug!("Request was not authorised, retrying with a new access token");
+      send_request(&client, url, auth)?
+    }
+    res => res
   };
 
-  match request.send() {
-    Ok(res) => if res.status().is_success() {
-      let pact_json: Result<Value, Error> = res.json();
-      match pact_json {
-        Ok(ref json) => Ok((url.clone(), json.clone())),
-        Err(err) => Err(anyhow!("Failed to parse JSON - {}", err))
-      }
-    } else {
-      Err(anyhow!("Request failed with status - {}", res.status()))
-    },
-    Err(err) => Err(anyhow!("Request failed - {}", err))
+  if res.status().is_success() {
+    let pact_json: Result<Value, Error> = res.json();
+    match pact_json {
+      Ok(ref json) => Ok((url.clone(), json.clone())),
+      Err(err) => Err(anyhow!("Failed to parse JSON - {}", err))
+    }
+  } else {
+    Err(anyhow!("Request failed with status - {}", res.status()))
   }
 }
 
+fn send_request(client: &Client, url: &str, auth: &Option<HttpAuth>) -> anyhow::Result<reqwest::blocking::Response> {
+  let request = match auth {
+    Some(auth) => auth.apply_to_request(client.get(url))?,
+    None => client.get(url)
+  };
+  request.send().map_err(|err| anyhow!("Request failed - {}", err))
+}
+
+/// Masks the value. Only values of at least 8 characters have their first 4 characters shown,
+/// so at least half of the value is always masked.
+fn mask(value: &str) -> String {
+  let len = value.chars().count();
+  let visible = if len >= 8 { 4 } else { 0 };
+  value.chars().take(visible).chain(std::iter::repeat('*').take(len - visible)).collect()
+}
+
 impl Display for HttpAuth {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     match *self {
-      HttpAuth::Token(ref t) =>
-        write!(f, "Token({:*<width$})", t.get(0..4).unwrap_or(""), width = t.len()),
+      HttpAuth::Token(ref t) => write!(f, "Token({})", mask(t)),
       HttpAuth::User(ref u, ref p) => {
         let username = if u.is_empty() { "none" } else { u.as_str() };
         if let Some(pass) = p {
-          write!(f, "User({}, {:*<width$})", username, pass.get(0..4).unwrap_or(""), width = pass.len())
+          write!(f, "User({}, {})", username, mask(pass))
         } else {
           write!(f, "User({}, [no password])", username)
         }
       }
+      HttpAuth::OAuth2(ref credentials) => write!(f, "OAuth2({}, {}, {})", credentials.token_url,
+        credentials.client_id, mask(&credentials.client_secret)),
+      HttpAuth::Header(ref name, ref value) => write!(f, "Header({}, {})", name, mask(value)),
       _ => write!(f, "None")
     }
   }
@@ -79,3 +296,69 @@ impl Default for HttpAuth {
     HttpAuth::User("".to_string(), None)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn display_masks_secrets() {
+    expect!(HttpAuth::Token("1234567890".to_string()).to_string()).to(be_equal_to("Token(1234******)"));
+    expect!(HttpAuth::User("user".to_string(), Some("password".to_string())).to_string())
+      .to(be_equal_to("User(user, pass****)"));
+    expect!(HttpAuth::Header("X-API-Key".to_string(), "abcdefgh".to_string()).to_string())
+      .to(be_equal_to("Header(X-API-Key, abcd****)"));
+    expect!(HttpAuth::OAuth2(OAuth2ClientCredentials::new("http://auth/token", "client", "secret", None)).to_string())
+      .to(be_equal_to("OAuth2(http://auth/token, client, ******)"));
+  }
+
+  #[test]
+  fn from_json_test() {
+    expect!(HttpAuth::from_json(&json!({"type": "bearer", "token": "abc"})).unwrap().to_string())
+      .to(be_equal_to("Token(***)"));
+    expect!(HttpAuth::from_json(&json!({"type": "basic", "username": "user"})).unwrap().to_string())
+      .to(be_equal_to("User(user, [no password])"));
+    expect!(HttpAuth::from_json(&json!({"type": "header", "name": "X-API-Key", "value": "1234"})).unwrap().to_string())
+      .to(be_equal_to("Header(X-API-Key, ****)"));
+    let oauth = HttpAuth::from_json(&json!({
+      "type": "oauth2",
+      "tokenUrl": "http://auth/token",
+      "clientId": "id",
+      "clientSecret": "secret",
+      "scope": "pacts"
+    })).unwrap();
+    match oauth {
+      HttpAuth::OAuth2(credentials) => {
+        expect!(credentials.token_url).to(be_equal_to("http://auth/token"));
+        expect!(credentials.scope).to(be_some().value("pacts"));
+      }
+      _ => panic!("Expected OAuth2 auth, got {:?}", oauth)
+    }
+    expect!(HttpAuth::from_json(&json!({"type": "header", "name": "X-API-Key"}))).to(be_err());
+    expect!(HttpAuth::from_json(&json!({"type": "digest"}))).to(be_err());
+    expect!(HttpAuth::from_json(&json!({}))).to(be_err());
+  }
+
+  #[test]
+  fn oauth2_caches_the_access_token_until_it_expires() {
+    let credentials = OAuth2ClientCredentials::new("http://auth/token", "client", "secret", None);
+    expect!(credentials.cached_token()).to(be_none());
+
+    expect!(credentials.update_token(&json!({"access_token": "token1", "expires_in": 3600})))
+      .to(be_ok().value("token1".to_string()));
+    expect!(credentials.clone().cached_token()).to(be_some().value("token1"));
+
+    expect!(credentials.update_token(&json!({"access_token": "token2", "expires_in": 10})))
+      .to(be_ok().value("token2".to_string()));
+    expect!(credentials.cached_token()).to(be_none());
+
+    expect!(credentials.update_token(&json!({"access_token": "token3"}))).to(be_ok());
+    expect!(credentials.invalidate()).to(be_true());
+    expect!(credentials.cached_token()).to(be_none());
+
+    expect!(credentials.update_token(&json!({"token_type": "bearer"}))).to(be_err());
+  }
+}
@@ -47,6 +47,7 @@ pub mod sync_pact;
 pub mod message_pact;
 mod iterator_utils;
 pub mod plugins;
+pub mod diff;
 
 /// A "prelude" or a default list of import types to include.
 pub mod prelude {
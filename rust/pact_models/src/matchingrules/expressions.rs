@@ -47,6 +47,8 @@
 //! | boolean     | Value must be a boolean                                                                               |                    | `matching(boolean, true)`                                                     |
 //! | server      | Value must match the semver specification                                                             |                    | `matching(semver, '1.0.0')`                                                   |
 //! | contentType | Value must be of the provided content type. This will preform a magic test on the bytes of the value. | Content type       | `matching(contentType, 'application/xml', '<?xml?><test/>')`                  |
+//! | oneOf       | Value must be equal to one of the values in the list                                                  | List of values     | `matching(oneOf, ['ACTIVE', 'CLOSED'], 'ACTIVE')`                             |
+//! | format      | Value must be of a well-known format (email, uri, ipv4, ipv6, uuid, ulid or hostname)                 | Format name        | `matching(format, 'email', 'a@b.com')`                                        |
 //!
 //! The final form is a reference to another key. This is used to setup type matching using an example value, and is normally
 //! used for collections. The name of the key must be a string value in single quotes.
@@ -96,6 +98,34 @@
 //!
 //! For example: `atMost(2)`
 //!
+//! ### range(MIN, MAX [, INCLUSIVE])
+//!
+//! Configures a matching rule that asserts a number is between the minimum and maximum values.
+//! The bounds are inclusive, unless the optional third parameter is `false`.
+//!
+//! For example: `matching(integer, 5), range(1, 10)` or `range(0, 1.5, false)`
+//!
+//! ### length(MIN, MAX)
+//!
+//! Configures a matching rule that asserts the length of a string is between the minimum and
+//! maximum values (inclusive).
+//!
+//! For example: `matching(type, 'Fred'), length(1, 20)`
+//!
+//! ### uniqueItems [ (KEY) ]
+//!
+//! Configures a matching rule that asserts all the items in a list are unique. If a key path is
+//! given, the values at that path in each item must be unique.
+//!
+//! For example: `uniqueItems` or `eachValue(matching(type, 'a')), uniqueItems('$.id')`
+//!
+//! ### sorted(FIELD [, ORDER])
+//!
+//! Configures a matching rule that asserts the items in a list are sorted by the value at the
+//! given path (use `'$'` for the items themselves). The order is either `asc` (the default) or `desc`.
+//!
+//! For example: `sorted('$.name')` or `sorted('$', desc)`
+//!
 //! ## Composing expressions
 //!
 //! Expressions can be composed by separating them with a comma. For example
@@ -109,7 +139,7 @@
 //!
 
 use std::char::REPLACEMENT_CHARACTER;
-use std::str::from_utf8;
+use std::str::{from_utf8, FromStr};
 
 use anyhow::{anyhow, Error};
 use ariadne::{Config, Label, Report, ReportKind, Source};
@@ -117,13 +147,15 @@ use bytes::{BufMut, BytesMut};
 use itertools::Either;
 use logos::{Lexer, Logos, Span};
 use semver::Version;
+use serde_json::Value;
 use tracing::{instrument, trace, warn};
 
 use crate::expression_parser::DataType;
 use crate::generators::Generator;
 use crate::generators::Generator::ProviderStateGenerator;
-use crate::matchingrules::MatchingRule;
-use crate::matchingrules::MatchingRule::{MaxType, MinType, NotEmpty};
+use crate::matchingrules::{FormatType, MatchingRule, SortOrder};
+use crate::matchingrules::MatchingRule::{MaxType, MinType, NotEmpty, NumberRange, Sorted, StringLength, UniqueItems};
+use crate::path_exp::DocPath;
 
 /// Type to associate with an expression element
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -253,7 +285,7 @@ impl MatchingRuleDefinition {
   }
 }
 
-#[derive(Logos, Debug, PartialEq)]
+#[derive(Logos, Debug, Clone, PartialEq)]
 #[logos(skip r"[ \t\n\f]+")]
 enum MatcherDefinitionToken {
   #[token("matching")]
@@ -274,12 +306,30 @@ enum MatcherDefinitionToken {
   #[token("atMost")]
   AtMost,
 
+  #[token("range")]
+  Range,
+
+  #[token("length")]
+  Length,
+
+  #[token("uniqueItems")]
+  UniqueItems,
+
+  #[token("sorted")]
+  Sorted,
+
   #[token("(")]
   LeftBracket,
 
   #[token(")")]
   RightBracket,
 
+  #[token("[")]
+  LeftSquareBracket,
+
+  #[token("]")]
+  RightSquareBracket,
+
   #[token(",")]
   Comma,
 
@@ -377,6 +427,10 @@ fn matching_definition(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyh
 //       | 'eachValue' LEFT_BRACKET e=matchingDefinitionExp RIGHT_BRACKET
 //       | 'atLeast' LEFT_BRACKET DIGIT+ RIGHT_BRACKET
 //       | 'atMost' LEFT_BRACKET DIGIT+ RIGHT_BRACKET
+//       | 'range' LEFT_BRACKET number COMMA number ( COMMA BOOLEAN_LITERAL )? RIGHT_BRACKET
+//       | 'length' LEFT_BRACKET DIGIT+ COMMA DIGIT+ RIGHT_BRACKET
+//       | 'uniqueItems' ( LEFT_BRACKET string RIGHT_BRACKET )?
+//       | 'sorted' LEFT_BRACKET string ( COMMA ( 'asc' | 'desc' ) )? RIGHT_BRACKET
 //     )
 //     ;
 fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<MatchingRuleDefinition> {
@@ -434,6 +488,42 @@ fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) ->
         generator: None,
         expression: v.to_string()
       })
+    } else if token == &MatcherDefinitionToken::Range {
+      let (min, max, inclusive) = parse_range_params(lex, v)?;
+      Ok(MatchingRuleDefinition {
+        value: String::default(),
+        value_type: ValueType::Unknown,
+        rules: vec![Either::Left(NumberRange(min, max, inclusive))],
+        generator: None,
+        expression: v.to_string()
+      })
+    } else if token == &MatcherDefinitionToken::Length {
+      let (min, max) = parse_length_params(lex, v)?;
+      Ok(MatchingRuleDefinition {
+        value: String::default(),
+        value_type: ValueType::Unknown,
+        rules: vec![Either::Left(StringLength(min, max))],
+        generator: None,
+        expression: v.to_string()
+      })
+    } else if token == &MatcherDefinitionToken::UniqueItems {
+      let key = parse_unique_items_params(lex, v)?;
+      Ok(MatchingRuleDefinition {
+        value: String::default(),
+        value_type: ValueType::Unknown,
+        rules: vec![Either::Left(UniqueItems(key))],
+        generator: None,
+        expression: v.to_string()
+      })
+    } else if token == &MatcherDefinitionToken::Sorted {
+      let (field, order) = parse_sorted_params(lex, v)?;
+      Ok(MatchingRuleDefinition {
+        value: String::default(),
+        value_type: ValueType::Unknown,
+        rules: vec![Either::Left(Sorted(field, order))],
+        generator: None,
+        expression: v.to_string()
+      })
     } else {
       let mut buffer = BytesMut::new().writer();
       let span = lex.span();
@@ -441,7 +531,7 @@ fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) ->
         .with_config(Config::default().with_color(false))
         .with_message(format!("Expected a type of matching rule definition, but got '{}'", lex.slice()))
         .with_label(Label::new(("expression", span)).with_message("Expected a matching rule definition here"))
-        .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost")
+        .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost, range, length, uniqueItems, sorted")
         .finish();
       report.write(("expression", Source::from(v)), &mut buffer)?;
       let message = from_utf8(&*buffer.get_ref())?.to_string();
@@ -454,7 +544,7 @@ fn matching_definition_exp(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) ->
       .with_config(Config::default().with_color(false))
       .with_message(format!("Expected a type of matching rule definition but got the end of the expression"))
       .with_label(Label::new(("expression", span)).with_message("Expected a matching rule definition here"))
-      .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost")
+      .with_note("valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost, range, length, uniqueItems, sorted")
       .finish();
     report.write(("expression", Source::from(v)), &mut buffer)?;
     let message = from_utf8(&*buffer.get_ref())?.to_string();
@@ -499,11 +589,16 @@ fn parse_each_value(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow:
 }
 
 fn error_message(lex: &mut Lexer<MatcherDefinitionToken>, v: &str, error: &str, additional: &str) -> Result<String, Error> {
+  error_report(lex, v, format!("{}, got '{}'", error, lex.slice()).as_str(), additional)
+}
+
+/// Formats the error report for the current token with the given message
+fn error_report(lex: &mut Lexer<MatcherDefinitionToken>, v: &str, message: &str, additional: &str) -> Result<String, Error> {
   let mut buffer = BytesMut::new().writer();
   let span = lex.span();
   let report = Report::build(ReportKind::Error, ("expression", span.start..span.start))
     .with_config(Config::default().with_color(false))
-    .with_message(format!("{}, got '{}'", error, lex.slice()))
+    .with_message(message)
     .with_label(Label::new(("expression", span)).with_message(additional))
     .finish();
   report.write(("expression", Source::from(v)), &mut buffer)?;
@@ -605,6 +700,8 @@ fn parse_matching(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::R
 //   | 'boolean' COMMA BOOLEAN_LITERAL { $rule = BooleanMatcher.INSTANCE; $value = $BOOLEAN_LITERAL.getText(); $type = ValueType.Boolean; }
 //   | 'semver' COMMA s=string { $rule = SemverMatcher.INSTANCE; $value = $s.contents; $type = ValueType.String; }
 //   | 'contentType' COMMA ct=string COMMA s=string { $rule = new ContentTypeMatcher($ct.contents); $value = $s.contents; $type = ValueType.Unknown; }
+//   | 'oneOf' COMMA LEFT_SQUARE_BRACKET primitiveValue ( COMMA primitiveValue )* RIGHT_SQUARE_BRACKET COMMA v=primitiveValue
+//   | 'format' COMMA f=string COMMA s=string { $rule = new FormatMatcher($f.contents); $value = $s.contents; $type = ValueType.String; }
 //   | DOLLAR ref=string { $reference = new MatchingReference($ref.contents); $type = ValueType.Unknown; }
 //   ;
 fn parse_matching_rule(lex: &mut logos::Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
@@ -625,6 +722,8 @@ fn parse_matching_rule(lex: &mut logos::Lexer<MatcherDefinitionToken>, v: &str)
       "boolean" => parse_boolean(lex, v),
       "contentType" => parse_content_type(lex, v),
       "semver" => parse_semver(lex, v),
+      "oneOf" => parse_one_of(lex, v),
+      "format" => parse_format(lex, v),
       _ => {
         let mut buffer = BytesMut::new().writer();
         let span = lex.span();
@@ -632,7 +731,7 @@ fn parse_matching_rule(lex: &mut logos::Lexer<MatcherDefinitionToken>, v: &str)
           .with_config(Config::default().with_color(false))
           .with_message(format!("Expected the type of matcher, got '{}'", lex.slice()))
           .with_label(Label::new(("expression", span)).with_message("This is not a valid matcher type"))
-          .with_note("Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver")
+          .with_note("Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver, oneOf, format")
           .finish();
         report.write(("expression", Source::from(v)), &mut buffer)?;
         let message = from_utf8(&*buffer.get_ref())?.to_string();
@@ -769,6 +868,38 @@ fn parse_include(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Re
   Ok((value.clone(), ValueType::String, Some(MatchingRule::Include(value)), None, None))
 }
 
+// COMMA f=string COMMA s=string { $rule = new FormatMatcher($f.contents); $value = $s.contents; $type = ValueType.String; }
+fn parse_format(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
+  parse_comma(lex, v)?;
+  let format = parse_string(lex, v)?;
+  match FormatType::from_str(format.as_str()) {
+    Ok(format) => {
+      parse_comma(lex, v)?;
+      let value = parse_string(lex, v)?;
+      if format.validate(value.as_str()).is_err() {
+        // The string token already includes its quotes
+        return Err(anyhow!(error_report(lex, v,
+          format!("Expected the example to be {}, got {}", format.description(), lex.slice()).as_str(),
+          format!("This is not {}", format.description()).as_str())?));
+      }
+      Ok((value, ValueType::String, Some(MatchingRule::Format(format)), Some(Generator::Format(format)), None))
+    }
+    Err(_) => {
+      let mut buffer = BytesMut::new().writer();
+      let span = lex.span();
+      let report = Report::build(ReportKind::Error, ("expression", span.start..span.start))
+        .with_config(Config::default().with_color(false))
+        .with_message(format!("Expected the name of a format, got {}", lex.slice()))
+        .with_label(Label::new(("expression", span)).with_message("This is not a valid format"))
+        .with_note("Valid formats are: email, uri, ipv4, ipv6, uuid, ulid, hostname")
+        .finish();
+      report.write(("expression", Source::from(v)), &mut buffer)?;
+      let message = from_utf8(&*buffer.get_ref())?.to_string();
+      Err(anyhow!(message))
+    }
+  }
+}
+
 // COMMA ct=string COMMA s=string { $rule = new ContentTypeMatcher($ct.contents); $value = $s.contents; $type = ValueType.Unknown; }
 fn parse_content_type(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
   parse_comma(lex, v)?;
@@ -778,6 +909,55 @@ fn parse_content_type(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyho
   Ok((value, ValueType::Unknown, Some(MatchingRule::ContentType(ct)), None, None))
 }
 
+// COMMA LEFT_SQUARE_BRACKET primitiveValue ( COMMA primitiveValue )* RIGHT_SQUARE_BRACKET COMMA v=primitiveValue
+fn parse_one_of(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<MatchingRule>, Option<Generator>, Option<MatchingReference>)> {
+  parse_comma(lex, v)?;
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "'['"))?;
+  if let Ok(MatcherDefinitionToken::LeftSquareBracket) = next {
+    let mut values = vec![];
+    loop {
+      let (value, value_type, _) = parse_primitive_value(lex, v, true)?;
+      values.push(one_of_value(lex, value.as_str(), value_type));
+      let next = lex.next().ok_or_else(|| end_of_expression(v, "']'"))?;
+      match next {
+        Ok(MatcherDefinitionToken::Comma) => continue,
+        Ok(MatcherDefinitionToken::RightSquareBracket) => break,
+        _ => return Err(anyhow!(error_message(lex, v, "Expected a comma or closing square bracket",
+          "Expected a comma or closing square bracket before this")?))
+      }
+    }
+    parse_comma(lex, v)?;
+    let (value, value_type, generator) = parse_primitive_value(lex, v, false)?;
+    if generator.is_none() && !values.contains(&one_of_value(lex, value.as_str(), value_type)) {
+      return Err(anyhow!(error_message(lex, v, "Expected the example to be one of the values",
+        "Expected one of the values in the list here")?));
+    }
+    Ok((value, value_type, Some(MatchingRule::OneOf(values)), generator, None))
+  } else {
+    Err(anyhow!(error_message(lex, v, "Expected an opening square bracket", "Expected an opening square bracket before this")?))
+  }
+}
+
+/// Converts the primitive value that was just parsed into a JSON value for a OneOf matcher. A
+/// null literal is parsed as an empty string, so it is checked for using the current token.
+fn one_of_value(lex: &Lexer<MatcherDefinitionToken>, value: &str, value_type: ValueType) -> Value {
+  if lex.slice() == "null" {
+    Value::Null
+  } else {
+    primitive_to_json(value, value_type)
+  }
+}
+
+/// Converts a primitive value from an expression into a JSON value
+fn primitive_to_json(value: &str, value_type: ValueType) -> Value {
+  match value_type {
+    ValueType::Integer => value.parse::<i64>().map(Value::from).unwrap_or_else(|_| Value::String(value.to_string())),
+    ValueType::Decimal | ValueType::Number => value.parse::<f64>().map(Value::from).unwrap_or_else(|_| Value::String(value.to_string())),
+    ValueType::Boolean => Value::Bool(value == "true"),
+    _ => Value::String(value.to_string())
+  }
+}
+
 // primitiveValue returns [ String value, ValueType type ] :
 //   string { $value = $string.contents; $type = ValueType.String; }
 //   | v=DECIMAL_LITERAL { $value = $v.getText(); $type = ValueType.Decimal; }
@@ -1077,6 +1257,152 @@ fn parse_length_param(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyho
   }
 }
 
+// LEFT_BRACKET number COMMA number ( COMMA BOOLEAN_LITERAL )? RIGHT_BRACKET
+fn parse_range_params(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(f64, f64, bool)> {
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "an opening bracket"))?;
+  if let Ok(MatcherDefinitionToken::LeftBracket) = next {
+    let min = parse_number_param(lex, v)?;
+    parse_comma(lex, v)?;
+    let max = parse_number_param(lex, v)?;
+    if max < min {
+      return Err(anyhow!(error_message(lex, v, "The maximum value must not be less than the minimum value",
+        "Expected a number greater than or equal to the minimum value here")?));
+    }
+    let next = lex.next().ok_or_else(|| end_of_expression(v, "')'"))?;
+    match next {
+      Ok(MatcherDefinitionToken::RightBracket) => Ok((min, max, true)),
+      Ok(MatcherDefinitionToken::Comma) => {
+        let next = lex.next().ok_or_else(|| end_of_expression(v, "a boolean"))?;
+        if let Ok(MatcherDefinitionToken::Boolean) = next {
+          let inclusive = lex.slice() == "true";
+          let next = lex.next().ok_or_else(|| end_of_expression(v, "')'"))?;
+          if let Ok(MatcherDefinitionToken::RightBracket) = next {
+            Ok((min, max, inclusive))
+          } else {
+            Err(anyhow!(error_message(lex, v, "Expected a closing bracket", "Expected a closing bracket before this")?))
+          }
+        } else {
+          Err(anyhow!(error_message(lex, v, "Expected a boolean", "Expected a boolean here")?))
+        }
+      }
+      _ => Err(anyhow!(error_message(lex, v, "Expected a closing bracket", "Expected a closing bracket before this")?))
+    }
+  } else {
+    Err(anyhow!(error_message(lex, v, "Expected an opening bracket", "Expected an opening bracket here")?))
+  }
+}
+
+// ( LEFT_BRACKET string RIGHT_BRACKET )?
+fn parse_unique_items_params(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<Option<DocPath>> {
+  let mut peek = lex.clone();
+  if let Some(Ok(MatcherDefinitionToken::LeftBracket)) = peek.next() {
+    lex.next();
+    let key = parse_path_param(lex, v)?;
+    let next = lex.next().ok_or_else(|| end_of_expression(v, "')'"))?;
+    if let Ok(MatcherDefinitionToken::RightBracket) = next {
+      Ok(Some(key))
+    } else {
+      Err(anyhow!(error_message(lex, v, "Expected a closing bracket", "Expected a closing bracket before this")?))
+    }
+  } else {
+    Ok(None)
+  }
+}
+
+// LEFT_BRACKET string ( COMMA ( 'asc' | 'desc' ) )? RIGHT_BRACKET
+fn parse_sorted_params(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(DocPath, SortOrder)> {
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "an opening bracket"))?;
+  if let Ok(MatcherDefinitionToken::LeftBracket) = next {
+    let field = parse_path_param(lex, v)?;
+    let next = lex.next().ok_or_else(|| end_of_expression(v, "')'"))?;
+    match next {
+      Ok(MatcherDefinitionToken::RightBracket) => Ok((field, SortOrder::Ascending)),
+      Ok(MatcherDefinitionToken::Comma) => {
+        let next = lex.next().ok_or_else(|| end_of_expression(v, "a sort order"))?;
+        let order = match next {
+          Ok(MatcherDefinitionToken::Id) => SortOrder::try_from(lex.slice()).ok(),
+          _ => None
+        };
+        if let Some(order) = order {
+          let next = lex.next().ok_or_else(|| end_of_expression(v, "')'"))?;
+          if let Ok(MatcherDefinitionToken::RightBracket) = next {
+            Ok((field, order))
+          } else {
+            Err(anyhow!(error_message(lex, v, "Expected a closing bracket", "Expected a closing bracket before this")?))
+          }
+        } else {
+          Err(anyhow!(error_message(lex, v, "Expected a sort order of either asc or desc", "Expected asc or desc here")?))
+        }
+      }
+      _ => Err(anyhow!(error_message(lex, v, "Expected a closing bracket", "Expected a closing bracket before this")?))
+    }
+  } else {
+    Err(anyhow!(error_message(lex, v, "Expected an opening bracket", "Expected an opening bracket here")?))
+  }
+}
+
+// string (that is a valid path expression)
+fn parse_path_param(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<DocPath> {
+  let path = parse_string(lex, v)?;
+  match DocPath::new(path.as_str()) {
+    Ok(path) => Ok(path),
+    Err(err) => Err(anyhow!(error_message(lex, v, "Expected a valid path expression",
+      format!("{}", err).as_str())?))
+  }
+}
+
+// LEFT_BRACKET DIGIT+ COMMA DIGIT+ RIGHT_BRACKET
+fn parse_length_params(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(usize, usize)> {
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "an opening bracket"))?;
+  if let Ok(MatcherDefinitionToken::LeftBracket) = next {
+    let next = lex.next().ok_or_else(|| end_of_expression(v, "an unsized integer"))?;
+    if let Ok(MatcherDefinitionToken::Num(min)) = next {
+      parse_comma(lex, v)?;
+      let next = lex.next().ok_or_else(|| end_of_expression(v, "an unsized integer"))?;
+      if let Ok(MatcherDefinitionToken::Num(max)) = next {
+        if max < min {
+          return Err(anyhow!(error_message(lex, v, "The maximum length must not be less than the minimum length",
+            "Expected a number greater than or equal to the minimum length here")?));
+        }
+        let next = lex.next().ok_or_else(|| end_of_expression(v, "')'"))?;
+        if let Ok(MatcherDefinitionToken::RightBracket) = next {
+          Ok((min, max))
+        } else {
+          Err(anyhow!(error_message(lex, v, "Expected a closing bracket", "Expected a closing bracket before this")?))
+        }
+      } else {
+        Err(anyhow!(error_message(lex, v, "Expected an unsigned number", "Expected an unsigned number here")?))
+      }
+    } else {
+      Err(anyhow!(error_message(lex, v, "Expected an unsigned number", "Expected an unsigned number here")?))
+    }
+  } else {
+    Err(anyhow!(error_message(lex, v, "Expected an opening bracket", "Expected an opening bracket here")?))
+  }
+}
+
+// number: DECIMAL_LITERAL | INTEGER_LITERAL
+fn parse_number_param(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<f64> {
+  let next = lex.next().ok_or_else(|| end_of_expression(v, "a number"))?;
+  let number = match next {
+    Ok(MatcherDefinitionToken::Decimal) => lex.slice().to_string(),
+    Ok(MatcherDefinitionToken::Int(_) | MatcherDefinitionToken::Num(_)) => {
+      // Logos is returning an INT token when a Decimal should match. We need to now parse the
+      // remaining pattern if it is a decimal
+      if lex.remainder().starts_with('.') {
+        let int_part = lex.slice().to_string();
+        let _ = lex.next().ok_or_else(|| end_of_expression(v, "a number"))?;
+        format!("{}{}", int_part, lex.slice())
+      } else {
+        lex.slice().to_string()
+      }
+    }
+    _ => return Err(anyhow!(error_message(lex, v, "Expected a number", "Expected a number here")?))
+  };
+  number.parse::<f64>()
+    .map_err(|_| anyhow!("'{}' is not a valid number", number))
+}
+
 // '(' exp=STRING_LITERAL COMMA v=primitiveValue ')'
 fn from_provider_state(lex: &mut Lexer<MatcherDefinitionToken>, v: &str) -> anyhow::Result<(String, ValueType, Option<Generator>)> {
   let next = lex.next().ok_or_else(|| end_of_expression(v, "'('"))?;
@@ -1428,7 +1754,7 @@ mod test {
             |   │       ────┬─── \u{0020}
             |   │           ╰───── This is not a valid matcher type
             |   │\u{0020}
-            |   │ Note: Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver
+            |   │ Note: Valid matchers are: equalTo, regex, type, datetime, date, time, include, number, integer, decimal, boolean, contentType, semver, oneOf, format
             |───╯
             |
             ".trim_margin().unwrap()));
@@ -1560,7 +1886,7 @@ mod test {
             |   │    │\u{0020}
             |   │    ╰─ Expected a matching rule definition here
             |   │\u{0020}
-            |   │ Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost
+            |   │ Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost, range, length, uniqueItems, sorted
             |───╯
             |
             ".trim_margin().unwrap()));
@@ -1575,7 +1901,7 @@ mod test {
             |   │ ──────┬────── \u{0020}
             |   │       ╰──────── Expected a matching rule definition here
             |   │\u{0020}
-            |   │ Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost
+            |   │ Note: valid matching rule definitions are: matching, notEmpty, eachKey, eachValue, atLeast, atMost, range, length, uniqueItems, sorted
             |───╯
             |
             ".trim_margin().unwrap()));
@@ -2048,4 +2374,211 @@ mod test {
         |
         ".trim_margin().unwrap());
   }
+
+  #[test]
+  fn parse_range_test() {
+    let exp = "range(1, 10)";
+    let mut lex = MatcherDefinitionToken::lexer(exp);
+    assert_eq!(super::matching_definition_exp(&mut lex, exp).unwrap(),
+      MatchingRuleDefinition {
+        value: "".to_string(),
+        value_type: ValueType::Unknown,
+        rules: vec![ Either::Left(MatchingRule::NumberRange(1.0, 10.0, true)) ],
+        generator: None,
+        expression: exp.to_string()
+      }
+    );
+
+    let exp = "range(-10.5, 0.25, false)";
+    let mut lex = MatcherDefinitionToken::lexer(exp);
+    expect!(super::matching_definition_exp(&mut lex, exp).unwrap().rules).to(
+      be_equal_to(vec![ Either::Left(MatchingRule::NumberRange(-10.5, 0.25, false)) ]));
+
+    expect!(parse_matcher_def("matching(integer, 5), range(1, 10)").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "5".to_string(),
+        value_type: ValueType::Integer,
+        rules: vec![
+          Either::Left(MatchingRule::Integer),
+          Either::Left(MatchingRule::NumberRange(1.0, 10.0, true))
+        ],
+        generator: None,
+        expression: "matching(integer, 5), range(1, 10)".to_string()
+      }));
+
+    expect!(parse_matcher_def("range")).to(be_err());
+    expect!(parse_matcher_def("range(1)")).to(be_err());
+    expect!(parse_matcher_def("range(10, 1)")).to(be_err());
+    expect!(parse_matcher_def("range('1', 10)")).to(be_err());
+    expect!(parse_matcher_def("range(1, 10, 'true')")).to(be_err());
+    expect!(parse_matcher_def("range(1, 10")).to(be_err());
+  }
+
+  #[test]
+  fn parse_length_test() {
+    let exp = "length(1, 20)";
+    let mut lex = MatcherDefinitionToken::lexer(exp);
+    assert_eq!(super::matching_definition_exp(&mut lex, exp).unwrap(),
+      MatchingRuleDefinition {
+        value: "".to_string(),
+        value_type: ValueType::Unknown,
+        rules: vec![ Either::Left(MatchingRule::StringLength(1, 20)) ],
+        generator: None,
+        expression: exp.to_string()
+      }
+    );
+
+    expect!(parse_matcher_def("matching(type, 'Fred'), length(1, 20)").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "Fred".to_string(),
+        value_type: ValueType::String,
+        rules: vec![
+          Either::Left(MatchingRule::Type),
+          Either::Left(MatchingRule::StringLength(1, 20))
+        ],
+        generator: None,
+        expression: "matching(type, 'Fred'), length(1, 20)".to_string()
+      }));
+
+    expect!(parse_matcher_def("length")).to(be_err());
+    expect!(parse_matcher_def("length(1)")).to(be_err());
+    expect!(parse_matcher_def("length(-1, 10)")).to(be_err());
+    expect!(parse_matcher_def("length(20, 1)")).to(be_err());
+    expect!(parse_matcher_def("length(1.5, 10)")).to(be_err());
+    expect!(parse_matcher_def("length(1, 10")).to(be_err());
+  }
+
+  #[test]
+  fn parse_unique_items_test() {
+    expect!(parse_matcher_def("uniqueItems").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "".to_string(),
+        value_type: ValueType::Unknown,
+        rules: vec![ Either::Left(MatchingRule::UniqueItems(None)) ],
+        generator: None,
+        expression: "uniqueItems".to_string()
+      }));
+    expect!(parse_matcher_def("atLeast(1), uniqueItems('$.id')").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "".to_string(),
+        value_type: ValueType::Unknown,
+        rules: vec![
+          Either::Left(MatchingRule::MinType(1)),
+          Either::Left(MatchingRule::UniqueItems(Some(DocPath::new_unwrap("$.id"))))
+        ],
+        generator: None,
+        expression: "atLeast(1), uniqueItems('$.id')".to_string()
+      }));
+    expect!(parse_matcher_def("uniqueItems, atLeast(1)").unwrap().rules).to(be_equal_to(vec![
+      Either::Left(MatchingRule::UniqueItems(None)),
+      Either::Left(MatchingRule::MinType(1))
+    ]));
+
+    expect!(parse_matcher_def("uniqueItems(1)")).to(be_err());
+    expect!(parse_matcher_def("uniqueItems('$.id'")).to(be_err());
+  }
+
+  #[test]
+  fn parse_sorted_test() {
+    expect!(parse_matcher_def("sorted('$.name')").unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "".to_string(),
+        value_type: ValueType::Unknown,
+        rules: vec![ Either::Left(MatchingRule::Sorted(DocPath::new_unwrap("$.name"), SortOrder::Ascending)) ],
+        generator: None,
+        expression: "sorted('$.name')".to_string()
+      }));
+    expect!(parse_matcher_def("sorted('$', desc)").unwrap().rules).to(be_equal_to(vec![
+      Either::Left(MatchingRule::Sorted(DocPath::root(), SortOrder::Descending))
+    ]));
+    expect!(parse_matcher_def("sorted('$.age', asc)").unwrap().rules).to(be_equal_to(vec![
+      Either::Left(MatchingRule::Sorted(DocPath::new_unwrap("$.age"), SortOrder::Ascending))
+    ]));
+
+    expect!(parse_matcher_def("sorted")).to(be_err());
+    expect!(parse_matcher_def("sorted()")).to(be_err());
+    expect!(parse_matcher_def("sorted('$.name', sideways)")).to(be_err());
+    expect!(parse_matcher_def("sorted('$.name', desc")).to(be_err());
+  }
+
+  #[test]
+  fn parse_one_of_test() {
+    let exp = "matching(oneOf, ['ACTIVE', 'SUSPENDED', 'CLOSED'], 'ACTIVE')";
+    expect!(parse_matcher_def(exp).unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "ACTIVE".to_string(),
+        value_type: ValueType::String,
+        rules: vec![
+          Either::Left(MatchingRule::OneOf(vec![
+            Value::String("ACTIVE".to_string()),
+            Value::String("SUSPENDED".to_string()),
+            Value::String("CLOSED".to_string())
+          ]))
+        ],
+        generator: None,
+        expression: exp.to_string()
+      }));
+
+    let exp = "matching(oneOf, [1, 2.5, true], 2.5)";
+    expect!(parse_matcher_def(exp).unwrap().rules).to(
+      be_equal_to(vec![
+        Either::Left(MatchingRule::OneOf(vec![Value::from(1), Value::from(2.5), Value::Bool(true)]))
+      ]));
+
+    let exp = "matching(oneOf, ['A', null], null)";
+    expect!(parse_matcher_def(exp).unwrap().rules).to(
+      be_equal_to(vec![
+        Either::Left(MatchingRule::OneOf(vec![Value::String("A".to_string()), Value::Null]))
+      ]));
+    expect!(parse_matcher_def("matching(oneOf, ['A', ''], null)")).to(be_err());
+
+    expect!(parse_matcher_def("matching(oneOf, ['A', 'B'], 'C')")).to(be_err());
+    expect!(parse_matcher_def("matching(oneOf, 'A', 'A')")).to(be_err());
+    expect!(parse_matcher_def("matching(oneOf, ['A', 'B' 'A')")).to(be_err());
+    expect!(parse_matcher_def("matching(oneOf, ['A', 'B'])")).to(be_err());
+  }
+
+  #[test]
+  fn parse_format_matcher() {
+    let exp = "matching(format, 'email', 'a@b.com')";
+    expect!(parse_matcher_def(exp).unwrap()).to(
+      be_equal_to(MatchingRuleDefinition {
+        value: "a@b.com".to_string(),
+        value_type: ValueType::String,
+        rules: vec![ Either::Left(MatchingRule::Format(FormatType::Email)) ],
+        generator: Some(Generator::Format(FormatType::Email)),
+        expression: exp.to_string()
+      }));
+    expect!(parse_matcher_def("matching(format, 'ipv6', '::1')").unwrap().rules).to(
+      be_equal_to(vec![ Either::Left(MatchingRule::Format(FormatType::Ipv6)) ]));
+
+    expect!(as_string!(super::parse_matcher_def("matching(format, 'phone', '555-1234')"))).to(
+      be_err().value(
+        "|Error: Expected the name of a format, got 'phone'
+            |   ╭─[ expression:1:18 ]
+            |   │
+            | 1 │ matching(format, 'phone', '555-1234')
+            |   │                  ───┬─── \u{0020}
+            |   │                     ╰───── This is not a valid format
+            |   │\u{0020}
+            |   │ Note: Valid formats are: email, uri, ipv4, ipv6, uuid, ulid, hostname
+            |───╯
+            |
+            ".trim_margin_with("|").unwrap()
+      ));
+    expect!(parse_matcher_def("matching(format, 'email')")).to(be_err());
+    expect!(parse_matcher_def("matching(format, email, 'a@b.com')")).to(be_err());
+    expect!(as_string!(super::parse_matcher_def("matching(format, 'ipv4', '300.1.1.1')"))).to(
+      be_err().value(
+        "|Error: Expected the example to be an IPv4 address, got '300.1.1.1'
+            |   ╭─[ expression:1:26 ]
+            |   │
+            | 1 │ matching(format, 'ipv4', '300.1.1.1')
+            |   │                          ─────┬───── \u{0020}
+            |   │                               ╰─────── This is not an IPv4 address
+            |───╯
+            |
+            ".trim_margin_with("|").unwrap()
+      ));
+  }
 }
@@ -0,0 +1,297 @@
+//! Well-known string formats that can be matched with the Format matching rule, and generated
+//! with the Format generator.
+
+use std::fmt::{Display, Formatter};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Well-known string format
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatType {
+  /// Email address (RFC 5321 mailbox, e.g. mary@example.com)
+  Email,
+  /// Absolute URI (RFC 3986, e.g. https://example.com/path?q=1)
+  Uri,
+  /// IPv4 address in dotted decimal form (e.g. 192.168.0.1)
+  Ipv4,
+  /// IPv6 address (RFC 4291, e.g. 2001:db8::1)
+  Ipv6,
+  /// UUID (RFC 4122, e.g. 936da01f-9abd-4d9d-80c7-02af85c822a8)
+  Uuid,
+  /// ULID (e.g. 01ARZ3NDEKTSV4RRFFQ69G5FAV)
+  Ulid,
+  /// Internet host name (RFC 1123, e.g. api.example.com)
+  Hostname
+}
+
+impl FormatType {
+  /// Description of the format to use in messages (e.g. "an email address")
+  pub fn description(&self) -> &'static str {
+    match self {
+      FormatType::Email => "an email address",
+      FormatType::Uri => "a URI",
+      FormatType::Ipv4 => "an IPv4 address",
+      FormatType::Ipv6 => "an IPv6 address",
+      FormatType::Uuid => "a UUID",
+      FormatType::Ulid => "a ULID",
+      FormatType::Hostname => "a hostname"
+    }
+  }
+
+  /// Validates that the value is of this format, returning the reason if it is not. The formats
+  /// are checked with parsers for the relevant specifications, not regular expressions.
+  pub fn validate(&self, value: &str) -> Result<(), String> {
+    match self {
+      FormatType::Email => validate_email(value),
+      FormatType::Uri => validate_uri(value),
+      FormatType::Ipv4 => Ipv4Addr::from_str(value).map(|_| ()).map_err(|err| err.to_string()),
+      FormatType::Ipv6 => Ipv6Addr::from_str(value).map(|_| ()).map_err(|err| err.to_string()),
+      FormatType::Uuid => Uuid::try_parse(value).map(|_| ()).map_err(|err| err.to_string()),
+      FormatType::Ulid => validate_ulid(value),
+      FormatType::Hostname => validate_hostname(value)
+    }
+  }
+}
+
+impl Display for FormatType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      FormatType::Email => write!(f, "email"),
+      FormatType::Uri => write!(f, "uri"),
+      FormatType::Ipv4 => write!(f, "ipv4"),
+      FormatType::Ipv6 => write!(f, "ipv6"),
+      FormatType::Uuid => write!(f, "uuid"),
+      FormatType::Ulid => write!(f, "ulid"),
+      FormatType::Hostname => write!(f, "hostname")
+    }
+  }
+}
+
+impl FromStr for FormatType {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "email" => Ok(FormatType::Email),
+      "uri" => Ok(FormatType::Uri),
+      "ipv4" => Ok(FormatType::Ipv4),
+      "ipv6" => Ok(FormatType::Ipv6),
+      "uuid" => Ok(FormatType::Uuid),
+      "ulid" => Ok(FormatType::Ulid),
+      "hostname" => Ok(FormatType::Hostname),
+      _ => Err(anyhow!("'{}' is not a valid format, it must be one of email, uri, ipv4, ipv6, uuid, ulid or hostname", s))
+    }
+  }
+}
+
+// RFC 5321 mailbox: local-part "@" ( domain / address-literal )
+fn validate_email(value: &str) -> Result<(), String> {
+  let (local, domain) = value.rsplit_once('@')
+    .ok_or_else(|| "it does not contain an '@'".to_string())?;
+
+  if local.is_empty() {
+    return Err("the local part is empty".to_string());
+  }
+  if local.len() > 64 {
+    return Err("the local part is longer than 64 characters".to_string());
+  }
+  if value.len() > 254 {
+    return Err("it is longer than 254 characters".to_string());
+  }
+
+  if local.len() >= 2 && local.starts_with('"') && local.ends_with('"') {
+    let mut chars = local[1..local.len() - 1].chars();
+    while let Some(ch) = chars.next() {
+      if ch == '\\' {
+        if chars.next().is_none() {
+          return Err("the quoted local part ends with an escape character".to_string());
+        }
+      } else if ch == '"' || !(ch == ' ' || ch.is_ascii_graphic()) {
+        return Err(format!("the quoted local part contains an invalid character '{}'", ch));
+      }
+    }
+  } else {
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+      return Err("the local part has a leading, trailing or repeated '.'".to_string());
+    }
+    if let Some(ch) = local.chars().find(|ch| !(ch.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(*ch))) {
+      return Err(format!("the local part contains an invalid character '{}'", ch));
+    }
+  }
+
+  if domain.starts_with('[') && domain.ends_with(']') {
+    let literal = &domain[1..domain.len() - 1];
+    let result = match literal.strip_prefix("IPv6:") {
+      Some(address) => Ipv6Addr::from_str(address).map(|_| ()),
+      None => Ipv4Addr::from_str(literal).map(|_| ())
+    };
+    result.map_err(|err| format!("the domain literal '{}' is not valid - {}", domain, err))
+  } else {
+    validate_hostname(domain).map_err(|err| format!("the domain '{}' is not valid - {}", domain, err))
+  }
+}
+
+// RFC 1123 host name: labels of 1 to 63 letters, digits or hyphens, separated by dots
+fn validate_hostname(value: &str) -> Result<(), String> {
+  let name = value.strip_suffix('.').unwrap_or(value);
+  if name.is_empty() {
+    return Err("it is empty".to_string());
+  }
+  if name.len() > 253 {
+    return Err("it is longer than 253 characters".to_string());
+  }
+
+  for label in name.split('.') {
+    if label.is_empty() {
+      return Err("it contains an empty label".to_string());
+    }
+    if label.len() > 63 {
+      return Err(format!("the label '{}' is longer than 63 characters", label));
+    }
+    if let Some(ch) = label.chars().find(|ch| !(ch.is_ascii_alphanumeric() || *ch == '-')) {
+      return Err(format!("the label '{}' contains an invalid character '{}'", label, ch));
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+      return Err(format!("the label '{}' starts or ends with a hyphen", label));
+    }
+  }
+
+  Ok(())
+}
+
+const CROCKFORD_BASE32: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+// 26 Crockford base32 characters, where the first is at most 7 (the value is 128 bits)
+fn validate_ulid(value: &str) -> Result<(), String> {
+  let length = value.chars().count();
+  if length != 26 {
+    return Err(format!("it must be 26 characters long, but was {}", length));
+  }
+  if let Some(ch) = value.chars().find(|ch| !CROCKFORD_BASE32.contains(ch.to_ascii_uppercase())) {
+    return Err(format!("it contains an invalid character '{}'", ch));
+  }
+  if value.starts_with(|ch: char| ch > '7') {
+    return Err("the timestamp is out of range (the first character must be between 0 and 7)".to_string());
+  }
+  Ok(())
+}
+
+// RFC 3986 absolute URI: scheme ":" hier-part [ "?" query ] [ "#" fragment ]
+fn validate_uri(value: &str) -> Result<(), String> {
+  let (scheme, rest) = value.split_once(':')
+    .ok_or_else(|| "it does not have a scheme".to_string())?;
+  if !scheme.starts_with(|ch: char| ch.is_ascii_alphabetic()) ||
+    !scheme.chars().all(|ch| ch.is_ascii_alphanumeric() || "+-.".contains(ch)) {
+    return Err(format!("'{}' is not a valid scheme", scheme));
+  }
+
+  let (rest, fragment) = match rest.split_once('#') {
+    Some((rest, fragment)) => (rest, Some(fragment)),
+    None => (rest, None)
+  };
+  let (hier_part, query) = match rest.split_once('?') {
+    Some((hier_part, query)) => (hier_part, Some(query)),
+    None => (rest, None)
+  };
+
+  let path = match hier_part.strip_prefix("//") {
+    Some(authority_and_path) => {
+      let (authority, path) = match authority_and_path.find('/') {
+        Some(index) => authority_and_path.split_at(index),
+        None => (authority_and_path, "")
+      };
+      validate_uri_authority(authority)?;
+      path
+    }
+    None => hier_part
+  };
+  validate_uri_component(path, ":@/", "path")?;
+  if let Some(query) = query {
+    validate_uri_component(query, ":@/?", "query")?;
+  }
+  if let Some(fragment) = fragment {
+    validate_uri_component(fragment, ":@/?", "fragment")?;
+  }
+  Ok(())
+}
+
+// authority = [ userinfo "@" ] host [ ":" port ]
+fn validate_uri_authority(authority: &str) -> Result<(), String> {
+  let host_and_port = match authority.rsplit_once('@') {
+    Some((user_info, host_and_port)) => {
+      validate_uri_component(user_info, ":", "user information")?;
+      host_and_port
+    }
+    None => authority
+  };
+
+  let port = if let Some(literal) = host_and_port.strip_prefix('[') {
+    let (address, port) = literal.split_once(']')
+      .ok_or_else(|| format!("the host '{}' is missing a closing ']'", host_and_port))?;
+    Ipv6Addr::from_str(address)
+      .map_err(|err| format!("the host '[{}]' is not a valid IPv6 address - {}", address, err))?;
+    match port {
+      "" => None,
+      _ => Some(port.strip_prefix(':')
+        .ok_or_else(|| format!("expected a ':' before the port, but got '{}'", port))?)
+    }
+  } else {
+    let (host, port) = match host_and_port.rsplit_once(':') {
+      Some((host, port)) => (host, Some(port)),
+      None => (host_and_port, None)
+    };
+    validate_uri_component(host, "", "host")?;
+    port
+  };
+
+  match port {
+    Some(port) if !port.chars().all(|ch| ch.is_ascii_digit()) => Err(format!("'{}' is not a valid port", port)),
+    _ => Ok(())
+  }
+}
+
+// Checks the characters are unreserved, sub-delims, percent-encoded or in the extra allowed set
+fn validate_uri_component(value: &str, extra: &str, component: &str) -> Result<(), String> {
+  let mut chars = value.chars();
+  while let Some(ch) = chars.next() {
+    if ch == '%' {
+      let hex = chars.by_ref().take(2).collect::<String>();
+      if hex.len() != 2 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err(format!("the {} has an invalid percent-encoded sequence '%{}'", component, hex));
+      }
+    } else if !(ch.is_ascii_alphanumeric() || "-._~!$&'()*+,;=".contains(ch) || extra.contains(ch)) {
+      return Err(format!("the {} contains an invalid character '{}'", component, ch));
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(FormatType::Email)]
+  #[case(FormatType::Uri)]
+  #[case(FormatType::Ipv4)]
+  #[case(FormatType::Ipv6)]
+  #[case(FormatType::Uuid)]
+  #[case(FormatType::Ulid)]
+  #[case(FormatType::Hostname)]
+  fn format_round_trips_as_a_string(#[case] format: FormatType) {
+    expect!(FormatType::from_str(format.to_string().as_str()).unwrap()).to(be_equal_to(format));
+  }
+
+  #[test]
+  fn from_str_with_invalid_format() {
+    expect!(FormatType::from_str("IPv4").unwrap()).to(be_equal_to(FormatType::Ipv4));
+    expect!(FormatType::from_str("phone")).to(be_err());
+  }
+}
@@ -15,11 +15,14 @@ use tracing::{error, trace};
 
 use crate::{HttpStatus, PactSpecification};
 use crate::generators::{Generator, GeneratorCategory, Generators};
-use crate::json_utils::{json_to_num, json_to_string};
+use crate::json_utils::{hash_json, json_to_num, json_to_string};
 use crate::matchingrules::expressions::{MatchingReference, MatchingRuleDefinition, ValueType};
 use crate::path_exp::{DocPath, PathToken};
 
 pub mod expressions;
+pub mod format;
+
+pub use format::FormatType;
 
 fn generator_from_json(json: &Map<String, Value>) -> Option<Generator> {
   if let Some(generator_json) = json.get("generator") {
@@ -58,7 +61,7 @@ fn rules_from_json(attributes: &Map<String, Value>) -> anyhow::Result<Vec<Either
 }
 
 /// Set of all matching rules
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone)]
 pub enum MatchingRule {
   /// Matcher using equals
   Equality,
@@ -105,7 +108,27 @@ pub enum MatchingRule {
   /// Matcher for keys in a map
   EachKey(MatchingRuleDefinition),
   /// Matcher for values in a collection. This delegates to the Values matcher for maps.
-  EachValue(MatchingRuleDefinition)
+  EachValue(MatchingRuleDefinition),
+  /// Value must be a number between the minimum and maximum values. The last parameter is if the
+  /// bounds are inclusive.
+  NumberRange(f64, f64, bool),
+  /// Value must be a string with a length (in characters) between the minimum and maximum values
+  /// (inclusive)
+  StringLength(usize, usize),
+  /// Value must be equal to one of the given values (enumerated values)
+  OneOf(Vec<Value>),
+  /// Value must be valid against the JSON Schema (draft 2020-12 subset)
+  JsonSchema(Value),
+  /// Items in a list must be unique. If a key path is given, the value at that path in each item
+  /// must be unique.
+  UniqueItems(Option<DocPath>),
+  /// Items in a list must be sorted by the value at the given path (`$` for the item itself)
+  Sorted(DocPath, SortOrder),
+  /// String value must be of the given well-known format (email, URI, IP address, etc.)
+  Format(FormatType),
+  /// Value must be equal to the result of the expression evaluated against the actual document
+  /// (i.e. `sum($.items[*].price)` or `$.password`)
+  Expression(String)
 }
 
 impl MatchingRule {
@@ -231,6 +254,20 @@ impl MatchingRule {
 
         Value::Object(map.clone())
       }
+      MatchingRule::NumberRange(min, max, inclusive) => json!({ "match": "numberRange",
+        "min": min, "max": max, "inclusive": inclusive }),
+      MatchingRule::StringLength(min, max) => json!({ "match": "stringLength",
+        "min": json!(*min as u64), "max": json!(*max as u64) }),
+      MatchingRule::OneOf(values) => json!({ "match": "oneOf", "values": values }),
+      MatchingRule::JsonSchema(schema) => json!({ "match": "jsonSchema", "schema": schema }),
+      MatchingRule::UniqueItems(key) => match key {
+        Some(key) => json!({ "match": "uniqueItems", "key": key.to_string() }),
+        None => json!({ "match": "uniqueItems" })
+      },
+      MatchingRule::Sorted(field, order) => json!({ "match": "sorted",
+        "field": field.to_string(), "order": order.to_string() }),
+      MatchingRule::Format(format) => json!({ "match": "format", "format": format.to_string() }),
+      MatchingRule::Expression(expression) => json!({ "match": "expression", "expression": expression })
     }
   }
 
@@ -276,7 +313,15 @@ impl MatchingRule {
       MatchingRule::NotEmpty => "not-empty",
       MatchingRule::Semver => "semver",
       MatchingRule::EachKey(_) => "each-key",
-      MatchingRule::EachValue(_) => "each-value"
+      MatchingRule::EachValue(_) => "each-value",
+      MatchingRule::NumberRange(_, _, _) => "number-range",
+      MatchingRule::StringLength(_, _) => "string-length",
+      MatchingRule::OneOf(_) => "one-of",
+      MatchingRule::JsonSchema(_) => "json-schema",
+      MatchingRule::UniqueItems(_) => "unique-items",
+      MatchingRule::Sorted(_, _) => "sorted",
+      MatchingRule::Format(_) => "format",
+      MatchingRule::Expression(_) => "expression"
     }.to_string()
   }
 
@@ -328,6 +373,21 @@ impl MatchingRule {
 
         map
       }
+      MatchingRule::NumberRange(min, max, inclusive) => hashmap!{
+        "min" => json!(min), "max" => json!(max), "inclusive" => json!(inclusive)
+      },
+      MatchingRule::StringLength(min, max) => hashmap!{ "min" => json!(min), "max" => json!(max) },
+      MatchingRule::OneOf(values) => hashmap!{ "values" => Value::Array(values.clone()) },
+      MatchingRule::JsonSchema(schema) => hashmap!{ "schema" => schema.clone() },
+      MatchingRule::UniqueItems(key) => match key {
+        Some(key) => hashmap!{ "key" => Value::String(key.to_string()) },
+        None => hashmap!{}
+      },
+      MatchingRule::Sorted(field, order) => hashmap!{
+        "field" => Value::String(field.to_string()), "order" => Value::String(order.to_string())
+      },
+      MatchingRule::Format(format) => hashmap!{ "format" => Value::String(format.to_string()) },
+      MatchingRule::Expression(expression) => hashmap!{ "expression" => Value::String(expression.clone()) }
     }
   }
 
@@ -471,6 +531,59 @@ impl MatchingRule {
         };
         Ok(MatchingRule::EachValue(definition))
       }
+      "numberRange" | "number-range" => {
+        let min = attributes.get("min").and_then(|v| v.as_f64())
+          .ok_or_else(|| anyhow!("NumberRange matcher missing 'min' field"))?;
+        let max = attributes.get("max").and_then(|v| v.as_f64())
+          .ok_or_else(|| anyhow!("NumberRange matcher missing 'max' field"))?;
+        let inclusive = attributes.get("inclusive").and_then(|v| v.as_bool()).unwrap_or(true);
+        Ok(MatchingRule::NumberRange(min, max, inclusive))
+      }
+      "stringLength" | "string-length" => {
+        let min = json_to_num(attributes.get("min").cloned())
+          .ok_or_else(|| anyhow!("StringLength matcher missing 'min' field"))?;
+        let max = json_to_num(attributes.get("max").cloned())
+          .ok_or_else(|| anyhow!("StringLength matcher missing 'max' field"))?;
+        Ok(MatchingRule::StringLength(min, max))
+      }
+      "oneOf" | "one-of" => match attributes.get("values") {
+        Some(Value::Array(values)) => Ok(MatchingRule::OneOf(values.clone())),
+        Some(_) => Err(anyhow!("OneOf matcher 'values' field is not an Array")),
+        None => Err(anyhow!("OneOf matcher missing 'values' field"))
+      }
+      "jsonSchema" | "json-schema" => match attributes.get("schema") {
+        Some(schema) if schema.is_object() || schema.is_boolean() => Ok(MatchingRule::JsonSchema(schema.clone())),
+        Some(_) => Err(anyhow!("JsonSchema matcher 'schema' field is not an Object or Boolean")),
+        None => Err(anyhow!("JsonSchema matcher missing 'schema' field"))
+      }
+      "uniqueItems" | "unique-items" => match attributes.get("key") {
+        Some(Value::String(key)) => Ok(MatchingRule::UniqueItems(Some(DocPath::new(key)?))),
+        Some(Value::Null) | None => Ok(MatchingRule::UniqueItems(None)),
+        Some(_) => Err(anyhow!("UniqueItems matcher 'key' field is not a String"))
+      }
+      "sorted" => {
+        let field = match attributes.get("field") {
+          Some(Value::String(field)) => DocPath::new(field)?,
+          Some(_) => return Err(anyhow!("Sorted matcher 'field' field is not a String")),
+          None => DocPath::root()
+        };
+        let order = match attributes.get("order") {
+          Some(Value::String(order)) => SortOrder::try_from(order.as_str())?,
+          Some(_) => return Err(anyhow!("Sorted matcher 'order' field is not a String")),
+          None => SortOrder::Ascending
+        };
+        Ok(MatchingRule::Sorted(field, order))
+      }
+      "format" => match attributes.get("format") {
+        Some(Value::String(format)) => Ok(MatchingRule::Format(FormatType::from_str(format)?)),
+        Some(_) => Err(anyhow!("Format matcher 'format' field is not a String")),
+        None => Err(anyhow!("Format matcher missing 'format' field"))
+      }
+      "expression" => match attributes.get("expression") {
+        Some(Value::String(expression)) => Ok(MatchingRule::Expression(expression.clone())),
+        Some(_) => Err(anyhow!("Expression matcher 'expression' field is not a String")),
+        None => Err(anyhow!("Expression matcher missing 'expression' field"))
+      }
       _ => Err(anyhow!("{} is not a valid matching rule type", rule_type)),
     }
   }
@@ -511,6 +624,9 @@ impl MatchingRule {
       MatchingRule::Values => false,
       MatchingRule::EachValue(_) => false,
       MatchingRule::EachKey(_) => false,
+      MatchingRule::UniqueItems(_) => false,
+      MatchingRule::Sorted(_, _) => false,
+      MatchingRule::Expression(_) => false,
       _ => true
     }
   }
@@ -563,7 +679,19 @@ impl MatchingRule {
       MatchingRule::NotEmpty => "must not be empty".to_string(),
       MatchingRule::Semver => "must match a semver version".to_string(),
       MatchingRule::EachKey(m) => format!("each key must match '{}'", m.expression()),
-      MatchingRule::EachValue(m) => format!("each value must match '{}'", m.expression())
+      MatchingRule::EachValue(m) => format!("each value must match '{}'", m.expression()),
+      MatchingRule::NumberRange(min, max, inclusive) => format!("must be a number between {} and {} ({})",
+        min, max, if *inclusive { "inclusive" } else { "exclusive" }),
+      MatchingRule::StringLength(min, max) => format!("must have a length of between {} and {} characters", min, max),
+      MatchingRule::OneOf(values) => format!("must be one of {}", values.iter().join(", ")),
+      MatchingRule::JsonSchema(_) => "must be valid against the JSON schema".to_string(),
+      MatchingRule::UniqueItems(key) => match key {
+        Some(key) => format!("must have unique values for '{}'", key),
+        None => "must have unique items".to_string()
+      },
+      MatchingRule::Sorted(field, order) => format!("must be sorted by '{}' in {} order", field, order.description()),
+      MatchingRule::Format(format) => format!("must be {}", format.description()),
+      MatchingRule::Expression(expression) => format!("must equal the value of the expression '{}'", expression)
     }
   }
 
@@ -606,6 +734,26 @@ impl Hash for MatchingRule {
           }
         }
       }
+      MatchingRule::NumberRange(min, max, inclusive) => {
+        min.to_bits().hash(state);
+        max.to_bits().hash(state);
+        inclusive.hash(state);
+      }
+      MatchingRule::StringLength(min, max) => {
+        min.hash(state);
+        max.hash(state);
+      }
+      MatchingRule::OneOf(values) => for value in values {
+        hash_json(value, state);
+      }
+      MatchingRule::JsonSchema(schema) => hash_json(schema, state),
+      MatchingRule::UniqueItems(key) => key.hash(state),
+      MatchingRule::Sorted(field, order) => {
+        field.hash(state);
+        order.hash(state);
+      }
+      MatchingRule::Format(format) => format.hash(state),
+      MatchingRule::Expression(expression) => expression.hash(state),
       _ => ()
     }
   }
@@ -626,11 +774,65 @@ impl PartialEq for MatchingRule {
       (MatchingRule::ArrayContains(variants1), MatchingRule::ArrayContains(variants2)) => variants1 == variants2,
       (MatchingRule::EachKey(definition1), MatchingRule::EachKey(definition2)) => definition1 == definition2,
       (MatchingRule::EachValue(definition1), MatchingRule::EachValue(definition2)) => definition1 == definition2,
+      (MatchingRule::NumberRange(min1, max1, inclusive1), MatchingRule::NumberRange(min2, max2, inclusive2)) =>
+        min1 == min2 && max1 == max2 && inclusive1 == inclusive2,
+      (MatchingRule::StringLength(min1, max1), MatchingRule::StringLength(min2, max2)) => min1 == min2 && max1 == max2,
+      (MatchingRule::OneOf(values1), MatchingRule::OneOf(values2)) => values1 == values2,
+      (MatchingRule::JsonSchema(schema1), MatchingRule::JsonSchema(schema2)) => schema1 == schema2,
+      (MatchingRule::UniqueItems(key1), MatchingRule::UniqueItems(key2)) => key1 == key2,
+      (MatchingRule::Sorted(field1, order1), MatchingRule::Sorted(field2, order2)) => field1 == field2 && order1 == order2,
+      (MatchingRule::Format(format1), MatchingRule::Format(format2)) => format1 == format2,
+      (MatchingRule::Expression(expression1), MatchingRule::Expression(expression2)) => expression1 == expression2,
       _ => mem::discriminant(self) == mem::discriminant(other)
     }
   }
 }
 
+// The range bounds of the NumberRange rule are floats, but they are never NaN as they come from
+// JSON numbers or expression literals
+impl Eq for MatchingRule {}
+
+/// Order that the items in a list must be sorted in for the Sorted matching rule
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, Default)]
+pub enum SortOrder {
+  /// Items must be in ascending order
+  #[default]
+  Ascending,
+  /// Items must be in descending order
+  Descending
+}
+
+impl SortOrder {
+  /// Description of the order to use in messages
+  pub fn description(&self) -> &'static str {
+    match self {
+      SortOrder::Ascending => "ascending",
+      SortOrder::Descending => "descending"
+    }
+  }
+}
+
+impl Display for SortOrder {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SortOrder::Ascending => write!(f, "asc"),
+      SortOrder::Descending => write!(f, "desc")
+    }
+  }
+}
+
+impl TryFrom<&str> for SortOrder {
+  type Error = anyhow::Error;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    match value.to_lowercase().as_str() {
+      "asc" | "ascending" => Ok(SortOrder::Ascending),
+      "desc" | "descending" => Ok(SortOrder::Descending),
+      _ => Err(anyhow!("'{}' is not a valid sort order, it must be either 'asc' or 'desc'", value))
+    }
+  }
+}
+
 /// Enumeration to define how to combine rules
 #[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
 pub enum RuleLogic {
@@ -2368,6 +2570,147 @@ mod tests {
       })));
   }
 
+  #[test]
+  fn number_range_and_string_length_rules_json_test() {
+    expect!(MatchingRule::from_json(&json!({ "match": "numberRange", "min": 1, "max": 10.5 }))).to(
+      be_ok().value(MatchingRule::NumberRange(1.0, 10.5, true)));
+    expect!(MatchingRule::from_json(&json!({ "match": "numberRange", "min": 0, "max": 1, "inclusive": false }))).to(
+      be_ok().value(MatchingRule::NumberRange(0.0, 1.0, false)));
+    expect!(MatchingRule::from_json(&json!({ "match": "numberRange", "min": 0 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "min": 1, "max": 20 }))).to(
+      be_ok().value(MatchingRule::StringLength(1, 20)));
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "max": 20 }))).to(be_err());
+
+    expect!(MatchingRule::NumberRange(1.0, 10.5, false).to_json()).to(
+      be_equal_to(json!({
+        "match": "numberRange",
+        "min": 1.0,
+        "max": 10.5,
+        "inclusive": false
+      })));
+    expect!(MatchingRule::StringLength(1, 20).to_json()).to(
+      be_equal_to(json!({
+        "match": "stringLength",
+        "min": 1,
+        "max": 20
+      })));
+  }
+
+  #[test]
+  fn one_of_rule_json_test() {
+    expect!(MatchingRule::from_json(&json!({ "match": "oneOf", "values": ["A", "B", 1, true] }))).to(
+      be_ok().value(MatchingRule::OneOf(vec![json!("A"), json!("B"), json!(1), json!(true)])));
+    expect!(MatchingRule::from_json(&json!({ "match": "oneOf", "values": "A" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "oneOf" }))).to(be_err());
+
+    let rule = MatchingRule::OneOf(vec![json!("ACTIVE"), json!("CLOSED")]);
+    expect!(rule.to_json()).to(be_equal_to(json!({
+      "match": "oneOf",
+      "values": ["ACTIVE", "CLOSED"]
+    })));
+    expect!(rule.generate_description(false)).to(be_equal_to("must be one of \"ACTIVE\", \"CLOSED\""));
+    expect!(MatchingRule::create(rule.name().as_str(), &Value::Object(rule.values().iter()
+      .map(|(k, v)| (k.to_string(), v.clone()))
+      .collect()))).to(be_ok().value(rule));
+  }
+
+  #[test]
+  fn json_schema_rule_json_test() {
+    let schema = json!({
+      "type": "object",
+      "required": ["id"],
+      "properties": { "id": { "type": "integer" } }
+    });
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonSchema", "schema": schema.clone() }))).to(
+      be_ok().value(MatchingRule::JsonSchema(schema.clone())));
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonSchema", "schema": "object" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonSchema" }))).to(be_err());
+
+    let rule = MatchingRule::JsonSchema(schema.clone());
+    expect!(rule.to_json()).to(be_equal_to(json!({
+      "match": "jsonSchema",
+      "schema": schema
+    })));
+    expect!(MatchingRule::create(rule.name().as_str(), &Value::Object(rule.values().iter()
+      .map(|(k, v)| (k.to_string(), v.clone()))
+      .collect()))).to(be_ok().value(rule));
+  }
+
+  #[test]
+  fn unique_items_and_sorted_rule_json_test() {
+    expect!(MatchingRule::from_json(&json!({ "match": "uniqueItems" }))).to(
+      be_ok().value(MatchingRule::UniqueItems(None)));
+    expect!(MatchingRule::from_json(&json!({ "match": "uniqueItems", "key": "$.id" }))).to(
+      be_ok().value(MatchingRule::UniqueItems(Some(DocPath::new_unwrap("$.id")))));
+    expect!(MatchingRule::from_json(&json!({ "match": "sorted" }))).to(
+      be_ok().value(MatchingRule::Sorted(DocPath::root(), SortOrder::Ascending)));
+    expect!(MatchingRule::from_json(&json!({ "match": "sorted", "field": "$.name", "order": "desc" }))).to(
+      be_ok().value(MatchingRule::Sorted(DocPath::new_unwrap("$.name"), SortOrder::Descending)));
+    expect!(MatchingRule::from_json(&json!({ "match": "sorted", "order": "sideways" }))).to(be_err());
+
+    expect!(MatchingRule::UniqueItems(Some(DocPath::new_unwrap("$.id"))).to_json()).to(be_equal_to(json!({
+      "match": "uniqueItems",
+      "key": "$.id"
+    })));
+    expect!(MatchingRule::Sorted(DocPath::new_unwrap("$.name"), SortOrder::Ascending).to_json()).to(be_equal_to(json!({
+      "match": "sorted",
+      "field": "$.name",
+      "order": "asc"
+    })));
+
+    for rule in [
+      MatchingRule::UniqueItems(None),
+      MatchingRule::UniqueItems(Some(DocPath::new_unwrap("$.id"))),
+      MatchingRule::Sorted(DocPath::new_unwrap("$.name"), SortOrder::Descending)
+    ] {
+      let values = Value::Object(rule.values().iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect());
+      expect!(MatchingRule::create(rule.name().as_str(), &values)).to(be_ok().value(rule));
+    }
+  }
+
+  #[test]
+  fn format_rule_json_test() {
+    expect!(MatchingRule::from_json(&json!({ "match": "format", "format": "email" }))).to(
+      be_ok().value(MatchingRule::Format(FormatType::Email)));
+    expect!(MatchingRule::from_json(&json!({ "match": "format", "format": "phone" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "format" }))).to(be_err());
+
+    let rule = MatchingRule::Format(FormatType::Ipv6);
+    expect!(rule.to_json()).to(be_equal_to(json!({ "match": "format", "format": "ipv6" })));
+    expect!(rule.generate_description(false)).to(be_equal_to("must be an IPv6 address"));
+    expect!(MatchingRule::create(rule.name().as_str(), &Value::Object(rule.values().iter()
+      .map(|(k, v)| (k.to_string(), v.clone()))
+      .collect()))).to(be_ok().value(rule));
+  }
+
+  #[test]
+  fn expression_rule_json_test() {
+    expect!(MatchingRule::from_json(&json!({ "match": "expression", "expression": "sum($.items[*].price)" }))).to(
+      be_ok().value(MatchingRule::Expression("sum($.items[*].price)".to_string())));
+    expect!(MatchingRule::from_json(&json!({ "match": "expression", "expression": 100 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "expression" }))).to(be_err());
+
+    let rule = MatchingRule::Expression("$.password".to_string());
+    expect!(rule.to_json()).to(be_equal_to(json!({ "match": "expression", "expression": "$.password" })));
+    expect!(rule.generate_description(false)).to(be_equal_to("must equal the value of the expression '$.password'"));
+    expect!(rule.can_cascade()).to(be_false());
+    expect!(MatchingRule::create(rule.name().as_str(), &Value::Object(rule.values().iter()
+      .map(|(k, v)| (k.to_string(), v.clone()))
+      .collect()))).to(be_ok().value(rule));
+  }
+
+  #[test]
+  fn number_range_and_string_length_rules_can_be_created_from_name_and_values() {
+    for rule in [MatchingRule::NumberRange(-1.5, 100.0, false), MatchingRule::StringLength(2, 10)] {
+      let values = Value::Object(rule.values().iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect());
+      expect!(MatchingRule::create(rule.name().as_str(), &values)).to(be_ok().value(rule));
+    }
+  }
+
   #[test]
   fn matcher_is_defined_returns_false_when_there_are_no_matchers() {
     let matchers = matchingrules!{};
@@ -2,17 +2,21 @@
 
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use itertools::Either;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use itertools::{Either, Itertools};
 use maplit::*;
 use serde_json::{json, Value};
 
 use pact_models::bodies::OptionalBody;
 use pact_models::content_types::JSON;
 use pact_models::provider_states::ProviderState;
+use pact_models::query_strings::build_query_string;
 use pact_models::v4::http_parts::HttpRequest;
 use tracing::warn;
 
@@ -87,6 +91,13 @@ pub trait ProviderStateExecutor: Debug {
 
   /// If a teardown call for the Executor should be performed
   fn teardown(self: &Self)-> bool;
+
+  /// Describes the call that would be made for the given provider state, without executing it.
+  /// This is used by dry runs to display the state change calls. Returns `None` if no call would
+  /// be made.
+  fn describe_call(&self, provider_state: &ProviderState, setup: bool) -> Option<String> {
+    Some(format!("{} provider state '{}'", if setup { "Set up" } else { "Tear down" }, provider_state.name))
+  }
 }
 
 /// Default provider state callback executor, which executes an HTTP request
@@ -114,6 +125,41 @@ impl Default for HttpRequestProviderStateExecutor {
   }
 }
 
+impl HttpRequestProviderStateExecutor {
+  /// Request that is sent to the state change URL for the provider state
+  fn state_change_request(&self, provider_state: &ProviderState, setup: bool) -> HttpRequest {
+    let mut state_change_request = HttpRequest { method: "POST".to_string(), .. HttpRequest::default() };
+    if self.state_change_body {
+      let json_body = json!({
+        "state".to_string() : provider_state.name.clone(),
+        "params".to_string() : provider_state.params.clone(),
+        "action".to_string() : if setup {
+          "setup".to_string()
+        } else {
+          "teardown".to_string()
+        }
+      });
+      state_change_request.body = OptionalBody::Present(json_body.to_string().into(), Some(JSON.clone()), None);
+      state_change_request.headers = Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] });
+    } else {
+      let mut query = hashmap!{ "state".to_string() => vec![Some(provider_state.name.clone())] };
+      if setup {
+        query.insert("action".to_string(), vec![Some("setup".to_string())]);
+      } else {
+        query.insert("action".to_string(), vec![Some("teardown".to_string())]);
+      }
+      for (k, v) in provider_state.params.clone() {
+        query.insert(k, vec![match v {
+          Value::String(ref s) => Some(s.clone()),
+          _ => Some(v.to_string())
+        }]);
+      }
+      state_change_request.query = Some(query);
+    }
+    state_change_request
+  }
+}
+
 #[async_trait]
 impl ProviderStateExecutor for HttpRequestProviderStateExecutor {
   async fn call(
@@ -125,34 +171,7 @@ impl ProviderStateExecutor for HttpRequestProviderStateExecutor {
   ) -> anyhow::Result<HashMap<String, Value>> {
     match &self.state_change_url {
       Some(state_change_url) => {
-        let mut state_change_request = HttpRequest { method: "POST".to_string(), .. HttpRequest::default() };
-        if self.state_change_body {
-          let json_body = json!({
-                    "state".to_string() : provider_state.name.clone(),
-                    "params".to_string() : provider_state.params.clone(),
-                    "action".to_string() : if setup {
-                        "setup".to_string()
-                    } else {
-                        "teardown".to_string()
-                    }
-                });
-          state_change_request.body = OptionalBody::Present(json_body.to_string().into(), Some(JSON.clone()), None);
-          state_change_request.headers = Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] });
-        } else {
-          let mut query = hashmap!{ "state".to_string() => vec![Some(provider_state.name.clone())] };
-          if setup {
-            query.insert("action".to_string(), vec![Some("setup".to_string())]);
-          } else {
-            query.insert("action".to_string(), vec![Some("teardown".to_string())]);
-          }
-          for (k, v) in provider_state.params.clone() {
-            query.insert(k, vec![match v {
-              Value::String(ref s) => Some(s.clone()),
-              _ => Some(v.to_string())
-            }]);
-          }
-          state_change_request.query = Some(query);
-        }
+        let state_change_request = self.state_change_request(provider_state, setup);
         make_state_change_request(client.unwrap_or(&reqwest::Client::default()), &state_change_url, &state_change_request, self.reties).await
           .map_err(|err| ProviderStateError { description: err.to_string(), interaction_id }.into())
       },
@@ -170,4 +189,118 @@ impl ProviderStateExecutor for HttpRequestProviderStateExecutor {
   ) -> bool {
     return self.state_change_teardown;
   }
+
+  fn describe_call(&self, provider_state: &ProviderState, setup: bool) -> Option<String> {
+    self.state_change_url.as_ref().map(|url| {
+      let request = self.state_change_request(provider_state, setup);
+      match &request.query {
+        Some(query) => format!("{} {}?{}", request.method, url, build_query_string(query.clone())),
+        None => format!("{} {} {}", request.method, url, request.body.display_string())
+      }
+    })
+  }
+}
+
+type StateCallback = Arc<dyn Fn(ProviderState, bool) -> BoxFuture<'static, anyhow::Result<HashMap<String, Value>>> + Send + Sync>;
+
+/// Provider state executor that invokes async callbacks registered for each provider state. This
+/// is intended for verifying providers running in the same process as the verifier.
+///
+/// The callbacks are passed the provider state and a flag indicating if this is a setup (true) or
+/// teardown (false) call, and can return a map of values to use in any provider state generators.
+#[derive(Clone, Default)]
+pub struct CallbackProviderStateExecutor {
+  callbacks: HashMap<String, StateCallback>,
+  teardown: bool
+}
+
+impl CallbackProviderStateExecutor {
+  /// Create an executor with no callbacks registered
+  pub fn new() -> Self {
+    CallbackProviderStateExecutor::default()
+  }
+
+  /// Registers the callback for the provider state
+  pub fn state<F, Fut>(mut self, name: &str, callback: F) -> Self
+    where F: Fn(ProviderState, bool) -> Fut + Send + Sync + 'static,
+          Fut: Future<Output = anyhow::Result<HashMap<String, Value>>> + Send + 'static
+  {
+    self.callbacks.insert(name.to_string(), Arc::new(move |state, setup| callback(state, setup).boxed()));
+    self
+  }
+
+  /// Sets if the callbacks should also be invoked to tear down the provider states (default is false)
+  pub fn with_teardown(mut self, teardown: bool) -> Self {
+    self.teardown = teardown;
+    self
+  }
+}
+
+impl Debug for CallbackProviderStateExecutor {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("CallbackProviderStateExecutor")
+      .field("states", &self.callbacks.keys().sorted().collect_vec())
+      .field("teardown", &self.teardown)
+      .finish()
+  }
+}
+
+#[async_trait]
+impl ProviderStateExecutor for CallbackProviderStateExecutor {
+  async fn call(
+    self: Arc<Self>,
+    interaction_id: Option<String>,
+    provider_state: &ProviderState,
+    setup: bool,
+    _client: Option<&reqwest::Client>
+  ) -> anyhow::Result<HashMap<String, Value>> {
+    match self.callbacks.get(&provider_state.name) {
+      Some(callback) => callback(provider_state.clone(), setup).await
+        .map_err(|err| ProviderStateError { description: err.to_string(), interaction_id }.into()),
+      None if setup && !provider_state.name.is_empty() => Err(ProviderStateError {
+        description: format!("No callback has been registered for provider state '{}'", provider_state.name),
+        interaction_id
+      }.into()),
+      None => Ok(hashmap!{})
+    }
+  }
+
+  fn teardown(&self) -> bool {
+    self.teardown
+  }
+
+  fn describe_call(&self, provider_state: &ProviderState, setup: bool) -> Option<String> {
+    self.callbacks.get(&provider_state.name)
+      .map(|_| format!("Callback for provider state '{}' ({})", provider_state.name,
+        if setup { "setup" } else { "teardown" }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn callback_provider_state_executor_invokes_the_registered_callback() {
+    let executor = Arc::new(CallbackProviderStateExecutor::new()
+      .state("a user exists", |state, setup| async move {
+        Ok(hashmap!{ "id".to_string() => json!(state.params.len()), "setup".to_string() => json!(setup) })
+      }));
+
+    let result = executor.clone().call(None, &ProviderState::default("a user exists"), true, None).await;
+    expect!(result.unwrap()).to(be_equal_to(hashmap!{
+      "id".to_string() => json!(0),
+      "setup".to_string() => json!(true)
+    }));
+
+    let result = executor.clone().call(Some("1234".to_string()), &ProviderState::default("no user exists"), true, None).await;
+    expect!(result.unwrap_err().to_string()).to(be_equal_to(
+      "Provider state failed: (interaction_id: 1234) No callback has been registered for provider state 'no user exists'"));
+
+    let result = executor.clone().call(None, &ProviderState::default("no user exists"), false, None).await;
+    expect!(result.unwrap()).to(be_equal_to(hashmap!{}));
+  }
 }
@@ -0,0 +1,289 @@
+//! Support for dry runs of the verification, where the requests that would be sent to the provider
+//! are generated and displayed, but the provider is not contacted.
+
+use std::collections::HashMap;
+use std::panic::RefUnwindSafe;
+use std::sync::Arc;
+
+use ansi_term::Colour::*;
+use ansi_term::Style;
+use pact_models::generators::{GENERATOR_SEED_KEY, GeneratorTestMode};
+use pact_models::interaction::Interaction;
+use pact_models::pact::Pact;
+use pact_models::provider_states::ProviderState;
+use pact_models::query_strings::build_query_string;
+use pact_models::v4::http_parts::HttpRequest;
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+use crate::{FilterInfo, filter_interaction, http_transport, ProviderInfo, VerificationOptions};
+use crate::callback_executors::{ProviderStateExecutor, RequestFilterExecutor};
+use crate::messages::{message_request, sync_message_request};
+use crate::provider_client::{apply_request_filter, join_paths, provider_base_url, with_custom_headers};
+use crate::verification_result::{DryRunInteractionResult, StateChangeCall};
+
+/// Does a dry run of the interactions in the Pact, returning the calls that would have been made
+/// along with the output to display
+pub(crate) async fn dry_run_pact<'a, F: RequestFilterExecutor, S: ProviderStateExecutor>(
+  provider_info: &ProviderInfo,
+  filter: &FilterInfo,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  options: &VerificationOptions<F>,
+  provider_state_executor: &Arc<S>
+) -> (Vec<DryRunInteractionResult>, Vec<String>) {
+  let mut results = vec![];
+  let mut output = vec![];
+
+  for interaction in pact.interactions().iter()
+    .filter(|interaction| filter_interaction(pact.consumer().name.as_str(), interaction.as_ref(), filter)) {
+    let result = dry_run_interaction(provider_info, interaction.as_ref(), &pact.consumer().name,
+      options, provider_state_executor).await;
+    display_dry_run_result(&result, interaction.as_ref(), options.coloured_output, &mut output);
+    results.push(result);
+  }
+
+  (results, output)
+}
+
+/// Generates the state change calls and request that would be made to verify the interaction
+pub(crate) async fn dry_run_interaction<F: RequestFilterExecutor, S: ProviderStateExecutor>(
+  provider: &ProviderInfo,
+  interaction: &(dyn Interaction + Send + Sync + RefUnwindSafe),
+  consumer_name: &str,
+  options: &VerificationOptions<F>,
+  provider_state_executor: &Arc<S>
+) -> DryRunInteractionResult {
+  debug!("Dry run of interaction {} {} ({:?})", interaction.type_of(), interaction.description(), interaction.id());
+  let provider_states = if interaction.provider_states().is_empty() {
+    vec![ProviderState::default("")]
+  } else {
+    interaction.provider_states()
+  };
+  let mut state_changes = state_change_calls(&provider_states, true, provider_state_executor);
+
+  // The state change calls are not made, so only the parameters from the Pact are available to
+  // the generators
+  let mut context: HashMap<String, Value> = interaction.provider_states().iter()
+    .flat_map(|state| state.params.clone())
+    .collect();
+  if let Some(seed) = options.generator_seed {
+    context.insert(GENERATOR_SEED_KEY.to_string(), json!(seed));
+  }
+  let context = context.iter()
+    .map(|(k, v)| (k.as_str(), v.clone()))
+    .collect();
+
+  let (request, base_url, message) = match dry_run_request(provider, interaction, options, &context).await {
+    Ok((request, base_url)) => (Some(request), Some(base_url), None),
+    Err(message) => (None, None, Some(message))
+  };
+
+  if provider_state_executor.teardown() {
+    state_changes.extend(state_change_calls(&provider_states, false, provider_state_executor));
+  }
+
+  DryRunInteractionResult {
+    interaction_id: interaction.id(),
+    interaction_key: interaction.as_v4().and_then(|i| i.key()),
+    interaction_description: interaction.description(),
+    consumer_name: consumer_name.to_string(),
+    state_changes,
+    base_url,
+    request,
+    message
+  }
+}
+
+fn state_change_calls<S: ProviderStateExecutor>(
+  provider_states: &[ProviderState],
+  setup: bool,
+  provider_state_executor: &Arc<S>
+) -> Vec<StateChangeCall> {
+  provider_states.iter()
+    .filter_map(|state| provider_state_executor.describe_call(state, setup)
+      .map(|description| StateChangeCall {
+        state: state.name.clone(),
+        setup,
+        description
+      }))
+    .collect()
+}
+
+async fn dry_run_request<F: RequestFilterExecutor>(
+  provider: &ProviderInfo,
+  interaction: &(dyn Interaction + Send + Sync + RefUnwindSafe),
+  options: &VerificationOptions<F>,
+  context: &HashMap<&str, Value>
+) -> Result<(HttpRequest, String), String> {
+  #[cfg(feature = "plugins")]
+  {
+    let plugin_transport = interaction.as_v4()
+      .and_then(|i| i.transport())
+      .and_then(|t| pact_plugin_driver::catalogue_manager::lookup_entry(&*format!("transport/{}", t)))
+      .filter(|entry| entry.provider_type == pact_plugin_driver::catalogue_manager::CatalogueEntryProviderType::PLUGIN);
+    if let Some(transport) = plugin_transport {
+      return Err(format!("The request for the '{}' transport is made by a plugin, so can not be displayed", transport.key));
+    }
+  }
+
+  let (request, transport) = if let Some(interaction) = interaction.as_v4_http() {
+    let request = pact_matching::generate_request(&interaction.request, &GeneratorTestMode::Provider, context).await;
+    (request, http_transport(provider, &interaction))
+  } else if interaction.is_message() {
+    message_request(provider, interaction)
+  } else if let Some(message) = interaction.as_v4_sync_message() {
+    sync_message_request(provider, &message)
+  } else {
+    return Err(format!("{} interactions are not supported", interaction.type_of()));
+  };
+
+  info!("Dry run, request for '{}' will not be sent to the provider", interaction.description());
+  let request = with_custom_headers(&apply_request_filter(&request, options), &options.custom_headers);
+
+  Ok((request, provider_base_url(provider, transport)))
+}
+
+fn display_dry_run_result(
+  result: &DryRunInteractionResult,
+  interaction: &(dyn Interaction + Send + Sync + RefUnwindSafe),
+  coloured: bool,
+  output: &mut Vec<String>
+) {
+  let plain = Style::new();
+  output.push(String::default());
+  output.push(format!("  {} {}", interaction.description(),
+    if coloured { Yellow.paint("[DRY RUN]") } else { plain.paint("[DRY RUN]") }));
+  for (index, state) in interaction.provider_states().iter().enumerate() {
+    output.push(format!("     {} {}", if index == 0 { "Given" } else { "And" }, state.name));
+  }
+
+  for call in result.state_changes.iter().filter(|call| call.setup) {
+    output.push(format!("    State change (setup): {}", call.description));
+  }
+
+  match (&result.request, &result.base_url) {
+    (Some(request), Some(base_url)) => {
+      let url = join_paths(base_url, &request.path);
+      let query = request.query.as_ref()
+        .filter(|query| !query.is_empty())
+        .map(|query| format!("?{}", build_query_string(query.clone())))
+        .unwrap_or_default();
+      output.push(format!("    Request: {} {}{}", request.method.to_uppercase(),
+        if coloured { Style::new().bold().paint(url) } else { plain.paint(url) }, query));
+      if let Some(headers) = &request.headers {
+        let mut headers = headers.iter().collect::<Vec<_>>();
+        headers.sort();
+        for (key, values) in headers {
+          output.push(format!("      {}: {}", key, values.join(", ")));
+        }
+      }
+      if request.body.is_present() {
+        output.push(String::default());
+        for line in request.body.display_string().lines() {
+          output.push(format!("      {}", line));
+        }
+      }
+    }
+    _ => if let Some(message) = &result.message {
+      output.push(format!("    {}", if coloured { Yellow.paint(message) } else { plain.paint(message) }));
+    }
+  }
+
+  for call in result.state_changes.iter().filter(|call| !call.setup) {
+    output.push(format!("    State change (teardown): {}", call.description));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_models::bodies::OptionalBody;
+  use pact_models::generators::{Generator, Generators, GeneratorCategory};
+  use pact_models::v4::synch_http::SynchronousHttp;
+
+  use crate::callback_executors::HttpRequestProviderStateExecutor;
+  use crate::NullRequestFilterExecutor;
+  use crate::ProviderTransport;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn dry_run_interaction_generates_the_request_and_state_changes() {
+    let mut generators = Generators::default();
+    generators.add_generator(&GeneratorCategory::PATH,
+      Generator::ProviderStateGenerator("/users/${id}".to_string(), None));
+    let interaction = SynchronousHttp {
+      description: "a request for a user".to_string(),
+      provider_states: vec![ProviderState {
+        name: "a user exists".to_string(),
+        params: hashmap!{ "id".to_string() => json!(100) }
+      }],
+      request: HttpRequest {
+        method: "POST".to_string(),
+        path: "/users/1".to_string(),
+        query: Some(hashmap!{ "a".to_string() => vec![Some("b".to_string())] }),
+        body: OptionalBody::from("{\"name\":\"Bob\"}"),
+        generators,
+        .. HttpRequest::default()
+      },
+      .. SynchronousHttp::default()
+    };
+    #[allow(deprecated)]
+    let provider = ProviderInfo {
+      transports: vec![ProviderTransport {
+        transport: "http".to_string(),
+        port: Some(8080),
+        path: None,
+        scheme: None
+      }],
+      .. ProviderInfo::default()
+    };
+    let options = VerificationOptions::<NullRequestFilterExecutor> {
+      custom_headers: hashmap!{ "X-Api-Key".to_string() => "1234".to_string() },
+      .. VerificationOptions::default()
+    };
+    let executor = Arc::new(HttpRequestProviderStateExecutor {
+      state_change_url: Some("http://localhost:8080/state".to_string()),
+      state_change_teardown: true,
+      state_change_body: false,
+      .. HttpRequestProviderStateExecutor::default()
+    });
+
+    let result = dry_run_interaction(&provider, &interaction, "consumer", &options, &executor).await;
+
+    expect!(result.base_url.as_ref()).to(be_some().value("http://localhost:8080"));
+    let request = result.request.unwrap();
+    expect!(request.path).to(be_equal_to("/users/100"));
+    expect!(request.headers.unwrap()).to(be_equal_to(hashmap!{
+      "X-Api-Key".to_string() => vec!["1234".to_string()]
+    }));
+    expect!(result.state_changes).to(be_equal_to(vec![
+      StateChangeCall {
+        state: "a user exists".to_string(),
+        setup: true,
+        description: "POST http://localhost:8080/state?action=setup&id=100&state=a+user+exists".to_string()
+      },
+      StateChangeCall {
+        state: "a user exists".to_string(),
+        setup: false,
+        description: "POST http://localhost:8080/state?action=teardown&id=100&state=a+user+exists".to_string()
+      }
+    ]));
+  }
+
+  #[tokio::test]
+  async fn dry_run_interaction_with_no_state_change_url() {
+    let interaction = SynchronousHttp {
+      description: "a request for a user".to_string(),
+      .. SynchronousHttp::default()
+    };
+    let options = VerificationOptions::<NullRequestFilterExecutor>::default();
+    let executor = Arc::new(HttpRequestProviderStateExecutor::default());
+
+    let result = dry_run_interaction(&ProviderInfo::default(), &interaction, "consumer", &options, &executor).await;
+
+    expect!(result.state_changes.is_empty()).to(be_true());
+    expect!(result.request.is_some()).to(be_true());
+  }
+}
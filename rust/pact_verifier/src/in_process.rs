@@ -0,0 +1,213 @@
+//! Support for verifying a provider that is running in the same process as the verifier. The
+//! requests are dispatched directly to a handler instead of being sent to the provider over the
+//! network, so a Rust provider can be verified as a normal test without binding to a port.
+//!
+//! With the `tower` feature, any `tower::Service` that handles `http` requests (e.g. an axum
+//! `Router`) can be used as the provider via [`TowerServiceProvider`].
+
+use std::fmt::Debug;
+use std::panic::RefUnwindSafe;
+
+use async_trait::async_trait;
+use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+
+/// Handler that the requests to an in-process provider are dispatched to. Set the
+/// `provider_handler` field of the `VerificationOptions` to use it. The handler must be
+/// `RefUnwindSafe`, as the verification options are shared across FFI calls that catch panics.
+#[async_trait]
+pub trait ProviderRequestHandler: Debug + Send + Sync + RefUnwindSafe {
+  /// Handle the request to the provider, returning the response from the provider
+  async fn handle(&self, request: &HttpRequest) -> anyhow::Result<HttpResponse>;
+}
+
+#[cfg(feature = "tower")]
+mod tower_service_provider {
+  use std::fmt::{Debug, Formatter};
+  use std::future::poll_fn;
+  use std::panic::AssertUnwindSafe;
+
+  use anyhow::anyhow;
+  use async_trait::async_trait;
+  use bytes::Bytes;
+  use http::{HeaderName, HeaderValue, Method, Request, Response};
+  use http_body_util::{BodyExt, Full};
+  use pact_models::bodies::OptionalBody;
+  use pact_models::query_strings::build_query_string;
+  use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+  use tower_service::Service;
+
+  use crate::provider_client::{extract_headers, response_body};
+
+  use super::ProviderRequestHandler;
+
+  type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+  /// Provider that dispatches the requests to a `tower::Service`, for instance an axum `Router`.
+  /// The service is cloned for each request.
+  pub struct TowerServiceProvider<S> {
+    // The shared service is only ever cloned, never called, so it can't be left in an
+    // inconsistent state by a panic
+    service: AssertUnwindSafe<S>
+  }
+
+  impl <S> TowerServiceProvider<S> {
+    /// Create a provider that dispatches requests to the service
+    pub fn new(service: S) -> Self {
+      TowerServiceProvider { service: AssertUnwindSafe(service) }
+    }
+  }
+
+  impl <S: Clone> Clone for TowerServiceProvider<S> {
+    fn clone(&self) -> Self {
+      TowerServiceProvider::new(self.service.0.clone())
+    }
+  }
+
+  impl <S> Debug for TowerServiceProvider<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+      f.debug_struct("TowerServiceProvider")
+        .field("service", &std::any::type_name::<S>())
+        .finish()
+    }
+  }
+
+  #[async_trait]
+  impl <S, B> ProviderRequestHandler for TowerServiceProvider<S>
+    where S: Service<Request<Full<Bytes>>, Response = Response<B>> + Clone + Send + Sync + 'static,
+          S::Future: Send,
+          S::Error: Into<BoxError>,
+          B: http_body::Body + Send + 'static,
+          B::Data: Send,
+          B::Error: Into<BoxError>
+  {
+    async fn handle(&self, request: &HttpRequest) -> anyhow::Result<HttpResponse> {
+      let native_request = to_native_request(request)?;
+      let mut service = self.service.0.clone();
+      poll_fn(|cx| service.poll_ready(cx)).await
+        .map_err(|err| anyhow!("Provider service is not ready - {}", Into::<BoxError>::into(err)))?;
+
+      let native_response = service.call(native_request).await
+        .map_err(|err| anyhow!("Provider service failed to handle the request - {}", Into::<BoxError>::into(err)))?;
+
+      let (parts, body) = native_response.into_parts();
+      let body = body.collect().await
+        .map_err(|err| anyhow!("Failed to read the response body - {}", Into::<BoxError>::into(err)))?
+        .to_bytes();
+      let response = HttpResponse {
+        status: parts.status.as_u16(),
+        headers: extract_headers(&parts.headers),
+        .. HttpResponse::default()
+      };
+      Ok(HttpResponse {
+        body: response_body(body, &response),
+        .. response
+      })
+    }
+  }
+
+  fn to_native_request(request: &HttpRequest) -> anyhow::Result<Request<Full<Bytes>>> {
+    let mut uri = if request.path.starts_with('/') {
+      request.path.clone()
+    } else {
+      format!("/{}", request.path)
+    };
+    if let Some(query) = request.query.as_ref().filter(|query| !query.is_empty()) {
+      uri.push('?');
+      uri.push_str(build_query_string(query.clone()).as_str());
+    }
+
+    let method = Method::from_bytes(request.method.as_bytes())
+      .map_err(|err| anyhow!("'{}' is not a valid HTTP method - {}", request.method, err))?;
+    let mut builder = Request::builder()
+      .method(method)
+      .uri(uri);
+    if let Some(headers) = &request.headers {
+      for (name, values) in headers {
+        let name = HeaderName::try_from(name.as_str())?;
+        for value in values {
+          builder = builder.header(name.clone(), HeaderValue::from_str(value.as_str())?);
+        }
+      }
+    }
+
+    let body = match &request.body {
+      OptionalBody::Present(body, _, _) => body.clone(),
+      OptionalBody::Null if request.content_type().unwrap_or_default().is_json() => Bytes::from("null"),
+      _ => Bytes::new()
+    };
+    Ok(builder.body(Full::new(body))?)
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use std::convert::Infallible;
+    use std::future::{Ready, ready};
+    use std::task::{Context, Poll};
+
+    use expectest::prelude::*;
+    use maplit::hashmap;
+
+    use super::*;
+
+    // Service that echos the request details back as JSON
+    #[derive(Clone)]
+    struct EchoService;
+
+    impl Service<Request<Full<Bytes>>> for EchoService {
+      type Response = Response<Full<Bytes>>;
+      type Error = Infallible;
+      type Future = Ready<Result<Self::Response, Self::Error>>;
+
+      fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+      }
+
+      fn call(&mut self, request: Request<Full<Bytes>>) -> Self::Future {
+        let body = format!("{{\"method\":\"{}\",\"uri\":\"{}\",\"header\":\"{}\"}}", request.method(),
+          request.uri(), request.headers().get("x-test").map(|v| v.to_str().unwrap()).unwrap_or_default());
+        ready(Ok(Response::builder()
+          .status(201)
+          .header("content-type", "application/json")
+          .body(Full::new(Bytes::from(body)))
+          .unwrap()))
+      }
+    }
+
+    #[tokio::test]
+    async fn tower_service_provider_dispatches_the_request_to_the_service() {
+      let provider = TowerServiceProvider::new(EchoService);
+      let request = HttpRequest {
+        method: "POST".to_string(),
+        path: "/users".to_string(),
+        query: Some(hashmap!{ "id".to_string() => vec![Some("1".to_string())] }),
+        headers: Some(hashmap!{ "X-Test".to_string() => vec!["test".to_string()] }),
+        body: OptionalBody::from("{}"),
+        .. HttpRequest::default()
+      };
+
+      let response = provider.handle(&request).await.unwrap();
+
+      expect!(response.status).to(be_equal_to(201));
+      expect!(response.headers.unwrap().get("content-type").cloned())
+        .to(be_some().value(vec!["application/json".to_string()]));
+      expect!(response.body.display_string())
+        .to(be_equal_to("{\"method\":\"POST\",\"uri\":\"/users?id=1\",\"header\":\"test\"}"));
+    }
+
+    #[tokio::test]
+    async fn tower_service_provider_returns_an_error_for_an_invalid_method() {
+      let provider = TowerServiceProvider::new(EchoService);
+      let request = HttpRequest {
+        method: "GET POST".to_string(),
+        .. HttpRequest::default()
+      };
+
+      let result = provider.handle(&request).await;
+
+      expect!(result.unwrap_err().to_string().starts_with("'GET POST' is not a valid HTTP method")).to(be_true());
+    }
+  }
+}
+
+#[cfg(feature = "tower")]
+pub use tower_service_provider::TowerServiceProvider;
@@ -23,7 +23,7 @@ use humantime::format_duration;
 use itertools::Itertools;
 #[cfg(feature = "plugins")] use itertools::Either;
 use maplit::*;
-use pact_models::generators::GeneratorTestMode;
+use pact_models::generators::{GENERATOR_SEED_KEY, GeneratorTestMode};
 use pact_models::http_utils::HttpAuth;
 use pact_models::interaction::Interaction;
 use pact_models::json_utils::json_to_string;
@@ -48,6 +48,7 @@ use pact_matching::{match_response, Mismatch};
 use pact_matching::metrics::{MetricEvent, send_metrics_async};
 
 use crate::callback_executors::{ProviderStateError, ProviderStateExecutor};
+use crate::in_process::ProviderRequestHandler;
 use crate::messages::{process_message_result, process_sync_message_result, verify_message_from_provider, verify_sync_message_from_provider};
 use crate::metrics::VerificationMetrics;
 use crate::pact_broker::{
@@ -58,7 +59,7 @@ use crate::pact_broker::{
   TestResult
 };
 pub use crate::pact_broker::{ConsumerVersionSelector, PactsForVerificationRequest};
-use crate::provider_client::make_provider_request;
+use crate::provider_client::{make_provider_request, ProviderRequestStats, wait_for_provider};
 use crate::request_response::process_request_response_result;
 use crate::utils::as_safe_ref;
 use crate::verification_result::{
@@ -72,6 +73,8 @@ pub mod pact_broker;
 pub mod callback_executors;
 mod request_response;
 mod messages;
+mod dry_run;
+pub mod in_process;
 pub mod selectors;
 pub mod metrics;
 pub mod verification_result;
@@ -335,18 +338,9 @@ impl Clone for MismatchResult {
   }
 }
 
-async fn verify_response_from_provider<F: RequestFilterExecutor>(
-  provider: &ProviderInfo,
-  interaction: &SynchronousHttp,
-  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe>,
-  options: &VerificationOptions<F>,
-  client: &Client,
-  verification_context: &HashMap<&str, Value>
-) -> Result<Option<String>, MismatchResult> {
-  let expected_response = &interaction.response;
-  let request = pact_matching::generate_request(&interaction.request,
-    &GeneratorTestMode::Provider, &verification_context).await;
-  let transport = if let Some(transport) = &interaction.transport {
+/// Transport to use to send the request for an HTTP interaction to the provider
+pub(crate) fn http_transport(provider: &ProviderInfo, interaction: &SynchronousHttp) -> Option<ProviderTransport> {
+  if let Some(transport) = &interaction.transport {
     provider.transports
       .iter()
       .find(|t| &t.transport == transport)
@@ -365,8 +359,23 @@ async fn verify_response_from_provider<F: RequestFilterExecutor>(
     } else {
       t
     }
-  });
-  match make_provider_request(provider, &request, options, client, transport).await {
+  })
+}
+
+async fn verify_response_from_provider<F: RequestFilterExecutor>(
+  provider: &ProviderInfo,
+  interaction: &SynchronousHttp,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe>,
+  options: &VerificationOptions<F>,
+  client: &Client,
+  verification_context: &HashMap<&str, Value>,
+  stats: &ProviderRequestStats
+) -> Result<Option<String>, MismatchResult> {
+  let expected_response = &interaction.response;
+  let request = pact_matching::generate_request(&interaction.request,
+    &GeneratorTestMode::Provider, &verification_context).await;
+  let transport = http_transport(provider, interaction);
+  match make_provider_request(provider, &request, options, client, transport, stats).await {
     Ok(ref actual_response) => {
       let mismatches = match_response(expected_response.clone(), actual_response.clone(), pact, &interaction.boxed()).await;
       if mismatches.is_empty() {
@@ -412,7 +421,8 @@ async fn verify_interaction<'a, F: RequestFilterExecutor, S: ProviderStateExecut
   interaction: &(dyn Interaction + Send + Sync + RefUnwindSafe),
   pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
   options: &VerificationOptions<F>,
-  provider_state_executor: &Arc<S>
+  provider_state_executor: &Arc<S>,
+  stats: &ProviderRequestStats
 ) -> Result<(Option<String>, Vec<String>, Duration), (MismatchResult, Vec<String>, Duration)> {
   let start = Instant::now();
   debug!("Verifying interaction {} {} ({:?})", interaction.type_of(), interaction.description(), interaction.id());
@@ -436,6 +446,9 @@ async fn verify_interaction<'a, F: RequestFilterExecutor, S: ProviderStateExecut
   for (k, v) in context {
     provider_states_context.insert(k, v);
   }
+  if let Some(seed) = options.generator_seed {
+    provider_states_context.insert(GENERATOR_SEED_KEY.to_string(), json!(seed));
+  }
   let provider_states_context = provider_states_context
     .iter()
     .map(|(k, v)| (k.as_str(), v.clone()))
@@ -457,9 +470,9 @@ async fn verify_interaction<'a, F: RequestFilterExecutor, S: ProviderStateExecut
 
     result = if let Some(transport) = &transport {
       trace!("Verifying interaction via {}", transport.key);
-      verify_interaction_using_transport(transport, provider, interaction, pact, options, &client, &provider_states_context).await
+      verify_interaction_using_transport(transport, provider, interaction, pact, options, &client, &provider_states_context, stats).await
     } else {
-      verify_v3_interaction(provider, interaction, &pact, options, &client, &provider_states_context)
+      verify_v3_interaction(provider, interaction, &pact, options, &client, &provider_states_context, stats)
         .await
         .map(|r| (r, vec![]))
         .map_err(|e| (e, vec![]))
@@ -468,7 +481,7 @@ async fn verify_interaction<'a, F: RequestFilterExecutor, S: ProviderStateExecut
 
   #[cfg(not(feature = "plugins"))]
   {
-    result = verify_v3_interaction(provider, interaction, &pact, options, &client, &provider_states_context)
+    result = verify_v3_interaction(provider, interaction, &pact, options, &client, &provider_states_context, stats)
       .await
       .map(|r| (r, vec![]))
       .map_err(|e| (e, vec![]));
@@ -494,7 +507,8 @@ async fn verify_interaction_using_transport<'a, F: RequestFilterExecutor>(
   pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
   options: &VerificationOptions<F>,
   client: &Arc<Client>,
-  config: &HashMap<&str, Value>
+  config: &HashMap<&str, Value>,
+  stats: &ProviderRequestStats
 ) -> Result<(Option<String>, Vec<String>), (MismatchResult, Vec<String>)> {
   if transport_entry.provider_type == CatalogueEntryProviderType::PLUGIN {
     match pact.as_v4_pact() {
@@ -588,7 +602,7 @@ async fn verify_interaction_using_transport<'a, F: RequestFilterExecutor>(
       }
     }
   } else {
-    verify_v3_interaction(provider, interaction, pact, options, client, config)
+    verify_v3_interaction(provider, interaction, pact, options, client, config, stats)
       .await
       .map(|r| (r, vec![]))
       .map_err(|e| (e, vec![]))
@@ -602,7 +616,8 @@ async fn verify_v3_interaction<'a, F: RequestFilterExecutor>(
   pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
   options: &VerificationOptions<F>,
   client: &Arc<Client>,
-  provider_states_context: &HashMap<&str, Value>
+  provider_states_context: &HashMap<&str, Value>,
+  stats: &ProviderRequestStats
 ) -> Result<Option<String>, MismatchResult> {
   let mut result = Err(MismatchResult::Error("No interaction was verified".into(), interaction.id().clone()));
 
@@ -610,19 +625,19 @@ async fn verify_v3_interaction<'a, F: RequestFilterExecutor>(
   if let Some(interaction) = interaction.as_v4_http() {
     debug!("Verifying a HTTP interaction");
     result = verify_response_from_provider(provider, &interaction, &pact.boxed(), options,
-                                           &client, &provider_states_context).await;
+                                           &client, &provider_states_context, stats).await;
   }
   // Verify an asynchronous message (single shot)
   if interaction.is_message() {
     debug!("Verifying an asynchronous message (single shot)");
     result = verify_message_from_provider(provider, pact, &interaction.boxed(), options,
-                                          &client, &provider_states_context).await;
+                                          &client, &provider_states_context, stats).await;
   }
   // Verify a synchronous message (request/response)
   if let Some(message) = interaction.as_v4_sync_message() {
     debug!("Verifying a synchronous message (request/response)");
     result = verify_sync_message_from_provider(provider, pact, message, options, &client,
-                                               &provider_states_context).await;
+                                               &provider_states_context, stats).await;
   }
 
   result
@@ -792,7 +807,19 @@ pub enum FilterInfo {
     /// Filter on the interaction provider state
     State(String),
     /// Filter on both the interaction description and provider state
-    DescriptionAndState(String, String)
+    DescriptionAndState(String, String),
+    /// Filter on the interaction key, ID or exact description. Interactions that match any of the
+    /// values will be verified. Keys are only available for V4 interactions, and IDs are only set
+    /// for interactions fetched from a Pact Broker.
+    Interactions {
+      /// Keys of the interactions to verify
+      keys: Vec<String>,
+      /// IDs of the interactions to verify
+      ids: Vec<String>,
+      /// Consumer names and descriptions of the interactions to verify (the descriptions must
+      /// match exactly, and only apply to the Pact with the given consumer)
+      descriptions: Vec<(String, String)>
+    }
 }
 
 impl FilterInfo {
@@ -859,10 +886,32 @@ impl FilterInfo {
       let re = Regex::new(&self.description()).unwrap();
       re.is_match(&interaction.description())
     }
+
+    /// If this filter is filtering on interaction keys, IDs or exact descriptions
+    pub fn has_interactions(&self) -> bool {
+      matches!(self, FilterInfo::Interactions { .. })
+    }
+
+    /// If the filter matches the interaction key, ID or description. The key of a V4 interaction
+    /// is the one from the Pact file, or the calculated key if it does not have one. Descriptions
+    /// are only matched for interactions from the Pact with the given consumer.
+    pub fn match_interaction(&self, consumer: &str, interaction: &dyn Interaction) -> bool {
+      match self {
+        FilterInfo::Interactions { keys, ids, descriptions } => {
+          let key = interaction.as_v4().map(|i| i.unique_key());
+          key.map(|key| keys.contains(&key)).unwrap_or(false) ||
+            interaction.id().map(|id| ids.contains(&id)).unwrap_or(false) ||
+            descriptions.iter().any(|(c, description)| c == consumer && *description == interaction.description())
+        }
+        _ => false
+      }
+    }
 }
 
-fn filter_interaction(interaction: &dyn Interaction, filter: &FilterInfo) -> bool {
-  if filter.has_description() && filter.has_state() {
+fn filter_interaction(consumer: &str, interaction: &dyn Interaction, filter: &FilterInfo) -> bool {
+  if filter.has_interactions() {
+    filter.match_interaction(consumer, interaction)
+  } else if filter.has_description() && filter.has_state() {
     filter.match_description(interaction) && filter.match_state(interaction)
   } else if filter.has_description() {
     filter.match_description(interaction)
@@ -918,7 +967,22 @@ pub struct VerificationOptions<F> where F: RequestFilterExecutor {
   /// If coloured output should be used (using ANSI escape codes)
   pub coloured_output: bool,
   /// If no pacts are found to verify, then this should be an error
-  pub no_pacts_is_error: bool
+  pub no_pacts_is_error: bool,
+  /// Number of times to retry a request to the provider that fails with a connection error or
+  /// a timeout
+  pub request_retries: u8,
+  /// Time in ms to wait before retrying a failed request to the provider
+  pub request_retry_delay: u64,
+  /// Readiness check to wait for the provider to be available before verifying any interactions
+  pub readiness_check: Option<ProviderReadinessCheck>,
+  /// Seed to use for the random generators, so that the same requests are generated on every run
+  pub generator_seed: Option<u64>,
+  /// Dry run mode. The requests and provider state change calls that would be made are generated
+  /// and displayed, but the provider is not contacted and nothing is verified
+  pub dry_run: bool,
+  /// Handler for a provider running in the same process. If set, the requests to the provider
+  /// are dispatched to the handler instead of being sent over the network
+  pub provider_handler: Option<Arc<dyn ProviderRequestHandler>>
 }
 
 impl <F: RequestFilterExecutor> Default for VerificationOptions<F> {
@@ -929,7 +993,37 @@ impl <F: RequestFilterExecutor> Default for VerificationOptions<F> {
       request_timeout: 5000,
       custom_headers: Default::default(),
       coloured_output: true,
-      no_pacts_is_error: true
+      no_pacts_is_error: true,
+      request_retries: 0,
+      request_retry_delay: 1000,
+      readiness_check: None,
+      generator_seed: None,
+      dry_run: false,
+      provider_handler: None
+    }
+  }
+}
+
+/// Readiness check used to wait for the provider to be available (i.e. while a container is
+/// starting up). The URL will be polled until it returns a successful (2xx) response.
+#[derive(Debug, Clone)]
+pub struct ProviderReadinessCheck {
+  /// URL to poll, normally a health check endpoint of the provider
+  pub url: String,
+  /// Maximum time in ms to wait for the provider to be ready
+  pub timeout: u64,
+  /// Time in ms to wait between each attempt
+  pub interval: u64
+}
+
+impl ProviderReadinessCheck {
+  /// Create a readiness check for the URL with the default timeout (30 seconds) and interval
+  /// (500 ms)
+  pub fn new(url: &str) -> Self {
+    ProviderReadinessCheck {
+      url: url.to_string(),
+      timeout: 30000,
+      interval: 500
     }
   }
 }
@@ -988,12 +1082,28 @@ pub async fn verify_provider_async<F: RequestFilterExecutor, S: ProviderStateExe
 ) -> anyhow::Result<VerificationExecutionResult> {
   pact_matching::matchers::configure_core_catalogue();
   async {
-    let pact_results = fetch_pacts(source, consumers, &provider_info).await;
-
     let mut total_results = 0;
     let mut pending_errors: Vec<(String, MismatchResult)> = vec![];
     let mut errors: Vec<(String, MismatchResult)> = vec![];
 
+    let provider_ready = match &verification_options.readiness_check {
+      Some(readiness_check) if !verification_options.dry_run => match check_provider_readiness(verification_options, readiness_check).await {
+        Ok(_) => true,
+        Err(err) => {
+          error!("Provider readiness check failed - {}", Red.paint(err.to_string()));
+          errors.push(("Provider readiness check failed".to_string(), MismatchResult::Error(err.to_string(), None)));
+          false
+        }
+      },
+      _ => true
+    };
+
+    let pact_results = if provider_ready {
+      fetch_pacts(source, consumers, &provider_info).await
+    } else {
+      vec![]
+    };
+
     let mut verification_result = VerificationExecutionResult::new();
 
     for pact_result in pact_results {
@@ -1027,7 +1137,13 @@ pub async fn verify_provider_async<F: RequestFilterExecutor, S: ProviderStateExe
               pact.consumer().name, pact.provider().name));
           }
 
-          if pact.interactions().is_empty() {
+          if verification_options.dry_run {
+            let (results, output) = dry_run::dry_run_pact(&provider_info, &filter, &pact,
+              verification_options, provider_state_executor).await;
+            total_results += results.len();
+            verification_result.output.extend(output);
+            verification_result.dry_run_results.extend(results);
+          } else if pact.interactions().is_empty() {
             if verification_options.coloured_output {
               verification_result.output.push(
                 Yellow.paint("WARNING: Pact file has no interactions").to_string()
@@ -1121,12 +1237,17 @@ pub async fn verify_provider_async<F: RequestFilterExecutor, S: ProviderStateExe
       app_name: "pact_verifier".to_string(),
       app_version: env!("CARGO_PKG_VERSION").to_string()
     });
-    send_metrics_async(MetricEvent::ProviderVerificationRan {
-      tests_run: total_results,
-      test_framework: metrics_data.test_framework,
-      app_name: metrics_data.app_name,
-      app_version: metrics_data.app_version
-    }).await;
+    if verification_options.dry_run {
+      verification_result.output.push(format!("\nDry run, {} request(s) were generated but not sent to the provider",
+        total_results));
+    } else {
+      send_metrics_async(MetricEvent::ProviderVerificationRan {
+        tests_run: total_results,
+        test_framework: metrics_data.test_framework,
+        app_name: metrics_data.app_name,
+        app_version: metrics_data.app_version
+      }).await;
+    }
 
     for (error, result) in &errors {
       verification_result.errors.push((error.clone(), result.into()));
@@ -1161,6 +1282,14 @@ pub async fn verify_provider_async<F: RequestFilterExecutor, S: ProviderStateExe
   }.instrument(tracing::trace_span!("verify_provider_async")).await
 }
 
+async fn check_provider_readiness<F: RequestFilterExecutor>(
+  options: &VerificationOptions<F>,
+  readiness_check: &ProviderReadinessCheck
+) -> anyhow::Result<()> {
+  let client = configure_http_client(options)?;
+  wait_for_provider(&client, readiness_check).await
+}
+
 fn process_errors(errors: &Vec<(String, MismatchResult)>, output: &mut Vec<String>, coloured_output: bool) {
   for (i, &(ref description, ref mismatch)) in errors.iter().enumerate() {
     match *mismatch {
@@ -1408,19 +1537,21 @@ pub async fn verify_pact_internal<'a, F: RequestFilterExecutor, S: ProviderState
   let interactions = pact.interactions();
   let mut output = vec![];
 
-  let results: Vec<(Box<dyn Interaction + Send + Sync + RefUnwindSafe>, Result<(Option<String>, Vec<String>, Duration), (MismatchResult, Vec<String>, Duration)>)> =
+  let results: Vec<(Box<dyn Interaction + Send + Sync + RefUnwindSafe>, Result<(Option<String>, Vec<String>, Duration), (MismatchResult, Vec<String>, Duration)>, ProviderRequestStats)> =
     futures::stream::iter(interactions.iter().map(|i| (&pact, i)))
-    .filter(|(_, interaction)| futures::future::ready(filter_interaction(interaction.as_ref(), filter)))
+    .filter(|(pact, interaction)| futures::future::ready(filter_interaction(pact.consumer().name.as_str(), interaction.as_ref(), filter)))
     .then( |(pact, interaction)| async move {
       let interaction_desc = interaction.description();
-      (interaction.boxed(), verify_interaction(provider_info, interaction.as_ref(), &pact.boxed(), options, provider_state_executor)
-        .instrument(debug_span!("verify_interaction", interaction = interaction_desc.as_str())).await)
+      let stats = ProviderRequestStats::default();
+      let result = verify_interaction(provider_info, interaction.as_ref(), &pact.boxed(), options, provider_state_executor, &stats)
+        .instrument(debug_span!("verify_interaction", interaction = interaction_desc.as_str())).await;
+      (interaction.boxed(), result, stats)
     })
     .collect()
     .await;
 
   let mut errors: Vec<VerificationInteractionResult> = vec![];
-  for (interaction, match_result) in results {
+  for (interaction, match_result, stats) in results {
     let mut description = format!("Verifying a pact between {} and {}",
       pact.consumer().name.clone(), pact.provider().name.clone());
 
@@ -1441,6 +1572,11 @@ pub async fn verify_pact_internal<'a, F: RequestFilterExecutor, S: ProviderState
         format_duration(duration)));
     };
 
+    if stats.retries() > 0 {
+      let message = format!("    (the request to the provider was retried {} time(s))", stats.retries());
+      output.push(if options.coloured_output { Yellow.paint(message).to_string() } else { message });
+    }
+
     if let Some((first, elements)) = interaction.provider_states().split_first() {
       let s = format!(" Given {}", first.name);
       description.push_str(&s);
@@ -1515,23 +1651,35 @@ pub async fn verify_pact_internal<'a, F: RequestFilterExecutor, S: ProviderState
       Ok(_) => {
         errors.push(VerificationInteractionResult {
           interaction_id: interaction.id(),
-          interaction_key,
+          interaction_key: interaction_key.clone(),
+          unique_key: interaction.as_v4().map(|i| i.unique_key()),
           description: description.clone(),
           interaction_description: interaction.description(),
+          consumer_name: pact.consumer().name.clone(),
+          provider_states: interaction.provider_states().iter().map(|state| state.name.clone()).collect(),
+          request: stats.request(),
           result: Ok(()),
           pending: pending || interaction.pending(),
-          duration
+          duration,
+          request_retries: stats.retries(),
+          provider_unavailable: stats.provider_unavailable()
         });
       },
       Err(err) => {
         errors.push(VerificationInteractionResult {
           interaction_id: interaction.id(),
-          interaction_key,
+          interaction_key: interaction_key.clone(),
+          unique_key: interaction.as_v4().map(|i| i.unique_key()),
           description: description.clone(),
           interaction_description: interaction.description(),
+          consumer_name: pact.consumer().name.clone(),
+          provider_states: interaction.provider_states().iter().map(|state| state.name.clone()).collect(),
+          request: stats.request(),
           result: Err(err.clone()),
           pending: pending || interaction.pending(),
-          duration
+          duration,
+          request_retries: stats.retries(),
+          provider_unavailable: stats.provider_unavailable()
         });
       }
     }
@@ -23,17 +23,15 @@ use pact_matching::{match_message, match_sync_message_response, Mismatch};
 
 use crate::{MismatchResult, ProviderInfo, ProviderTransport, VerificationOptions};
 use crate::callback_executors::RequestFilterExecutor;
-use crate::provider_client::make_provider_request;
+use crate::provider_client::{make_provider_request, ProviderRequestStats};
 use crate::utils::as_safe_ref;
 
-pub(crate) async fn verify_message_from_provider<'a, F: RequestFilterExecutor>(
+/// Request that is sent to the provider to fetch the message for an asynchronous message
+/// interaction, along with the transport to send it with
+pub(crate) fn message_request(
   provider: &ProviderInfo,
-  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
-  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
-  options: &VerificationOptions<F>,
-  client: &reqwest::Client,
-  _: &HashMap<&str, Value>
-) -> Result<Option<String>, MismatchResult> {
+  interaction: &(dyn Interaction + Send + Sync + RefUnwindSafe)
+) -> (HttpRequest, Option<ProviderTransport>) {
   let mut request_body = json!({
     "description": interaction.description()
   });
@@ -84,7 +82,21 @@ pub(crate) async fn verify_message_from_provider<'a, F: RequestFilterExecutor>(
     }
   });
 
-  match make_provider_request(provider, &message_request, options, client, transport).await {
+  (message_request, transport)
+}
+
+pub(crate) async fn verify_message_from_provider<'a, F: RequestFilterExecutor>(
+  provider: &ProviderInfo,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  options: &VerificationOptions<F>,
+  client: &reqwest::Client,
+  _: &HashMap<&str, Value>,
+  stats: &ProviderRequestStats
+) -> Result<Option<String>, MismatchResult> {
+  let (message_request, transport) = message_request(provider, interaction.as_ref());
+
+  match make_provider_request(provider, &message_request, options, client, transport, stats).await {
     Ok(ref actual_response) => {
       let metadata = extract_metadata(actual_response);
       let actual = AsynchronousMessage {
@@ -269,18 +281,12 @@ fn extract_metadata(actual_response: &HttpResponse) -> HashMap<String, Value> {
   default
 }
 
-pub(crate) async fn verify_sync_message_from_provider<'a, F: RequestFilterExecutor>(
+/// Request that is sent to the provider to get the response for a synchronous message
+/// interaction, along with the transport to send it with
+pub(crate) fn sync_message_request(
   provider: &ProviderInfo,
-  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
-  message: SynchronousMessage,
-  options: &VerificationOptions<F>,
-  client: &reqwest::Client,
-  _: &HashMap<&str, Value>
-) -> Result<Option<String>, MismatchResult> {
-  if message.response.len() > 1 {
-    warn!("Matching synchronous messages with more than one response is not currently supported, will only use the first response");
-  }
-
+  message: &SynchronousMessage
+) -> (HttpRequest, Option<ProviderTransport>) {
   let mut request_body = json!({
     "description": message.description(),
     "request": message.request.to_json()
@@ -323,7 +329,25 @@ pub(crate) async fn verify_sync_message_from_provider<'a, F: RequestFilterExecut
     }
   });
 
-  match make_provider_request(provider, &message_request, options, client, transport).await {
+  (message_request, transport)
+}
+
+pub(crate) async fn verify_sync_message_from_provider<'a, F: RequestFilterExecutor>(
+  provider: &ProviderInfo,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  message: SynchronousMessage,
+  options: &VerificationOptions<F>,
+  client: &reqwest::Client,
+  _: &HashMap<&str, Value>,
+  stats: &ProviderRequestStats
+) -> Result<Option<String>, MismatchResult> {
+  if message.response.len() > 1 {
+    warn!("Matching synchronous messages with more than one response is not currently supported, will only use the first response");
+  }
+
+  let (message_request, transport) = sync_message_request(provider, &message);
+
+  match make_provider_request(provider, &message_request, options, client, transport, stats).await {
     Ok(ref actual_response) => {
       if actual_response.is_success() {
         let metadata = extract_metadata(actual_response);
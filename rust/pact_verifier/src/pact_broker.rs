@@ -13,7 +13,7 @@ use pact_models::http_utils::HttpAuth;
 use pact_models::json_utils::json_to_string;
 use pact_models::pact::{load_pact_from_json, Pact};
 use regex::{Captures, Regex};
-use reqwest::{Method, Url};
+use reqwest::{Method, RequestBuilder, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use serde_with::skip_serializing_none;
@@ -296,16 +296,8 @@ impl HALClient {
     let url = self.resolve_path(path)?;
     debug!("Final broker URL: {}", url);
 
-    let request_builder = match self.auth {
-        Some(ref auth) => match auth {
-            HttpAuth::User(username, password) => self.client.get(url).basic_auth(username, password.clone()),
-            HttpAuth::Token(token) => self.client.get(url).bearer_auth(token),
-            _ => self.client.get(url)
-        },
-        None => self.client.get(url)
-    }.header("accept", "application/hal+json, application/json");
-
-    let response = with_retries(self.retries, request_builder).await
+    let response = self.send_with_auth(|| self.client.get(url.clone())
+      .header("accept", "application/hal+json, application/json")).await
       .map_err(|err| {
           PactBrokerError::IoError(format!("Failed to access pact broker path '{}' - {}. URL: '{}'",
               &path,
@@ -480,25 +472,13 @@ impl HALClient {
       base_url.join(&url.path())?
     };
 
-    let request_builder = match self.auth {
-      Some(ref auth) => match auth {
-        HttpAuth::User(username, password) => self.client
-          .request(method, url.clone())
-          .basic_auth(username, password.clone()),
-        HttpAuth::Token(token) => self.client
-          .request(method, url.clone())
-          .bearer_auth(token),
-        _ => self.client.request(method, url.clone())
-      },
-      None => self.client.request(method, url.clone())
-    }
+    let response = self.send_with_auth(|| self.client
+      .request(method.clone(), url.clone())
       .header("Content-Type", "application/json")
       .header("Accept", "application/hal+json")
       .header("Accept", "application/json")
       .header("Accept-Charset", "utf-8")
-      .body(body.to_string());
-
-    let response = with_retries(self.retries, request_builder).await;
+      .body(body.to_string())).await;
     match response {
       Ok(res) => self.parse_broker_response(url.path().to_string(), res).await,
       Err(err) => Err(PactBrokerError::IoError(
@@ -507,6 +487,28 @@ impl HALClient {
     }
   }
 
+  /// Adds any authentication to the request built by the given function and sends it (with
+  /// retries). If the broker rejects a cached OAuth2 access token, a new token will be fetched
+  /// and the request will be sent again.
+  async fn send_with_auth<F>(&self, build_request: F) -> anyhow::Result<reqwest::Response>
+    where F: Fn() -> RequestBuilder {
+    let response = with_retries(self.retries, self.apply_auth(build_request()).await?).await?;
+    if response.status() == StatusCode::UNAUTHORIZED &&
+      self.auth.as_ref().map(|auth| auth.invalidate_token()).unwrap_or(false) {
+      debug!("Request to the pact broker was not authorised, retrying with a new access token");
+      Ok(with_retries(self.retries, self.apply_auth(build_request()).await?).await?)
+    } else {
+      Ok(response)
+    }
+  }
+
+  async fn apply_auth(&self, request_builder: RequestBuilder) -> anyhow::Result<RequestBuilder> {
+    match &self.auth {
+      Some(auth) => auth.apply_to_async_request(request_builder).await,
+      None => Ok(request_builder)
+    }
+  }
+
   fn with_doc_context(&self, doc_attributes: &[Link]) -> Result<HALClient, PactBrokerError> {
     let links: serde_json::Map<String, serde_json::Value> = doc_attributes.iter()
       .map(|link| (link.name.clone(), link.as_json())).collect();
@@ -6,9 +6,12 @@ use std::collections::hash_map::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
-use futures::future::*;
+use bytes::Bytes;
 use http::{HeaderMap, HeaderValue, Method};
 use http::header::{HeaderName, InvalidHeaderName, InvalidHeaderValue};
 use http::header::CONTENT_TYPE;
@@ -21,9 +24,10 @@ use pact_models::headers::parse_header;
 use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
 use reqwest::{Client, Error, RequestBuilder};
 use serde_json::Value;
+use tokio::time::sleep;
 use tracing::{debug, info, trace, warn};
 
-use crate::{ProviderInfo, ProviderTransport, RequestFilterExecutor, VerificationOptions};
+use crate::{ProviderInfo, ProviderReadinessCheck, ProviderTransport, RequestFilterExecutor, VerificationOptions};
 use crate::utils::with_retries;
 
 #[derive(Debug)]
@@ -135,7 +139,7 @@ fn create_native_request(
   Ok(builder)
 }
 
-fn extract_headers(headers: &HeaderMap) -> Option<HashMap<String, Vec<String>>> {
+pub(crate) fn extract_headers(headers: &HeaderMap) -> Option<HashMap<String, Vec<String>>> {
   if !headers.is_empty() {
     let result = headers.keys()
       .map(|name| {
@@ -163,10 +167,15 @@ fn extract_headers(headers: &HeaderMap) -> Option<HashMap<String, Vec<String>>>
 
 async fn extract_body(response: reqwest::Response, pact_response: &HttpResponse) -> anyhow::Result<OptionalBody> {
   let body = response.bytes().await?;
+  Ok(response_body(body, pact_response))
+}
+
+/// Converts the body returned from the provider, using the content type from the response headers
+pub(crate) fn response_body(body: Bytes, pact_response: &HttpResponse) -> OptionalBody {
   if !body.is_empty() {
-    Ok(OptionalBody::Present(body, pact_response.content_type(), None))
+    OptionalBody::Present(body, pact_response.content_type(), None)
   } else {
-    Ok(OptionalBody::Empty)
+    OptionalBody::Empty
   }
 }
 
@@ -188,45 +197,146 @@ async fn native_response_to_pact_response(native_response: reqwest::Response) ->
   })
 }
 
-/// This function makes the actual request to the provider, executing any request filter before
-/// executing the request
-pub async fn make_provider_request<F: RequestFilterExecutor>(
-  provider: &ProviderInfo,
+/// Error returned when the provider could not be reached due to a connection error or a timeout,
+/// after any configured retries
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Provider could not be reached after {attempts} attempt(s) - {message}")]
+pub struct ProviderUnavailableError {
+  /// Number of attempts that were made
+  pub attempts: u16,
+  /// Error from the last attempt
+  pub message: String
+}
+
+/// Records the retries made for the requests to the provider while verifying an interaction
+#[derive(Debug, Default)]
+pub struct ProviderRequestStats {
+  retries: AtomicU8,
+  unavailable: AtomicBool,
+  request: Mutex<Option<HttpRequest>>
+}
+
+impl ProviderRequestStats {
+  /// Number of times a request to the provider was retried
+  pub fn retries(&self) -> u8 {
+    self.retries.load(Ordering::SeqCst)
+  }
+
+  /// If a request to the provider failed because the provider could not be reached
+  pub fn provider_unavailable(&self) -> bool {
+    self.unavailable.load(Ordering::SeqCst)
+  }
+
+  /// The last request that was sent to the provider (after any request filter was applied)
+  pub fn request(&self) -> Option<HttpRequest> {
+    self.request.lock().ok().and_then(|request| request.clone())
+  }
+
+  fn record_request(&self, request: &HttpRequest) {
+    if let Ok(mut guard) = self.request.lock() {
+      *guard = Some(request.clone());
+    }
+  }
+
+  fn record_retry(&self) {
+    self.retries.fetch_add(1, Ordering::SeqCst);
+  }
+
+  fn record_unavailable(&self) {
+    self.unavailable.store(true, Ordering::SeqCst);
+  }
+}
+
+/// Invokes the request filter (if there is one) on the request to be sent to the provider
+pub(crate) fn apply_request_filter<F: RequestFilterExecutor>(
   request: &HttpRequest,
-  options: &VerificationOptions<F>,
-  client: &Client,
-  transport: Option<ProviderTransport>
-) -> anyhow::Result<HttpResponse> {
-  let request_filter_option = options.request_filter.clone();
-  let request = if request_filter_option.is_some() {
-    let request_filter = request_filter_option.unwrap();
-    info!("Invoking request filter for request");
-    request_filter.call(request)
-  } else {
-    request.clone()
-  };
+  options: &VerificationOptions<F>
+) -> HttpRequest {
+  match &options.request_filter {
+    Some(request_filter) => {
+      info!("Invoking request filter for request");
+      request_filter.clone().call(request)
+    }
+    None => request.clone()
+  }
+}
 
+/// Adds the custom headers to the request, replacing any existing headers with the same name
+pub(crate) fn with_custom_headers(request: &HttpRequest, custom_headers: &HashMap<String, String>) -> HttpRequest {
+  let mut request = request.clone();
+  if !custom_headers.is_empty() {
+    let headers = request.headers.get_or_insert_with(HashMap::new);
+    for (key, value) in custom_headers {
+      headers.insert(key.clone(), vec![value.clone()]);
+    }
+  }
+  request
+}
+
+/// Base URL to send the requests to the provider to, using the transport if one is provided
+pub(crate) fn provider_base_url(provider: &ProviderInfo, transport: Option<ProviderTransport>) -> String {
   trace!("transport = {:?}", transport);
   #[allow(deprecated)]
-  let base_url = transport
+  transport
     .map(|trans| trans.base_url(&provider.host))
     .unwrap_or_else(|| {
       match provider.port {
         Some(port) => format!("{}://{}:{}{}", provider.protocol, provider.host, port, provider.path),
         None => format!("{}://{}{}", provider.protocol, provider.host, provider.path),
       }
-    });
+    })
+}
+
+/// This function makes the actual request to the provider, executing any request filter before
+/// executing the request. If the request fails with a connection error or a timeout, it will be
+/// retried up to the number of request retries configured in the verification options.
+pub async fn make_provider_request<F: RequestFilterExecutor>(
+  provider: &ProviderInfo,
+  request: &HttpRequest,
+  options: &VerificationOptions<F>,
+  client: &Client,
+  transport: Option<ProviderTransport>,
+  stats: &ProviderRequestStats
+) -> anyhow::Result<HttpResponse> {
+  let request = apply_request_filter(request, options);
+  stats.record_request(&request);
+
+  if let Some(handler) = &options.provider_handler {
+    info!("Dispatching request to in-process provider");
+    info!("Sending request {request}");
+    debug!("body:\n{}", request.body.display_string());
+    let response = handler.handle(&with_custom_headers(&request, &options.custom_headers)).await?;
+    info!("Received response: {}", response);
+    debug!("body:\n{}", response.body.display_string());
+    return Ok(response);
+  }
+
+  let base_url = provider_base_url(provider, transport);
 
   info!("Sending request to provider at {base_url}");
   debug!("Provider details = {provider:?}");
   info!("Sending request {request}");
   debug!("body:\n{}", request.body.display_string());
-  let request = create_native_request(client, &base_url, &request, &options.custom_headers)?;
-
-  let response = request.send()
-    .map_err(|err| anyhow!(err))
-    .and_then(native_response_to_pact_response)
-    .await?;
+  // The counter is wider than the retry count, so the last attempt can not overflow it
+  let mut attempts: u16 = 1;
+  let response = loop {
+    let native_request = create_native_request(client, &base_url, &request, &options.custom_headers)?;
+    match native_request.send().await {
+      Ok(native_response) => break native_response_to_pact_response(native_response).await?,
+      Err(err) if err.is_connect() || err.is_timeout() => {
+        if attempts > u16::from(options.request_retries) {
+          stats.record_unavailable();
+          return Err(anyhow!(ProviderUnavailableError { attempts, message: err.to_string() }));
+        }
+        warn!("Request to provider failed ({}), will retry in {}ms (retry {} of {})", err,
+          options.request_retry_delay, attempts, options.request_retries);
+        stats.record_retry();
+        attempts += 1;
+        sleep(Duration::from_millis(options.request_retry_delay)).await;
+      }
+      Err(err) => return Err(anyhow!(err))
+    }
+  };
 
   info!("Received response: {}", response);
   debug!("body:\n{}", response.body.display_string());
@@ -234,6 +344,32 @@ pub async fn make_provider_request<F: RequestFilterExecutor>(
   Ok(response)
 }
 
+/// Polls the readiness check URL until the provider returns a successful (2xx) response, or the
+/// timeout of the check is reached.
+pub async fn wait_for_provider(client: &Client, check: &ProviderReadinessCheck) -> anyhow::Result<()> {
+  info!("Waiting for the provider to be ready at {}", check.url);
+  let start = Instant::now();
+  let mut attempts = 0;
+  loop {
+    attempts += 1;
+    let last_error = match client.get(check.url.as_str()).send().await {
+      Ok(response) if response.status().is_success() => {
+        info!("Provider is ready after {} attempt(s)", attempts);
+        return Ok(());
+      }
+      Ok(response) => format!("request failed with status {}", response.status()),
+      Err(err) => err.to_string()
+    };
+
+    if start.elapsed() >= Duration::from_millis(check.timeout) {
+      return Err(anyhow!("Provider was not ready at {} after {} attempt(s) - {}", check.url,
+        attempts, last_error));
+    }
+    debug!("Provider is not ready yet ({}), will try again in {}ms", last_error, check.interval);
+    sleep(Duration::from_millis(check.interval)).await;
+  }
+}
+
 /// Make a state change request. If the response returns a JSON body, convert that into a HashMap
 /// and return it. The request will be retried on 50x errors to a maximum of the `retries` parameter.
 pub async fn make_state_change_request(
@@ -302,7 +438,7 @@ mod tests {
     VerificationOptions
   };
 
-  use super::{create_native_request, extract_headers, join_paths};
+  use super::{create_native_request, extract_headers, join_paths, ProviderRequestStats, ProviderUnavailableError};
 
   #[test]
   fn extract_headers_tests() {
@@ -488,7 +624,61 @@ mod tests {
       port: server.url().port(),
       .. ProviderInfo::default()
     };
-    super::make_provider_request(&provider, &request, &options, &client, None).await.unwrap();
+    super::make_provider_request(&provider, &request, &options, &client, None, &ProviderRequestStats::default()).await.unwrap();
+  }
+
+  #[tokio::test]
+  async fn make_provider_request_retries_on_connection_errors() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let options = VerificationOptions {
+      request_retries: 2,
+      request_retry_delay: 1,
+      .. VerificationOptions::<NullRequestFilterExecutor>::default()
+    };
+    let client = configure_http_client(&options).unwrap();
+    #[allow(deprecated)]
+    let provider = ProviderInfo {
+      host: "127.0.0.1".to_string(),
+      port: Some(port),
+      .. ProviderInfo::default()
+    };
+    let stats = ProviderRequestStats::default();
+
+    let result = super::make_provider_request(&provider, &HttpRequest::default(), &options, &client,
+      None, &stats).await;
+
+    let error = result.unwrap_err();
+    let error = error.downcast_ref::<ProviderUnavailableError>().unwrap();
+    expect!(error.attempts).to(be_equal_to(3));
+    expect!(stats.retries()).to(be_equal_to(2));
+    expect!(stats.provider_unavailable()).to(be_true());
+  }
+
+  #[tokio::test]
+  async fn make_provider_request_with_the_maximum_number_of_retries() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let options = VerificationOptions {
+      request_retries: u8::MAX,
+      request_retry_delay: 0,
+      .. VerificationOptions::<NullRequestFilterExecutor>::default()
+    };
+    let client = configure_http_client(&options).unwrap();
+    #[allow(deprecated)]
+    let provider = ProviderInfo {
+      host: "127.0.0.1".to_string(),
+      port: Some(port),
+      .. ProviderInfo::default()
+    };
+    let stats = ProviderRequestStats::default();
+
+    let result = super::make_provider_request(&provider, &HttpRequest::default(), &options, &client,
+      None, &stats).await;
+
+    let error = result.unwrap_err();
+    let error = error.downcast_ref::<ProviderUnavailableError>().unwrap();
+    expect!(error.attempts).to(be_equal_to(256));
+    expect!(stats.retries()).to(be_equal_to(u8::MAX));
+    expect!(stats.provider_unavailable()).to(be_true());
   }
 
   #[test]
@@ -18,6 +18,8 @@ use pact_models::pact::Pact;
 use pact_models::provider_states::*;
 use pact_models::sync_interaction::RequestResponseInteraction;
 use pact_models::sync_pact::RequestResponsePact;
+use pact_models::v4::interaction::V4Interaction;
+use pact_models::v4::synch_http::SynchronousHttp;
 
 use crate::{
   NullRequestFilterExecutor,
@@ -31,6 +33,7 @@ use crate::{
 };
 use crate::callback_executors::HttpRequestProviderStateExecutor;
 use crate::pact_broker::Link;
+use crate::provider_client::ProviderRequestStats;
 use crate::verification_result::VerificationInteractionResult;
 use crate::VERIFIER_VERSION;
 
@@ -39,79 +42,112 @@ use super::{execute_state_change, filter_consumers, filter_interaction, FilterIn
 #[test]
 fn if_no_interaction_filter_is_defined_returns_true() {
   let interaction = RequestResponseInteraction::default();
-  expect!(filter_interaction(&interaction, &FilterInfo::None)).to(be_true());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::None)).to(be_true());
 }
 
 #[test]
 fn if_an_interaction_filter_is_defined_returns_false_if_the_description_does_not_match() {
   let interaction = RequestResponseInteraction { description: "bob".to_string(), .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::Description("fred".to_string()))).to(be_false());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::Description("fred".to_string()))).to(be_false());
 }
 
 #[test]
 fn if_an_interaction_filter_is_defined_returns_true_if_the_description_does_match() {
   let interaction = RequestResponseInteraction { description: "bob".to_string(), .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::Description("bob".to_string()))).to(be_true());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::Description("bob".to_string()))).to(be_true());
 }
 
 #[test]
 fn uses_regexs_to_match_the_description() {
   let interaction = RequestResponseInteraction { description: "bobby".to_string(), .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::Description("bob.*".to_string()))).to(be_true());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::Description("bob.*".to_string()))).to(be_true());
 }
 
 #[test]
 fn if_an_interaction_state_filter_is_defined_returns_false_if_the_state_does_not_match() {
   let interaction = RequestResponseInteraction { provider_states: vec![ ProviderState::default(&"bob".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::State("fred".to_string()))).to(be_false());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::State("fred".to_string()))).to(be_false());
 }
 
 #[test]
 fn if_an_interaction_state_filter_is_defined_returns_true_if_the_state_does_match() {
   let interaction = RequestResponseInteraction { provider_states: vec![ ProviderState::default(&"bob".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::State("bob".to_string()))).to(be_true());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::State("bob".to_string()))).to(be_true());
 }
 
 #[test]
 fn uses_regexs_to_match_the_state() {
   let interaction = RequestResponseInteraction { provider_states: vec![ ProviderState::default(&"bobby".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::State("bob.*".to_string()))).to(be_true());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::State("bob.*".to_string()))).to(be_true());
 }
 
 #[test]
 fn if_the_state_filter_is_empty_returns_false_if_the_interaction_state_is_defined() {
   let interaction = RequestResponseInteraction { provider_states: vec![ ProviderState::default(&"bobby".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::State("".to_string()))).to(be_false());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::State("".to_string()))).to(be_false());
 }
 
 #[test]
 fn if_the_state_filter_is_empty_returns_true_if_the_interaction_state_is_not_defined() {
   let interaction = RequestResponseInteraction { provider_states: vec![], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::State("".to_string()))).to(be_true());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::State("".to_string()))).to(be_true());
 }
 
 #[test]
 fn if_the_state_filter_and_interaction_filter_is_defined_must_match_both() {
   let interaction = RequestResponseInteraction { description: "freddy".to_string(), provider_states: vec![ ProviderState::default(&"bobby".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_true());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_true());
 }
 
 #[test]
 fn if_the_state_filter_and_interaction_filter_is_defined_is_false_if_the_provider_state_does_not_match() {
   let interaction = RequestResponseInteraction { description: "freddy".to_string(), provider_states: vec![ ProviderState::default(&"boddy".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_false());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_false());
 }
 
 #[test]
 fn if_the_state_filter_and_interaction_filter_is_defined_is_false_if_the_description_does_not_match() {
   let interaction = RequestResponseInteraction { description: "frebby".to_string(), provider_states: vec![ ProviderState::default(&"bobby".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_false());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_false());
 }
 
 #[test]
 fn if_the_state_filter_and_interaction_filter_is_defined_is_false_if_both_do_not_match() {
   let interaction = RequestResponseInteraction { description: "joe".to_string(), provider_states: vec![ ProviderState::default(&"author".to_string()) ], .. RequestResponseInteraction::default() };
-  expect!(filter_interaction(&interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_false());
+  expect!(filter_interaction("consumer", &interaction, &FilterInfo::DescriptionAndState(".*ddy".to_string(), "bob.*".to_string()))).to(be_false());
+}
+
+#[test]
+fn interactions_filter_matches_on_the_interaction_id() {
+  let interaction = RequestResponseInteraction { id: Some("abc123".to_string()), .. RequestResponseInteraction::default() };
+  let filter = FilterInfo::Interactions { keys: vec![], ids: vec!["abc123".to_string()], descriptions: vec![] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_true());
+  let filter = FilterInfo::Interactions { keys: vec![], ids: vec!["abc".to_string()], descriptions: vec![] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_false());
+}
+
+#[test]
+fn interactions_filter_matches_on_the_interaction_key() {
+  let interaction = SynchronousHttp { key: Some("1234abcd".to_string()), .. SynchronousHttp::default() };
+  let filter = FilterInfo::Interactions { keys: vec!["1234abcd".to_string()], ids: vec![], descriptions: vec![] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_true());
+
+  let interaction = SynchronousHttp { description: "bob".to_string(), .. SynchronousHttp::default() };
+  let filter = FilterInfo::Interactions { keys: vec![interaction.unique_key()], ids: vec![], descriptions: vec![] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_true());
+  let filter = FilterInfo::Interactions { keys: vec!["1234abcd".to_string()], ids: vec![], descriptions: vec![] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_false());
+}
+
+#[test]
+fn interactions_filter_matches_the_description_exactly() {
+  let interaction = RequestResponseInteraction { description: "bobby".to_string(), .. RequestResponseInteraction::default() };
+  let filter = FilterInfo::Interactions { keys: vec![], ids: vec![], descriptions: vec![("consumer".to_string(), "bobby".to_string())] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_true());
+  let filter = FilterInfo::Interactions { keys: vec![], ids: vec![], descriptions: vec![("consumer".to_string(), "bob.*".to_string())] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_false());
+  let filter = FilterInfo::Interactions { keys: vec![], ids: vec![], descriptions: vec![("other".to_string(), "bobby".to_string())] };
+  expect!(filter_interaction("consumer", &interaction, &filter)).to(be_false());
 }
 
 #[test]
@@ -328,11 +364,17 @@ async fn publish_successful_result_to_broker() {
   publish_result(&[VerificationInteractionResult {
       interaction_id: Some("1".to_string()),
       interaction_key: None,
+      unique_key: None,
       description: "".to_string(),
       interaction_description: "".to_string(),
+      consumer_name: "".to_string(),
+      provider_states: vec![],
+      request: None,
       result: Ok(()),
       pending: false,
       duration: Default::default(),
+      request_retries: 0,
+      provider_unavailable: false
     }], &source, &options, None
   ).await;
 
@@ -351,11 +393,17 @@ async fn publish_successful_result_to_broker() {
   super::publish_result(&[VerificationInteractionResult {
       interaction_id: Some("1".to_string()),
       interaction_key: None,
+      unique_key: None,
       description: "".to_string(),
       interaction_description: "".to_string(),
+      consumer_name: "".to_string(),
+      provider_states: vec![],
+      request: None,
       result: Ok(()),
       pending: false,
       duration: Default::default(),
+      request_retries: 0,
+      provider_unavailable: false
     }], &source, &options, None
   ).await;
 }
@@ -1141,6 +1189,92 @@ async fn support_passing_provider_state_params_to_provider_state_generator() {
   })).unwrap();
   let interaction = pact.interactions.first().unwrap();
 
-  let result = super::verify_interaction(&provider, interaction, &pact.boxed(), &verification_options, &provider_states).await;
+  let result = super::verify_interaction(&provider, interaction, &pact.boxed(), &verification_options, &provider_states,
+    &ProviderRequestStats::default()).await;
   expect!(result).to(be_ok());
 }
+
+#[cfg(feature = "tower")]
+#[derive(Clone, Default)]
+struct UserService {
+  users: Arc<std::sync::Mutex<Vec<String>>>
+}
+
+#[cfg(feature = "tower")]
+impl tower_service::Service<http::Request<http_body_util::Full<bytes::Bytes>>> for UserService {
+  type Response = http::Response<http_body_util::Full<bytes::Bytes>>;
+  type Error = std::convert::Infallible;
+  type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+    std::task::Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, request: http::Request<http_body_util::Full<bytes::Bytes>>) -> Self::Future {
+    let users = self.users.lock().unwrap();
+    let response = match request.uri().path().strip_prefix("/users/") {
+      Some(id) if users.contains(&id.to_string()) => http::Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(http_body_util::Full::new(bytes::Bytes::from(json!({ "id": id, "name": "Bob" }).to_string()))),
+      _ => http::Response::builder()
+        .status(404)
+        .body(http_body_util::Full::default())
+    };
+    std::future::ready(Ok(response.unwrap()))
+  }
+}
+
+#[cfg(feature = "tower")]
+#[test_log::test(tokio::test)]
+async fn verify_provider_async_with_an_in_process_provider() {
+  use crate::callback_executors::CallbackProviderStateExecutor;
+  use crate::in_process::TowerServiceProvider;
+
+  let pact = PactBuilder::new_v4("Consumer", "InProcessProvider")
+    .interaction("a request for a user", "", |mut i| {
+      i.given("user 100 exists");
+      i.request.path("/users/100");
+      i.response
+        .ok()
+        .content_type("application/json")
+        .json_body(json_pattern!({ "id": like!("100"), "name": like!("Bob") }));
+      i
+    })
+    .interaction("a request for a missing user", "", |mut i| {
+      i.request.path("/users/200");
+      i.response.status(404);
+      i
+    })
+    .build();
+  let pact_json = pact.to_json(pact_models::PactSpecification::V4).unwrap().to_string();
+
+  let service = UserService::default();
+  let users = service.users.clone();
+  let provider_states = Arc::new(CallbackProviderStateExecutor::new()
+    .state("user 100 exists", move |_, setup| {
+      let users = users.clone();
+      async move {
+        if setup {
+          users.lock().unwrap().push("100".to_string());
+        }
+        Ok(hashmap!{})
+      }
+    }));
+  let verification_options = VerificationOptions::<NullRequestFilterExecutor> {
+    provider_handler: Some(Arc::new(TowerServiceProvider::new(service))),
+    .. VerificationOptions::default()
+  };
+  let provider = ProviderInfo {
+    name: "InProcessProvider".to_string(),
+    .. ProviderInfo::default()
+  };
+
+  let result = super::verify_provider_async(
+    provider, vec![PactSource::String(pact_json)], FilterInfo::None, vec![],
+    &verification_options, None, &provider_states, None
+  ).await.unwrap();
+
+  expect!(result.result).to(be_true());
+  expect!(result.interaction_results.len()).to(be_equal_to(2));
+}
@@ -7,6 +7,7 @@ use itertools::Itertools;
 use serde_json::{json, Value};
 
 use pact_matching::Mismatch;
+use pact_models::v4::http_parts::HttpRequest;
 
 /// Result of verifying a Pact interaction
 #[derive(Clone, Debug)]
@@ -15,16 +16,95 @@ pub struct VerificationInteractionResult {
   pub interaction_id: Option<String>,
   /// Interaction key (this will be set if the Pact is a V4 Pact)
   pub interaction_key: Option<String>,
+  /// Unique key of the interaction, which is the interaction key or the calculated key if the
+  /// interaction does not have one (this will be set if the Pact is a V4 Pact)
+  pub unique_key: Option<String>,
   /// Descriptive text of the verification that was preformed
   pub description: String,
   /// Interaction description from the Pact file
   pub interaction_description: String,
+  /// Name of the consumer of the Pact that the interaction is from
+  pub consumer_name: String,
+  /// Provider states defined for the interaction
+  pub provider_states: Vec<String>,
+  /// Request that was sent to the provider (after any request filter was applied), if one was made
+  pub request: Option<HttpRequest>,
   /// Result of the verification
   pub result: Result<(), crate::MismatchResult>,
   /// If the Pact or interaction is pending
   pub pending: bool,
   /// Duration that the verification took
-  pub duration: Duration
+  pub duration: Duration,
+  /// Number of times the request to the provider was retried due to connection errors or timeouts
+  pub request_retries: u8,
+  /// If the verification failed because the provider could not be reached (after any retries)
+  pub provider_unavailable: bool
+}
+
+/// Provider state change call for an interaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateChangeCall {
+  /// Name of the provider state (this will be empty if the interaction has no provider states)
+  pub state: String,
+  /// If this is the setup call (otherwise it is the teardown call)
+  pub setup: bool,
+  /// Description of the call that would be made to change the provider state
+  pub description: String
+}
+
+/// Result of a dry run of a Pact interaction, with the calls that would have been made
+#[derive(Clone, Debug)]
+pub struct DryRunInteractionResult {
+  /// Interaction ID, this will only be set if the Pact was loaded from a Pact broker
+  pub interaction_id: Option<String>,
+  /// Interaction key (this will be set if the Pact is a V4 Pact)
+  pub interaction_key: Option<String>,
+  /// Interaction description from the Pact file
+  pub interaction_description: String,
+  /// Name of the consumer of the Pact that the interaction is from
+  pub consumer_name: String,
+  /// Provider state change calls that would be made, in order
+  pub state_changes: Vec<StateChangeCall>,
+  /// Base URL of the provider that the request would be sent to
+  pub base_url: Option<String>,
+  /// Request that would be sent to the provider (after generators and any request filter have
+  /// been applied). This will not be set if the request can not be previewed (i.e. for plugin
+  /// transports).
+  pub request: Option<HttpRequest>,
+  /// Any message about why the request could not be previewed
+  pub message: Option<String>
+}
+
+impl Into<Value> for &DryRunInteractionResult {
+  fn into(self) -> Value {
+    let mut json = json!({
+      "consumer": self.consumer_name,
+      "description": self.interaction_description,
+      "interactionKey": self.interaction_key,
+      "interactionId": self.interaction_id,
+      "stateChanges": self.state_changes.iter().map(|call| json!({
+        "state": call.state,
+        "action": if call.setup { "setup" } else { "teardown" },
+        "call": call.description
+      })).collect_vec()
+    });
+
+    if let Some(base_url) = &self.base_url {
+      json["baseUrl"] = json!(base_url);
+    }
+    if let Some(request) = &self.request {
+      json["request"] = HttpRequest {
+        matching_rules: Default::default(),
+        generators: Default::default(),
+        .. request.clone()
+      }.to_json();
+    }
+    if let Some(message) = &self.message {
+      json["message"] = json!(message);
+    }
+
+    json
+  }
 }
 
 /// Result of verifying a Pact
@@ -49,7 +129,9 @@ pub struct VerificationExecutionResult {
   /// Errors that occurred that are not considered pending
   pub errors: Vec<(String, VerificationMismatchResult)>,
   /// Result for each interaction that was verified
-  pub interaction_results: Vec<VerificationInteractionResult>
+  pub interaction_results: Vec<VerificationInteractionResult>,
+  /// Result for each interaction when doing a dry run
+  pub dry_run_results: Vec<DryRunInteractionResult>
 }
 
 impl VerificationExecutionResult {
@@ -62,13 +144,31 @@ impl VerificationExecutionResult {
       pending_errors: vec![],
       errors: vec![],
       interaction_results: vec![],
+      dry_run_results: vec![]
     }
   }
 }
 
+impl VerificationExecutionResult {
+  /// Results for the interactions where the request to the provider had to be retried or the
+  /// provider could not be reached
+  pub fn retried_interactions(&self) -> Vec<&VerificationInteractionResult> {
+    self.interaction_results.iter()
+      .filter(|result| result.request_retries > 0 || result.provider_unavailable)
+      .collect()
+  }
+
+  /// Results for the interactions that failed verification (including pending ones)
+  pub fn failed_interactions(&self) -> Vec<&VerificationInteractionResult> {
+    self.interaction_results.iter()
+      .filter(|result| result.result.is_err())
+      .collect()
+  }
+}
+
 impl Into<Value> for &VerificationExecutionResult {
   fn into(self) -> Value {
-    json!({
+    let mut json = json!({
       "result": self.result,
       "notices": self.notices.iter().map(|m| Value::Object(
         m.iter().map(|(k, v)| (k.clone(), Value::String(v.clone()))).collect()
@@ -88,7 +188,36 @@ impl Into<Value> for &VerificationExecutionResult {
           "mismatch": err
         })
       }).collect_vec()
-    })
+    });
+
+    let retried_interactions = self.retried_interactions();
+    if !retried_interactions.is_empty() {
+      json["retries"] = retried_interactions.iter().map(|result| json!({
+        "interaction": result.description,
+        "requestRetries": result.request_retries,
+        "providerUnavailable": result.provider_unavailable
+      })).collect();
+    }
+
+    if !self.dry_run_results.is_empty() {
+      json["dryRun"] = self.dry_run_results.iter().map(|result| {
+        let json: Value = result.into();
+        json
+      }).collect();
+    }
+
+    let failed_interactions = self.failed_interactions();
+    if !failed_interactions.is_empty() {
+      json["failedInteractions"] = failed_interactions.iter().map(|result| json!({
+        "consumer": result.consumer_name,
+        "description": result.interaction_description,
+        "interactionKey": result.unique_key,
+        "interactionId": result.interaction_id,
+        "pending": result.pending
+      })).collect();
+    }
+
+    json
   }
 }
 
@@ -173,7 +302,7 @@ mod tests {
   use pact_matching::Mismatch;
 
   use crate::VerificationExecutionResult;
-  use crate::verification_result::VerificationMismatchResult;
+  use crate::verification_result::{VerificationInteractionResult, VerificationMismatchResult};
 
   #[test]
   fn match_result_to_json() {
@@ -249,6 +378,7 @@ mod tests {
         )
       ],
       interaction_results: vec![],
+      dry_run_results: vec![]
     };
     let json: Value = result.into();
     expect!(json).to(be_equal_to(json!({
@@ -286,4 +416,98 @@ mod tests {
       "result": false
     })));
   }
+
+  #[test]
+  fn verification_execution_result_to_json_with_retries() {
+    let interaction_result = VerificationInteractionResult {
+      interaction_id: None,
+      interaction_key: None,
+      unique_key: None,
+      description: "interaction 1".to_string(),
+      interaction_description: "interaction 1".to_string(),
+      consumer_name: "consumer".to_string(),
+      provider_states: vec![],
+      request: None,
+      result: Ok(()),
+      pending: false,
+      duration: Default::default(),
+      request_retries: 0,
+      provider_unavailable: false
+    };
+    let result = VerificationExecutionResult {
+      interaction_results: vec![
+        interaction_result.clone(),
+        VerificationInteractionResult {
+          description: "interaction 2".to_string(),
+          request_retries: 2,
+          .. interaction_result.clone()
+        },
+        VerificationInteractionResult {
+          description: "interaction 3".to_string(),
+          result: Err(crate::MismatchResult::Error("Provider could not be reached".to_string(), None)),
+          request_retries: 3,
+          provider_unavailable: true,
+          .. interaction_result.clone()
+        }
+      ],
+      .. VerificationExecutionResult::new()
+    };
+    let json: Value = result.into();
+    expect!(&json["retries"]).to(be_equal_to(&json!([
+      {
+        "interaction": "interaction 2",
+        "requestRetries": 2,
+        "providerUnavailable": false
+      },
+      {
+        "interaction": "interaction 3",
+        "requestRetries": 3,
+        "providerUnavailable": true
+      }
+    ])));
+  }
+
+  #[test]
+  fn verification_execution_result_to_json_with_failed_interactions() {
+    let interaction_result = VerificationInteractionResult {
+      interaction_id: None,
+      interaction_key: Some("1234abcd".to_string()),
+      unique_key: Some("1234abcd".to_string()),
+      description: "interaction 1".to_string(),
+      interaction_description: "interaction 1".to_string(),
+      consumer_name: "consumer".to_string(),
+      provider_states: vec![],
+      request: None,
+      result: Ok(()),
+      pending: false,
+      duration: Default::default(),
+      request_retries: 0,
+      provider_unavailable: false
+    };
+    let result = VerificationExecutionResult {
+      interaction_results: vec![
+        interaction_result.clone(),
+        VerificationInteractionResult {
+          interaction_id: Some("abc123".to_string()),
+          interaction_key: None,
+          unique_key: Some("5678efgh".to_string()),
+          interaction_description: "interaction 2".to_string(),
+          result: Err(crate::MismatchResult::Error("Boom!".to_string(), Some("abc123".to_string()))),
+          pending: true,
+          .. interaction_result.clone()
+        }
+      ],
+      .. VerificationExecutionResult::new()
+    };
+    let json: Value = result.into();
+    expect!(&json["failedInteractions"]).to(be_equal_to(&json!([
+      {
+        "consumer": "consumer",
+        "description": "interaction 2",
+        "interactionKey": "5678efgh",
+        "interactionId": "abc123",
+        "pending": true
+      }
+    ])));
+  }
 }
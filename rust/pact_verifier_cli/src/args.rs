@@ -12,6 +12,10 @@ fn integer_value(v: &str) -> Result<u64, String> {
   v.parse::<u64>().map_err(|e| format!("'{}' is not a valid integer value: {}", v, e) )
 }
 
+fn retries_value(v: &str) -> Result<u8, String> {
+  v.parse::<u8>().map_err(|e| format!("'{}' is not a valid number of retries: {}", v, e) )
+}
+
 fn validate_regex(val: &str) -> Result<String, String> {
   if val.is_empty() {
     Err("filter value can not be empty".to_string())
@@ -26,6 +30,13 @@ fn json_value(v: &str) -> Result<Value, String> {
   serde_json::from_str(v).map_err(|err| format!("'{}' is not valid JSON: {}", v, err))
 }
 
+fn header_value(v: &str) -> Result<(String, String), String> {
+  match v.split_once('=') {
+    Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+    _ => Err(format!("'{}' is not a valid header value, it must be in the form KEY=VALUE", v))
+  }
+}
+
 lazy_static! {
   static ref TRANSPORT_VALUE_RE: Regex = Regex::new(r#"^(\w+):(\d+)(\/[^\s]*)?$"#).unwrap();
 }
@@ -61,6 +72,12 @@ pub(crate) fn setup_app() -> Command {
       .long("version")
       .action(ArgAction::Version)
       .help("Print version information and exit"))
+    .arg(Arg::new("config")
+      .long("config")
+      .env("PACT_VERIFIER_CONFIG")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .help("Configuration file (TOML or YAML) to load the options from. Options given on the command line or with environment variables override the values from the file"))
 
     .group(ArgGroup::new("logging").multiple(true))
     .next_help_heading("Logging options")
@@ -99,6 +116,18 @@ pub(crate) fn setup_app() -> Command {
       .action(ArgAction::Set)
       .value_parser(NonEmptyStringValueParser::new())
       .help("Generate a JUnit XML report of the verification (requires the junit feature)"))
+    .arg(Arg::new("html-file")
+      .long("html")
+      .env("PACT_VERIFIER_HTML_REPORT")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .help("Generate a self-contained HTML report of the verification"))
+    .arg(Arg::new("markdown-file")
+      .long("markdown")
+      .env("PACT_VERIFIER_MARKDOWN_REPORT")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .help("Generate a Markdown summary of the verification (suitable for PR comments)"))
     .arg(Arg::new("no-colour")
       .long("no-colour")
       .action(ArgAction::SetTrue)
@@ -174,6 +203,42 @@ pub(crate) fn setup_app() -> Command {
       .value_parser(NonEmptyStringValueParser::new())
       .conflicts_with("user")
       .help("Bearer token to use when fetching pacts from URLS"))
+    .arg(Arg::new("oauth2-token-url")
+      .long("oauth2-token-url")
+      .env("PACT_BROKER_OAUTH2_TOKEN_URL")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .requires_all(&["oauth2-client-id", "oauth2-client-secret"])
+      .conflicts_with_all(&["user", "token"])
+      .help("OAuth2 token endpoint to fetch an access token from (using the client credentials grant) when fetching pacts from URLS"))
+    .arg(Arg::new("oauth2-client-id")
+      .long("oauth2-client-id")
+      .env("PACT_BROKER_OAUTH2_CLIENT_ID")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .requires("oauth2-token-url")
+      .help("OAuth2 client ID to use with the client credentials grant"))
+    .arg(Arg::new("oauth2-client-secret")
+      .long("oauth2-client-secret")
+      .env("PACT_BROKER_OAUTH2_CLIENT_SECRET")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .requires("oauth2-token-url")
+      .help("OAuth2 client secret to use with the client credentials grant"))
+    .arg(Arg::new("oauth2-scope")
+      .long("oauth2-scope")
+      .env("PACT_BROKER_OAUTH2_SCOPE")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .requires("oauth2-token-url")
+      .help("Scope to request with the OAuth2 access token"))
+    .arg(Arg::new("auth-header")
+      .long("auth-header")
+      .env("PACT_BROKER_AUTH_HEADER")
+      .action(ArgAction::Set)
+      .value_parser(header_value)
+      .conflicts_with_all(&["user", "token", "oauth2-token-url"])
+      .help("Custom header to authenticate with when fetching pacts from URLS. Values must be in the form KEY=VALUE, i.e. X-API-Key=1234"))
 
     .group(ArgGroup::new("provider").multiple(true))
     .next_help_heading("Provider options")
@@ -225,6 +290,37 @@ pub(crate) fn setup_app() -> Command {
       .action(ArgAction::Set)
       .value_parser(integer_value)
       .help("Sets the HTTP request timeout in milliseconds for requests to the target API and for state change requests."))
+    .arg(Arg::new("request-retries")
+      .long("request-retries")
+      .env("PACT_PROVIDER_REQUEST_RETRIES")
+      .action(ArgAction::Set)
+      .value_parser(retries_value)
+      .help("Number of times to retry a request to the provider if it fails with a connection error or a timeout (defaults to 0)"))
+    .arg(Arg::new("request-retry-delay")
+      .long("request-retry-delay")
+      .env("PACT_PROVIDER_REQUEST_RETRY_DELAY")
+      .action(ArgAction::Set)
+      .value_parser(integer_value)
+      .help("Time in milliseconds to wait before retrying a failed request to the provider (defaults to 1000)"))
+    .arg(Arg::new("readiness-url")
+      .long("readiness-url")
+      .env("PACT_PROVIDER_READINESS_URL")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .help("URL to poll until it returns a successful response before verifying any interactions (i.e. a health check endpoint of the provider)"))
+    .arg(Arg::new("readiness-timeout")
+      .long("readiness-timeout")
+      .env("PACT_PROVIDER_READINESS_TIMEOUT")
+      .action(ArgAction::Set)
+      .value_parser(integer_value)
+      .requires("readiness-url")
+      .help("Maximum time in milliseconds to wait for the provider to be ready (defaults to 30000)"))
+    .arg(Arg::new("generator-seed")
+      .long("generator-seed")
+      .env("PACT_GENERATOR_SEED")
+      .action(ArgAction::Set)
+      .value_parser(integer_value)
+      .help("Seed to use for the random value generators, so that the same requests are generated on every run"))
     .arg(Arg::new("custom-header")
       .long("header")
       .short('H')
@@ -235,6 +331,11 @@ pub(crate) fn setup_app() -> Command {
       .long("disable-ssl-verification")
       .action(ArgAction::SetTrue)
       .help("Disables validation of SSL certificates"))
+    .arg(Arg::new("dry-run")
+      .long("dry-run")
+      .action(ArgAction::SetTrue)
+      .conflicts_with("publish")
+      .help("Generates and displays the requests and provider state change calls that would be made, without sending them to the provider"))
 
     .group(ArgGroup::new("states").multiple(true))
     .next_help_heading("Provider state options")
@@ -279,6 +380,25 @@ pub(crate) fn setup_app() -> Command {
       .env("PACT_PROVIDER_NO_STATE")
       .conflicts_with("filter-state")
       .help("Only validate interactions that have no defined provider state"))
+    .arg(Arg::new("filter-key")
+      .long("filter-key")
+      .action(ArgAction::Append)
+      .value_parser(NonEmptyStringValueParser::new())
+      .conflicts_with_all(&["filter-description", "filter-state", "filter-no-state"])
+      .help("Only validate the interaction with this key (V4 interactions only, can be repeated)"))
+    .arg(Arg::new("filter-id")
+      .long("filter-id")
+      .action(ArgAction::Append)
+      .value_parser(NonEmptyStringValueParser::new())
+      .conflicts_with_all(&["filter-description", "filter-state", "filter-no-state"])
+      .help("Only validate the interaction with this ID (interactions fetched from a Pact Broker only, can be repeated)"))
+    .arg(Arg::new("rerun-failed")
+      .long("rerun-failed")
+      .value_name("json-report")
+      .action(ArgAction::Set)
+      .value_parser(NonEmptyStringValueParser::new())
+      .conflicts_with_all(&["filter-description", "filter-state", "filter-no-state", "filter-key", "filter-id"])
+      .help("Only validate the interactions that failed in a previous verification, loaded from its JSON report"))
     .arg(Arg::new("filter-consumer")
       .short('c')
       .long("filter-consumer")
@@ -353,7 +473,7 @@ mod test {
 
   use crate::args::setup_app;
 
-  use super::{integer_value, port_value, transport_value, validate_regex};
+  use super::{header_value, integer_value, port_value, retries_value, transport_value, validate_regex};
 
   #[test]
   fn validates_port_value() {
@@ -368,6 +488,13 @@ mod test {
     expect!(integer_value("1234x")).to(be_err());
   }
 
+  #[test]
+  fn validates_retries_value() {
+    expect!(retries_value("3")).to(be_ok().value(3));
+    expect!(retries_value("3x")).to(be_err());
+    expect!(retries_value("300")).to(be_err());
+  }
+
   #[test]
   fn validates_transport_value() {
     expect!(transport_value("http:1234")).to(be_ok().value(("http".to_string(), 1234, None)));
@@ -393,6 +520,14 @@ mod test {
     expect!(transport_value(value)).to(be_ok().value(expected_value));
   }
 
+  #[test]
+  fn validates_header_value() {
+    expect!(header_value("X-API-Key=1234")).to(be_ok().value(("X-API-Key".to_string(), "1234".to_string())));
+    expect!(header_value("X-API-Key=a=b")).to(be_ok().value(("X-API-Key".to_string(), "a=b".to_string())));
+    expect!(header_value("X-API-Key")).to(be_err());
+    expect!(header_value("=1234")).to(be_err());
+  }
+
   #[test]
   fn validates_regex_value() {
     expect!(validate_regex("\\d+")).to(be_ok().value("\\d+".to_string()));
@@ -0,0 +1,109 @@
+//! The `pact_diff` command compares two Pact files and reports the interactions that have been
+//! added, removed or changed between them.
+//!
+//! ```console,ignore
+//! $ pact_diff --help
+//! Compares two Pact files and displays the differences between them
+//!
+//! Usage: pact_diff [OPTIONS] <old> <new>
+//!
+//! Arguments:
+//!   <old>  Pact file to compare from
+//!   <new>  Pact file to compare to
+//!
+//! Options:
+//!   -o, --output <output>  Format to display the differences in [default: text] [possible values: text, json]
+//!       --exit-code        Exit with a status of 1 if there are any differences
+//!       --help             Print help and exit
+//!   -v, --version          Print version information and exit
+//! ```
+
+use std::path::Path;
+
+use clap::{Arg, ArgAction, ArgMatches, Command, command};
+use clap::builder::PossibleValuesParser;
+use pact_models::diff::diff_pacts;
+use pact_models::pact::read_pact;
+
+fn setup_app() -> Command {
+  command!()
+    .name("pact_diff")
+    .about("Compares two Pact files and displays the differences between them")
+    .disable_help_flag(true)
+    .disable_version_flag(true)
+    .arg(Arg::new("old")
+      .required(true)
+      .action(ArgAction::Set)
+      .help("Pact file to compare from"))
+    .arg(Arg::new("new")
+      .required(true)
+      .action(ArgAction::Set)
+      .help("Pact file to compare to"))
+    .arg(Arg::new("output")
+      .short('o')
+      .long("output")
+      .action(ArgAction::Set)
+      .value_parser(PossibleValuesParser::new(["text", "json"]))
+      .default_value("text")
+      .help("Format to display the differences in"))
+    .arg(Arg::new("exit-code")
+      .long("exit-code")
+      .action(ArgAction::SetTrue)
+      .help("Exit with a status of 1 if there are any differences"))
+    .arg(Arg::new("help")
+      .long("help")
+      .action(ArgAction::Help)
+      .help("Print help and exit"))
+    .arg(Arg::new("version")
+      .short('v')
+      .long("version")
+      .action(ArgAction::Version)
+      .help("Print version information and exit"))
+}
+
+fn diff(matches: &ArgMatches) -> Result<(), i32> {
+  let old_file = matches.get_one::<String>("old").unwrap();
+  let new_file = matches.get_one::<String>("new").unwrap();
+  let old = read_pact(Path::new(old_file)).map_err(|err| {
+    eprintln!("ERROR: Failed to load pact file '{}' - {}", old_file, err);
+    2
+  })?;
+  let new = read_pact(Path::new(new_file)).map_err(|err| {
+    eprintln!("ERROR: Failed to load pact file '{}' - {}", new_file, err);
+    2
+  })?;
+
+  let diff = diff_pacts(old.as_ref(), new.as_ref()).map_err(|err| {
+    eprintln!("ERROR: Failed to compare the pact files - {}", err);
+    2
+  })?;
+
+  if matches.get_one::<String>("output").map(|s| s.as_str()) == Some("json") {
+    println!("{}", diff.to_json());
+  } else {
+    print!("{}", diff);
+  }
+
+  if matches.get_flag("exit-code") && !diff.is_empty() {
+    Err(1)
+  } else {
+    Ok(())
+  }
+}
+
+fn main() {
+  let matches = setup_app().get_matches();
+  if let Err(err) = diff(&matches) {
+    std::process::exit(err);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::setup_app;
+
+  #[test]
+  fn verify_cli() {
+    setup_app().debug_assert();
+  }
+}
@@ -0,0 +1,448 @@
+//! Support for loading the verifier options from a TOML or YAML configuration file.
+//!
+//! The values from the file are converted into command line arguments, so they go through the
+//! same validation as the command line. Any option given on the command line or with an environment
+//! variable overrides the value from the file.
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use serde_json::Value;
+
+use crate::args;
+
+/// How a configuration value is converted into command line arguments
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ConfigValueType {
+  /// Single value
+  Value,
+  /// List of values, with the argument repeated for each value
+  List,
+  /// List of values, joined with commas
+  DelimitedList,
+  /// Boolean flag
+  Flag,
+  /// List of tables, with each table passed as a JSON string
+  JsonList,
+  /// Table of names to values, passed as NAME=VALUE
+  Pairs
+}
+
+/// Configuration key and the command line argument it maps to
+struct ConfigOption {
+  key: &'static str,
+  arg_id: &'static str,
+  value_type: ConfigValueType
+}
+
+const fn option(key: &'static str, arg_id: &'static str, value_type: ConfigValueType) -> ConfigOption {
+  ConfigOption { key, arg_id, value_type }
+}
+
+const OUTPUT_OPTIONS: &[ConfigOption] = &[
+  option("loglevel", "loglevel", ConfigValueType::Value),
+  option("pretty-log", "pretty-log", ConfigValueType::Flag),
+  option("full-log", "full-log", ConfigValueType::Flag),
+  option("compact-log", "compact-log", ConfigValueType::Flag),
+  option("json", "json-file", ConfigValueType::Value),
+  option("junit", "junit-file", ConfigValueType::Value),
+  option("html", "html-file", ConfigValueType::Value),
+  option("markdown", "markdown-file", ConfigValueType::Value),
+  option("no-colour", "no-colour", ConfigValueType::Flag)
+];
+
+const SOURCE_OPTIONS: &[ConfigOption] = &[
+  option("files", "file", ConfigValueType::List),
+  option("dirs", "dir", ConfigValueType::List),
+  option("urls", "url", ConfigValueType::List),
+  option("broker-url", "broker-url", ConfigValueType::Value),
+  option("webhook-callback-url", "webhook-callback-url", ConfigValueType::Value),
+  option("consumer-version-tags", "consumer-version-tags", ConfigValueType::DelimitedList),
+  option("consumer-version-selectors", "consumer-version-selectors", ConfigValueType::JsonList),
+  option("enable-pending", "enable-pending", ConfigValueType::Flag),
+  option("include-wip-pacts-since", "include-wip-pacts-since", ConfigValueType::Value)
+];
+
+const AUTH_OPTIONS: &[ConfigOption] = &[
+  option("user", "user", ConfigValueType::Value),
+  option("password", "password", ConfigValueType::Value),
+  option("token", "token", ConfigValueType::Value),
+  option("oauth2-token-url", "oauth2-token-url", ConfigValueType::Value),
+  option("oauth2-client-id", "oauth2-client-id", ConfigValueType::Value),
+  option("oauth2-client-secret", "oauth2-client-secret", ConfigValueType::Value),
+  option("oauth2-scope", "oauth2-scope", ConfigValueType::Value),
+  option("auth-header", "auth-header", ConfigValueType::Value)
+];
+
+const PROVIDER_OPTIONS: &[ConfigOption] = &[
+  option("name", "provider-name", ConfigValueType::Value),
+  option("hostname", "hostname", ConfigValueType::Value),
+  option("port", "port", ConfigValueType::Value),
+  option("transport", "transport", ConfigValueType::Value),
+  option("transports", "transports", ConfigValueType::List),
+  option("base-path", "base-path", ConfigValueType::Value),
+  option("state-change-url", "state-change-url", ConfigValueType::Value),
+  option("state-change-as-query", "state-change-as-query", ConfigValueType::Flag),
+  option("state-change-teardown", "state-change-teardown", ConfigValueType::Flag)
+];
+
+const FILTER_OPTIONS: &[ConfigOption] = &[
+  option("description", "filter-description", ConfigValueType::Value),
+  option("state", "filter-state", ConfigValueType::Value),
+  option("no-state", "filter-no-state", ConfigValueType::Flag),
+  option("keys", "filter-key", ConfigValueType::List),
+  option("ids", "filter-id", ConfigValueType::List),
+  option("rerun-failed", "rerun-failed", ConfigValueType::Value),
+  option("consumers", "filter-consumer", ConfigValueType::List)
+];
+
+const VERIFICATION_OPTIONS: &[ConfigOption] = &[
+  option("request-timeout", "request-timeout", ConfigValueType::Value),
+  option("request-retries", "request-retries", ConfigValueType::Value),
+  option("request-retry-delay", "request-retry-delay", ConfigValueType::Value),
+  option("readiness-url", "readiness-url", ConfigValueType::Value),
+  option("readiness-timeout", "readiness-timeout", ConfigValueType::Value),
+  option("generator-seed", "generator-seed", ConfigValueType::Value),
+  option("headers", "custom-header", ConfigValueType::Pairs),
+  option("disable-ssl-verification", "disable-ssl-verification", ConfigValueType::Flag),
+  option("dry-run", "dry-run", ConfigValueType::Flag),
+  option("ignore-no-pacts-error", "ignore-no-pacts-error", ConfigValueType::Flag)
+];
+
+const PUBLISH_OPTIONS: &[ConfigOption] = &[
+  option("enabled", "publish", ConfigValueType::Flag),
+  option("provider-version", "provider-version", ConfigValueType::Value),
+  option("build-url", "build-url", ConfigValueType::Value),
+  option("provider-tags", "provider-tags", ConfigValueType::DelimitedList),
+  option("provider-branch", "provider-branch", ConfigValueType::Value)
+];
+
+/// Sections of the configuration file
+const CONFIG_SECTIONS: &[(&str, &[ConfigOption])] = &[
+  ("output", OUTPUT_OPTIONS),
+  ("sources", SOURCE_OPTIONS),
+  ("auth", AUTH_OPTIONS),
+  ("provider", PROVIDER_OPTIONS),
+  ("filter", FILTER_OPTIONS),
+  ("verification", VERIFICATION_OPTIONS),
+  ("publish", PUBLISH_OPTIONS)
+];
+
+/// Configuration file formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ConfigFormat {
+  /// TOML file (.toml)
+  Toml,
+  /// YAML file (.yaml or .yml). As YAML is a superset of JSON, this is also used for JSON files.
+  Yaml
+}
+
+impl ConfigFormat {
+  /// Determines the format of the configuration file from its extension
+  pub(crate) fn from_path(path: &Path) -> anyhow::Result<ConfigFormat> {
+    match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()) {
+      Some(ext) if ext == "toml" => Ok(ConfigFormat::Toml),
+      Some(ext) if ext == "yaml" || ext == "yml" || ext == "json" => Ok(ConfigFormat::Yaml),
+      _ => Err(anyhow!("Configuration file '{}' must be a TOML (.toml) or YAML (.yaml, .yml) file", path.display()))
+    }
+  }
+}
+
+/// Parses the contents of a configuration file
+pub(crate) fn parse_config(contents: &str, format: ConfigFormat) -> anyhow::Result<Value> {
+  match format {
+    ConfigFormat::Toml => toml::from_str(contents).map_err(|err| anyhow!(err)),
+    ConfigFormat::Yaml => serde_yaml::from_str(contents).map_err(|err| anyhow!(err))
+  }
+}
+
+/// Loads the configuration file given with the `--config` option (or `PACT_VERIFIER_CONFIG`
+/// environment variable), and merges the values from it into the command line arguments. If
+/// no configuration file is given, the arguments are returned unchanged.
+pub(crate) fn apply_config_file(args: Vec<OsString>) -> anyhow::Result<Vec<OsString>> {
+  let matches = match args::setup_app().ignore_errors(true).try_get_matches_from(&args) {
+    Ok(matches) => matches,
+    // Help and version requests, let the main parser deal with them
+    Err(_) => return Ok(args)
+  };
+
+  if let Some(config_file) = matches.get_one::<String>("config") {
+    let path = Path::new(config_file);
+    let format = ConfigFormat::from_path(path)?;
+    let contents = fs::read_to_string(path)
+      .map_err(|err| anyhow!("Failed to read configuration file '{}' - {}", config_file, err))?;
+    let config = parse_config(contents.as_str(), format)
+      .map_err(|err| anyhow!("Failed to parse configuration file '{}' - {}", config_file, err))?;
+    let config_args = config_to_args(&config, &matches)
+      .map_err(|errors| anyhow!("Configuration file '{}' is not valid:\n  {}", config_file, errors.join("\n  ")))?;
+    Ok(args.iter().take(1).cloned()
+      .chain(config_args)
+      .chain(args.iter().skip(1).cloned())
+      .collect())
+  } else {
+    Ok(args)
+  }
+}
+
+/// Converts the configuration into command line arguments. Values for arguments that have been
+/// provided on the command line or by environment variables (or that conflict with them) are
+/// skipped. Returns a list of all the validation errors if the configuration is not valid.
+pub(crate) fn config_to_args(config: &Value, matches: &ArgMatches) -> Result<Vec<OsString>, Vec<String>> {
+  let sections = match config {
+    Value::Object(sections) => sections,
+    Value::Null => return Ok(vec![]),
+    _ => return Err(vec![ "The configuration must be a table of sections".to_string() ])
+  };
+
+  let mut config_args = vec![];
+  let mut errors = vec![];
+  for (section_name, section) in sections {
+    let options = match CONFIG_SECTIONS.iter().find(|(name, _)| name == section_name) {
+      Some((_, options)) => options,
+      None => {
+        errors.push(format!("Unknown section '{}', valid sections are: {}", section_name,
+          CONFIG_SECTIONS.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")));
+        continue;
+      }
+    };
+    let values = match section {
+      Value::Object(values) => values,
+      _ => {
+        errors.push(format!("'{}' must be a table of options", section_name));
+        continue;
+      }
+    };
+    for (key, value) in values {
+      match options.iter().find(|option| option.key == key) {
+        Some(option) => match option_to_args(option, value) {
+          Ok(args) => if !args.is_empty() && !provided_externally(option.arg_id, matches) {
+            config_args.extend(args);
+          }
+          Err(err) => errors.push(format!("'{}.{}' {}", section_name, key, err))
+        }
+        None => errors.push(format!("Unknown key '{}.{}', valid keys are: {}", section_name, key,
+          options.iter().map(|option| option.key).collect::<Vec<_>>().join(", ")))
+      }
+    }
+  }
+
+  if errors.is_empty() {
+    Ok(config_args)
+  } else {
+    Err(errors)
+  }
+}
+
+/// If the argument (or any argument it conflicts with) has been given on the command line or with
+/// an environment variable
+fn provided_externally(arg_id: &str, matches: &ArgMatches) -> bool {
+  let app = args::setup_app();
+  let is_external = |id: &str| matches!(matches.value_source(id),
+    Some(ValueSource::CommandLine) | Some(ValueSource::EnvVariable));
+  is_external(arg_id) || app.get_arguments()
+    .find(|arg| arg.get_id() == arg_id)
+    .map(|arg| app.get_arg_conflicts_with(arg).iter().any(|conflict| is_external(conflict.get_id().as_str())))
+    .unwrap_or_default()
+}
+
+fn option_to_args(option: &ConfigOption, value: &Value) -> Result<Vec<OsString>, String> {
+  let flag = format!("--{}", long_name(option.arg_id));
+  match option.value_type {
+    ConfigValueType::Value => scalar_value(value)
+      .map(|value| vec![ OsString::from(flag), OsString::from(value) ])
+      .ok_or_else(|| "must be a string, number or boolean value".to_string()),
+    ConfigValueType::List => list_values(value)
+      .map(|values| values.iter()
+        .flat_map(|value| [ OsString::from(&flag), OsString::from(value) ])
+        .collect())
+      .ok_or_else(|| "must be a list of values".to_string()),
+    ConfigValueType::DelimitedList => list_values(value)
+      .map(|values| if values.is_empty() {
+        vec![]
+      } else {
+        vec![ OsString::from(flag), OsString::from(values.join(",")) ]
+      })
+      .ok_or_else(|| "must be a list of values".to_string()),
+    ConfigValueType::Flag => match value {
+      Value::Bool(true) => Ok(vec![ OsString::from(flag) ]),
+      Value::Bool(false) => Ok(vec![]),
+      _ => Err("must be true or false".to_string())
+    },
+    ConfigValueType::JsonList => match value {
+      Value::Array(values) if values.iter().all(|value| value.is_object()) => Ok(values.iter()
+        .flat_map(|value| [ OsString::from(&flag), OsString::from(value.to_string()) ])
+        .collect()),
+      _ => Err("must be a list of tables".to_string())
+    },
+    ConfigValueType::Pairs => match value {
+      Value::Object(values) => values.iter()
+        .map(|(name, value)| scalar_value(value)
+          .map(|value| [ OsString::from(&flag), OsString::from(format!("{}={}", name, value)) ])
+          .ok_or_else(|| format!("value for '{}' must be a string, number or boolean value", name)))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|args| args.into_iter().flatten().collect()),
+      _ => Err("must be a table of names to values".to_string())
+    }
+  }
+}
+
+fn long_name(arg_id: &str) -> String {
+  args::setup_app().get_arguments()
+    .find(|arg| arg.get_id() == arg_id)
+    .and_then(|arg| arg.get_long())
+    .unwrap_or(arg_id)
+    .to_string()
+}
+
+fn scalar_value(value: &Value) -> Option<String> {
+  match value {
+    Value::String(s) => Some(s.clone()),
+    Value::Number(n) => Some(n.to_string()),
+    Value::Bool(b) => Some(b.to_string()),
+    _ => None
+  }
+}
+
+fn list_values(value: &Value) -> Option<Vec<String>> {
+  match value {
+    Value::Array(values) => values.iter().map(scalar_value).collect(),
+    _ => scalar_value(value).map(|value| vec![ value ])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use super::*;
+
+  fn matches_from(args: &[&str]) -> ArgMatches {
+    args::setup_app().ignore_errors(true).get_matches_from(args)
+  }
+
+  fn to_strings(args: Vec<OsString>) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string_lossy().to_string()).collect()
+  }
+
+  #[test]
+  fn config_format_from_path() {
+    expect!(ConfigFormat::from_path(Path::new("pact-verifier.toml"))).to(be_ok().value(ConfigFormat::Toml));
+    expect!(ConfigFormat::from_path(Path::new("pact-verifier.yaml"))).to(be_ok().value(ConfigFormat::Yaml));
+    expect!(ConfigFormat::from_path(Path::new("pact-verifier.YML"))).to(be_ok().value(ConfigFormat::Yaml));
+    expect!(ConfigFormat::from_path(Path::new("pact-verifier.ini"))).to(be_err());
+    expect!(ConfigFormat::from_path(Path::new("pact-verifier"))).to(be_err());
+  }
+
+  #[test]
+  fn toml_and_yaml_configs_are_equivalent() {
+    let toml = r#"
+      [provider]
+      name = "happy_provider"
+      port = 8080
+      transports = ["http:8080", "message:8081"]
+
+      [sources]
+      broker-url = "http://localhost:9292"
+      consumer-version-selectors = [ { branch = "main" }, { deployedOrReleased = true } ]
+
+      [verification]
+      headers = { X-Api-Key = "1234" }
+    "#;
+    let yaml = r#"
+      provider:
+        name: happy_provider
+        port: 8080
+        transports:
+          - http:8080
+          - message:8081
+      sources:
+        broker-url: http://localhost:9292
+        consumer-version-selectors:
+          - branch: main
+          - deployedOrReleased: true
+      verification:
+        headers:
+          X-Api-Key: "1234"
+    "#;
+    let toml_config = parse_config(toml, ConfigFormat::Toml).unwrap();
+    let yaml_config = parse_config(yaml, ConfigFormat::Yaml).unwrap();
+    expect!(toml_config.clone()).to(be_equal_to(yaml_config));
+
+    let matches = matches_from(&["pact_verifier_cli"]);
+    let args = to_strings(config_to_args(&toml_config, &matches).unwrap());
+    expect!(args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "--provider-name", "happy_provider",
+      "--port", "8080",
+      "--transports", "http:8080",
+      "--transports", "message:8081",
+      "--broker-url", "http://localhost:9292",
+      "--consumer-version-selectors", "{\"branch\":\"main\"}",
+      "--consumer-version-selectors", "{\"deployedOrReleased\":true}",
+      "--header", "X-Api-Key=1234"
+    ]));
+  }
+
+  #[test]
+  fn config_to_args_converts_flags_and_lists() {
+    let config = json!({
+      "output": { "loglevel": "debug", "no-colour": true, "pretty-log": false },
+      "sources": { "files": "a.json", "consumer-version-tags": ["main", "prod"] },
+      "filter": { "consumers": ["A", "B"], "no-state": true },
+      "publish": { "enabled": true, "provider-version": "1.0.0", "provider-tags": ["main"] }
+    });
+    let matches = matches_from(&["pact_verifier_cli"]);
+    let args = to_strings(config_to_args(&config, &matches).unwrap());
+    expect!(args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "--filter-consumer", "A",
+      "--filter-consumer", "B",
+      "--filter-no-state",
+      "--loglevel", "debug",
+      "--no-colour",
+      "--publish",
+      "--provider-tags", "main",
+      "--provider-version", "1.0.0",
+      "--consumer-version-tags", "main,prod",
+      "--file", "a.json"
+    ]));
+  }
+
+  #[test]
+  fn command_line_values_override_config_values() {
+    let config = json!({
+      "provider": { "hostname": "config.example.com", "port": 8080 },
+      "sources": { "files": ["config.json"] },
+      "auth": { "token": "1234" }
+    });
+    let matches = matches_from(&["pact_verifier_cli", "--port", "9000", "-f", "cli.json",
+      "--user", "fred"]);
+    let args = to_strings(config_to_args(&config, &matches).unwrap());
+    expect!(args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "--hostname", "config.example.com"
+    ]));
+  }
+
+  #[test]
+  fn config_to_args_reports_all_invalid_keys() {
+    let config = json!({
+      "provider": { "hostnam": "localhost", "port": [8080] },
+      "verification": { "headers": "X-Api-Key=1234", "disable-ssl-verification": "yes" },
+      "sources": { "consumer-version-selectors": ["main"] },
+      "logging": { "level": "debug" }
+    });
+    let matches = matches_from(&["pact_verifier_cli"]);
+    expect!(config_to_args(&config, &matches)).to(be_err().value(vec![
+      "Unknown section 'logging', valid sections are: output, sources, auth, provider, filter, verification, publish".to_string(),
+      "Unknown key 'provider.hostnam', valid keys are: name, hostname, port, transport, transports, base-path, state-change-url, state-change-as-query, state-change-teardown".to_string(),
+      "'provider.port' must be a string, number or boolean value".to_string(),
+      "'sources.consumer-version-selectors' must be a list of tables".to_string(),
+      "'verification.disable-ssl-verification' must be true or false".to_string(),
+      "'verification.headers' must be a table of names to values".to_string()
+    ]));
+    expect!(config_to_args(&json!(["provider"]), &matches)).to(be_err());
+  }
+}
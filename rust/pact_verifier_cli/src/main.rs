@@ -21,17 +21,20 @@
 //! Usage: pact_verifier_cli [OPTIONS]
 //!
 //! Options:
-//!       --help     Print help and exit
-//!   -v, --version  Print version information and exit
+//!       --help             Print help and exit
+//!   -v, --version          Print version information and exit
+//!       --config <config>  Configuration file (TOML or YAML) to load the options from. Options given on the command line or with environment variables override the values from the file [env: PACT_VERIFIER_CONFIG=]
 //!
 //! Logging options:
-//!   -l, --loglevel <loglevel>  Log level to emit log events at (defaults to warn) [possible values: error, warn, info, debug, trace, none]
-//!       --pretty-log           Emits excessively pretty, multi-line logs, optimized for human readability.
-//!       --full-log             This emits human-readable, single-line logs for each event that occurs, with the current span context displayed before the formatted representation of the event.
-//!       --compact-log          Emit logs optimized for short line lengths.
-//!   -j, --json <json-file>     Generate a JSON report of the verification [env: PACT_VERIFIER_JSON_REPORT=]
-//!   -x, --junit <junit-file>   Generate a JUnit XML report of the verification (requires the junit feature) [env: PACT_VERIFIER_JUNIT_REPORT=]
-//!       --no-colour            Disables ANSI escape codes in the output [aliases: no-color]
+//!   -l, --loglevel <loglevel>       Log level to emit log events at (defaults to warn) [possible values: error, warn, info, debug, trace, none]
+//!       --pretty-log                Emits excessively pretty, multi-line logs, optimized for human readability.
+//!       --full-log                  This emits human-readable, single-line logs for each event that occurs, with the current span context displayed before the formatted representation of the event.
+//!       --compact-log               Emit logs optimized for short line lengths.
+//!   -j, --json <json-file>          Generate a JSON report of the verification [env: PACT_VERIFIER_JSON_REPORT=]
+//!   -x, --junit <junit-file>        Generate a JUnit XML report of the verification (requires the junit feature) [env: PACT_VERIFIER_JUNIT_REPORT=]
+//!       --html <html-file>          Generate a self-contained HTML report of the verification [env: PACT_VERIFIER_HTML_REPORT=]
+//!       --markdown <markdown-file>  Generate a Markdown summary of the verification (suitable for PR comments) [env: PACT_VERIFIER_MARKDOWN_REPORT=]
+//!       --no-colour                 Disables ANSI escape codes in the output [aliases: no-color]
 //!
 //! Loading pacts options:
 //!   -f, --file <file>
@@ -48,9 +51,22 @@
 //!           Do not fail if no pacts are found to verify
 //!
 //! Authentication options:
-//!       --user <user>          Username to use when fetching pacts from URLS [env: PACT_BROKER_USERNAME=]
-//!       --password <password>  Password to use when fetching pacts from URLS [env: PACT_BROKER_PASSWORD=]
-//!   -t, --token <token>        Bearer token to use when fetching pacts from URLS [env: PACT_BROKER_TOKEN=]
+//!       --user <user>
+//!           Username to use when fetching pacts from URLS [env: PACT_BROKER_USERNAME=]
+//!       --password <password>
+//!           Password to use when fetching pacts from URLS [env: PACT_BROKER_PASSWORD=]
+//!   -t, --token <token>
+//!           Bearer token to use when fetching pacts from URLS [env: PACT_BROKER_TOKEN=]
+//!       --oauth2-token-url <oauth2-token-url>
+//!           OAuth2 token endpoint to fetch an access token from (using the client credentials grant) when fetching pacts from URLS [env: PACT_BROKER_OAUTH2_TOKEN_URL=]
+//!       --oauth2-client-id <oauth2-client-id>
+//!           OAuth2 client ID to use with the client credentials grant [env: PACT_BROKER_OAUTH2_CLIENT_ID=]
+//!       --oauth2-client-secret <oauth2-client-secret>
+//!           OAuth2 client secret to use with the client credentials grant [env: PACT_BROKER_OAUTH2_CLIENT_SECRET=]
+//!       --oauth2-scope <oauth2-scope>
+//!           Scope to request with the OAuth2 access token [env: PACT_BROKER_OAUTH2_SCOPE=]
+//!       --auth-header <auth-header>
+//!           Custom header to authenticate with when fetching pacts from URLS. Values must be in the form KEY=VALUE, i.e. X-API-Key=1234 [env: PACT_BROKER_AUTH_HEADER=]
 //!
 //! Provider options:
 //!   -h, --hostname <hostname>
@@ -67,10 +83,22 @@
 //!           Base path to add to all requests [env: PACT_PROVIDER_BASE_PATH=]
 //!       --request-timeout <request-timeout>
 //!           Sets the HTTP request timeout in milliseconds for requests to the target API and for state change requests. [env: PACT_PROVIDER_REQUEST_TIMEOUT=]
+//!       --request-retries <request-retries>
+//!           Number of times to retry a request to the provider if it fails with a connection error or a timeout (defaults to 0) [env: PACT_PROVIDER_REQUEST_RETRIES=]
+//!       --request-retry-delay <request-retry-delay>
+//!           Time in milliseconds to wait before retrying a failed request to the provider (defaults to 1000) [env: PACT_PROVIDER_REQUEST_RETRY_DELAY=]
+//!       --readiness-url <readiness-url>
+//!           URL to poll until it returns a successful response before verifying any interactions (i.e. a health check endpoint of the provider) [env: PACT_PROVIDER_READINESS_URL=]
+//!       --readiness-timeout <readiness-timeout>
+//!           Maximum time in milliseconds to wait for the provider to be ready (defaults to 30000) [env: PACT_PROVIDER_READINESS_TIMEOUT=]
+//!       --generator-seed <generator-seed>
+//!           Seed to use for the random value generators, so that the same requests are generated on every run [env: PACT_GENERATOR_SEED=]
 //!   -H, --header <custom-header>
 //!           Add a custom header to be included in the calls to the provider. Values must be in the form KEY=VALUE, where KEY and VALUE contain ASCII characters (32-127) only. Can be repeated.
 //!       --disable-ssl-verification
 //!           Disables validation of SSL certificates
+//!       --dry-run
+//!           Generates and displays the requests and provider state change calls that would be made, without sending them to the provider
 //!
 //! Provider state options:
 //!   -s, --state-change-url <state-change-url>
@@ -87,6 +115,12 @@
 //!           Only validate interactions whose provider states match this filter (regex format) [env: PACT_PROVIDER_STATE=]
 //!       --filter-no-state
 //!           Only validate interactions that have no defined provider state [env: PACT_PROVIDER_NO_STATE=]
+//!       --filter-key <filter-key>
+//!           Only validate the interaction with this key (V4 interactions only, can be repeated)
+//!       --filter-id <filter-id>
+//!           Only validate the interaction with this ID (interactions fetched from a Pact Broker only, can be repeated)
+//!       --rerun-failed <json-report>
+//!           Only validate the interactions that failed in a previous verification, loaded from its JSON report
 //!   -c, --filter-consumer <filter-consumer>
 //!           Consumer name to filter the pacts to be verified (can be repeated)
 //!
@@ -137,6 +171,20 @@
 //! just the Pact from the webhook call, use the `--webhook-callback-url` set to the supplied URL in conjunction with the 
 //! `--broker-url` option.
 //!
+//! ### Authentication
+//!
+//! Pacts fetched from URLs or a Pact Broker can use the following authentication options. Only one of them can be used.
+//!
+//! | Option                                   | Description                                                                                             |
+//! |------------------------------------------|---------------------------------------------------------------------------------------------------------|
+//! | `--user <user>`, `--password <password>` | Basic authentication                                                                                    |
+//! | `-t, --token <token>`                    | Bearer token authentication                                                                             |
+//! | `--oauth2-token-url <oauth2-token-url>`  | OAuth2 client credentials grant. Requires the `--oauth2-client-id` and `--oauth2-client-secret` options |
+//! | `--auth-header <auth-header>`            | Sends a custom header with each request, i.e. `--auth-header X-API-Key=1234`                            |
+//!
+//! With OAuth2, an access token is fetched from the token endpoint before the first request and sent as a bearer token.
+//! The token is refreshed when it expires, or if the broker rejects it. Use `--oauth2-scope` to request a particular scope.
+//!
 //! ### Provider Options
 //!
 //! The running provider can be specified with the following options:
@@ -149,6 +197,29 @@
 //! | `--base-path <base-path>`             | If the provider is mounted on a sub-path, you can use this option to set the base path to add to all requests |
 //! | `--transport <transport>`             | Protocol transport to use. Defaults to HTTP.                                                                  |
 //!
+//! #### Waiting for the provider
+//!
+//! If the provider takes some time to start up, the verifier can poll a URL (i.e. a health check endpoint)
+//! until it returns a successful response with `--readiness-url`, waiting at most `--readiness-timeout`
+//! milliseconds (defaults to 30 seconds). Requests to the provider that fail with a connection error or a
+//! timeout can also be retried with `--request-retries`, waiting `--request-retry-delay` milliseconds
+//! between each attempt. Any interactions where the request was retried are noted in the output, and
+//! listed in the JSON report.
+//!
+//! #### Generating the same requests on every run
+//!
+//! Any random generators (i.e. random integers, strings or UUIDs) configured for the requests will generate new values on
+//! every run. To get the same values each time (for instance, to be able to snapshot the requests), a seed for the
+//! generators can be provided with the `--generator-seed` option (or the `PACT_GENERATOR_SEED` environment variable).
+//!
+//! #### Previewing the requests (dry run)
+//!
+//! With the `--dry-run` option, the verifier will load and filter the pacts, run the generators and any request filter,
+//! and then display the requests and provider state change calls that would be made, without contacting the provider (or
+//! the state change URL). As the state change calls are not made, only the parameters of the provider states from the
+//! Pact files are available to the generators. The requests are also included in the JSON report (`--json`), and
+//! results can't be published in this mode.
+//!
 //! ### Filtering the interactions
 //!
 //! The interactions that are verified can be filtered by the following options:
@@ -173,6 +244,23 @@
 //! This option will filter the interactions that are verified that don't have a defined provider state. Can't be used
 //! with the `--filter-state` option.
 //!
+//! #### `--filter-key <filter-key>` and `--filter-id <filter-id>`
+//!
+//! These options will only verify the interactions with the given keys or IDs, and can be repeated. V4 interactions have
+//! a key (the `key` attribute in the Pact file), and interactions fetched from a Pact Broker have an ID. They can't be
+//! used with the description or provider state filters.
+//!
+//! #### `--rerun-failed <json-report>`
+//!
+//! This option will only verify the interactions that failed in a previous run, using the JSON report written by the
+//! `--json` option. The failed interactions are selected by key or ID, or by their exact description if they have neither.
+//! If no `--filter-consumer` options are given, only the pacts for the consumers of the failed interactions are verified.
+//!
+//! ```console
+//! $ pact_verifier_cli -f pact.json -p 8080 --json report.json
+//! $ pact_verifier_cli -f pact.json -p 8080 --rerun-failed report.json
+//! ```
+//!
 //! ### State change requests
 //!
 //! [Provider states](https://docs.pact.io/getting_started/provider_states) are a mechanism to define the state that the 
@@ -225,6 +313,53 @@
 //! --consumer-version-selectors '{"branch": "master"}'
 //! ```
 //!
+//! ### Configuration file
+//!
+//! Instead of passing all the options on the command line, they can be loaded from a TOML or YAML configuration file with
+//! `--config <config>` (or the `PACT_VERIFIER_CONFIG` environment variable). The file format is determined by the extension
+//! (`.toml`, `.yaml` or `.yml`). Each section of the file maps onto a group of options, and the keys are the names of the
+//! command line options. Options given on the command line or with environment variables override the values from the file.
+//!
+//! ```toml
+//! [provider]
+//! name = "happy_provider"
+//! port = 8080
+//! state-change-url = "http://localhost:8080/provider-state"
+//!
+//! [sources]
+//! broker-url = "http://localhost:9292"
+//! consumer-version-selectors = [ { mainBranch = true }, { deployedOrReleased = true } ]
+//! enable-pending = true
+//!
+//! [auth]
+//! token = "1234"
+//!
+//! [filter]
+//! consumers = ["Consumer", "Consumer2"]
+//!
+//! [verification]
+//! request-timeout = 10000
+//! headers = { X-Api-Key = "1234" }
+//!
+//! [publish]
+//! enabled = true
+//! provider-version = "1.0.0"
+//! provider-branch = "main"
+//! ```
+//!
+//! | Section        | Keys                                                                                                                                                                                           |
+//! |----------------|------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
+//! | `output`       | `loglevel`, `pretty-log`, `full-log`, `compact-log`, `json`, `junit`, `html`, `markdown`, `no-colour`                                                                                          |
+//! | `sources`      | `files`, `dirs`, `urls`, `broker-url`, `webhook-callback-url`, `consumer-version-tags`, `consumer-version-selectors`, `enable-pending`, `include-wip-pacts-since`                              |
+//! | `auth`         | `user`, `password`, `token`, `oauth2-token-url`, `oauth2-client-id`, `oauth2-client-secret`, `oauth2-scope`, `auth-header`                                                                     |
+//! | `provider`     | `name`, `hostname`, `port`, `transport`, `transports`, `base-path`, `state-change-url`, `state-change-as-query`, `state-change-teardown`                                                       |
+//! | `filter`       | `description`, `state`, `no-state`, `keys`, `ids`, `rerun-failed`, `consumers`                                                                                                                 |
+//! | `verification` | `request-timeout`, `request-retries`, `request-retry-delay`, `readiness-url`, `readiness-timeout`, `generator-seed`, `headers`, `disable-ssl-verification`, `dry-run`, `ignore-no-pacts-error` |
+//! | `publish`      | `enabled`, `provider-version`, `build-url`, `provider-tags`, `provider-branch`                                                                                                                 |
+//!
+//! Any unknown sections or keys, or values of the wrong type, are reported as errors and the verifier will exit without
+//! running the verification.
+//!
 //! ## Example run
 //!
 //! This will verify all the pacts for the `happy_provider` found in the pact broker (running on localhost) against the provider running on localhost port 5050. Only the pacts for the consumers `Consumer` and `Consumer2` will be verified.
@@ -384,6 +519,25 @@
 //! allow configuring different ports to send the different requests to.
 //!
 //! For example, `--transports http:8080 message:8081` will send HTTP requests to port 8080 and message requests to port 8081.
+//!
+//! ## Comparing Pact files
+//!
+//! A `pact_diff` binary is also included, which compares two Pact files and displays the interactions that have been
+//! added, removed or changed between them. Interactions are matched using their type, description and provider states,
+//! and for changed interactions the differences in the requests, responses, matching rules and generators are listed.
+//!
+//! ```console,ignore
+//! $ pact_diff consumer-provider-v1.json consumer-provider-v2.json
+//! Added interactions:
+//!   + Synchronous/HTTP 'a request to delete a user' (given a user exists)
+//! Changed interactions:
+//!   ~ Synchronous/HTTP 'a request for a user' (given a user exists)
+//!       response: $.response.body.content.email was added with "mary@example.com"
+//!       matching rules: $.response.matchingRules.body['$.email'] was added with {"combine":"AND","matchers":[{"match":"type"}]}
+//! ```
+//!
+//! The differences can be output as JSON with `--output json`, and `--exit-code` will cause the command to exit with a
+//! status of 1 if there are any differences.
 //! ```
 
 #![warn(missing_docs)]
@@ -402,6 +556,7 @@ use log::{LevelFilter};
 use maplit::hashmap;
 use serde_json::Value;
 use pact_models::{PACT_RUST_VERSION, PactSpecification};
+use pact_models::http_utils::OAuth2ClientCredentials;
 use pact_models::prelude::HttpAuth;
 use tokio::time::sleep;
 use tracing::{debug, debug_span, error, Instrument, warn};
@@ -412,6 +567,7 @@ use pact_verifier::{
   NullRequestFilterExecutor,
   PactSource,
   ProviderInfo,
+  ProviderReadinessCheck,
   PublishOptions,
   VerificationOptions,
   verify_provider_async,
@@ -423,14 +579,20 @@ use pact_verifier::selectors::{consumer_tags_to_selectors, json_to_selectors};
 use tracing_log::LogTracer;
 
 mod args;
+mod config;
 mod reports;
 
 /// Handles the command line arguments from the running process
 pub async fn handle_cli(version: &'static str) -> Result<(), i32> {
+  let cli_args = config::apply_config_file(env::args_os().collect())
+    .map_err(|err| {
+      eprintln!("ERROR: {}", err);
+      3
+    })?;
   let app = args::setup_app();
   let matches = app
     .arg_required_else_help(true)
-    .try_get_matches();
+    .try_get_matches_from(cli_args);
 
   match matches {
     Ok(results) => handle_matches(&results).await,
@@ -458,7 +620,25 @@ async fn handle_matches(matches: &ArgMatches) -> Result<(), i32> {
 
   let provider = configure_provider(matches);
   let source = pact_source(matches);
-  let filter = interaction_filter(matches);
+  let mut consumers = matches.get_many::<String>("filter-consumer").unwrap_or_default()
+    .map(|v| v.to_string())
+    .collect::<Vec<_>>();
+  let filter = if let Some(report) = matches.get_one::<String>("rerun-failed") {
+    let failed = reports::read_failed_interactions(report.as_str()).map_err(|err| {
+      error!("Failed to load the failed interactions from '{report}' - {err}");
+      3
+    })?;
+    if failed.is_empty() {
+      println!("There are no failed interactions in '{}', so there is nothing to verify", report);
+      return Ok(());
+    }
+    if consumers.is_empty() {
+      consumers = failed.consumers.clone();
+    }
+    failed.filter()
+  } else {
+    interaction_filter(matches)
+  };
   let provider_state_executor = Arc::new(HttpRequestProviderStateExecutor {
     state_change_url: matches.get_one::<String>("state-change-url").cloned(),
     state_change_body: !matches.get_flag("state-change-as-query"),
@@ -484,6 +664,17 @@ async fn handle_matches(matches: &ArgMatches) -> Result<(), i32> {
     custom_headers,
     coloured_output,
     no_pacts_is_error: !matches.get_flag("ignore-no-pacts-error"),
+    request_retries: matches.get_one::<u8>("request-retries").copied().unwrap_or(0),
+    request_retry_delay: matches.get_one::<u64>("request-retry-delay").copied().unwrap_or(1000),
+    readiness_check: matches.get_one::<String>("readiness-url").map(|url| {
+      let mut readiness_check = ProviderReadinessCheck::new(url);
+      if let Some(timeout) = matches.get_one::<u64>("readiness-timeout") {
+        readiness_check.timeout = *timeout;
+      }
+      readiness_check
+    }),
+    generator_seed: matches.get_one::<u64>("generator-seed").copied(),
+    dry_run: matches.get_flag("dry-run"),
     .. VerificationOptions::default()
   };
 
@@ -508,7 +699,7 @@ async fn handle_matches(matches: &ArgMatches) -> Result<(), i32> {
     provider,
     source,
     filter,
-    matches.get_many::<String>("filter-consumer").unwrap_or_default().map(|v| v.to_string()).collect::<Vec<_>>(),
+    consumers,
     &verification_options,
     publish_options.as_ref(),
     &provider_state_executor,
@@ -541,6 +732,20 @@ async fn handle_matches(matches: &ArgMatches) -> Result<(), i32> {
         warn!("junit feature is not enabled, ignoring junit-file option");
       }
 
+      if let Some(html_file) = matches.get_one::<String>("html-file") {
+        if let Err(err) = reports::write_html_report(&result, html_file.as_str(), &provider_name) {
+          error!("Failed to write HTML report to '{html_file}' - {err}");
+          return Err(2)
+        }
+      }
+
+      if let Some(markdown_file) = matches.get_one::<String>("markdown-file") {
+        if let Err(err) = reports::write_markdown_report(&result, markdown_file.as_str(), &provider_name) {
+          error!("Failed to write Markdown report to '{markdown_file}' - {err}");
+          return Err(2)
+        }
+      }
+
       if result.result { Ok(()) } else { Err(1) }
     })
 }
@@ -641,14 +846,30 @@ fn print_version(version: &str) {
   println!("models version          : v{}", PACT_RUST_VERSION.unwrap_or_default());
 }
 
+fn broker_auth(matches: &ArgMatches) -> Option<HttpAuth> {
+  if let Some(user) = matches.get_one::<String>("user") {
+    Some(HttpAuth::User(user.clone(), matches.get_one::<String>("password").cloned()))
+  } else if let Some(token) = matches.get_one::<String>("token") {
+    Some(HttpAuth::Token(token.clone()))
+  } else if let Some(token_url) = matches.get_one::<String>("oauth2-token-url") {
+    Some(HttpAuth::OAuth2(OAuth2ClientCredentials::new(
+      token_url,
+      matches.get_one::<String>("oauth2-client-id").cloned().unwrap_or_default().as_str(),
+      matches.get_one::<String>("oauth2-client-secret").cloned().unwrap_or_default().as_str(),
+      matches.get_one::<String>("oauth2-scope").cloned()
+    )))
+  } else {
+    matches.get_one::<(String, String)>("auth-header")
+      .map(|(name, value)| HttpAuth::Header(name.clone(), value.clone()))
+  }
+}
+
 fn pact_source(matches: &ArgMatches) -> Vec<PactSource> {
   let mut sources = vec![];
 
   if let Some(webhook_url) = matches.get_one::<String>("webhook-callback-url") {
     let broker_url = matches.get_one::<String>("broker-url").unwrap();
-    let auth = matches.get_one::<String>("user").map(|user| {
-      HttpAuth::User(user.clone(), matches.get_one::<String>("password").cloned())
-    }).or_else(|| matches.get_one::<String>("token").map(|t| HttpAuth::Token(t.clone())));
+    let auth = broker_auth(matches);
     sources.push(PactSource::WebhookCallbackUrl {
       pact_url: webhook_url.clone(),
       broker_url: broker_url.clone(),
@@ -664,23 +885,13 @@ fn pact_source(matches: &ArgMatches) -> Vec<PactSource> {
     };
 
     if let Some(values) = matches.get_many::<String>("url") {
-      sources.extend(values.map(|v| {
-        if let Some(user) = matches.get_one::<String>("user") {
-          PactSource::URL(v.clone(), Some(HttpAuth::User(user.clone(),
-                                                         matches.get_one::<String>("password").map(|p| p.clone()))))
-        } else if let Some(token) = matches.get_one::<String>("token") {
-          PactSource::URL(v.clone(), Some(HttpAuth::Token(token.clone())))
-        } else {
-          PactSource::URL(v.clone(), None)
-        }
-      }).collect::<Vec<PactSource>>());
+      sources.extend(values.map(|v| PactSource::URL(v.clone(), broker_auth(matches)))
+        .collect::<Vec<PactSource>>());
     };
 
     if let Some(broker_url) = matches.get_one::<String>("broker-url") {
       let name = matches.get_one::<String>("provider-name").cloned().unwrap_or_default();
-      let auth = matches.get_one::<String>("user").map(|user| {
-        HttpAuth::User(user.clone(), matches.get_one::<String>("password").cloned())
-      }).or_else(|| matches.get_one::<String>("token").map(|t| HttpAuth::Token(t.clone())));
+      let auth = broker_auth(matches);
 
       let source = if matches.contains_id("consumer-version-selectors") || matches.contains_id("consumer-version-tags") {
         let pending = matches.get_flag("enable-pending");
@@ -721,7 +932,13 @@ fn pact_source(matches: &ArgMatches) -> Vec<PactSource> {
 }
 
 fn interaction_filter(matches: &ArgMatches) -> FilterInfo {
-  if matches.contains_id("filter-description") &&
+  if matches.contains_id("filter-key") || matches.contains_id("filter-id") {
+    FilterInfo::Interactions {
+      keys: matches.get_many::<String>("filter-key").unwrap_or_default().cloned().collect(),
+      ids: matches.get_many::<String>("filter-id").unwrap_or_default().cloned().collect(),
+      descriptions: vec![]
+    }
+  } else if matches.contains_id("filter-description") &&
     (matches.contains_id("filter-state") || matches.get_flag("filter-no-state")) {
     if let Some(state) = matches.get_one::<String>("filter-state") {
       FilterInfo::DescriptionAndState(matches.get_one::<String>("filter-description").unwrap().clone(),
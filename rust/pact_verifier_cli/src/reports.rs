@@ -1,13 +1,18 @@
-use std::fs::File;
+use std::collections::HashMap;
+use std::fs::{self, File};
 use std::io::Write;
 
+use anyhow::anyhow;
+
 #[cfg(feature = "junit")] use junit_report::{ReportBuilder, TestCaseBuilder, TestSuiteBuilder};
-#[cfg(feature = "junit")] use strip_ansi_escapes;
+use pact_models::interaction::Interaction;
+use pact_models::query_strings::build_query_string;
+use pact_models::v4::http_parts::HttpRequest;
 use serde_json::Value;
 use tracing::debug;
 
-#[cfg(feature = "junit")] use pact_verifier::{interaction_mismatch_output, MismatchResult};
-use pact_verifier::verification_result::VerificationExecutionResult;
+use pact_verifier::{FilterInfo, interaction_mismatch_output, MismatchResult};
+use pact_verifier::verification_result::{VerificationExecutionResult, VerificationInteractionResult};
 
 pub(crate) fn write_json_report(result: &VerificationExecutionResult, file_name: &str) -> anyhow::Result<()> {
   debug!("Writing JSON result of the verification to '{file_name}'");
@@ -17,6 +22,88 @@ pub(crate) fn write_json_report(result: &VerificationExecutionResult, file_name:
   Ok(())
 }
 
+/// Interactions that failed in a previous verification, loaded from its JSON report
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct FailedInteractions {
+  /// Consumers of the failed interactions
+  pub(crate) consumers: Vec<String>,
+  /// Keys of the failed V4 interactions
+  pub(crate) keys: Vec<String>,
+  /// IDs of the failed interactions that were fetched from a Pact Broker
+  pub(crate) ids: Vec<String>,
+  /// Consumers and descriptions of the failed interactions that have no key or ID
+  pub(crate) descriptions: Vec<(String, String)>
+}
+
+impl FailedInteractions {
+  /// If there were no failed interactions in the report
+  pub(crate) fn is_empty(&self) -> bool {
+    self.keys.is_empty() && self.ids.is_empty() && self.descriptions.is_empty()
+  }
+
+  /// Filter that will only select the failed interactions
+  pub(crate) fn filter(&self) -> FilterInfo {
+    FilterInfo::Interactions {
+      keys: self.keys.clone(),
+      ids: self.ids.clone(),
+      descriptions: self.descriptions.clone()
+    }
+  }
+
+  fn push<T: PartialEq>(values: &mut Vec<T>, value: T) {
+    if !values.contains(&value) {
+      values.push(value);
+    }
+  }
+}
+
+pub(crate) fn read_failed_interactions(file_name: &str) -> anyhow::Result<FailedInteractions> {
+  debug!("Loading failed interactions from JSON report '{file_name}'");
+  let contents = fs::read_to_string(file_name)?;
+  let report: Value = serde_json::from_str(contents.as_str())?;
+  failed_interactions_from_report(&report)
+}
+
+fn failed_interactions_from_report(report: &Value) -> anyhow::Result<FailedInteractions> {
+  if !report.is_object() {
+    return Err(anyhow!("Expected a JSON report from a previous verification"));
+  }
+
+  let mut failed = FailedInteractions::default();
+  if let Some(failed_interactions) = report.get("failedInteractions").and_then(|v| v.as_array()) {
+    for interaction in failed_interactions {
+      let consumer = interaction.get("consumer").and_then(|v| v.as_str());
+      if let Some(consumer) = consumer {
+        FailedInteractions::push(&mut failed.consumers, consumer.to_string());
+      }
+      let key = interaction.get("interactionKey").and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+      let id = interaction.get("interactionId").and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+      let description = interaction.get("description").and_then(|v| v.as_str());
+      // Descriptions are only unique within a Pact, so they are scoped to the consumer
+      match (key, id, consumer.zip(description)) {
+        (Some(key), _, _) => FailedInteractions::push(&mut failed.keys, key.to_string()),
+        (None, Some(id), _) => FailedInteractions::push(&mut failed.ids, id.to_string()),
+        (None, None, Some((consumer, description))) => FailedInteractions::push(&mut failed.descriptions,
+          (consumer.to_string(), description.to_string())),
+        (None, None, None) => return Err(anyhow!("Failed interaction entry has no key, ID or consumer and description: {}", interaction))
+      }
+    }
+  } else {
+    // Reports from older versions only have the interaction IDs for interactions fetched from a
+    // Pact Broker
+    for error in ["errors", "pendingErrors"].iter()
+      .flat_map(|field| report.get(field).and_then(|v| v.as_array()).cloned().unwrap_or_default()) {
+      match error.pointer("/mismatch/interactionId").and_then(|v| v.as_str()).filter(|v| !v.is_empty()) {
+        Some(id) => FailedInteractions::push(&mut failed.ids, id.to_string()),
+        None => return Err(anyhow!("The report does not have the keys or IDs of the failed interactions, \
+          it may have been generated by an older version of the verifier"))
+      }
+    }
+  }
+
+  Ok(failed)
+}
+
 #[cfg(feature = "junit")]
 pub(crate) fn write_junit_report(result: &VerificationExecutionResult, file_name: &str, provider: &String) -> anyhow::Result<()> {
   debug!("Writing JUnit result of the verification to '{file_name}'");
@@ -67,3 +154,469 @@ pub(crate) fn write_junit_report(result: &VerificationExecutionResult, file_name
   report.write_xml(&mut f)?;
   Ok(())
 }
+
+pub(crate) fn write_markdown_report(result: &VerificationExecutionResult, file_name: &str, provider: &str) -> anyhow::Result<()> {
+  debug!("Writing Markdown result of the verification to '{file_name}'");
+  let mut f = File::create(file_name)?;
+  f.write_all(markdown_report(result, provider).as_bytes())?;
+  Ok(())
+}
+
+pub(crate) fn write_html_report(result: &VerificationExecutionResult, file_name: &str, provider: &str) -> anyhow::Result<()> {
+  debug!("Writing HTML result of the verification to '{file_name}'");
+  let mut f = File::create(file_name)?;
+  f.write_all(html_report(result, provider).as_bytes())?;
+  Ok(())
+}
+
+/// Details of a failed interaction to display in the HTML and Markdown reports
+struct FailureDetails {
+  expected: Option<String>,
+  actual: Option<String>,
+  output: String
+}
+
+fn interaction_status(result: &VerificationInteractionResult) -> &'static str {
+  match &result.result {
+    Ok(_) => "passed",
+    Err(_) if result.pending => "pending",
+    Err(_) => "failed"
+  }
+}
+
+/// Groups the interaction results by consumer, keeping the order in which they were verified
+fn group_by_consumer(result: &VerificationExecutionResult) -> Vec<(&str, Vec<&VerificationInteractionResult>)> {
+  let mut groups: Vec<(&str, Vec<&VerificationInteractionResult>)> = vec![];
+  for interaction_result in &result.interaction_results {
+    let consumer = interaction_result.consumer_name.as_str();
+    match groups.iter_mut().find(|(name, _)| *name == consumer) {
+      Some((_, results)) => results.push(interaction_result),
+      None => groups.push((consumer, vec![interaction_result]))
+    }
+  }
+  groups
+}
+
+fn format_headers(headers: &Option<HashMap<String, Vec<String>>>) -> Vec<String> {
+  let mut lines: Vec<String> = headers.iter()
+    .flat_map(|headers| headers.iter())
+    .map(|(key, values)| format!("{}: {}", key, values.join(", ")))
+    .collect();
+  lines.sort();
+  lines
+}
+
+fn format_request(request: &HttpRequest) -> String {
+  let mut lines = vec![];
+  match &request.query {
+    Some(query) if !query.is_empty() => lines.push(format!("{} {}?{}", request.method, request.path,
+      build_query_string(query.clone()))),
+    _ => lines.push(format!("{} {}", request.method, request.path))
+  }
+  lines.extend(format_headers(&request.headers));
+  if request.body.is_present() {
+    lines.push(String::default());
+    lines.push(request.body.display_string());
+  }
+  lines.join("\n")
+}
+
+fn format_response(interaction: &dyn Interaction) -> String {
+  match interaction.as_v4_http() {
+    Some(http) => {
+      let mut lines = vec![format!("HTTP {}", http.response.status)];
+      lines.extend(format_headers(&http.response.headers));
+      if http.response.body.is_present() {
+        lines.push(String::default());
+        lines.push(http.response.body.display_string());
+      }
+      lines.join("\n")
+    }
+    None => interaction.contents_for_verification().display_string()
+  }
+}
+
+fn failure_details(result: &VerificationInteractionResult) -> Option<FailureDetails> {
+  match &result.result {
+    Ok(_) => None,
+    Err(MismatchResult::Mismatches { mismatches, expected, actual, .. }) => {
+      let mut output = vec![];
+      interaction_mismatch_output(&mut output, false, 0, &result.description, mismatches,
+        expected.as_ref(), actual.as_ref());
+      Some(FailureDetails {
+        expected: Some(format_response(expected.as_ref())),
+        actual: Some(format_response(actual.as_ref())),
+        output: strip_ansi_escapes::strip_str(output.join("\n")).trim().to_string()
+      })
+    }
+    Err(MismatchResult::Error(error, _)) => Some(FailureDetails {
+      expected: None,
+      actual: None,
+      output: strip_ansi_escapes::strip_str(error)
+    })
+  }
+}
+
+fn summary_counts(result: &VerificationExecutionResult) -> (usize, usize, usize) {
+  result.interaction_results.iter()
+    .fold((0, 0, 0), |(passed, failed, pending), r| match interaction_status(r) {
+      "passed" => (passed + 1, failed, pending),
+      "pending" => (passed, failed, pending + 1),
+      _ => (passed, failed + 1, pending)
+    })
+}
+
+/// Generates a Markdown summary of the verification, suitable for adding as a PR comment
+pub(crate) fn markdown_report(result: &VerificationExecutionResult, provider: &str) -> String {
+  let (passed, failed, pending) = summary_counts(result);
+  let mut buffer = vec![
+    format!("# Verification of {}", provider),
+    String::default(),
+    format!("**Result:** {} ({} passed, {} failed, {} pending)",
+      if result.result { "✅ Passed" } else { "❌ Failed" }, passed, failed, pending)
+  ];
+
+  for (consumer, results) in group_by_consumer(result) {
+    buffer.push(String::default());
+    buffer.push(format!("## {}", consumer));
+    buffer.push(String::default());
+    buffer.push("| Interaction | Status | Duration |".to_string());
+    buffer.push("| --- | --- | --- |".to_string());
+    for r in &results {
+      let status = match interaction_status(r) {
+        "passed" => "✅ passed",
+        "pending" => "⏳ pending",
+        _ => "❌ failed"
+      };
+      buffer.push(format!("| {} | {} | {}ms |", r.description.replace('|', "\\|").replace('\n', " "),
+        status, r.duration.as_millis()));
+    }
+
+    for r in results.iter().filter(|r| r.result.is_err()) {
+      buffer.push(String::default());
+      buffer.push(format!("### {}", r.description.replace('\n', " ")));
+      buffer.push(String::default());
+      if r.pending {
+        buffer.push("_This interaction is pending, so the failure will not fail the build._".to_string());
+        buffer.push(String::default());
+      }
+      if !r.provider_states.is_empty() {
+        buffer.push("**Provider states:**".to_string());
+        buffer.push(String::default());
+        for state in &r.provider_states {
+          buffer.push(format!("* {}", state));
+        }
+        buffer.push(String::default());
+      }
+      if let Some(request) = &r.request {
+        push_markdown_code_block(&mut buffer, "Request", "http", &format_request(request));
+      }
+      if let Some(details) = failure_details(r) {
+        if let Some(expected) = &details.expected {
+          push_markdown_code_block(&mut buffer, "Expected response", "", expected);
+        }
+        if let Some(actual) = &details.actual {
+          push_markdown_code_block(&mut buffer, "Actual response", "", actual);
+        }
+        push_markdown_code_block(&mut buffer, "Mismatches", "diff", &details.output);
+      }
+    }
+  }
+
+  buffer.push(String::default());
+  buffer.join("\n")
+}
+
+fn push_markdown_code_block(buffer: &mut Vec<String>, title: &str, language: &str, contents: &str) {
+  buffer.push(format!("**{}:**", title));
+  buffer.push(String::default());
+  buffer.push(format!("```{}", language));
+  buffer.push(contents.to_string());
+  buffer.push("```".to_string());
+  buffer.push(String::default());
+}
+
+fn escape_html(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len());
+  for ch in value.chars() {
+    match ch {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&#39;"),
+      _ => escaped.push(ch)
+    }
+  }
+  escaped
+}
+
+const HTML_STYLE: &str = r#"body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #24292f; }
+h1, h2 { border-bottom: 1px solid #d0d7de; padding-bottom: 0.3em; }
+.summary span { margin-right: 1em; }
+details { border: 1px solid #d0d7de; border-radius: 6px; margin: 0.5em 0; padding: 0.5em 1em; }
+summary { cursor: pointer; }
+.status { display: inline-block; min-width: 5em; font-weight: bold; text-transform: uppercase; font-size: 0.8em; }
+.passed { color: #1a7f37; }
+.failed { color: #cf222e; }
+.pending { color: #9a6700; }
+.duration { color: #57606a; float: right; }
+pre { background: #f6f8fa; padding: 0.75em; overflow-x: auto; }
+.diff-added { color: #1a7f37; }
+.diff-removed { color: #cf222e; }
+.columns { display: flex; gap: 1em; }
+.columns > div { flex: 1; min-width: 0; }"#;
+
+/// Generates a self-contained HTML report of the verification
+pub(crate) fn html_report(result: &VerificationExecutionResult, provider: &str) -> String {
+  let (passed, failed, pending) = summary_counts(result);
+  let title = format!("Verification of {}", escape_html(provider));
+  let mut buffer = vec![
+    "<!DOCTYPE html>".to_string(),
+    "<html>".to_string(),
+    "<head>".to_string(),
+    "<meta charset=\"utf-8\">".to_string(),
+    format!("<title>{}</title>", title),
+    format!("<style>\n{}\n</style>", HTML_STYLE),
+    "</head>".to_string(),
+    "<body>".to_string(),
+    format!("<h1>{}</h1>", title),
+    format!("<p class=\"summary\"><span class=\"{}\">{}</span><span class=\"passed\">{} passed</span><span class=\"failed\">{} failed</span><span class=\"pending\">{} pending</span></p>",
+      if result.result { "passed" } else { "failed" },
+      if result.result { "Passed" } else { "Failed" },
+      passed, failed, pending)
+  ];
+
+  for (consumer, results) in group_by_consumer(result) {
+    buffer.push(format!("<h2>{}</h2>", escape_html(consumer)));
+    for r in results {
+      let status = interaction_status(r);
+      buffer.push(format!("<details{}>", if status == "failed" { " open" } else { "" }));
+      buffer.push(format!("<summary><span class=\"status {status}\">{status}</span> {} <span class=\"duration\">{}ms</span></summary>",
+        escape_html(&r.description), r.duration.as_millis()));
+      if !r.provider_states.is_empty() {
+        buffer.push("<h4>Provider states</h4>".to_string());
+        buffer.push("<ul>".to_string());
+        for state in &r.provider_states {
+          buffer.push(format!("<li>{}</li>", escape_html(state)));
+        }
+        buffer.push("</ul>".to_string());
+      }
+      if let Some(request) = &r.request {
+        buffer.push("<h4>Request</h4>".to_string());
+        buffer.push(format!("<pre>{}</pre>", escape_html(&format_request(request))));
+      }
+      if let Some(details) = failure_details(r) {
+        if details.expected.is_some() || details.actual.is_some() {
+          buffer.push("<div class=\"columns\">".to_string());
+          buffer.push(format!("<div><h4>Expected response</h4><pre>{}</pre></div>",
+            escape_html(details.expected.as_deref().unwrap_or_default())));
+          buffer.push(format!("<div><h4>Actual response</h4><pre>{}</pre></div>",
+            escape_html(details.actual.as_deref().unwrap_or_default())));
+          buffer.push("</div>".to_string());
+        }
+        buffer.push("<h4>Mismatches</h4>".to_string());
+        buffer.push(format!("<pre>{}</pre>", html_diff(&details.output)));
+      }
+      buffer.push("</details>".to_string());
+    }
+  }
+
+  buffer.push("</body>".to_string());
+  buffer.push("</html>".to_string());
+  buffer.push(String::default());
+  buffer.join("\n")
+}
+
+/// Escapes the mismatch output, highlighting any lines from a body diff
+fn html_diff(output: &str) -> String {
+  output.lines()
+    .map(|line| {
+      let trimmed = line.trim_start();
+      if trimmed.starts_with('+') {
+        format!("<span class=\"diff-added\">{}</span>", escape_html(line))
+      } else if trimmed.starts_with('-') {
+        format!("<span class=\"diff-removed\">{}</span>", escape_html(line))
+      } else {
+        escape_html(line)
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use expectest::prelude::*;
+  use maplit::hashmap;
+  use pact_matching::Mismatch;
+  use pact_models::bodies::OptionalBody;
+  use pact_models::v4::http_parts::HttpResponse;
+  use pact_models::v4::synch_http::SynchronousHttp;
+  use serde_json::json;
+
+  use super::*;
+
+  fn interaction_result(
+    consumer: &str,
+    description: &str,
+    result: Result<(), MismatchResult>,
+    pending: bool
+  ) -> VerificationInteractionResult {
+    VerificationInteractionResult {
+      interaction_id: None,
+      interaction_key: None,
+      unique_key: None,
+      description: description.to_string(),
+      interaction_description: description.to_string(),
+      consumer_name: consumer.to_string(),
+      provider_states: vec!["user <b>exists</b>".to_string()],
+      request: Some(HttpRequest {
+        method: "GET".to_string(),
+        path: "/users".to_string(),
+        query: Some(hashmap! { "id".to_string() => vec![Some("1".to_string())] }),
+        headers: Some(hashmap! { "Accept".to_string() => vec!["application/json".to_string()] }),
+        .. HttpRequest::default()
+      }),
+      result,
+      pending,
+      duration: Duration::from_millis(12),
+      request_retries: 0,
+      provider_unavailable: false
+    }
+  }
+
+  fn execution_result() -> VerificationExecutionResult {
+    let expected = SynchronousHttp {
+      response: HttpResponse {
+        status: 200,
+        body: OptionalBody::from("{\"id\":1}"),
+        .. HttpResponse::default()
+      },
+      .. SynchronousHttp::default()
+    };
+    let actual = SynchronousHttp {
+      response: HttpResponse {
+        status: 404,
+        .. HttpResponse::default()
+      },
+      .. SynchronousHttp::default()
+    };
+    VerificationExecutionResult {
+      result: false,
+      interaction_results: vec![
+        interaction_result("Consumer A", "a request for a user", Ok(()), false),
+        interaction_result("Consumer B", "a request for a missing user", Err(MismatchResult::Mismatches {
+          mismatches: vec![Mismatch::StatusMismatch {
+            expected: 200,
+            actual: 404,
+            mismatch: "expected 200 but was 404".to_string()
+          }],
+          expected: Box::new(expected),
+          actual: Box::new(actual),
+          interaction_id: None
+        }), false),
+        interaction_result("Consumer A", "a request for all users", Err(MismatchResult::Error(
+          "Connection refused".to_string(), None)), true)
+      ],
+      .. VerificationExecutionResult::new()
+    }
+  }
+
+  #[test]
+  fn markdown_report_groups_interactions_by_consumer() {
+    let report = markdown_report(&execution_result(), "Provider");
+
+    expect!(report.contains("**Result:** ❌ Failed (1 passed, 1 failed, 1 pending)")).to(be_true());
+    let consumer_a = report.find("## Consumer A").unwrap();
+    let consumer_b = report.find("## Consumer B").unwrap();
+    expect!(consumer_a < consumer_b).to(be_true());
+    expect!(report.contains("| a request for a user | ✅ passed | 12ms |")).to(be_true());
+    expect!(report.contains("| a request for all users | ⏳ pending | 12ms |")).to(be_true());
+    expect!(report.contains("| a request for a missing user | ❌ failed | 12ms |")).to(be_true());
+    expect!(report.contains("```http\nGET /users?id=1\nAccept: application/json\n```")).to(be_true());
+    expect!(report.contains("**Expected response:**\n\n```\nHTTP 200")).to(be_true());
+    expect!(report.contains("**Actual response:**\n\n```\nHTTP 404")).to(be_true());
+    expect!(report.contains("Connection refused")).to(be_true());
+  }
+
+  #[test]
+  fn html_report_escapes_content() {
+    let report = html_report(&execution_result(), "<Provider>");
+
+    expect!(report.starts_with("<!DOCTYPE html>")).to(be_true());
+    expect!(report.contains("<h1>Verification of &lt;Provider&gt;</h1>")).to(be_true());
+    expect!(report.contains("<li>user &lt;b&gt;exists&lt;/b&gt;</li>")).to(be_true());
+    expect!(report.contains("<h2>Consumer A</h2>")).to(be_true());
+    expect!(report.contains("<span class=\"status pending\">pending</span> a request for all users")).to(be_true());
+    expect!(report.contains("<details open>\n<summary><span class=\"status failed\">failed</span> a request for a missing user")).to(be_true());
+    expect!(report.contains("\u{1b}[")).to(be_false());
+  }
+
+  #[test]
+  fn failed_interactions_from_report_test() {
+    let report = json!({
+      "result": false,
+      "failedInteractions": [
+        { "consumer": "A", "description": "one", "interactionKey": "1234abcd", "interactionId": null, "pending": false },
+        { "consumer": "B", "description": "two", "interactionKey": null, "interactionId": "abc123", "pending": true },
+        { "consumer": "A", "description": "three", "interactionKey": null, "interactionId": null, "pending": false },
+        { "consumer": "B", "description": "three", "interactionKey": null, "interactionId": null, "pending": false },
+        { "consumer": "A", "description": "one", "interactionKey": "1234abcd", "interactionId": null, "pending": false }
+      ]
+    });
+    expect!(failed_interactions_from_report(&report).unwrap()).to(be_equal_to(FailedInteractions {
+      consumers: vec!["A".to_string(), "B".to_string()],
+      keys: vec!["1234abcd".to_string()],
+      ids: vec!["abc123".to_string()],
+      descriptions: vec![
+        ("A".to_string(), "three".to_string()),
+        ("B".to_string(), "three".to_string())
+      ]
+    }));
+
+    let report = json!({
+      "result": false,
+      "failedInteractions": [
+        { "description": "three", "interactionKey": null, "interactionId": null, "pending": false }
+      ]
+    });
+    expect!(failed_interactions_from_report(&report)).to(be_err());
+
+    let report = json!({ "result": true, "errors": [], "pendingErrors": [] });
+    expect!(failed_interactions_from_report(&report).unwrap().is_empty()).to(be_true());
+  }
+
+  #[test]
+  fn failed_interactions_from_older_report() {
+    let report = json!({
+      "result": false,
+      "errors": [
+        { "interaction": "one", "mismatch": { "type": "error", "message": "Boom", "interactionId": "abc123" } }
+      ],
+      "pendingErrors": [
+        { "interaction": "two", "mismatch": { "type": "error", "message": "Boom", "interactionId": "def456" } }
+      ]
+    });
+    expect!(failed_interactions_from_report(&report).unwrap().ids).to(be_equal_to(vec![
+      "abc123".to_string(), "def456".to_string()
+    ]));
+
+    let report = json!({
+      "result": false,
+      "errors": [
+        { "interaction": "one", "mismatch": { "type": "error", "message": "Boom", "interactionId": "" } }
+      ]
+    });
+    expect!(failed_interactions_from_report(&report)).to(be_err());
+    expect!(failed_interactions_from_report(&json!([]))).to(be_err());
+  }
+
+  #[test]
+  fn html_diff_highlights_added_and_removed_lines() {
+    expect!(html_diff("body:\n  -  \"a\"\n  +  \"<b>\"")).to(be_equal_to(
+      "body:\n<span class=\"diff-removed\">  -  \"a\"</span>\n<span class=\"diff-added\">  +  \"&lt;b&gt;\"</span>"));
+  }
+}